@@ -10,21 +10,21 @@ use auth::{
     serve_redirect::{self, ProcessAuthorizationError},
 };
 use bridge::{
-    handle::{BackendHandle, BackendReceiver, FrontendHandle}, install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath}, instance::{ContentType, InstanceID}, message::{EmbeddedOrRaw, MessageToFrontend}, modal_action::{ModalAction, ModalActionVisitUrl, ProgressTracker, ProgressTrackerFinishType}, safe_path::SafePath
+    handle::{BackendHandle, BackendReceiver, FrontendHandle}, install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath}, instance::{ContentType, InstanceContentSummary, InstanceID}, message::{EmbeddedOrRaw, MessageToFrontend}, modal_action::{ModalAction, ModalActionVisitUrl, ProgressTracker, ProgressTrackerFinishType}, safe_path::SafePath
 };
 use image::ImageFormat;
 use indexmap::IndexSet;
 use parking_lot::RwLock;
 use reqwest::{StatusCode, redirect::Policy};
-use rustc_hash::FxHashMap;
-use schema::{auxiliary::AuxiliaryContentMeta, backend_config::{BackendConfig, ProxyConfig, SyncTargets}, content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeGetFilesRequest}, instance::InstanceConfiguration, loader::Loader, minecraft_profile::MinecraftProfileResponse, modrinth::ModrinthSideRequirement};
+use rustc_hash::{FxHashMap, FxHashSet};
+use schema::{auxiliary::AuxiliaryContentMeta, backend_config::{BackendConfig, ProxyConfig, SyncTargets}, content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeFingerprintsRequest, CurseforgeGetFilesRequest}, instance::{InstanceConfiguration, InstanceKind}, loader::Loader, minecraft_profile::MinecraftProfileResponse, modrinth::ModrinthSideRequirement, server_history::ServerHistory};
 use sha1::{Digest, Sha1};
 use strum::IntoEnumIterator;
 use tokio::sync::{OnceCell, Semaphore, mpsc::Receiver};
 use uuid::Uuid;
 
 use crate::{
-    account::{BackendAccountInfo, MinecraftLoginInfo}, directories::LauncherDirectories, id_slab::IdSlab, instance::{ContentFolder, Instance}, launch::Launcher, metadata::{items::{CurseforgeGetFilesMetadataItem, MinecraftVersionManifestMetadataItem}, manager::MetadataManager}, mod_metadata::ModMetadataManager, persistent::Persistent, server_list_pinger::ServerListPinger, skin_manager::SkinManager
+    account::{BackendAccountInfo, MinecraftLoginInfo}, directories::LauncherDirectories, id_slab::IdSlab, instance::{ContentFolder, Instance}, launch::Launcher, metadata::{items::{CurseforgeFingerprintsMetadataItem, CurseforgeGetFilesMetadataItem, MinecraftVersionManifestMetadataItem}, manager::MetadataManager}, mod_metadata::ModMetadataManager, persistent::Persistent, server_history::ServerSampleLog, server_list_pinger::ServerListPinger, skin_manager::SkinManager
 };
 
 fn build_http_clients(user_agent: &str, proxy_config: &ProxyConfig, proxy_password: Option<&str>) -> (reqwest::Client, reqwest::Client) {
@@ -129,6 +129,10 @@ pub fn start(launcher_dir: PathBuf, send: FrontendHandle, self_handle: BackendHa
     // Load accounts
     let account_info = Persistent::load(directories.accounts_json.clone());
 
+    // Load server status history
+    let mut server_history = Persistent::load(directories.server_history_json.clone());
+    let server_history_samples = ServerSampleLog::load(directories.server_history_samples_jsonl.clone(), &mut server_history);
+
     let state = BackendState {
         self_handle,
         send: send.clone(),
@@ -147,6 +151,8 @@ pub fn start(launcher_dir: PathBuf, send: FrontendHandle, self_handle: BackendHa
         cached_minecraft_profiles: Default::default(),
         skin_manager: Default::default(),
         server_list_pinger: Arc::new(ServerListPinger::new()),
+        server_history: Arc::new(RwLock::new(server_history)),
+        server_history_samples: Arc::new(RwLock::new(server_history_samples)),
     };
 
     log::debug!("Doing initial backend load");
@@ -205,6 +211,8 @@ pub struct BackendState {
     pub cached_minecraft_profiles: Arc<RwLock<FxHashMap<Uuid, CachedMinecraftProfile>>>,
     pub skin_manager: Arc<RwLock<SkinManager>>,
     pub server_list_pinger: Arc<ServerListPinger>,
+    pub server_history: Arc<RwLock<Persistent<ServerHistory>>>,
+    pub server_history_samples: Arc<RwLock<ServerSampleLog>>,
 }
 
 pub struct CachedMinecraftProfile {
@@ -237,7 +245,10 @@ impl BackendState {
         // Pre-fetch version manifest
         self.meta.load(&MinecraftVersionManifestMetadataItem).await;
 
-        Arc::new(self).handle(recv, watcher_rx).await;
+        let state = Arc::new(self);
+        tokio::task::spawn(crate::server_history::poll_server_history(state.clone()));
+
+        state.handle(recv, watcher_rx).await;
     }
 
     pub async fn load_all_instances(&self) {
@@ -428,16 +439,41 @@ impl BackendState {
         let mut instance_state = self.instance_state.write();
         for instance in instance_state.instances.iter_mut() {
             let mut killed = false;
+            let mut crashed = None;
             instance.processes.retain_mut(|child| {
-                if matches!(child.try_wait(), Ok(None)) {
-                    true
-                } else {
-                    log::debug!("Child process {} is no longer alive", child.id());
-                    killed = true;
-                    false
+                match child.try_wait() {
+                    Ok(None) => true,
+                    result => {
+                        log::debug!("Child process {} is no longer alive", child.id());
+                        if let Ok(Some(status)) = result && !status.success() {
+                            log::warn!("Child process {} exited abnormally: {status}", child.id());
+                            crashed = Some(status.code());
+                        }
+                        killed = true;
+                        false
+                    }
                 }
             });
 
+            if let Some(exit_code) = crashed && !instance.stop_requested {
+                let id = instance.id;
+                let name = instance.name;
+                let dot_minecraft_path = instance.dot_minecraft_path.clone();
+                let launched_at = instance.stats.get().last_played_unix_ms;
+                let send = self.send.clone();
+                tokio::task::spawn_blocking(move || {
+                    let analysis = crate::crash_report::analyze_exit(&dot_minecraft_path, launched_at, exit_code);
+                    send.send(MessageToFrontend::InstanceCrashed { id, name, analysis });
+                });
+            }
+
+            if killed && instance.processes.is_empty() {
+                instance.stop_requested = false;
+                if let Some(post_exit_hook) = instance.post_exit_hook.take() {
+                    post_exit_hook.run();
+                }
+            }
+
             if killed {
                 instance.update_session();
                 self.send.send(instance.create_modify_message());
@@ -648,16 +684,31 @@ impl BackendState {
     }
 
     pub fn apply_syncing_to_instance(&self, id: InstanceID) {
-        let (disable, path) = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
-            (instance.configuration.get().disable_file_syncing, instance.dot_minecraft_path.clone())
+        let mut instance_state = self.instance_state.write();
+        let (disable, profile, path) = if let Some(instance) = instance_state.instances.get_mut(id) {
+            let configuration = instance.configuration.get();
+            (configuration.disable_file_syncing, configuration.sync_profile.clone(), instance.dot_minecraft_path.clone())
         } else {
             return;
         };
+        let members = crate::syncing::profile_members(profile.as_deref(), &mut *instance_state);
+        drop(instance_state);
 
-        if disable {
-            crate::syncing::apply_to_instance(&SyncTargets::default(), &self.directories, path);
+        let mut config = self.config.write();
+        let sync_targets = if disable {
+            None
+        } else {
+            config.get().sync_targets_for(profile.as_deref())
+        };
+        let synced_dir = crate::syncing::synced_dir_for(profile.as_deref(), &self.directories);
+
+        if let Some(sync_targets) = sync_targets && let Some(synced_dir) = synced_dir {
+            crate::syncing::apply_to_instance(sync_targets, &synced_dir, &members, &self.directories, path);
         } else {
-            crate::syncing::apply_to_instance(&self.config.write().get().sync_targets, &self.directories, path);
+            if !disable {
+                log::warn!("Instance uses unknown sync profile {:?}, syncing is disabled", profile);
+            }
+            crate::syncing::apply_to_instance(&SyncTargets::default(), &self.directories.synced_dir, &[], &self.directories, path);
         }
     }
 
@@ -689,7 +740,7 @@ impl BackendState {
             overrides: Arc<[(SafePath, Arc<[u8]>)]>,
         }
 
-        let loader_supports_add_mods = loader == Loader::Fabric;
+        let loader_supports_add_mods = loader == Loader::Fabric || loader == Loader::Quilt;
 
         // Remove .pandora.filename mods
         if let Ok(read_dir) = std::fs::read_dir(&mod_dir) {
@@ -1017,7 +1068,7 @@ impl BackendState {
         add_mods
     }
 
-    pub async fn create_instance_sanitized(&self, name: &str, version: &str, loader: Loader, icon: Option<EmbeddedOrRaw>) -> Option<PathBuf> {
+    pub async fn create_instance_sanitized(&self, name: &str, version: &str, loader: Loader, kind: InstanceKind, icon: Option<EmbeddedOrRaw>) -> Option<PathBuf> {
         let mut name = sanitize_filename::sanitize_with_options(name, sanitize_filename::Options { windows: true, ..Default::default() });
 
         if self.instance_state.read().instances.iter().any(|i| i.name == name) {
@@ -1031,26 +1082,32 @@ impl BackendState {
             }
         }
 
-        return self.create_instance(&name, version, loader, icon).await;
+        return self.create_instance(&name, version, loader, kind, icon).await;
     }
 
-    pub async fn create_instance(&self, name: &str, version: &str, loader: Loader, icon: Option<EmbeddedOrRaw>) -> Option<PathBuf> {
+    pub async fn create_instance(&self, name: &str, version: &str, loader: Loader, kind: InstanceKind, icon: Option<EmbeddedOrRaw>) -> Option<PathBuf> {
+        match self.try_create_instance(name, version, loader, kind, icon).await {
+            Ok(instance_dir) => Some(instance_dir),
+            Err(error) => {
+                self.send.send_warning(error);
+                None
+            },
+        }
+    }
+
+    pub async fn try_create_instance(&self, name: &str, version: &str, loader: Loader, kind: InstanceKind, icon: Option<EmbeddedOrRaw>) -> Result<PathBuf, String> {
         log::info!("Creating instance {name}");
         if loader == Loader::Unknown {
-            self.send.send_warning(format!("Unable to create instance, unknown loader"));
-            return None;
+            return Err("Unable to create instance, unknown loader".to_string());
         }
         if !crate::is_single_component_path_str(&name) {
-            self.send.send_warning(format!("Unable to create instance, name must not be a path: {}", name));
-            return None;
+            return Err(format!("Unable to create instance, name must not be a path: {}", name));
         }
         if !sanitize_filename::is_sanitized_with_options(&*name, sanitize_filename::OptionsForCheck { windows: true, ..Default::default() }) {
-            self.send.send_warning(format!("Unable to create instance, name is invalid: {}", name));
-            return None;
+            return Err(format!("Unable to create instance, name is invalid: {}", name));
         }
         if self.instance_state.read().instances.iter().any(|i| i.name == name) {
-            self.send.send_warning("Unable to create instance, name is already used".to_string());
-            return None;
+            return Err("Unable to create instance, name is already used".to_string());
         }
 
         self.file_watching.write().watch_filesystem(self.directories.instances_dir.clone(), WatchTarget::InstancesDir);
@@ -1060,6 +1117,7 @@ impl BackendState {
         _ = std::fs::create_dir_all(&instance_dir);
 
         let mut instance_info = InstanceConfiguration::new(version.into(), loader);
+        instance_info.kind = kind;
 
         match icon {
             Some(EmbeddedOrRaw::Embedded(e)) => {
@@ -1083,7 +1141,7 @@ impl BackendState {
         let info_path = instance_dir.join("info_v1.json");
         crate::write_safe(&info_path, serde_json::to_string(&instance_info).unwrap().as_bytes()).unwrap();
 
-        Some(instance_dir.clone())
+        Ok(instance_dir.clone())
     }
 
     pub async fn rename_instance(self: &Arc<Self>, id: InstanceID, name: &str) {
@@ -1115,6 +1173,8 @@ impl BackendState {
     }
 
     pub async fn get_login_info(&self, modal_action: &ModalAction, instance_account: Option<Uuid>) -> Option<MinecraftLoginInfo> {
+        let offline_mode = self.config.write().get().offline_mode;
+
         let selected_account = {
             let mut account_info = self.account_info.write();
             let account_info = account_info.get();
@@ -1123,7 +1183,8 @@ impl BackendState {
 
             if let Some(uuid) = selected_account {
                 if let Some(account) = account_info.accounts.get(&uuid) {
-                    if account.offline {
+                    // Refreshing the access token isn't possible in offline mode, so the game is launched without one
+                    if account.offline || offline_mode {
                         return Some(MinecraftLoginInfo {
                             uuid,
                             username: account.username.clone(),
@@ -1138,6 +1199,10 @@ impl BackendState {
             selected_account
         };
 
+        if offline_mode {
+            return None;
+        }
+
         let Some((profile, access_token)) = self.login_flow(modal_action, selected_account).await else {
             return None;
         };
@@ -1148,6 +1213,73 @@ impl BackendState {
             access_token: Some(access_token),
         })
     }
+
+    // Looks up manually installed content on CurseForge by fingerprint, marking any exact matches as coming from CurseForge
+    pub async fn identify_curseforge_content(&self, content: &[InstanceContentSummary]) {
+        let unidentified: Vec<([u8; 20], Arc<Path>)> = {
+            let sources = self.mod_metadata_manager.read_content_sources();
+            content.iter().filter(|summary| {
+                !matches!(summary.content_summary.extra, ContentType::ModrinthModpack { .. } | ContentType::CurseforgeModpack { .. })
+                    && matches!(sources.get(&summary.content_summary.hash), None | Some(ContentSource::Manual))
+            }).map(|summary| (summary.content_summary.hash, summary.path.clone())).collect()
+        };
+
+        if unidentified.is_empty() {
+            return;
+        }
+
+        let fingerprints = tokio::task::spawn_blocking(move || {
+            unidentified.into_iter().filter_map(|(hash, path)| {
+                Some((crate::curseforge_fingerprint(&path).ok()?, hash))
+            }).collect::<FxHashMap<u32, [u8; 20]>>()
+        }).await.unwrap();
+
+        let mut request_fingerprints: Vec<u32> = fingerprints.keys().copied().collect();
+        request_fingerprints.sort();
+
+        let result = self.meta.fetch(&CurseforgeFingerprintsMetadataItem(&CurseforgeFingerprintsRequest {
+            fingerprints: request_fingerprints,
+        })).await;
+
+        let matches = match result {
+            Ok(matches) => matches,
+            Err(err) => {
+                log::warn!("Unable to match content fingerprints on CurseForge: {err}");
+                return;
+            },
+        };
+
+        let hashes: FxHashSet<[u8; 20]> = fingerprints.into_values().collect();
+
+        let mut sources = Vec::new();
+        for fingerprint_match in matches.data.exact_matches.iter() {
+            let file = &fingerprint_match.file;
+            let sha1 = file.hashes.iter()
+                .find(|hash| hash.algo == 1).map(|hash| &hash.value);
+            let Some(sha1) = sha1 else {
+                continue;
+            };
+
+            let mut hash = [0u8; 20];
+            let Ok(_) = hex::decode_to_slice(&**sha1, &mut hash) else {
+                continue;
+            };
+
+            if !hashes.contains(&hash) {
+                continue;
+            }
+
+            self.mod_metadata_manager.set_cached_curseforge_info(file.id, CachedCurseforgeFileInfo {
+                hash,
+                filename: file.file_name.clone(),
+                disabled_third_party_downloads: file.download_url.is_none()
+            });
+            sources.push((hash, ContentSource::CurseforgeProject { project_id: fingerprint_match.id }));
+        }
+
+        log::info!("Identified {} files using CurseForge fingerprints", sources.len());
+        self.mod_metadata_manager.set_content_sources(sources.into_iter());
+    }
 }
 
 impl BackendStateFileWatching {
@@ -1,11 +1,11 @@
-use std::{borrow::Cow, io::{BufRead, Read}, sync::Arc, time::{Duration, Instant, SystemTime}};
+use std::{borrow::Cow, io::{BufRead, Read, Write}, sync::Arc, time::{Duration, Instant, SystemTime}};
 
 use auth::{credentials::AccountCredentials, models::MinecraftAccessToken, secret::PlatformSecretStorage};
 use bridge::{
-    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget}, instance::{ContentSummary, ContentType}, keep_alive::KeepAlive, message::{AccountCapesResult, AccountSkinResult, BackendConfigWithPassword, EmbeddedOrRaw, LogFiles, MessageToBackend, MessageToFrontend}, meta::MetadataResult, modal_action::{ModalAction, ModalActionVisitUrl, ProgressTracker, ProgressTrackerFinishType}, safe_path::SafePath, serial::AtomicOptionSerial
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget}, instance::{ContentDependencyIssueKind, ContentSummary, ContentType, InstanceContentID}, keep_alive::KeepAlive, message::{AccountCapesResult, AccountSkinResult, BackendConfigWithPassword, EmbeddedOrRaw, LogFiles, MessageToBackend, MessageToFrontend}, meta::MetadataResult, modal_action::{ModalAction, ModalActionVisitUrl, ProgressTracker, ProgressTrackerFinishType}, safe_path::SafePath, serial::AtomicOptionSerial
 };
 use futures::TryFutureExt;
-use schema::{auxiliary::AuxiliaryContentMeta, content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeModLoaderType}, minecraft_profile::{MinecraftProfileResponse, SkinVariant}, modrinth::{ModrinthLoader, ModrinthSideRequirement}, version::{LaunchArgument, LaunchArgumentValue}};
+use schema::{auxiliary::AuxiliaryContentMeta, backend_config::{SyncMergeStrategy, SyncTargets}, content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeModLoaderType}, instance::InstanceKind, instance_snapshot::InstanceSnapshotReason, loader::Loader, minecraft_profile::{MinecraftProfileResponse, SkinVariant}, modrinth::{ModrinthLoader, ModrinthSideRequirement}, version::{LaunchArgument, LaunchArgumentValue}};
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use tokio::{io::AsyncBufReadExt, sync::{Semaphore, TryAcquireError}};
@@ -13,7 +13,7 @@ use ustr::Ustr;
 use uuid::Uuid;
 
 use crate::{
-    BackendState, CachedMinecraftProfile, FolderChanges, LoginError, account::BackendAccount, arcfactory::ArcStrFactory, instance::{ContentFolder, Instance}, launch::{ArgumentExpansionKey, LaunchError}, log_reader, metadata::{items::{AssetsIndexMetadataItem, CurseforgeGetFilesMetadataItem, CurseforgeGetModFilesMetadataItem, CurseforgeSearchMetadataItem, FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, MinecraftVersionManifestMetadataItem, MinecraftVersionMetadataItem, ModrinthProjectMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthSearchMetadataItem, ModrinthV3VersionUpdateMetadataItem, ModrinthVersionUpdateMetadataItem, MojangJavaRuntimeComponentMetadataItem, MojangJavaRuntimesMetadataItem, NeoforgeInstallerMavenMetadataItem, VersionUpdateParameters, VersionV3LoaderFields, VersionV3UpdateParameters}, manager::MetaLoadError}, mod_metadata::{ContentUpdateAction, ContentUpdateKey}, skin_manager::SkinManager
+    BackendState, CachedMinecraftProfile, FolderChanges, LoginError, account::BackendAccount, arcfactory::ArcStrFactory, instance::{ContentFolder, Instance}, launch::{ArgumentExpansionKey, LaunchError}, launch_hooks::LaunchHooks, log_reader, metadata::{items::{AssetsIndexMetadataItem, CurseforgeGetFilesMetadataItem, CurseforgeGetModFilesMetadataItem, CurseforgeSearchMetadataItem, FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, MinecraftVersionManifestMetadataItem, MinecraftVersionMetadataItem, ModrinthProjectMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthSearchMetadataItem, ModrinthV3VersionUpdateMetadataItem, ModrinthVersionUpdateMetadataItem, MojangJavaRuntimeComponentMetadataItem, MojangJavaRuntimesMetadataItem, NeoforgeInstallerMavenMetadataItem, QuiltLoaderManifestMetadataItem, VersionUpdateParameters, VersionV3LoaderFields, VersionV3UpdateParameters}, manager::MetaLoadError}, mod_metadata::{ContentUpdateAction, ContentUpdateKey}, skin_manager::SkinManager
 };
 
 impl BackendState {
@@ -32,6 +32,10 @@ impl BackendState {
                             let (result, handle) = meta.fetch_with_keepalive(&FabricLoaderManifestMetadataItem, force_reload).await;
                             (result.map(MetadataResult::FabricLoaderManifest), handle)
                         },
+                        bridge::meta::MetadataRequest::QuiltLoaderManifest => {
+                            let (result, handle) = meta.fetch_with_keepalive(&QuiltLoaderManifestMetadataItem, force_reload).await;
+                            (result.map(MetadataResult::QuiltLoaderManifest), handle)
+                        },
                         bridge::meta::MetadataRequest::ForgeMavenManifest => {
                             let (result, handle) = meta.fetch_with_keepalive(&ForgeInstallerMavenMetadataItem, force_reload).await;
                             (result.map(MetadataResult::ForgeMavenManifest), handle)
@@ -81,8 +85,16 @@ impl BackendState {
             MessageToBackend::RequestLoadResourcePacks { id } => {
                 tokio::task::spawn(Instance::load_content(self.clone(), id, ContentFolder::ResourcePacks));
             },
-            MessageToBackend::CreateInstance { name, version, loader, icon } => {
-                self.create_instance(&name, &version, loader, icon).await;
+            MessageToBackend::CreateInstance { name, version, loader, kind, icon, channel } => {
+                if let Some(channel) = channel {
+                    let result = self.try_create_instance(&name, &version, loader, kind, icon).await;
+                    _ = channel.send(result.map(Arc::from).map_err(Arc::from));
+                } else {
+                    self.create_instance(&name, &version, loader, kind, icon).await;
+                }
+            },
+            MessageToBackend::Flush { channel } => {
+                _ = channel.send(());
             },
             MessageToBackend::DeleteInstance { id } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
@@ -96,6 +108,7 @@ impl BackendState {
                 self.rename_instance(id, &name).await;
             },
             MessageToBackend::SetInstanceMinecraftVersion { id, version } => {
+                crate::instance_snapshot::take_snapshot(self, id, InstanceSnapshotReason::VersionChange).await;
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
                         configuration.minecraft_version = version;
@@ -103,6 +116,7 @@ impl BackendState {
                 }
             },
             MessageToBackend::SetInstanceLoader { id, loader } => {
+                crate::instance_snapshot::take_snapshot(self, id, InstanceSnapshotReason::LoaderChange).await;
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
                         configuration.loader = loader;
@@ -132,6 +146,18 @@ impl BackendState {
                 }
                 self.apply_syncing_to_instance(id);
             },
+            MessageToBackend::SetInstanceSyncProfile { id, profile } => {
+                if let Some(profile) = &profile && !self.config.write().get().sync_profiles.contains_key(profile) {
+                    self.send.send_error(format!("Unknown sync profile {profile}"));
+                    return;
+                }
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.sync_profile = profile;
+                    });
+                }
+                self.apply_syncing_to_instance(id);
+            },
             MessageToBackend::SetInstanceMemory { id, memory } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
@@ -139,6 +165,13 @@ impl BackendState {
                     });
                 }
             },
+            MessageToBackend::SetInstanceWorldBackups { id, world_backups } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.world_backups = Some(world_backups);
+                    });
+                }
+            },
             MessageToBackend::SetInstanceWrapperCommand { id, wrapper_command } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
@@ -146,6 +179,13 @@ impl BackendState {
                     });
                 }
             },
+            MessageToBackend::SetInstanceHookCommands { id, hook_commands } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.hook_commands = Some(hook_commands);
+                    });
+                }
+            },
             MessageToBackend::SetInstanceJvmFlags { id, jvm_flags } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
@@ -153,6 +193,60 @@ impl BackendState {
                     });
                 }
             },
+            MessageToBackend::SetInstanceEnvironmentVariables { id, environment_variables } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.environment_variables = Some(environment_variables);
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceGameArguments { id, game_arguments } => {
+                if crate::launch::has_multiline_game_argument(&game_arguments.arguments) {
+                    self.send.send_error("Game arguments can't contain line breaks");
+                    return;
+                }
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.game_arguments = Some(game_arguments);
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceWindow { id, window } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.window = Some(window);
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceGroup { id, group } => {
+                let group = group.as_deref().map(str::trim).filter(|group| !group.is_empty()).map(Arc::from);
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.group = group;
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceTags { id, tags } => {
+                let mut normalized_tags: Vec<Arc<str>> = Vec::with_capacity(tags.len());
+                for tag in &tags {
+                    let tag = tag.trim();
+                    if !tag.is_empty() && !normalized_tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                        normalized_tags.push(tag.into());
+                    }
+                }
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.tags = normalized_tags;
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceFavourite { id, favourite } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.favourite = favourite;
+                    });
+                }
+            },
             MessageToBackend::SetInstanceJvmBinary { id, jvm_binary } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
@@ -243,7 +337,16 @@ impl BackendState {
                     return;
                 }
 
-                for mut process in instance.processes.drain(..) {
+                instance.stop_requested = true;
+
+                let is_server = instance.configuration.get().kind == InstanceKind::Server;
+                for mut process in std::mem::take(&mut instance.processes) {
+                    // Servers are asked to stop first so that the world gets saved, a second request kills them
+                    if is_server && let Some(mut stdin) = process.stdin.take() && stdin.write_all(b"stop\n").and_then(|_| stdin.flush()).is_ok() {
+                        instance.processes.push(process);
+                        continue;
+                    }
+
                     let result = process.kill();
                     if result.is_err() {
                         self.send.send_error("Failed to kill instance");
@@ -257,11 +360,12 @@ impl BackendState {
             MessageToBackend::StartInstance {
                 id,
                 quick_play,
+                screen_size,
                 modal_action,
             } => {
                 let keepalive = KeepAlive::new();
 
-                let (dot_minecraft, configuration) = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                let (root_path, dot_minecraft, configuration, name) = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     if let Some(launch_keepalive) = &instance.launch_keepalive && launch_keepalive.is_alive() {
                         modal_action.set_error_message("Can't launch instance, already launching".into());
                         modal_action.set_finished();
@@ -275,7 +379,7 @@ impl BackendState {
                     });
                     self.send.send(instance.create_modify_message());
 
-                    (instance.dot_minecraft_path.clone(), instance.configuration.get().clone())
+                    (instance.root_path.clone(), instance.dot_minecraft_path.clone(), instance.configuration.get().clone(), instance.name)
                 } else {
                     self.send.send_error("Can't launch instance, unknown id");
                     modal_action.set_error_message("Can't launch instance, unknown id".into());
@@ -294,13 +398,36 @@ impl BackendState {
                     }
                 }
 
-                let Some(login_info) = self.get_login_info(&modal_action, configuration.preferred_account).await else {
-                    modal_action.set_error_message("Unable to log in to Minecraft account".into());
+                let is_server = configuration.kind == InstanceKind::Server;
+                if is_server && !crate::launch::is_server_eula_accepted(&dot_minecraft) {
+                    self.send.send(MessageToFrontend::ServerEulaRequired { id, name });
                     return;
+                }
+
+                // Dedicated servers don't need an account, and file syncing only covers client files
+                let login_info = if is_server {
+                    None
+                } else {
+                    let Some(login_info) = self.get_login_info(&modal_action, configuration.preferred_account).await else {
+                        modal_action.set_error_message("Unable to log in to Minecraft account".into());
+                        return;
+                    };
+
+                    crate::world_backup::backup_before_launch(self, id, &modal_action).await;
+
+                    Some(login_info)
+                };
+
+                let prelaunch = async {
+                    if is_server {
+                        self.prelaunch_apply_modpacks(id, &modal_action).await
+                    } else {
+                        self.prelaunch(id, &modal_action).await
+                    }
                 };
 
                 let add_mods = tokio::select! {
-                    add_mods = self.prelaunch(id, &modal_action) => add_mods,
+                    add_mods = prelaunch => add_mods,
                     _ = modal_action.request_cancel.cancelled() => {
                         self.send.send(MessageToFrontend::CloseModal);
                         return;
@@ -312,10 +439,27 @@ impl BackendState {
                     return;
                 }
 
+                let issues = crate::dependency_analyzer::analyze_instance(self, id, false).await;
+                let serious_issues = issues.iter().filter(|issue| !matches!(issue.kind, ContentDependencyIssueKind::Discouraged { .. })).count();
+                if serious_issues > 0 {
+                    self.send.send_warning(format!("Found {serious_issues} mod dependency issue(s), use 'Check dependencies' on the mods page for details"));
+                }
+
                 let launch_tracker = ProgressTracker::new(Arc::from("Launching"), self.send.clone());
                 modal_action.trackers.push(launch_tracker.clone());
 
-                let result = self.launcher.launch(&self.redirecting_http_client, dot_minecraft, configuration, quick_play, login_info, add_mods, &launch_tracker, &modal_action).await;
+                let (offline, java_runtimes, instance_defaults) = {
+                    let mut config = self.config.write();
+                    let config = config.get();
+                    (config.offline_mode, config.java_runtimes.clone(), config.instance_defaults.clone())
+                };
+                let configuration = configuration.resolve_defaults(&instance_defaults);
+                let hooks = LaunchHooks::new(id, name, root_path, dot_minecraft.clone(), configuration.hook_commands.as_ref(), self.send.clone());
+                let result = if let Some(login_info) = login_info {
+                    self.launcher.launch(&self.redirecting_http_client, dot_minecraft, configuration, quick_play, screen_size, login_info, add_mods, offline, &java_runtimes, &hooks, &launch_tracker, &modal_action).await
+                } else {
+                    self.launcher.launch_server(&self.redirecting_http_client, dot_minecraft, configuration, add_mods, &java_runtimes, &hooks, &launch_tracker, &modal_action).await
+                };
 
                 if matches!(result, Err(LaunchError::CancelledByUser)) {
                     self.send.send(MessageToFrontend::CloseModal);
@@ -327,17 +471,20 @@ impl BackendState {
                     Ok(mut child) => {
                         if !self.config.write().get().dont_open_game_output_when_launching {
                             if let Some(stdout) = child.stdout.take() {
-                                log_reader::start_game_output(stdout, child.stderr.take(), self.send.clone());
+                                log_reader::start_game_output(hooks.game_output().clone(), stdout, child.stderr.take());
                             }
                         }
 
-                        // Close handles if unused
+                        // Close handles if unused, stdin is kept so that input can be sent to the process
                         child.stderr.take();
-                        child.stdin.take();
                         child.stdout.take();
 
                         if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                            if instance.processes.is_empty() {
+                                instance.stop_requested = false;
+                            }
                             instance.processes.push(child);
+                            instance.post_exit_hook = hooks.into_post_exit_hook();
                             instance.update_session();
                         }
                     },
@@ -350,6 +497,56 @@ impl BackendState {
                 launch_tracker.set_finished(ProgressTrackerFinishType::from_err(is_err));
                 launch_tracker.notify();
             },
+            MessageToBackend::AcceptServerEula { id } => {
+                let dot_minecraft = if let Some(instance) = self.instance_state.read().instances.get(id) {
+                    instance.dot_minecraft_path.clone()
+                } else {
+                    return;
+                };
+
+                if let Err(err) = crate::launch::accept_server_eula(&dot_minecraft) {
+                    self.send.send_error(format!("Unable to write eula.txt: {}", err));
+                }
+            },
+            MessageToBackend::SendGameInput { id, line } => {
+                let mut instance_state = self.instance_state.write();
+                let Some(instance) = instance_state.instances.get_mut(id) else {
+                    self.send.send_error("Can't send input, unknown id");
+                    return;
+                };
+
+                // The client's stdin is owned by the launch wrapper, only servers read commands from it
+                if instance.configuration.get().kind != InstanceKind::Server {
+                    self.send.send_error("Can't send input, only servers accept console input");
+                    return;
+                }
+
+                let Some(stdin) = instance.processes.last_mut().and_then(|process| process.stdin.as_mut()) else {
+                    self.send.send_error("Can't send input, instance isn't running");
+                    return;
+                };
+
+                let result = stdin.write_all(line.trim_end_matches(['\r', '\n']).as_bytes())
+                    .and_then(|_| stdin.write_all(b"\n"))
+                    .and_then(|_| stdin.flush());
+                if let Err(err) = result {
+                    log::error!("Failed to write to process stdin: {:?}", err);
+                    self.send.send_error(format!("Failed to send input: {}", err));
+                }
+            },
+            MessageToBackend::RunRconCommand { id, command, channel } => {
+                let dot_minecraft = if let Some(instance) = self.instance_state.read().instances.get(id) {
+                    instance.dot_minecraft_path.clone()
+                } else {
+                    _ = channel.send(Err("Unknown instance".into()));
+                    return;
+                };
+
+                tokio::task::spawn(async move {
+                    let result = crate::rcon::run_local_command(&dot_minecraft, &command).await;
+                    _ = channel.send(result.map_err(|err| err.to_string().into()));
+                });
+            },
             MessageToBackend::SetContentEnabled { id, content_ids: mod_ids, enabled } => {
                 let mut instance_state = self.instance_state.write();
                 let Some(instance) = instance_state.instances.get_mut(id) else {
@@ -590,6 +787,8 @@ impl BackendState {
                     return;
                 }
 
+                self.identify_curseforge_content(&content).await;
+
                 let tracker = ProgressTracker::new("Checking content".into(), self.send.clone());
                 tracker.set_total(content.len());
                 modal_action.trackers.push(tracker.clone());
@@ -606,6 +805,11 @@ impl BackendState {
                     game_versions: [version].into(),
                 };
 
+                let quilt_mod_params = &VersionUpdateParameters {
+                    loaders: [ModrinthLoader::Quilt].into(),
+                    game_versions: [version].into(),
+                };
+
                 let forge_mod_params = &VersionUpdateParameters {
                     loaders: [ModrinthLoader::Forge].into(),
                     game_versions: [version].into(),
@@ -661,6 +865,12 @@ impl BackendState {
                                                 params: fabric_mod_params.clone()
                                             }).await
                                         },
+                                        ContentType::Quilt => {
+                                            meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                                sha1: hex::encode(summary.content_summary.hash).into(),
+                                                params: quilt_mod_params.clone()
+                                            }).await
+                                        },
                                         ContentType::Forge | ContentType::LegacyForge => {
                                             meta.fetch(&ModrinthVersionUpdateMetadataItem {
                                                 sha1: hex::encode(summary.content_summary.hash).into(),
@@ -739,12 +949,21 @@ impl BackendState {
                                         ContentType::Fabric => {
                                             Some(CurseforgeModLoaderType::Fabric as u32)
                                         },
+                                        ContentType::Quilt => {
+                                            Some(CurseforgeModLoaderType::Quilt as u32)
+                                        },
                                         ContentType::Forge | ContentType::LegacyForge => {
                                             Some(CurseforgeModLoaderType::Forge as u32)
                                         },
                                         ContentType::NeoForge => {
                                             Some(CurseforgeModLoaderType::NeoForge as u32)
                                         },
+                                        ContentType::JavaModule | ContentType::Unknown => {
+                                            match loader.as_curseforge_loader() {
+                                                CurseforgeModLoaderType::Any => None,
+                                                mod_loader_type => Some(mod_loader_type as u32),
+                                            }
+                                        },
                                         _ => None
                                     };
 
@@ -837,106 +1056,29 @@ impl BackendState {
                 tracker.set_finished(ProgressTrackerFinishType::Normal);
                 modal_action.set_finished();
             },
-            MessageToBackend::UpdateContent { instance: id, content_id: mod_id, modal_action } => {
+            MessageToBackend::UpdateContent { instance: id, content_ids, modal_action } => {
                 let content_install = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     let configuration = instance.configuration.get();
                     let (loader, minecraft_version) = (configuration.loader, configuration.minecraft_version);
-                    let Some((mod_summary, _)) = instance.try_get_content(mod_id) else {
-                        self.send.send_error("Can't update mod in instance, unknown mod id");
-                        modal_action.set_finished();
-                        return;
-                    };
 
-                    let Some(update_info) = self.mod_metadata_manager.updates.read().get(&ContentUpdateKey {
-                        hash: mod_summary.content_summary.hash,
-                        loader: loader,
-                        version: minecraft_version
-                    }).cloned() else {
-                        self.send.send_error("Can't update mod in instance, missing update action");
+                    let mut files = Vec::with_capacity(content_ids.len());
+                    for mod_id in content_ids.iter() {
+                        match self.create_content_update_file(instance, *mod_id, loader, minecraft_version) {
+                            Ok(file) => files.push(file),
+                            Err(error) => self.send.send_error(format!("Can't update mod in instance, {error}")),
+                        }
+                    }
+
+                    if files.is_empty() {
                         modal_action.set_finished();
                         return;
-                    };
-
-                    match update_info {
-                        ContentUpdateAction::ErrorNotFound => {
-                            self.send.send_error("Can't update mod in instance, 404 not found");
-                            modal_action.set_finished();
-                            return;
-                        },
-                        ContentUpdateAction::ErrorInvalidHash => {
-                            self.send.send_error("Can't update mod in instance, returned invalid hash");
-                            modal_action.set_finished();
-                            return;
-                        },
-                        ContentUpdateAction::AlreadyUpToDate => {
-                            self.send.send_error("Can't update mod in instance, already up-to-date");
-                            modal_action.set_finished();
-                            return;
-                        },
-                        ContentUpdateAction::ManualInstall => {
-                            self.send.send_error("Can't update mod in instance, mod was manually installed");
-                            modal_action.set_finished();
-                            return;
-                        },
-                        ContentUpdateAction::Modrinth { file, project_id } => {
-                            let mut path = mod_summary.path.with_file_name(&*file.filename);
-                            if !mod_summary.enabled {
-                                path.add_extension("disabled");
-                            }
-                            debug_assert!(path.is_absolute());
-                            ContentInstall {
-                                target: InstallTarget::Instance(id),
-                                loader_hint: loader,
-                                version_hint: Some(minecraft_version.into()),
-                                files: [ContentInstallFile {
-                                    replace_old: Some(mod_summary.path.clone()),
-                                    path: bridge::install::ContentInstallPath::Raw(path.into()),
-                                    download: ContentDownload::Url {
-                                        url: file.url.clone(),
-                                        sha1: file.hashes.sha1.clone(),
-                                        size: file.size,
-                                    },
-                                    content_source: ContentSource::ModrinthProject { project_id },
-                                }].into(),
-                            }
-                        },
-                        ContentUpdateAction::Curseforge { file, project_id } => {
-                            let mut path = mod_summary.path.with_file_name(&*file.file_name);
-                            if !mod_summary.enabled {
-                                path.add_extension("disabled");
-                            }
-                            debug_assert!(path.is_absolute());
-
-                            let sha1 = file.hashes.iter()
-                                .find(|hash| hash.algo == 1).map(|hash| &hash.value);
-                            let Some(sha1) = sha1 else {
-                                self.send.send_error("Can't update mod in instance, missing sha1 hash");
-                                modal_action.set_finished();
-                                return;
-                            };
-
-                            let Some(url) = file.download_url.clone() else {
-                                self.send.send_error("Can't update mod in instance, author has blocked third party downloads");
-                                modal_action.set_finished();
-                                return;
-                            };
+                    }
 
-                            ContentInstall {
-                                target: InstallTarget::Instance(id),
-                                loader_hint: loader,
-                                version_hint: Some(minecraft_version.into()),
-                                files: [ContentInstallFile {
-                                    replace_old: Some(mod_summary.path.clone()),
-                                    path: bridge::install::ContentInstallPath::Raw(path.into()),
-                                    download: ContentDownload::Url {
-                                        url,
-                                        sha1: sha1.clone(),
-                                        size: file.file_length as usize,
-                                    },
-                                    content_source: ContentSource::CurseforgeProject { project_id },
-                                }].into(),
-                            }
-                        },
+                    ContentInstall {
+                        target: InstallTarget::Instance(id),
+                        loader_hint: loader,
+                        version_hint: Some(minecraft_version.into()),
+                        files: files.into(),
                     }
                 } else {
                     self.send.send_error("Can't update mod in instance, unknown instance id");
@@ -944,6 +1086,9 @@ impl BackendState {
                     return;
                 };
 
+                // A single snapshot for the whole batch, so that updating many mods doesn't push the pre-update state out
+                crate::instance_snapshot::take_snapshot(self, id, InstanceSnapshotReason::UpdateContent).await;
+
                 self.install_content(content_install, modal_action.clone()).await;
                 modal_action.set_finished();
                 self.send.send(MessageToFrontend::Refresh);
@@ -1112,12 +1257,55 @@ impl BackendState {
                     }
                 }
             },
+            MessageToBackend::GetInstanceSnapshots { id, channel } => {
+                let root_path = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.root_path.clone()
+                } else {
+                    return;
+                };
+                let snapshots = tokio::task::spawn_blocking(move || crate::instance_snapshot::list_snapshots(&root_path)).await.unwrap();
+                _ = channel.send(snapshots.into());
+            },
+            MessageToBackend::RestoreInstanceSnapshot { id, snapshot, modal_action } => {
+                crate::instance_snapshot::restore_snapshot(self, id, snapshot, modal_action).await;
+            },
+            MessageToBackend::AnalyzeContentDependencies { id, channel } => {
+                let issues = crate::dependency_analyzer::analyze_instance(self, id, true).await;
+                _ = channel.send(issues.into());
+            },
+            MessageToBackend::BackupWorld { id, world, modal_action } => {
+                crate::world_backup::backup_world(self, id, world, modal_action).await;
+            },
+            MessageToBackend::GetWorldBackups { id, world, channel } => {
+                let root_path = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.root_path.clone()
+                } else {
+                    return;
+                };
+                let backups = tokio::task::spawn_blocking(move || crate::world_backup::list_backups(&root_path, &world)).await.unwrap();
+                _ = channel.send(backups.into());
+            },
+            MessageToBackend::RestoreWorldBackup { id, world, backup, modal_action } => {
+                crate::world_backup::restore_backup(self, id, world, backup, modal_action).await;
+            },
             MessageToBackend::GetImportFromOtherLauncherJob { channel, launcher, path } => {
                 let result = crate::launcher_import::get_import_from_other_launcher_job(launcher, path);
                 _ = channel.send(result);
             },
-            MessageToBackend::GetSyncState { channel } => {
-                let result = crate::syncing::get_sync_state(&self.config.write().get().sync_targets, &mut *self.instance_state.write(), &self.directories);
+            MessageToBackend::GetSyncState { profile, channel } => {
+                let mut config = self.config.write();
+                let config = config.get();
+                let Some(sync_targets) = config.sync_targets_for(profile.as_deref()) else {
+                    self.send.send_error(format!("Unknown sync profile {}", profile.as_deref().unwrap_or_default()));
+                    return;
+                };
+                let Some(synced_dir) = crate::syncing::synced_dir_for(profile.as_deref(), &self.directories) else {
+                    self.send.send_error("Invalid sync profile name");
+                    return;
+                };
+                let profiles = config.sync_profiles.keys().cloned().collect();
+                let members = crate::syncing::profile_members(profile.as_deref(), &mut *self.instance_state.write());
+                let result = crate::syncing::get_sync_state(sync_targets, profile, profiles, synced_dir.into(), &members);
 
                 match result {
                     Ok(state) => {
@@ -1128,13 +1316,80 @@ impl BackendState {
                     },
                 }
             },
-            MessageToBackend::SetSyncing { target, is_file, value } => {
+            MessageToBackend::GetSyncProfiles { channel } => {
+                let profiles = self.config.write().get().sync_profiles.keys().cloned().collect();
+                _ = channel.send(profiles);
+            },
+            MessageToBackend::CreateSyncProfile { name } => {
+                let name: Arc<str> = name.trim().into();
+                if !crate::syncing::is_valid_profile_name(&name) {
+                    self.send.send_error(format!("Invalid sync profile name: {name}"));
+                    return;
+                }
+
+                let mut config = self.config.write();
+                if config.get().sync_profiles.contains_key(&name) {
+                    self.send.send_error(format!("A sync profile named {name} already exists"));
+                    return;
+                }
+
+                if let Some(synced_dir) = crate::syncing::synced_dir_for(Some(&name), &self.directories)
+                    && let Err(error) = std::fs::create_dir_all(&synced_dir)
+                {
+                    self.send.send_error(format!("Unable to create sync profile folder: {error}"));
+                    return;
+                }
+
+                config.modify(|config| {
+                    config.sync_profiles.insert(name, SyncTargets::default());
+                });
+            },
+            MessageToBackend::DeleteSyncProfile { name } => {
+                let mut config = self.config.write();
+                let Some(sync_targets) = config.get().sync_profiles.get(&name).cloned() else {
+                    return;
+                };
+
+                // The profile folder is kept, only the links into it are removed
+                if let Some(synced_dir) = crate::syncing::synced_dir_for(Some(&name), &self.directories) {
+                    for folder in sync_targets.folders.iter() {
+                        if let Err(error) = crate::syncing::disable_all(folder, false, &synced_dir, &self.directories) {
+                            self.send.send_error(format!("Error while disabling syncing: {error}"));
+                            return;
+                        }
+                    }
+                }
+
+                config.modify(|config| {
+                    config.sync_profiles.remove(&name);
+                });
+                drop(config);
+
+                for instance in self.instance_state.write().instances.iter_mut() {
+                    if instance.configuration.get().sync_profile.as_deref() == Some(&*name) {
+                        instance.configuration.modify(|configuration| {
+                            configuration.sync_profile = None;
+                        });
+                    }
+                }
+            },
+            MessageToBackend::SetSyncing { profile, target, is_file, value } => {
                 let mut write = self.config.write();
 
+                if write.get().sync_targets_for(profile.as_deref()).is_none() {
+                    self.send.send_error(format!("Unknown sync profile {}", profile.as_deref().unwrap_or_default()));
+                    return;
+                }
+                let Some(synced_dir) = crate::syncing::synced_dir_for(profile.as_deref(), &self.directories) else {
+                    self.send.send_error("Invalid sync profile name");
+                    return;
+                };
+
                 let result = if value {
-                    crate::syncing::enable_all(&target, is_file, &mut *self.instance_state.write(), &self.directories)
+                    let members = crate::syncing::profile_members(profile.as_deref(), &mut *self.instance_state.write());
+                    crate::syncing::enable_all(&target, is_file, &synced_dir, &members)
                 } else {
-                    crate::syncing::disable_all(&target, is_file, &self.directories).map(|_| true)
+                    crate::syncing::disable_all(&target, is_file, &synced_dir, &self.directories).map(|_| true)
                 };
 
                 match result {
@@ -1151,10 +1406,13 @@ impl BackendState {
                 }
 
                 write.modify(|config| {
+                    let Some(sync_targets) = config.sync_targets_for_mut(profile.as_deref()) else {
+                        return;
+                    };
                     let (set, other_set) = if is_file {
-                        (&mut config.sync_targets.files, &mut config.sync_targets.folders)
+                        (&mut sync_targets.files, &mut sync_targets.folders)
                     } else {
-                        (&mut config.sync_targets.folders, &mut config.sync_targets.files)
+                        (&mut sync_targets.folders, &mut sync_targets.files)
                     };
 
                     other_set.remove(&target);
@@ -1165,6 +1423,18 @@ impl BackendState {
                     }
                 });
             },
+            MessageToBackend::SetSyncStrategy { profile, target, strategy } => {
+                self.config.write().modify(|config| {
+                    let Some(sync_targets) = config.sync_targets_for_mut(profile.as_deref()) else {
+                        return;
+                    };
+                    if strategy == SyncMergeStrategy::default_for(&target) {
+                        sync_targets.strategies.remove(&target);
+                    } else {
+                        sync_targets.strategies.insert(target, strategy);
+                    }
+                });
+            },
             MessageToBackend::GetBackendConfiguration { channel } => {
                 let configuration = self.config.write().get().clone();
                 let proxy_password = if configuration.proxy.enabled && configuration.proxy.auth_enabled {
@@ -1397,6 +1667,47 @@ impl BackendState {
                     config.dont_open_game_output_when_launching = !value;
                 });
             },
+            MessageToBackend::SetOfflineMode { value } => {
+                self.config.write().modify(|config| {
+                    config.offline_mode = value;
+                });
+            },
+            MessageToBackend::SetPollServerHistory { value } => {
+                self.config.write().modify(|config| {
+                    config.dont_poll_server_history = !value;
+                });
+            },
+            MessageToBackend::RefreshJavaRuntimes { channel } => {
+                let backend = self.clone();
+                let discovered = tokio::task::spawn_blocking(move || backend.launcher.discover_java_runtimes()).await.unwrap();
+                self.config.write().modify(|config| {
+                    config.java_runtimes.discovered = discovered.clone();
+                });
+                _ = channel.send(discovered.into());
+            },
+            MessageToBackend::SetDefaultJavaRuntime { major_version, path } => {
+                self.config.write().modify(|config| {
+                    if let Some(path) = path {
+                        config.java_runtimes.defaults.insert(major_version, path);
+                    } else {
+                        config.java_runtimes.defaults.remove(&major_version);
+                    }
+                });
+            },
+            MessageToBackend::SetPreferDiscoveredJavaRuntimes { value } => {
+                self.config.write().modify(|config| {
+                    config.java_runtimes.prefer_discovered = value;
+                });
+            },
+            MessageToBackend::SetInstanceDefaults { defaults } => {
+                if crate::launch::has_multiline_game_argument(&defaults.game_arguments) {
+                    self.send.send_error("Game arguments can't contain line breaks");
+                    return;
+                }
+                self.config.write().modify(|config| {
+                    config.instance_defaults = defaults;
+                });
+            },
             MessageToBackend::SetProxyConfiguration { config, password } => {
                 self.config.write().modify(|backend_config| {
                     backend_config.proxy = config;
@@ -1545,11 +1856,17 @@ impl BackendState {
 
                 }
             },
+            MessageToBackend::ExportInstance { id, format, path, include, modal_action } => {
+                crate::instance_export::export_instance(self, id, format, path, include, modal_action).await;
+            },
             MessageToBackend::InstallUpdate { update, modal_action } => {
                 tokio::task::spawn(crate::update::install_update(self.redirecting_http_client.clone(), self.directories.clone(), self.send.clone(), update, modal_action));
             },
-            MessageToBackend::ImportFromOtherLauncher { launcher, import_job, modal_action } => {
-                crate::launcher_import::import_from_other_launcher(self, launcher, import_job, modal_action).await;
+            MessageToBackend::ImportFromOtherLauncher { launcher, import_job, modal_action, channel } => {
+                let result = crate::launcher_import::import_from_other_launcher(self, launcher, import_job, modal_action).await;
+                if let Some(channel) = channel {
+                    _ = channel.send(result);
+                }
             },
             MessageToBackend::GetAccountSkin { account, result } => {
                 let backend = self.clone();
@@ -1684,6 +2001,48 @@ impl BackendState {
                     self.cached_minecraft_profiles.write().insert(account, CachedMinecraftProfile::new(profile));
                 }
             },
+            MessageToBackend::AddServer { ids, name, ip, resource_packs } => {
+                let mut added = 0;
+                for id in ids.iter() {
+                    match crate::servers_dat::add_server(self, *id, &name, &ip, resource_packs) {
+                        Ok(()) => added += 1,
+                        Err(crate::servers_dat::ServersDatError::AlreadyExists(_)) if ids.len() > 1 => {},
+                        Err(error) => {
+                            let instance_name = self.instance_state.read().instances.get(*id).map(|instance| instance.name);
+                            match instance_name {
+                                Some(instance_name) => self.send.send_error(format!("Unable to add server to {instance_name}: {error}")),
+                                None => self.send.send_error(format!("Unable to add server: {error}")),
+                            }
+                        },
+                    }
+                }
+                if ids.len() > 1 && added > 0 {
+                    self.send.send_success(format!("Added {ip} to {added} instances"));
+                }
+            },
+            MessageToBackend::EditServer { id, server, name, ip, resource_packs } => {
+                if let Err(error) = crate::servers_dat::edit_server(self, id, &server, &name, &ip, resource_packs) {
+                    self.send.send_error(format!("Unable to edit server: {error}"));
+                }
+            },
+            MessageToBackend::RemoveServer { id, server } => {
+                if let Err(error) = crate::servers_dat::remove_server(self, id, &server) {
+                    self.send.send_error(format!("Unable to remove server: {error}"));
+                }
+            },
+            MessageToBackend::MoveServer { id, from, to } => {
+                if let Err(error) = crate::servers_dat::move_server(self, id, &from, &to) {
+                    self.send.send_error(format!("Unable to move server: {error}"));
+                }
+            },
+            MessageToBackend::SetServerResourcePackPolicy { id, server, policy } => {
+                if let Err(error) = crate::servers_dat::set_resource_pack_policy(self, id, &server, policy) {
+                    self.send.send_error(format!("Unable to change server resource packs: {error}"));
+                }
+            },
+            MessageToBackend::SetServerFavourite { address, favourite } => {
+                crate::server_history::set_favourite(&self, address, favourite);
+            },
             MessageToBackend::RequestSkinLibrary => {
                 SkinManager::load_skin_library(&self);
             },
@@ -1790,6 +2149,72 @@ impl BackendState {
         }
     }
 
+    fn create_content_update_file(&self, instance: &Instance, mod_id: InstanceContentID, loader: Loader, minecraft_version: Ustr) -> Result<ContentInstallFile, &'static str> {
+        let Some((mod_summary, _)) = instance.try_get_content(mod_id) else {
+            return Err("unknown mod id");
+        };
+
+        let Some(update_info) = self.mod_metadata_manager.updates.read().get(&ContentUpdateKey {
+            hash: mod_summary.content_summary.hash,
+            loader: loader,
+            version: minecraft_version
+        }).cloned() else {
+            return Err("missing update action");
+        };
+
+        match update_info {
+            ContentUpdateAction::ErrorNotFound => Err("404 not found"),
+            ContentUpdateAction::ErrorInvalidHash => Err("returned invalid hash"),
+            ContentUpdateAction::AlreadyUpToDate => Err("already up-to-date"),
+            ContentUpdateAction::ManualInstall => Err("mod was manually installed"),
+            ContentUpdateAction::Modrinth { file, project_id } => {
+                let mut path = mod_summary.path.with_file_name(&*file.filename);
+                if !mod_summary.enabled {
+                    path.add_extension("disabled");
+                }
+                debug_assert!(path.is_absolute());
+                Ok(ContentInstallFile {
+                    replace_old: Some(mod_summary.path.clone()),
+                    path: bridge::install::ContentInstallPath::Raw(path.into()),
+                    download: ContentDownload::Url {
+                        url: file.url.clone(),
+                        sha1: file.hashes.sha1.clone(),
+                        size: file.size,
+                    },
+                    content_source: ContentSource::ModrinthProject { project_id },
+                })
+            },
+            ContentUpdateAction::Curseforge { file, project_id } => {
+                let mut path = mod_summary.path.with_file_name(&*file.file_name);
+                if !mod_summary.enabled {
+                    path.add_extension("disabled");
+                }
+                debug_assert!(path.is_absolute());
+
+                let sha1 = file.hashes.iter()
+                    .find(|hash| hash.algo == 1).map(|hash| &hash.value);
+                let Some(sha1) = sha1 else {
+                    return Err("missing sha1 hash");
+                };
+
+                let Some(url) = file.download_url.clone() else {
+                    return Err("author has blocked third party downloads");
+                };
+
+                Ok(ContentInstallFile {
+                    replace_old: Some(mod_summary.path.clone()),
+                    path: bridge::install::ContentInstallPath::Raw(path.into()),
+                    download: ContentDownload::Url {
+                        url,
+                        sha1: sha1.clone(),
+                        size: file.file_length as usize,
+                    },
+                    content_source: ContentSource::CurseforgeProject { project_id },
+                })
+            },
+        }
+    }
+
     pub async fn get_minecraft_profile(&self, account: Uuid) -> Option<MinecraftProfileResponse> {
         if let Some(cached_profile) = self.cached_minecraft_profiles.read().get(&account) {
             if cached_profile.is_valid(Instant::now()) {
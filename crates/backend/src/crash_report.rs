@@ -0,0 +1,206 @@
+use std::{path::{Path, PathBuf}, sync::Arc, time::SystemTime};
+
+use bridge::crash_report::{CrashReportAnalysis, CrashReportKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::log_reader;
+
+const MAX_STACK_TRACE_LINES: usize = 40;
+
+// Ids that show up in every mod list and are never the culprit themselves
+const IGNORED_MOD_IDS: &[&str] = &[
+    "minecraft", "java", "fabricloader", "fabric-loader", "quilt_loader", "forge", "neoforge", "mixinextras", "fabric-api", "fabric",
+];
+
+// Injector methods merged by mixin are named like handler$zza000$modid$method
+static MIXIN_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:handler|redirect|modify|wrapOperation|wrapWithCondition|localvar|constant|inject)\$[0-9a-z]+\$([a-zA-Z0-9_\-]+)\$"#).unwrap()
+});
+// Forge/NeoForge annotate transformed frames like {re:mixin,pl:mixin:APP:modid.mixins.json:SomeMixin}
+static MIXIN_CONFIG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"pl:mixin:A[A-Z]*:([a-zA-Z0-9_\-]+)(?:\.[a-zA-Z0-9_\-]+)*\.mixins?\.json"#).unwrap()
+});
+// Forge "Mod List:" rows, e.g. "sodium-1.0.jar |Sodium |sodium |1.0 |DONE"
+static FORGE_MOD_LIST_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*\S.*?\|.*?\|\s*([a-z0-9_\-]+)\s*\|"#).unwrap()
+});
+// Fabric "Fabric Mods:" rows, e.g. "sodium: Sodium 0.5.8"
+static FABRIC_MOD_LIST_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s+([a-z0-9_\-]+): "#).unwrap()
+});
+
+pub fn analyze_exit(dot_minecraft_path: &Path, launched_at_ms: Option<i64>, exit_code: Option<i32>) -> CrashReportAnalysis {
+    let since = launched_at_ms
+        .and_then(|ms| u64::try_from(ms).ok())
+        .map(|ms| SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(ms))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let crash_report = find_newest(&dot_minecraft_path.join("crash-reports"), since, |name| name.starts_with("crash-") && name.ends_with(".txt"));
+    if let Some(path) = crash_report && let Ok(content) = std::fs::read_to_string(&path) {
+        return analyze_game_crash_report(&content, path, exit_code);
+    }
+
+    let jvm_report = find_newest(dot_minecraft_path, since, |name| name.starts_with("hs_err_pid") && name.ends_with(".log"));
+    if let Some(path) = jvm_report && let Ok(content) = std::fs::read_to_string(&path) {
+        return analyze_jvm_crash_report(&content, path, exit_code);
+    }
+
+    CrashReportAnalysis {
+        kind: CrashReportKind::Unknown,
+        exit_code,
+        report_path: None,
+        description: None,
+        stack_trace: [].into(),
+        suspected_mods: [].into(),
+    }
+}
+
+fn find_newest(dir: &Path, since: SystemTime, filter: impl Fn(&str) -> bool) -> Option<PathBuf> {
+    let read_dir = std::fs::read_dir(dir).ok()?;
+
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for entry in read_dir.flatten() {
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        if !filter(file_name) {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) else {
+            continue;
+        };
+        if modified < since {
+            continue;
+        }
+        if newest.as_ref().is_none_or(|(newest_modified, _)| modified > *newest_modified) {
+            newest = Some((modified, entry.path()));
+        }
+    }
+    newest.map(|(_, path)| path)
+}
+
+fn analyze_game_crash_report(content: &str, path: PathBuf, exit_code: Option<i32>) -> CrashReportAnalysis {
+    let mut lines = content.lines();
+
+    let mut description = None;
+    for line in lines.by_ref() {
+        if let Some(value) = line.strip_prefix("Description:") {
+            description = Some(value.trim());
+            break;
+        }
+    }
+
+    // The exception follows the description after a blank line and ends at the next blank line
+    let stack_trace: Vec<&str> = lines
+        .skip_while(|line| line.trim().is_empty())
+        .take_while(|line| !line.trim().is_empty())
+        .collect();
+
+    let mut mod_list = Vec::new();
+    let mut in_mod_list = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed == "Mod List:" || trimmed == "Fabric Mods:" || trimmed == "Quilted Fabric Mods:" {
+            in_mod_list = true;
+            continue;
+        }
+        if !in_mod_list {
+            continue;
+        }
+        let entry = FORGE_MOD_LIST_ENTRY.captures(line).or_else(|| FABRIC_MOD_LIST_ENTRY.captures(line));
+        if let Some(entry) = entry {
+            mod_list.push(entry[1].to_string());
+        } else if !line.starts_with(char::is_whitespace) || trimmed.is_empty() {
+            in_mod_list = false;
+        }
+    }
+
+    let suspected_mods = find_suspected_mods(&stack_trace, &mod_list);
+
+    CrashReportAnalysis {
+        kind: CrashReportKind::Game,
+        exit_code,
+        report_path: Some(path.into()),
+        description: description.map(|description| log_reader::replace(description).into()),
+        stack_trace: redact_lines(&stack_trace),
+        suspected_mods,
+    }
+}
+
+fn analyze_jvm_crash_report(content: &str, path: PathBuf, exit_code: Option<i32>) -> CrashReportAnalysis {
+    let mut description = None;
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("# Problematic frame:") {
+            description = lines.next().map(|frame| frame.trim_start_matches('#').trim());
+            break;
+        } else if description.is_none() && (line.starts_with("#  SIG") || line.starts_with("#  EXCEPTION_") || line.starts_with("#  Internal Error")) {
+            description = Some(line.trim_start_matches('#').trim());
+        }
+    }
+
+    let mut stack_trace = Vec::new();
+    let mut in_frames = false;
+    for line in content.lines() {
+        if line.starts_with("Java frames:") || line.starts_with("Native frames:") {
+            in_frames = true;
+            stack_trace.push(line);
+            continue;
+        }
+        if in_frames {
+            if line.trim().is_empty() {
+                in_frames = false;
+            } else {
+                stack_trace.push(line);
+            }
+        }
+    }
+
+    let suspected_mods = find_suspected_mods(&stack_trace, &[]);
+
+    CrashReportAnalysis {
+        kind: CrashReportKind::Jvm,
+        exit_code,
+        report_path: Some(path.into()),
+        description: description.map(|description| log_reader::replace(description).into()),
+        stack_trace: redact_lines(&stack_trace),
+        suspected_mods,
+    }
+}
+
+fn find_suspected_mods(stack_trace: &[&str], mod_list: &[String]) -> Arc<[Arc<str>]> {
+    let mut suspected: Vec<Arc<str>> = Vec::new();
+    let mut add = |id: &str| {
+        if !IGNORED_MOD_IDS.contains(&id) && !suspected.iter().any(|existing| &**existing == id) {
+            suspected.push(id.into());
+        }
+    };
+
+    for line in stack_trace {
+        for captures in MIXIN_HANDLER.captures_iter(line) {
+            add(&captures[1]);
+        }
+        for captures in MIXIN_CONFIG.captures_iter(line) {
+            add(&captures[1]);
+        }
+    }
+
+    // Mods whose id appears as a package segment of a frame
+    for id in mod_list {
+        if id.len() < 3 {
+            continue;
+        }
+        let package_segment = format!(".{id}.");
+        if stack_trace.iter().any(|line| line.trim_start().starts_with("at ") && line.contains(&package_segment)) {
+            add(id);
+        }
+    }
+
+    suspected.into()
+}
+
+fn redact_lines(lines: &[&str]) -> Arc<[Arc<str>]> {
+    lines.iter().take(MAX_STACK_TRACE_LINES).map(|line| Arc::from(&*log_reader::replace(line))).collect()
+}
@@ -0,0 +1,453 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use bridge::instance::{ContentDependencyIssue, ContentDependencyIssueKind, ContentDependencyKind, ContentSummary, ContentType, ContentVersionRequirement, InstanceID};
+use rustc_hash::FxHashMap;
+use schema::modrinth::ModrinthProjectRequest;
+
+use crate::{BackendState, instance::{ContentFolder, Instance}, metadata::items::ModrinthProjectMetadataItem};
+
+// Dependencies that are provided by the game or the loader itself rather than by other content
+const BUILTIN_IDS: &[&str] = &[
+    "java", "fabricloader", "fabric-loader", "quilt_loader", "forge", "neoforge", "javafml", "lowcodefml", "mclanguage",
+];
+
+pub async fn analyze_instance(backend: &Arc<BackendState>, id: InstanceID, lookup_modrinth: bool) -> Vec<ContentDependencyIssue> {
+    let minecraft_version = if let Some(instance) = backend.instance_state.read().instances.get(id) {
+        instance.configuration.get().minecraft_version
+    } else {
+        return Vec::new();
+    };
+
+    let Some(summaries) = Instance::load_content(backend.clone(), id, ContentFolder::Mods).await else {
+        return Vec::new();
+    };
+
+    let mut contents = Vec::new();
+    for summary in summaries.iter().filter(|summary| summary.enabled) {
+        contents.push(summary.content_summary.clone());
+        match &summary.content_summary.extra {
+            ContentType::ModrinthModpack { summaries, .. } => {
+                contents.extend(summaries.iter().flatten().cloned());
+            },
+            ContentType::CurseforgeModpack { summaries, .. } => {
+                contents.extend(summaries.iter().filter_map(|(summary, _)| summary.clone()));
+            },
+            _ => {},
+        }
+    }
+
+    let mut issues = analyze(&contents, minecraft_version.as_str());
+
+    if lookup_modrinth {
+        let mut project_ids: FxHashMap<Arc<str>, Option<Arc<str>>> = FxHashMap::default();
+        for issue in &mut issues {
+            let ContentDependencyIssueKind::Missing { modrinth_project_id } = &mut issue.kind else {
+                continue;
+            };
+            if !project_ids.contains_key(&issue.dependency_id) {
+                let project_id = lookup_modrinth_project(backend, &issue.dependency_id).await;
+                project_ids.insert(issue.dependency_id.clone(), project_id);
+            }
+            *modrinth_project_id = project_ids.get(&issue.dependency_id).cloned().flatten();
+        }
+    }
+
+    issues
+}
+
+async fn lookup_modrinth_project(backend: &Arc<BackendState>, mod_id: &str) -> Option<Arc<str>> {
+    // Mod ids commonly use underscores where the Modrinth slug uses dashes
+    let mut candidates = vec![mod_id.to_string()];
+    if mod_id.contains('_') {
+        candidates.push(mod_id.replace('_', "-"));
+    }
+
+    for candidate in candidates {
+        let request = ModrinthProjectRequest {
+            project_id: candidate.into(),
+        };
+        if let Ok(project) = backend.meta.fetch(&ModrinthProjectMetadataItem(&request)).await {
+            return Some(project.id.clone());
+        }
+    }
+    None
+}
+
+fn analyze(contents: &[Arc<ContentSummary>], minecraft_version: &str) -> Vec<ContentDependencyIssue> {
+    let mut installed: FxHashMap<&str, &str> = FxHashMap::default();
+    for content in contents {
+        if let Some(id) = &content.id {
+            installed.insert(id, content.version_str.strip_prefix('v').unwrap_or(&content.version_str));
+        }
+        for (id, version) in content.provides.iter() {
+            installed.entry(id).or_insert(version);
+        }
+    }
+
+    let minecraft_version_is_release = !minecraft_version.is_empty() && minecraft_version.chars().all(|c| c.is_ascii_digit() || c == '.');
+
+    let mut issues = Vec::new();
+    for content in contents {
+        let content_name = content.name.clone().or_else(|| content.id.clone()).unwrap_or_else(|| "Unknown".into());
+
+        for dependency in content.dependencies.iter() {
+            // Quilt allows maven-style group:id identifiers
+            let dependency_id = dependency.id.rsplit(':').next().unwrap_or(&dependency.id);
+
+            if BUILTIN_IDS.contains(&dependency_id) {
+                continue;
+            }
+
+            let installed_version = if dependency_id == "minecraft" {
+                if !minecraft_version_is_release {
+                    continue;
+                }
+                Some(minecraft_version)
+            } else {
+                installed.get(dependency_id).copied()
+            };
+
+            let kind = match (dependency.kind, installed_version) {
+                (ContentDependencyKind::Required, None) => ContentDependencyIssueKind::Missing {
+                    modrinth_project_id: None,
+                },
+                (ContentDependencyKind::Required, Some(installed_version)) => {
+                    if requirement_matches(&dependency.versions, installed_version) {
+                        continue;
+                    }
+                    ContentDependencyIssueKind::VersionMismatch {
+                        installed_version: installed_version.into(),
+                    }
+                },
+                (_, None) => continue,
+                (ContentDependencyKind::Incompatible, Some(installed_version)) => {
+                    if !requirement_matches(&dependency.versions, installed_version) {
+                        continue;
+                    }
+                    ContentDependencyIssueKind::Incompatible {
+                        installed_version: installed_version.into(),
+                    }
+                },
+                (ContentDependencyKind::Discouraged, Some(installed_version)) => {
+                    if !requirement_matches(&dependency.versions, installed_version) {
+                        continue;
+                    }
+                    ContentDependencyIssueKind::Discouraged {
+                        installed_version: installed_version.into(),
+                    }
+                },
+            };
+
+            issues.push(ContentDependencyIssue {
+                content_name: content_name.clone(),
+                dependency_id: dependency_id.into(),
+                requirement: format!("{}", dependency.versions).into(),
+                kind,
+            });
+        }
+    }
+    issues
+}
+
+fn requirement_matches(requirement: &ContentVersionRequirement, version: &str) -> bool {
+    match requirement {
+        ContentVersionRequirement::Any => true,
+        ContentVersionRequirement::Predicates(predicates) => {
+            predicates.is_empty() || predicates.iter().any(|predicate| predicate_matches(predicate, version))
+        },
+        ContentVersionRequirement::MavenRange(range) => maven_range_matches(range, version),
+    }
+}
+
+// Fabric-style predicate, space separated constraints must all match
+fn predicate_matches(predicate: &str, version: &str) -> bool {
+    predicate.split_whitespace().all(|constraint| constraint_matches(constraint, version))
+}
+
+fn constraint_matches(constraint: &str, version: &str) -> bool {
+    if constraint == "*" {
+        return true;
+    }
+
+    let (operator, target) = if let Some(target) = constraint.strip_prefix(">=") {
+        (">=", target)
+    } else if let Some(target) = constraint.strip_prefix("<=") {
+        ("<=", target)
+    } else if let Some(target) = constraint.strip_prefix('>') {
+        (">", target)
+    } else if let Some(target) = constraint.strip_prefix('<') {
+        ("<", target)
+    } else if let Some(target) = constraint.strip_prefix('=') {
+        ("=", target)
+    } else if let Some(target) = constraint.strip_prefix('~') {
+        ("~", target)
+    } else if let Some(target) = constraint.strip_prefix('^') {
+        ("^", target)
+    } else {
+        ("=", constraint)
+    };
+
+    // Wildcards such as 1.20.x only constrain the components before them
+    if operator == "=" && let Some(wildcard) = target.find(|c| c == 'x' || c == 'X' || c == '*') {
+        let prefix = target[..wildcard].trim_end_matches('.');
+        let version_core = version_core(version);
+        return prefix.is_empty() || version_core == prefix || version_core.starts_with(&format!("{prefix}."));
+    }
+
+    let ordering = compare_versions(version, target);
+    match operator {
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        "=" => ordering == Ordering::Equal,
+        "~" | "^" => {
+            if ordering == Ordering::Less {
+                return false;
+            }
+            let mut components: Vec<u64> = version_core(target).split('.').map(|component| component.parse().unwrap_or(0)).collect();
+            let bump_index = if operator == "~" { 1 } else { 0 };
+            components.resize(components.len().max(bump_index + 1), 0);
+            components[bump_index] += 1;
+            components.truncate(bump_index + 1);
+            let upper = components.iter().map(|component| component.to_string()).collect::<Vec<_>>().join(".");
+            compare_versions(version_core(version), &upper) == Ordering::Less
+        },
+        _ => unreachable!(),
+    }
+}
+
+// Maven-style version range, e.g. [1.0,2.0) or a union like (,1.0],[1.2,)
+fn maven_range_matches(range: &str, version: &str) -> bool {
+    let range = range.trim();
+    if !range.starts_with(['[', '(']) {
+        // A bare version is a soft requirement in maven, any version is acceptable
+        return true;
+    }
+
+    // Ranges come from mod metadata, a malformed one is treated as having no constraint rather than failing the launch
+    let mut sets = Vec::new();
+    let mut remaining = range;
+    while !remaining.is_empty() {
+        let Some(end) = remaining.find([']', ')']) else {
+            return true;
+        };
+        let set = &remaining[..=end];
+        remaining = remaining[end + 1..].trim_start_matches(',').trim();
+
+        let Some(inner) = set.strip_prefix(['[', '(']).and_then(|set| set.get(..set.len() - 1)) else {
+            return true;
+        };
+        sets.push((set.starts_with('['), set.ends_with(']'), inner));
+    }
+
+    sets.into_iter().any(|(lower_inclusive, upper_inclusive, inner)| {
+        if let Some((lower, upper)) = inner.split_once(',') {
+            let (lower, upper) = (lower.trim(), upper.trim());
+            let lower_ok = lower.is_empty() || match compare_versions(version, lower) {
+                Ordering::Greater => true,
+                Ordering::Equal => lower_inclusive,
+                Ordering::Less => false,
+            };
+            let upper_ok = upper.is_empty() || match compare_versions(version, upper) {
+                Ordering::Less => true,
+                Ordering::Equal => upper_inclusive,
+                Ordering::Greater => false,
+            };
+            lower_ok && upper_ok
+        } else {
+            compare_versions(version, inner.trim()) == Ordering::Equal
+        }
+    })
+}
+
+fn version_core(version: &str) -> &str {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version.split('+').next().unwrap_or(version);
+    version.split('-').next().unwrap_or(version)
+}
+
+fn compare_versions(left: &str, right: &str) -> Ordering {
+    let left = left.strip_prefix('v').unwrap_or(left).split('+').next().unwrap_or(left);
+    let right = right.strip_prefix('v').unwrap_or(right).split('+').next().unwrap_or(right);
+
+    let (left_core, left_pre) = left.split_once('-').map(|(core, pre)| (core, Some(pre))).unwrap_or((left, None));
+    let (right_core, right_pre) = right.split_once('-').map(|(core, pre)| (core, Some(pre))).unwrap_or((right, None));
+
+    let ordering = compare_components(left_core, right_core);
+    if ordering != Ordering::Equal {
+        return ordering;
+    }
+
+    match (left_pre, right_pre) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(left_pre), Some(right_pre)) => compare_components(left_pre, right_pre),
+    }
+}
+
+fn compare_components(left: &str, right: &str) -> Ordering {
+    let mut left_components = left.split('.');
+    let mut right_components = right.split('.');
+    loop {
+        let ordering = match (left_components.next(), right_components.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(left), None) => compare_component(left, "0"),
+            (None, Some(right)) => compare_component("0", right),
+            (Some(left), Some(right)) => compare_component(left, right),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn compare_component(left: &str, right: &str) -> Ordering {
+    match (left.parse::<u64>(), right.parse::<u64>()) {
+        (Ok(left), Ok(right)) => left.cmp(&right),
+        (Ok(_), Err(_)) => Ordering::Greater,
+        (Err(_), Ok(_)) => Ordering::Less,
+        (Err(_), Err(_)) => left.cmp(right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use bridge::instance::ContentVersionRequirement;
+
+    use super::{compare_versions, constraint_matches, maven_range_matches, predicate_matches, requirement_matches};
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("v1.2", "1.2"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("0.15.11", "0.16.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_pre_releases() {
+        assert_eq!(compare_versions("1.0.0-beta", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0", "1.0.0-rc.1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0-alpha.1", "1.0.0-beta.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-rc.2", "1.0.0-rc.10"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.1-alpha", "1.0.0"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_comparison_constraints() {
+        assert!(constraint_matches("*", "0.1"));
+        assert!(constraint_matches(">=1.2", "1.2"));
+        assert!(!constraint_matches(">=1.2", "1.1.9"));
+        assert!(constraint_matches("<=1.2", "1.2.0"));
+        assert!(constraint_matches(">1.2", "1.2.1"));
+        assert!(!constraint_matches("<1.2", "1.2"));
+        assert!(constraint_matches("=1.2", "1.2.0"));
+        assert!(constraint_matches("1.2", "1.2"));
+        assert!(!constraint_matches("1.2", "1.2.1"));
+    }
+
+    #[test]
+    fn test_tilde_and_caret_constraints() {
+        assert!(constraint_matches("~1.2.3", "1.2.3"));
+        assert!(constraint_matches("~1.2.3", "1.2.9"));
+        assert!(!constraint_matches("~1.2.3", "1.2.2"));
+        assert!(!constraint_matches("~1.2.3", "1.3.0"));
+        assert!(!constraint_matches("~1.2.3", "1.3.0-alpha"));
+
+        assert!(constraint_matches("^1.2.3", "1.2.3"));
+        assert!(constraint_matches("^1.2.3", "1.9.0"));
+        assert!(!constraint_matches("^1.2.3", "1.2.2"));
+        assert!(!constraint_matches("^1.2.3", "2.0.0"));
+    }
+
+    #[test]
+    fn test_wildcard_constraints() {
+        assert!(constraint_matches("1.20.x", "1.20"));
+        assert!(constraint_matches("1.20.x", "1.20.4"));
+        assert!(constraint_matches("1.20.X", "1.20.1-pre1"));
+        assert!(!constraint_matches("1.20.x", "1.21"));
+        assert!(!constraint_matches("1.20.x", "1.200.1"));
+        assert!(constraint_matches("1.x", "1.20.1"));
+        assert!(constraint_matches("1.*", "1.0"));
+        assert!(constraint_matches("x", "2.0"));
+    }
+
+    #[test]
+    fn test_pre_release_constraints() {
+        assert!(!constraint_matches(">=1.20", "1.20-pre1"));
+        assert!(constraint_matches(">=1.0.0-beta.1", "1.0.0-beta.2"));
+        assert!(!constraint_matches(">=1.0.0-beta.2", "1.0.0-beta.1"));
+        assert!(constraint_matches("<1.0.0", "1.0.0-rc.1"));
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert!(predicate_matches(">=1.2 <2", "1.5"));
+        assert!(!predicate_matches(">=1.2 <2", "2.0"));
+        assert!(!predicate_matches(">=1.2 <2", "1.1"));
+
+        let predicates = ContentVersionRequirement::Predicates(vec![">=1.2 <1.3".into(), "~2.0".into()].into());
+        assert!(requirement_matches(&predicates, "1.2.5"));
+        assert!(requirement_matches(&predicates, "2.0.3"));
+        assert!(!requirement_matches(&predicates, "1.4"));
+        assert!(requirement_matches(&ContentVersionRequirement::Predicates(Vec::new().into()), "1.4"));
+        assert!(requirement_matches(&ContentVersionRequirement::Any, "1.4"));
+    }
+
+    #[test]
+    fn test_maven_ranges() {
+        assert!(maven_range_matches("[1.0,2.0)", "1.0"));
+        assert!(maven_range_matches("[1.0,2.0)", "1.5"));
+        assert!(!maven_range_matches("[1.0,2.0)", "2.0"));
+        assert!(!maven_range_matches("(1.0,2.0]", "1.0"));
+        assert!(maven_range_matches("(1.0,2.0]", "2.0"));
+        assert!(maven_range_matches("[47,)", "47.2.0"));
+        assert!(!maven_range_matches("[47,)", "46.0.1"));
+        assert!(maven_range_matches("[1.5]", "1.5"));
+        assert!(!maven_range_matches("[1.5]", "1.5.1"));
+    }
+
+    #[test]
+    fn test_maven_range_unions() {
+        let range = "(,1.0],[1.2,)";
+        assert!(maven_range_matches(range, "0.9"));
+        assert!(maven_range_matches(range, "1.0"));
+        assert!(!maven_range_matches(range, "1.1"));
+        assert!(maven_range_matches(range, "1.2"));
+        assert!(maven_range_matches(range, "5"));
+
+        let range = "[1.0,1.1), [2.0,2.1)";
+        assert!(maven_range_matches(range, "1.0.5"));
+        assert!(!maven_range_matches(range, "1.5"));
+        assert!(maven_range_matches(range, "2.0.1"));
+    }
+
+    #[test]
+    fn test_maven_malformed_ranges() {
+        // Must not panic, malformed ranges don't constrain the version
+        assert!(maven_range_matches("[1.0]]", "2.0"));
+        assert!(maven_range_matches("[1,2),]", "5"));
+        assert!(maven_range_matches("[1,2),)", "5"));
+        assert!(maven_range_matches("[1,2),é]", "5"));
+        assert!(maven_range_matches("[1,2)é", "5"));
+        assert!(maven_range_matches("(", "5"));
+        assert!(requirement_matches(&ContentVersionRequirement::MavenRange("[1.0]]".into()), "2.0"));
+
+        // Empty sets are well-formed and match nothing
+        assert!(!maven_range_matches("[]", "5"));
+    }
+
+    #[test]
+    fn test_maven_bare_versions() {
+        // A bare version is only a recommendation
+        assert!(maven_range_matches("1.0", "0.1"));
+        assert!(maven_range_matches("1.0", "1.0"));
+        assert!(maven_range_matches(" 2.3.4 ", "9"));
+        assert!(requirement_matches(&ContentVersionRequirement::MavenRange("47.1".into()), "46"));
+    }
+}
@@ -4,6 +4,7 @@ pub struct LauncherDirectories {
     pub instances_dir: Arc<Path>,
 
     pub synced_dir: Arc<Path>,
+    pub sync_profiles_dir: Arc<Path>,
     pub skin_library_dir: Arc<Path>,
 
     pub metadata_dir: Arc<Path>,
@@ -26,6 +27,8 @@ pub struct LauncherDirectories {
     pub root_launcher_dir: Arc<Path>,
     pub config_json: Arc<Path>,
     pub accounts_json: Arc<Path>,
+    pub server_history_json: Arc<Path>,
+    pub server_history_samples_jsonl: Arc<Path>,
 }
 
 impl LauncherDirectories {
@@ -33,6 +36,7 @@ impl LauncherDirectories {
         let instances_dir = launcher_dir.join("instances");
 
         let synced_dir = launcher_dir.join("synced");
+        let sync_profiles_dir = launcher_dir.join("sync_profiles");
         let skin_library_dir = launcher_dir.join("skins");
 
         let metadata_dir = launcher_dir.join("metadata");
@@ -56,11 +60,14 @@ impl LauncherDirectories {
 
         let config_json = launcher_dir.join("config.json");
         let accounts_json = launcher_dir.join("accounts.json");
+        let server_history_json = launcher_dir.join("server_history.json");
+        let server_history_samples_jsonl = launcher_dir.join("server_history_samples.jsonl");
 
         Self {
             instances_dir: instances_dir.into(),
 
             synced_dir: synced_dir.into(),
+            sync_profiles_dir: sync_profiles_dir.into(),
             skin_library_dir: skin_library_dir.into(),
 
             metadata_dir: metadata_dir.into(),
@@ -83,6 +90,8 @@ impl LauncherDirectories {
             root_launcher_dir: launcher_dir.into(),
             config_json: config_json.into(),
             accounts_json: accounts_json.into(),
+            server_history_json: server_history_json.into(),
+            server_history_samples_jsonl: server_history_samples_jsonl.into(),
         }
     }
 }
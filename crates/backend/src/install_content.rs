@@ -6,7 +6,7 @@ use bridge::{
 use parking_lot::Mutex;
 use reqwest::StatusCode;
 use rustc_hash::FxHashSet;
-use schema::{content::ContentSource, curseforge::{CURSEFORGE_RELATION_TYPE_REQUIRED_DEPENDENCY, CachedCurseforgeFileInfo, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeModLoaderType}, loader::Loader, modrinth::{ModrinthDependencyType, ModrinthLoader, ModrinthProjectVersionsRequest}};
+use schema::{content::ContentSource, curseforge::{CURSEFORGE_RELATION_TYPE_REQUIRED_DEPENDENCY, CachedCurseforgeFileInfo, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeModLoaderType}, instance::InstanceKind, loader::Loader, modrinth::{ModrinthDependencyType, ModrinthLoader, ModrinthProjectVersionsRequest}};
 use sha1::{Digest, Sha1};
 use strum::IntoEnumIterator;
 use tokio::sync::Semaphore;
@@ -188,7 +188,7 @@ impl BackendState {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(instance_id) {
                     if instance.configuration.get().loader == Loader::Vanilla {
                         if loader_hint == Loader::Unknown {
-                            loader_hint = determine_loader_from_content(&files).unwrap_or(Loader::Unknown);
+                            loader_hint = determine_loader_from_content(files.iter().map(|file| &*file.mod_summary)).unwrap_or(Loader::Unknown);
                         }
                         if loader_hint != Loader::Unknown {
                             instance.configuration.modify(|config| {
@@ -213,7 +213,7 @@ impl BackendState {
 
                 if let Some(version_hint) = version_hint {
                     if loader_hint == Loader::Unknown {
-                        loader_hint = determine_loader_from_content(&files).unwrap_or(Loader::Unknown);
+                        loader_hint = determine_loader_from_content(files.iter().map(|file| &*file.mod_summary)).unwrap_or(Loader::Unknown);
                     }
 
                     let mut name = name;
@@ -223,7 +223,7 @@ impl BackendState {
                     let name = name.as_deref().unwrap_or("New Instance");
 
                     // todo: use icon of mod/modpack/etc. for icon of instance
-                    instance_dir = self.create_instance_sanitized(&name, &version_hint, loader_hint, None).await
+                    instance_dir = self.create_instance_sanitized(&name, &version_hint, loader_hint, InstanceKind::Client, None).await
                         .map(|v| v.join(".minecraft").into());
                 }
             },
@@ -292,7 +292,10 @@ impl BackendState {
                     Some(version)
                 } else {
                     let modrinth_loader = content.loader_hint.as_modrinth_loader();
-                    let loaders = if modrinth_loader != ModrinthLoader::Unknown {
+                    let loaders = if modrinth_loader == ModrinthLoader::Quilt {
+                        // Quilt is able to load most Fabric mods
+                        Some(Arc::from([ModrinthLoader::Quilt, ModrinthLoader::Fabric]))
+                    } else if modrinth_loader != ModrinthLoader::Unknown {
                         Some(Arc::from([modrinth_loader]))
                     } else {
                         None
@@ -388,7 +391,7 @@ impl BackendState {
                     ContentInstallPath::Safe(safe_path) => safe_path.to_path(Path::new("")).into(),
                     ContentInstallPath::Automatic => {
                         let base = match mod_summary.extra {
-                            ContentType::Fabric | ContentType::Forge | ContentType::LegacyForge | ContentType::NeoForge | ContentType::JavaModule | ContentType::ModrinthModpack { .. } | ContentType::CurseforgeModpack { .. } => {
+                            ContentType::Fabric | ContentType::Quilt | ContentType::Forge | ContentType::LegacyForge | ContentType::NeoForge | ContentType::JavaModule | ContentType::ModrinthModpack { .. } | ContentType::CurseforgeModpack { .. } => {
                                 Path::new("mods")
                             },
                             ContentType::ResourcePack => {
@@ -473,6 +476,7 @@ impl BackendState {
                     Loader::Fabric => Some(CurseforgeModLoaderType::Fabric as u32),
                     Loader::Forge => Some(CurseforgeModLoaderType::Forge as u32),
                     Loader::NeoForge => Some(CurseforgeModLoaderType::NeoForge as u32),
+                    Loader::Quilt => Some(CurseforgeModLoaderType::Quilt as u32),
                     Loader::Unknown => None,
                 };
 
@@ -562,7 +566,8 @@ impl BackendState {
                 if is_wrong_version && mod_summary.extra.is_strict_minecraft_version() {
                     return Err(ContentInstallError::UnableToFindVersion);
                 }
-                if is_wrong_loader && mod_summary.extra.is_strict_loader() {
+                let is_fabric_on_quilt = content.loader_hint == Loader::Quilt && matches!(mod_summary.extra, ContentType::Fabric);
+                if is_wrong_loader && mod_summary.extra.is_strict_loader() && !is_fabric_on_quilt {
                     // todo: determine loader(s) from summary and check if it is compatible with the loader hint
                     // todo: if we install a fabric mod on forge 1.20.1 / neoforge 1.21.1, we can install sinytra instead of erroring
                     return Err(ContentInstallError::UnableToFindVersion);
@@ -954,10 +959,11 @@ impl BackendState {
     }
 }
 
-fn determine_loader_from_content(content: &[InstallFromContentLibrary]) -> Option<Loader> {
+pub(crate) fn determine_loader_from_content<'a>(content: impl IntoIterator<Item = &'a ContentSummary>) -> Option<Loader> {
     for content in content {
-        match &content.mod_summary.extra {
+        match &content.extra {
             ContentType::Fabric => return Some(Loader::Fabric),
+            ContentType::Quilt => return Some(Loader::Quilt),
             ContentType::LegacyForge => return Some(Loader::Forge),
             ContentType::Forge => return Some(Loader::Forge),
             ContentType::NeoForge => return Some(Loader::NeoForge),
@@ -968,6 +974,7 @@ fn determine_loader_from_content(content: &[InstallFromContentLibrary]) -> Optio
                         "forge" => return Some(Loader::Forge),
                         "neoforge" => return Some(Loader::NeoForge),
                         "fabric-loader" => return Some(Loader::Fabric),
+                        "quilt-loader" => return Some(Loader::Quilt),
                         _ => {}
                     }
                 }
@@ -985,6 +992,7 @@ fn determine_minecraft_version_from_content(content: &[InstallFromContentLibrary
     for content in content {
         match &content.mod_summary.extra {
             ContentType::Fabric => {},
+            ContentType::Quilt => {},
             ContentType::LegacyForge => {},
             ContentType::Forge => {},
             ContentType::NeoForge => {},
@@ -19,7 +19,7 @@ use thiserror::Error;
 
 use ustr::Ustr;
 
-use crate::{BackendState, BackendStateFileWatching, FolderChanges, IoOrSerializationError, WatchTarget, id_slab::{GetId, Id}, launcher_import, mod_metadata::{ContentUpdateAction, ContentUpdateKey, ModMetadataManager}, persistent::Persistent, server_list_pinger::{PingResult, ServerListPinger}};
+use crate::{BackendState, BackendStateFileWatching, FolderChanges, IoOrSerializationError, WatchTarget, id_slab::{GetId, Id}, launch_hooks::PostExitHook, launcher_import, mod_metadata::{ContentUpdateAction, ContentUpdateKey, ModMetadataManager}, persistent::Persistent, server_list_pinger::{PingResult, ServerListPinger}};
 
 #[derive(Debug)]
 pub struct Instance {
@@ -35,6 +35,10 @@ pub struct Instance {
 
     pub launch_keepalive: Option<KeepAliveHandle>,
     pub processes: Vec<Child>,
+    // Runs once every process has exited
+    pub post_exit_hook: Option<PostExitHook>,
+    // Set when the user kills or stops the instance, so that the exit isn't reported as a crash
+    pub stop_requested: bool,
     session_started_at: Option<Instant>,
 
     pub worlds_state: BridgeDataLoadState,
@@ -714,6 +718,8 @@ impl Instance {
 
             launch_keepalive: None,
             processes: Vec::new(),
+            post_exit_hook: None,
+            stop_requested: false,
             session_started_at: None,
 
             worlds_state: BridgeDataLoadState::default(),
@@ -1128,8 +1134,10 @@ fn load_servers_summary(server_dat_path: &Path, backend: &Arc<BackendState>, ver
         }
 
         summaries.push(InstanceServerSummary {
+            index: summaries.len(),
             name,
             ip,
+            resource_packs: crate::servers_dat::resource_pack_policy(server.find_byte("acceptTextures").copied()),
             png_icon: icon,
             pinging,
             status,
@@ -1137,5 +1145,8 @@ fn load_servers_summary(server_dat_path: &Path, backend: &Arc<BackendState>, ver
         });
     }
 
+    let addresses: Vec<Arc<str>> = summaries.iter().map(|summary| summary.ip.clone()).collect();
+    crate::server_history::register_servers(backend, &addresses);
+
     Ok(summaries)
 }
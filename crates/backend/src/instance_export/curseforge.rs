@@ -0,0 +1,153 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::{instance::{ContentType, InstanceContentSummary, InstanceID}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}};
+use rustc_hash::FxHashSet;
+use schema::{
+    content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeGetModFilesRequest, CurseforgeModLoaderType, CurseforgeModpackFile, CurseforgeModpackManifestJsonExport, CurseforgeModpackMinecraft, CurseforgeModpackModLoader}, loader::Loader
+};
+use tokio::sync::Semaphore;
+use ustr::Ustr;
+
+use crate::{
+    BackendState, instance_export::{ExportError, ExportSource}, metadata::items::CurseforgeGetModFilesMetadataItem
+};
+
+pub async fn export_curseforge_zip(backend: &Arc<BackendState>, id: InstanceID, path: &Path, include: &[Arc<str>], modal_action: &ModalAction) -> Result<(), ExportError> {
+    let source = ExportSource::read(backend, id)?;
+    let loader_version = super::resolve_loader_version(&backend.meta, &source.configuration).await?;
+
+    let mut mod_loaders = Vec::new();
+    if let Some(loader_version) = loader_version {
+        let prefix = match source.configuration.loader {
+            Loader::Fabric => "fabric",
+            Loader::Forge => "forge",
+            Loader::NeoForge => "neoforge",
+            Loader::Quilt => "quilt",
+            Loader::Vanilla | Loader::Unknown => unreachable!(),
+        };
+        mod_loaders.push(CurseforgeModpackModLoader {
+            id: format!("{prefix}-{loader_version}").into(),
+            primary: true,
+        });
+    }
+
+    let mut content = Vec::new();
+    { // Scope is needed so await doesn't complain about the non-send RwLockReadGuard
+        let all_content = super::collect_content(backend, id, include).await?;
+        let sources = backend.mod_metadata_manager.read_content_sources();
+        for (folder_name, summary) in all_content {
+            if let Some(ContentSource::CurseforgeProject { project_id }) = sources.get(&summary.content_summary.hash) {
+                content.push((folder_name, summary, project_id));
+            }
+        }
+    }
+
+    let tracker = ProgressTracker::new("Looking up CurseForge files".into(), backend.send.clone());
+    tracker.set_total(content.len());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let minecraft_version = source.configuration.minecraft_version;
+    let semaphore = Semaphore::new(8);
+    let lookups = content.iter().map(|(folder_name, summary, project_id)| {
+        let semaphore = &semaphore;
+        let tracker = &tracker;
+        async move {
+            let permit = semaphore.acquire().await.unwrap();
+            let file_id = lookup_curseforge_file_id(backend, minecraft_version, summary, *project_id).await;
+            drop(permit);
+
+            tracker.add_count(1);
+            tracker.notify();
+
+            let file_id = file_id?;
+            Some((format!("{folder_name}/{}", summary.filename), CurseforgeModpackFile {
+                project_id: *project_id,
+                file_id,
+                required: summary.enabled,
+            }))
+        }
+    });
+    let files: Vec<(String, CurseforgeModpackFile)> = futures::future::join_all(lookups).await.into_iter().flatten().collect();
+
+    tracker.set_finished(ProgressTrackerFinishType::Normal);
+    tracker.notify();
+
+    let downloaded_paths: FxHashSet<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+    let overrides: Vec<_> = super::collect_files(&source.dot_minecraft_path, include).into_iter()
+        .filter(|(relative, _)| !downloaded_paths.contains(relative.as_str()))
+        .collect();
+
+    let manifest = CurseforgeModpackManifestJsonExport {
+        minecraft: CurseforgeModpackMinecraft {
+            version: Some(minecraft_version.as_str().into()),
+            mod_loaders: mod_loaders.into(),
+            recommended_ram: None,
+        },
+        manifest_type: "minecraftModpack",
+        manifest_version: 1,
+        name: source.name.as_str().into(),
+        version: "1.0.0".into(),
+        author: "".into(),
+        files: files.into_iter().map(|(_, file)| file).collect(),
+        overrides: "overrides",
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+
+    super::write_archive(backend, modal_action, path, vec![("manifest.json".into(), manifest_bytes)], "overrides", overrides).await
+}
+
+async fn lookup_curseforge_file_id(backend: &BackendState, minecraft_version: Ustr, summary: &InstanceContentSummary, project_id: u32) -> Option<u32> {
+    let hash = summary.content_summary.hash;
+    if let Some(file_id) = backend.mod_metadata_manager.find_cached_curseforge_file_id(&hash) {
+        return Some(file_id);
+    }
+
+    let mod_loader_type = match summary.content_summary.extra {
+        ContentType::Fabric => Some(CurseforgeModLoaderType::Fabric as u32),
+        ContentType::Quilt => Some(CurseforgeModLoaderType::Quilt as u32),
+        ContentType::Forge | ContentType::LegacyForge => Some(CurseforgeModLoaderType::Forge as u32),
+        ContentType::NeoForge => Some(CurseforgeModLoaderType::NeoForge as u32),
+        _ => None
+    };
+
+    let result = backend.meta.fetch(&CurseforgeGetModFilesMetadataItem(&CurseforgeGetModFilesRequest {
+        mod_id: project_id,
+        game_version: Some(minecraft_version),
+        mod_loader_type,
+        page_size: Some(50),
+    })).await;
+
+    let files = match result {
+        Ok(files) => files,
+        Err(err) => {
+            log::warn!("Unable to find CurseForge file for {}, it will be added to overrides instead: {err}", summary.filename);
+            return None;
+        },
+    };
+
+    for file in files.data.iter() {
+        let sha1 = file.hashes.iter()
+            .find(|hash| hash.algo == 1).map(|hash| &hash.value);
+        let Some(sha1) = sha1 else {
+            continue;
+        };
+
+        let mut file_hash = [0u8; 20];
+        let Ok(_) = hex::decode_to_slice(&**sha1, &mut file_hash) else {
+            continue;
+        };
+
+        if file_hash == hash {
+            backend.mod_metadata_manager.set_cached_curseforge_info(file.id, CachedCurseforgeFileInfo {
+                hash,
+                filename: file.file_name.clone(),
+                disabled_third_party_downloads: file.download_url.is_none()
+            });
+            return Some(file.id);
+        }
+    }
+
+    log::warn!("Unable to find CurseForge file for {}, it will be added to overrides instead", summary.filename);
+    None
+}
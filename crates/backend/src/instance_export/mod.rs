@@ -0,0 +1,209 @@
+use std::{path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{export::InstanceExportFormat, instance::{InstanceContentSummary, InstanceID}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}};
+use schema::{instance::InstanceConfiguration, loader::Loader};
+use strum::IntoEnumIterator;
+use ustr::Ustr;
+
+use crate::{
+    BackendState, instance::{ContentFolder, Instance, InstanceStats}, launch::{latest_fabric_loader_version, latest_forgelike_loader_version, latest_quilt_loader_version}, metadata::{items::{FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, NeoforgeInstallerMavenMetadataItem, QuiltLoaderManifestMetadataItem}, manager::{MetaLoadError, MetadataManager}}
+};
+
+mod curseforge;
+mod modrinth;
+mod prism;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExportError {
+    #[error("Instance no longer exists")]
+    MissingInstance,
+    #[error("Instances using an unknown loader can't be exported")]
+    UnknownLoader,
+    #[error("Unable to find a loader version for Minecraft {0}")]
+    MissingLoaderVersion(Ustr),
+    #[error("Failed to load metadata:\n{0}")]
+    MetaLoadError(#[from] MetaLoadError),
+    #[error("Failed to serialize data:\n{0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("Failed to write archive:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+pub async fn export_instance(backend: &Arc<BackendState>, id: InstanceID, format: InstanceExportFormat, path: PathBuf, include: Vec<Arc<str>>, modal_action: ModalAction) {
+    let result = match format {
+        InstanceExportFormat::Modrinth => modrinth::export_mrpack(backend, id, &path, &include, &modal_action).await,
+        InstanceExportFormat::CurseForge => curseforge::export_curseforge_zip(backend, id, &path, &include, &modal_action).await,
+        InstanceExportFormat::Prism => prism::export_prism_zip(backend, id, &path, &include, &modal_action).await,
+    };
+
+    match result {
+        Ok(()) => {
+            backend.send.send_success(format!("Exported instance to {}", path.display()));
+        },
+        Err(err) => {
+            log::error!("Error exporting instance as {format}: {err}");
+            modal_action.set_error_message(format!("{err}").into());
+        },
+    }
+    modal_action.set_finished();
+}
+
+struct ExportSource {
+    name: Ustr,
+    icon: Option<Arc<[u8]>>,
+    configuration: InstanceConfiguration,
+    stats: InstanceStats,
+    dot_minecraft_path: Arc<Path>,
+}
+
+impl ExportSource {
+    fn read(backend: &BackendState, id: InstanceID) -> Result<Self, ExportError> {
+        let mut instance_state = backend.instance_state.write();
+        let Some(instance) = instance_state.instances.get_mut(id) else {
+            return Err(ExportError::MissingInstance);
+        };
+
+        Ok(Self {
+            name: instance.name,
+            icon: instance.icon.clone(),
+            configuration: instance.configuration.get().clone(),
+            stats: instance.stats.get().clone(),
+            dot_minecraft_path: instance.dot_minecraft_path.clone(),
+        })
+    }
+}
+
+async fn resolve_loader_version(meta: &MetadataManager, configuration: &InstanceConfiguration) -> Result<Option<Ustr>, ExportError> {
+    if configuration.loader == Loader::Vanilla {
+        return Ok(None);
+    } else if configuration.loader == Loader::Unknown {
+        return Err(ExportError::UnknownLoader);
+    }
+
+    if let Some(preferred_loader_version) = configuration.preferred_loader_version {
+        return Ok(Some(preferred_loader_version));
+    }
+
+    let minecraft_version = configuration.minecraft_version;
+    let latest = match configuration.loader {
+        Loader::Fabric => {
+            let manifest = meta.fetch(&FabricLoaderManifestMetadataItem).await?;
+            latest_fabric_loader_version(&manifest)
+        },
+        Loader::Forge => {
+            let manifest = meta.fetch(&ForgeInstallerMavenMetadataItem).await?;
+            latest_forgelike_loader_version(minecraft_version.as_str(), &manifest.0, false)
+        },
+        Loader::NeoForge => {
+            let manifest = meta.fetch(&NeoforgeInstallerMavenMetadataItem).await?;
+            latest_forgelike_loader_version(minecraft_version.as_str(), &manifest.0, true)
+        },
+        Loader::Quilt => {
+            let manifest = meta.fetch(&QuiltLoaderManifestMetadataItem).await?;
+            latest_quilt_loader_version(&manifest)
+        },
+        Loader::Vanilla | Loader::Unknown => unreachable!(),
+    };
+
+    latest.map(Some).ok_or(ExportError::MissingLoaderVersion(minecraft_version))
+}
+
+// Returns the content of the included content folders, along with the name of the folder
+async fn collect_content(backend: &Arc<BackendState>, id: InstanceID, include: &[Arc<str>]) -> Result<Vec<(&'static str, InstanceContentSummary)>, ExportError> {
+    let mut content = Vec::new();
+    for folder in ContentFolder::iter() {
+        let folder_name = folder.path().as_str();
+        if !include.iter().any(|entry| &**entry == folder_name) {
+            continue;
+        }
+        let Some(summaries) = Instance::load_content(backend.clone(), id, folder).await else {
+            return Err(ExportError::MissingInstance);
+        };
+        content.extend(summaries.iter().map(|summary| (folder_name, summary.clone())));
+    }
+    Ok(content)
+}
+
+// Writes the generated files at the root of the archive, followed by the overrides inside overrides_folder
+async fn write_archive(backend: &BackendState, modal_action: &ModalAction, path: &Path, files: Vec<(String, Vec<u8>)>, overrides_folder: &'static str, overrides: Vec<(String, PathBuf)>) -> Result<(), ExportError> {
+    let tracker = ProgressTracker::new("Writing archive".into(), backend.send.clone());
+    tracker.set_total(overrides.len() + files.len());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let path = path.to_path_buf();
+    let tracker2 = tracker.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        crate::zip_writer::write_zip_safe(&path, |zip| {
+            for (name, data) in files {
+                zip.write_file(&name, &data)?;
+                tracker2.add_count(1);
+                tracker2.notify();
+            }
+
+            for (relative, absolute) in overrides {
+                zip.write_file_from_path(&format!("{overrides_folder}/{relative}"), &absolute)?;
+                tracker2.add_count(1);
+                tracker2.notify();
+            }
+
+            Ok(())
+        })
+    }).await.unwrap();
+
+    if result.is_err() {
+        tracker.set_finished(ProgressTrackerFinishType::Error);
+    } else {
+        tracker.set_finished(ProgressTrackerFinishType::Normal);
+    }
+    tracker.notify();
+
+    Ok(result?)
+}
+
+// Returns the files inside the included top-level entries of .minecraft, as (path relative to .minecraft, absolute path)
+fn collect_files(dot_minecraft_path: &Path, include: &[Arc<str>]) -> Vec<(String, PathBuf)> {
+    let mut files = Vec::new();
+
+    for entry in include {
+        if !crate::is_single_component_path_str(entry) {
+            continue;
+        }
+
+        let walker = walkdir::WalkDir::new(dot_minecraft_path.join(&**entry)).follow_links(true);
+        for file in walker {
+            let Ok(file) = file else {
+                continue;
+            };
+            if !file.file_type().is_file() {
+                continue;
+            }
+            if file.file_name().as_encoded_bytes().starts_with(b".pandora.") {
+                continue;
+            }
+            let Ok(relative) = file.path().strip_prefix(dot_minecraft_path) else {
+                continue;
+            };
+
+            let mut relative_str = String::new();
+            for component in relative.components() {
+                let Some(component) = component.as_os_str().to_str() else {
+                    relative_str.clear();
+                    break;
+                };
+                if !relative_str.is_empty() {
+                    relative_str.push('/');
+                }
+                relative_str.push_str(component);
+            }
+            if relative_str.is_empty() {
+                log::warn!("Skipping non-utf8 path {:?} during export", file.path());
+                continue;
+            }
+
+            files.push((relative_str, file.into_path()));
+        }
+    }
+
+    files
+}
@@ -0,0 +1,108 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::{instance::{InstanceContentSummary, InstanceID}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}};
+use indexmap::IndexMap;
+use rustc_hash::FxHashSet;
+use schema::{content::ContentSource, loader::Loader, mrpack::{ModrinthIndexJsonExport, ModrinthIndexJsonExportFile, ModrinthIndexJsonExportHashes}};
+use tokio::sync::Semaphore;
+
+use crate::{
+    BackendState, instance_export::{ExportError, ExportSource}, metadata::items::ModrinthVersionFileMetadataItem
+};
+
+pub async fn export_mrpack(backend: &Arc<BackendState>, id: InstanceID, path: &Path, include: &[Arc<str>], modal_action: &ModalAction) -> Result<(), ExportError> {
+    let source = ExportSource::read(backend, id)?;
+    let loader_version = super::resolve_loader_version(&backend.meta, &source.configuration).await?;
+
+    let mut dependencies = IndexMap::new();
+    dependencies.insert("minecraft", Arc::from(source.configuration.minecraft_version.as_str()));
+    if let Some(loader_version) = loader_version {
+        let key = match source.configuration.loader {
+            Loader::Fabric => "fabric-loader",
+            Loader::Forge => "forge",
+            Loader::NeoForge => "neoforge",
+            Loader::Quilt => "quilt-loader",
+            Loader::Vanilla | Loader::Unknown => unreachable!(),
+        };
+        dependencies.insert(key, Arc::from(loader_version.as_str()));
+    }
+
+    let mut content = super::collect_content(backend, id, include).await?;
+    content.retain(|(_, summary)| summary.enabled);
+
+    { // Scope is needed so await doesn't complain about the non-send RwLockReadGuard
+        let sources = backend.mod_metadata_manager.read_content_sources();
+        content.retain(|(_, summary)| {
+            matches!(sources.get(&summary.content_summary.hash), Some(ContentSource::ModrinthProject { .. }))
+        });
+    }
+
+    let tracker = ProgressTracker::new("Looking up Modrinth files".into(), backend.send.clone());
+    tracker.set_total(content.len());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let semaphore = Semaphore::new(8);
+    let lookups = content.iter().map(|(folder_name, summary)| {
+        let semaphore = &semaphore;
+        let tracker = &tracker;
+        async move {
+            let permit = semaphore.acquire().await.unwrap();
+            let file = lookup_modrinth_file(backend, folder_name, summary).await;
+            drop(permit);
+
+            tracker.add_count(1);
+            tracker.notify();
+            file
+        }
+    });
+    let files: Vec<ModrinthIndexJsonExportFile> = futures::future::join_all(lookups).await.into_iter().flatten().collect();
+
+    tracker.set_finished(ProgressTrackerFinishType::Normal);
+    tracker.notify();
+
+    let downloaded_paths: FxHashSet<&str> = files.iter().map(|file| &*file.path).collect();
+    let overrides: Vec<_> = super::collect_files(&source.dot_minecraft_path, include).into_iter()
+        .filter(|(relative, _)| !downloaded_paths.contains(relative.as_str()))
+        .collect();
+
+    let index = ModrinthIndexJsonExport {
+        format_version: 1,
+        game: "minecraft",
+        version_id: "1.0.0".into(),
+        name: source.name.as_str().into(),
+        files,
+        dependencies,
+    };
+    let index_bytes = serde_json::to_vec_pretty(&index)?;
+
+    super::write_archive(backend, modal_action, path, vec![("modrinth.index.json".into(), index_bytes)], "overrides", overrides).await
+}
+
+async fn lookup_modrinth_file(backend: &BackendState, folder_name: &str, summary: &InstanceContentSummary) -> Option<ModrinthIndexJsonExportFile> {
+    if !summary.path.is_file() {
+        return None;
+    }
+
+    let sha1: Arc<str> = hex::encode(summary.content_summary.hash).into();
+    let version = match backend.meta.fetch(&ModrinthVersionFileMetadataItem(sha1.clone())).await {
+        Ok(version) => version,
+        Err(err) => {
+            log::warn!("Unable to find Modrinth version for {}, it will be added to overrides instead: {err}", summary.filename);
+            return None;
+        },
+    };
+
+    let file = version.files.iter().find(|file| *file.hashes.sha1 == *sha1)?;
+    let sha512 = file.hashes.sha512.clone()?;
+
+    Some(ModrinthIndexJsonExportFile {
+        path: format!("{folder_name}/{}", summary.filename).into(),
+        hashes: ModrinthIndexJsonExportHashes {
+            sha1,
+            sha512,
+        },
+        downloads: vec![file.url.clone()],
+        file_size: file.size,
+    })
+}
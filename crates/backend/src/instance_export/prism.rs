@@ -0,0 +1,170 @@
+use std::{borrow::Cow, fmt::Write, path::Path, sync::Arc};
+
+use bridge::{instance::InstanceID, modal_action::ModalAction};
+use schema::{instance::{InstanceConfiguration, InstanceSettingMode, LwjglLibraryPath}, loader::Loader};
+use serde::Serialize;
+use ustr::Ustr;
+
+use crate::{BackendState, instance::InstanceStats, instance_export::{ExportError, ExportSource}};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MMCPackExport {
+    components: Vec<MMCPackExportComponent>,
+    format_version: u32,
+}
+
+#[derive(Serialize)]
+struct MMCPackExportComponent {
+    uid: &'static str,
+    version: Ustr,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    important: bool,
+}
+
+pub async fn export_prism_zip(backend: &Arc<BackendState>, id: InstanceID, path: &Path, include: &[Arc<str>], modal_action: &ModalAction) -> Result<(), ExportError> {
+    let source = ExportSource::read(backend, id)?;
+    let loader_version = super::resolve_loader_version(&backend.meta, &source.configuration).await?;
+
+    let minecraft_version = source.configuration.minecraft_version;
+    let mut components = vec![MMCPackExportComponent {
+        uid: "net.minecraft",
+        version: minecraft_version,
+        important: true,
+    }];
+    if let Some(loader_version) = loader_version {
+        let (uid, version) = match source.configuration.loader {
+            Loader::Fabric => ("net.fabricmc.fabric-loader", loader_version),
+            // Our forge versions include the minecraft version (1.20.1-47.2.0), Prism only wants the forge part
+            Loader::Forge => ("net.minecraftforge", loader_version.as_str().strip_prefix(&*format!("{minecraft_version}-")).map(Ustr::from).unwrap_or(loader_version)),
+            Loader::NeoForge => ("net.neoforged", loader_version),
+            Loader::Quilt => ("org.quiltmc.quilt-loader", loader_version),
+            Loader::Vanilla | Loader::Unknown => unreachable!(),
+        };
+        components.push(MMCPackExportComponent {
+            uid,
+            version,
+            important: false,
+        });
+    }
+
+    let mmc_pack = MMCPackExport {
+        components,
+        format_version: 1,
+    };
+    let mmc_pack_bytes = serde_json::to_vec_pretty(&mmc_pack)?;
+
+    // Prism picks up <iconKey>.png from the instance root when importing
+    let icon_key = if source.icon.is_some() {
+        let key: String = source.name.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_ascii_lowercase();
+        Some(if key.is_empty() { "pandora".to_string() } else { format!("pandora_{key}") })
+    } else {
+        None
+    };
+
+    // Export the settings the instance is actually launched with, Prism has no equivalent of inheriting our defaults
+    let instance_defaults = backend.config.write().get().instance_defaults.clone();
+    let configuration = source.configuration.resolve_defaults(&instance_defaults);
+    let instance_cfg = create_instance_cfg(source.name, &configuration, &source.stats, icon_key.as_deref());
+
+    let mut files = vec![
+        ("instance.cfg".to_string(), instance_cfg.into_bytes()),
+        ("mmc-pack.json".to_string(), mmc_pack_bytes),
+    ];
+    if let Some(icon_key) = icon_key && let Some(icon) = &source.icon {
+        files.push((format!("{icon_key}.png"), icon.to_vec()));
+    }
+
+    let overrides = super::collect_files(&source.dot_minecraft_path, include);
+    super::write_archive(backend, modal_action, path, files, ".minecraft", overrides).await
+}
+
+fn create_instance_cfg(name: Ustr, configuration: &InstanceConfiguration, stats: &InstanceStats, icon_key: Option<&str>) -> String {
+    let mut cfg = String::new();
+    let mut set = |key: &str, value: &str| {
+        _ = writeln!(cfg, "{key}={}", ini_value(value));
+    };
+
+    set("ConfigVersion", "1.2");
+    set("InstanceType", "OneSix");
+    set("name", &name);
+    set("iconKey", icon_key.unwrap_or("default"));
+
+    if let Some(memory) = &configuration.memory {
+        set("OverrideMemory", &(memory.mode == InstanceSettingMode::Override).to_string());
+        set("MinMemAlloc", &memory.min.to_string());
+        set("MaxMemAlloc", &memory.max.to_string());
+    }
+
+    if let Some(jvm_flags) = &configuration.jvm_flags {
+        set("OverrideJavaArgs", &(jvm_flags.mode == InstanceSettingMode::Override).to_string());
+        set("JvmArgs", &jvm_flags.flags);
+    }
+
+    if let Some(jvm_binary) = &configuration.jvm_binary && let Some(java_path) = &jvm_binary.path {
+        set("OverrideJavaLocation", &(jvm_binary.mode == InstanceSettingMode::Override).to_string());
+        set("JavaPath", &java_path.to_string_lossy());
+    }
+
+    // Prism uses a single override for the wrapper and hook commands
+    let wrapper_command_enabled = configuration.wrapper_command.as_ref().is_some_and(|wrapper_command| wrapper_command.mode == InstanceSettingMode::Override);
+    let hook_commands_enabled = configuration.hook_commands.as_ref().is_some_and(|hook_commands| hook_commands.enabled);
+    if configuration.wrapper_command.is_some() || configuration.hook_commands.is_some() {
+        set("OverrideCommands", &(wrapper_command_enabled || hook_commands_enabled).to_string());
+    }
+    if let Some(wrapper_command) = &configuration.wrapper_command {
+        set("WrapperCommand", &wrapper_command.flags);
+    }
+    if let Some(hook_commands) = &configuration.hook_commands {
+        set("PreLaunchCommand", &hook_commands.pre_launch);
+        set("PostExitCommand", &hook_commands.post_exit);
+    }
+
+    if let Some(window) = &configuration.window {
+        set("OverrideWindow", &(window.mode == InstanceSettingMode::Override).to_string());
+        set("LaunchMaximized", &window.window.maximised.to_string());
+        set("MinecraftWinWidth", &window.window.width.to_string());
+        set("MinecraftWinHeight", &window.window.height.to_string());
+    }
+
+    if let Some(linux_wrapper) = &configuration.linux_wrapper {
+        set("OverridePerformance", &(linux_wrapper.mode == InstanceSettingMode::Override).to_string());
+        set("EnableFeralGamemode", &linux_wrapper.use_gamemode.to_string());
+        set("EnableMangoHud", &linux_wrapper.use_mangohud.to_string());
+        set("UseDiscreteGpu", &linux_wrapper.use_discrete_gpu.to_string());
+    }
+
+    if let Some(system_libraries) = &configuration.system_libraries {
+        set("OverrideNativeWorkarounds", &(system_libraries.mode == InstanceSettingMode::Override).to_string());
+        set("UseNativeGLFW", &system_libraries.override_glfw.to_string());
+        set("CustomGLFWPath", &library_path(&system_libraries.glfw));
+        set("UseNativeOpenAL", &system_libraries.override_openal.to_string());
+        set("CustomOpenALPath", &library_path(&system_libraries.openal));
+    }
+
+    if stats.total_playtime_secs > 0 {
+        set("totalTimePlayed", &stats.total_playtime_secs.to_string());
+    }
+    if let Some(last_played) = stats.last_played_unix_ms {
+        set("lastLaunchTime", &last_played.to_string());
+    }
+
+    format!("[General]\n{cfg}")
+}
+
+fn library_path(path: &LwjglLibraryPath) -> Cow<'_, str> {
+    match path {
+        LwjglLibraryPath::Auto => Cow::Borrowed(""),
+        LwjglLibraryPath::AutoPreferred(path) | LwjglLibraryPath::Explicit(path) => path.to_string_lossy(),
+    }
+}
+
+// Qt's ini format treats commas as list separators and semicolons as comments, so such values need to be quoted
+fn ini_value(value: &str) -> Cow<'_, str> {
+    let needs_quotes = value.contains([',', ';', '"', '\\', '=', '#']) || value.starts_with(' ') || value.ends_with(' ');
+    if needs_quotes {
+        Cow::Owned(format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
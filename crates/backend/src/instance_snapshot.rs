@@ -0,0 +1,488 @@
+use std::{path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{instance::{InstanceID, InstanceSnapshotSummary}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}};
+use rustc_hash::FxHashMap;
+use schema::{instance::InstanceConfiguration, instance_snapshot::{InstanceSnapshotFile, InstanceSnapshotManifest, InstanceSnapshotReason}};
+use sha1::{Digest, Sha1};
+use strum::IntoEnumIterator;
+
+use crate::{BackendState, FolderChanges, IoOrSerializationError, instance::{ContentFolder, Instance}};
+
+const MAX_SNAPSHOTS: usize = 10;
+
+// Entries of .minecraft that are copied into the snapshot as-is
+const CONFIG_ENTRIES: &[&str] = &["config", "options.txt"];
+
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotError {
+    #[error("Instance no longer exists")]
+    MissingInstance,
+    #[error("Can't restore a snapshot while the instance is running")]
+    InstanceRunning,
+    #[error("Snapshot doesn't exist")]
+    MissingSnapshot,
+    #[error("Content library is missing {0}, unable to restore snapshot")]
+    MissingContent(Arc<str>),
+    #[error("Failed to read snapshot:\n{0}")]
+    ReadError(#[from] IoOrSerializationError),
+    #[error("Failed to serialize snapshot:\n{0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("I/O error:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+struct SnapshotSource {
+    root_path: Arc<Path>,
+    dot_minecraft_path: Arc<Path>,
+    configuration: InstanceConfiguration,
+}
+
+impl SnapshotSource {
+    fn read(backend: &BackendState, id: InstanceID) -> Result<Self, SnapshotError> {
+        let mut instance_state = backend.instance_state.write();
+        let Some(instance) = instance_state.instances.get_mut(id) else {
+            return Err(SnapshotError::MissingInstance);
+        };
+
+        Ok(Self {
+            root_path: instance.root_path.clone(),
+            dot_minecraft_path: instance.dot_minecraft_path.clone(),
+            configuration: instance.configuration.get().clone(),
+        })
+    }
+}
+
+fn snapshots_dir(root_path: &Path) -> PathBuf {
+    root_path.join("snapshots")
+}
+
+pub async fn take_snapshot(backend: &Arc<BackendState>, id: InstanceID, reason: InstanceSnapshotReason) {
+    let result = try_take_snapshot(backend, id, reason).await;
+    if let Err(err) = result {
+        log::error!("Unable to take snapshot of instance: {err}");
+        backend.send.send_error(format!("Unable to take snapshot of instance: {err}"));
+    }
+}
+
+async fn try_take_snapshot(backend: &Arc<BackendState>, id: InstanceID, reason: InstanceSnapshotReason) -> Result<(), SnapshotError> {
+    let source = SnapshotSource::read(backend, id)?;
+    let known_hashes = load_known_hashes(backend, id).await?;
+    let content_library_dir = backend.directories.content_library_dir.clone();
+
+    tokio::task::spawn_blocking(move || {
+        write_snapshot(&source, &known_hashes, &content_library_dir, reason)?;
+        if reason != InstanceSnapshotReason::BeforeRestore {
+            prune_snapshots(&source.root_path);
+        }
+        Ok(())
+    }).await.unwrap()
+}
+
+// Uses the hashes the content folders already computed, so only unrecognized files need to be hashed
+async fn load_known_hashes(backend: &Arc<BackendState>, id: InstanceID) -> Result<FxHashMap<Arc<Path>, [u8; 20]>, SnapshotError> {
+    let mut known_hashes = FxHashMap::default();
+    for folder in ContentFolder::iter() {
+        let Some(summaries) = Instance::load_content(backend.clone(), id, folder).await else {
+            return Err(SnapshotError::MissingInstance);
+        };
+        known_hashes.extend(summaries.iter().map(|summary| (summary.path.clone(), summary.content_summary.hash)));
+    }
+    Ok(known_hashes)
+}
+
+fn write_snapshot(source: &SnapshotSource, known_hashes: &FxHashMap<Arc<Path>, [u8; 20]>, content_library_dir: &Path, reason: InstanceSnapshotReason) -> Result<(), SnapshotError> {
+    let mut content = Vec::new();
+    for (relative, path) in list_content_files(&source.dot_minecraft_path) {
+        let hash = match known_hashes.get(path.as_path()) {
+            Some(hash) => *hash,
+            None => hash_file(&path)?,
+        };
+
+        // Make sure the file can be restored even if it gets deleted from the instance
+        let library_path = crate::create_content_library_path(content_library_dir, hash, path.extension().and_then(|ext| ext.to_str()));
+        if !library_path.exists() {
+            if let Some(parent) = library_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            if std::fs::hard_link(&path, &library_path).is_err() {
+                std::fs::copy(&path, &library_path)?;
+            }
+        }
+
+        content.push(InstanceSnapshotFile {
+            path: relative.into(),
+            sha1: hex::encode(hash).into(),
+        });
+    }
+    content.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let snapshots_dir = snapshots_dir(&source.root_path);
+
+    // Skip the snapshot if nothing changed since the last one
+    if reason != InstanceSnapshotReason::BeforeRestore && let Some((_, manifest)) = list_snapshot_manifests(&snapshots_dir).into_iter().next() {
+        if same_versions(&manifest.configuration, &source.configuration) && manifest.content == content {
+            return Ok(());
+        }
+    }
+
+    let created = chrono::Utc::now().timestamp_millis();
+    let snapshot_dir = snapshots_dir.join(created.to_string());
+    std::fs::create_dir_all(&snapshot_dir)?;
+
+    for entry in CONFIG_ENTRIES {
+        copy_recursive(&source.dot_minecraft_path.join(entry), &snapshot_dir.join(entry))?;
+    }
+
+    let manifest = InstanceSnapshotManifest {
+        created,
+        reason,
+        configuration: source.configuration.clone(),
+        content,
+    };
+
+    // The manifest is written last, snapshots without one are incomplete and ignored
+    crate::write_safe(&snapshot_dir.join("snapshot.json"), &serde_json::to_vec_pretty(&manifest)?)?;
+    Ok(())
+}
+
+pub fn list_snapshots(root_path: &Path) -> Vec<InstanceSnapshotSummary> {
+    list_snapshot_manifests(&snapshots_dir(root_path)).into_iter().map(|(name, manifest)| {
+        InstanceSnapshotSummary {
+            name: name.into(),
+            created: manifest.created,
+            reason: manifest.reason,
+            minecraft_version: manifest.configuration.minecraft_version,
+            loader: manifest.configuration.loader,
+            content_count: manifest.content.len(),
+        }
+    }).collect()
+}
+
+// Returns the manifests of all complete snapshots, newest first
+fn list_snapshot_manifests(snapshots_dir: &Path) -> Vec<(String, InstanceSnapshotManifest)> {
+    let Ok(read_dir) = std::fs::read_dir(snapshots_dir) else {
+        return Vec::new();
+    };
+
+    let mut manifests = Vec::new();
+    for entry in read_dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        match crate::read_json::<InstanceSnapshotManifest>(&entry.path().join("snapshot.json")) {
+            Ok(manifest) => manifests.push((name, manifest)),
+            Err(err) => log::warn!("Ignoring invalid snapshot {:?}: {err}", entry.path()),
+        }
+    }
+
+    manifests.sort_by_key(|(_, manifest)| std::cmp::Reverse(manifest.created));
+    manifests
+}
+
+fn prune_snapshots(root_path: &Path) {
+    let snapshots_dir = snapshots_dir(root_path);
+    for (name, _) in list_snapshot_manifests(&snapshots_dir).into_iter().skip(MAX_SNAPSHOTS) {
+        if let Err(err) = std::fs::remove_dir_all(snapshots_dir.join(&name)) {
+            log::warn!("Unable to remove old snapshot {name}: {err}");
+        }
+    }
+}
+
+pub async fn restore_snapshot(backend: &Arc<BackendState>, id: InstanceID, snapshot: Arc<str>, modal_action: ModalAction) {
+    let result = try_restore_snapshot(backend, id, &snapshot, &modal_action).await;
+
+    match result {
+        Ok(()) => {
+            backend.send.send_success("Restored instance snapshot");
+        },
+        Err(err) => {
+            log::error!("Error restoring snapshot {snapshot}: {err}");
+            modal_action.set_error_message(format!("{err}").into());
+        },
+    }
+    modal_action.set_finished();
+}
+
+async fn try_restore_snapshot(backend: &Arc<BackendState>, id: InstanceID, snapshot: &str, modal_action: &ModalAction) -> Result<(), SnapshotError> {
+    if !crate::is_single_component_path_str(snapshot) {
+        return Err(SnapshotError::MissingSnapshot);
+    }
+
+    let running = backend.instance_state.write().instances.get_mut(id).map(|instance| !instance.processes.is_empty());
+    match running {
+        Some(true) => return Err(SnapshotError::InstanceRunning),
+        Some(false) => {},
+        None => return Err(SnapshotError::MissingInstance),
+    }
+
+    let source = SnapshotSource::read(backend, id)?;
+    let snapshot_dir = snapshots_dir(&source.root_path).join(snapshot);
+    let manifest_path = snapshot_dir.join("snapshot.json");
+    if !manifest_path.exists() {
+        return Err(SnapshotError::MissingSnapshot);
+    }
+    let manifest: InstanceSnapshotManifest = crate::read_json(&manifest_path)?;
+
+    // Keep the current state around so the restore itself can be undone
+    try_take_snapshot(backend, id, InstanceSnapshotReason::BeforeRestore).await?;
+
+    let tracker = ProgressTracker::new("Restoring snapshot".into(), backend.send.clone());
+    tracker.set_total(manifest.content.len());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let known_hashes = load_known_hashes(backend, id).await?;
+    let content_library_dir = backend.directories.content_library_dir.clone();
+
+    let dot_minecraft_path = source.dot_minecraft_path.clone();
+    let root_path = source.root_path.clone();
+    let content = manifest.content.clone();
+    let tracker2 = tracker.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let skipped = restore_files(&dot_minecraft_path, &snapshot_dir, &content, &known_hashes, &content_library_dir, &tracker2)?;
+        prune_snapshots(&root_path);
+        Ok(skipped)
+    }).await.unwrap();
+
+    let skipped = match result {
+        Ok(skipped) => skipped,
+        Err(err) => {
+            tracker.set_finished(ProgressTrackerFinishType::Error);
+            tracker.notify();
+            return Err(err);
+        },
+    };
+    tracker.set_finished(ProgressTrackerFinishType::Normal);
+    tracker.notify();
+
+    if !skipped.is_empty() {
+        backend.send.send_warning(format!("Skipped synced folders when restoring snapshot: {}", skipped.join(", ")));
+    }
+
+    // Only the parts of the configuration that go together with the content are restored, unrelated settings changed since are kept
+    if let Some(instance) = backend.instance_state.write().instances.get_mut(id) {
+        instance.configuration.modify(|configuration| {
+            configuration.minecraft_version = manifest.configuration.minecraft_version;
+            configuration.loader = manifest.configuration.loader;
+            configuration.preferred_loader_version = manifest.configuration.preferred_loader_version;
+        });
+        for folder in ContentFolder::iter() {
+            instance.mark_content_dirty(backend, folder, FolderChanges::all_dirty(), true);
+        }
+    }
+
+    Ok(())
+}
+
+fn same_versions(a: &InstanceConfiguration, b: &InstanceConfiguration) -> bool {
+    a.minecraft_version == b.minecraft_version && a.loader == b.loader && a.preferred_loader_version == b.preferred_loader_version
+}
+
+// Returns the synced folders and entries that were left untouched
+fn restore_files(dot_minecraft_path: &Path, snapshot_dir: &Path, content: &[InstanceSnapshotFile], known_hashes: &FxHashMap<Arc<Path>, [u8; 20]>, content_library_dir: &Path, tracker: &ProgressTracker) -> Result<Vec<&'static str>, SnapshotError> {
+    // Synced folders link into the shared sync folder, changing them would affect every other instance syncing them
+    let mut skipped: Vec<&'static str> = ContentFolder::iter()
+        .map(|folder| folder.path().as_str())
+        .filter(|folder_name| is_link(&dot_minecraft_path.join(folder_name)))
+        .collect();
+    let is_skipped = |relative: &str| Path::new(relative).parent().and_then(|parent| parent.to_str()).is_some_and(|parent| skipped.contains(&parent));
+
+    // Check that every file is available before touching the instance
+    let mut wanted = FxHashMap::default();
+    for file in content {
+        if is_skipped(&file.path) {
+            tracker.add_count(1);
+            tracker.notify();
+            continue;
+        }
+        let mut hash = [0u8; 20];
+        let Ok(_) = hex::decode_to_slice(&*file.sha1, &mut hash) else {
+            return Err(SnapshotError::MissingContent(file.path.clone()));
+        };
+        if !crate::is_single_component_path(Path::new(&*file.path).parent().unwrap_or(Path::new(""))) {
+            return Err(SnapshotError::MissingContent(file.path.clone()));
+        }
+        let extension = Path::new(&*file.path).extension().and_then(|ext| ext.to_str());
+        let library_path = crate::create_content_library_path(content_library_dir, hash, extension);
+        if !library_path.exists() {
+            return Err(SnapshotError::MissingContent(file.path.clone()));
+        }
+        wanted.insert(file.path.clone(), (hash, library_path));
+    }
+
+    for (relative, path) in list_content_files(dot_minecraft_path) {
+        if is_skipped(&relative) {
+            continue;
+        }
+        if let Some((hash, _)) = wanted.get(relative.as_str()) {
+            let current_hash = match known_hashes.get(path.as_path()) {
+                Some(hash) => *hash,
+                None => hash_file(&path)?,
+            };
+            if current_hash == *hash {
+                wanted.remove(relative.as_str());
+                tracker.add_count(1);
+                tracker.notify();
+                continue;
+            }
+        }
+        std::fs::remove_file(&path)?;
+    }
+
+    for (relative, (_, library_path)) in wanted {
+        let target = dot_minecraft_path.join(&*relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if std::fs::hard_link(&library_path, &target).is_err() {
+            std::fs::copy(&library_path, &target)?;
+        }
+        tracker.add_count(1);
+        tracker.notify();
+    }
+
+    for entry in CONFIG_ENTRIES {
+        let target = dot_minecraft_path.join(entry);
+        if is_link(&target) {
+            skipped.push(entry);
+            continue;
+        }
+        if target.is_dir() {
+            std::fs::remove_dir_all(&target)?;
+        } else if target.exists() {
+            std::fs::remove_file(&target)?;
+        }
+        copy_recursive(&snapshot_dir.join(entry), &target)?;
+    }
+
+    Ok(skipped)
+}
+
+fn is_link(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+// Returns the top-level files of every content folder, as (path relative to .minecraft, absolute path)
+fn list_content_files(dot_minecraft_path: &Path) -> Vec<(String, PathBuf)> {
+    let mut files = Vec::new();
+    for folder in ContentFolder::iter() {
+        let folder_name = folder.path().as_str();
+        let Ok(read_dir) = std::fs::read_dir(dot_minecraft_path.join(folder_name)) else {
+            continue;
+        };
+        for entry in read_dir {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if !file_type.is_file() {
+                continue;
+            }
+            let Ok(filename) = entry.file_name().into_string() else {
+                continue;
+            };
+            if filename.starts_with(".pandora.") {
+                continue;
+            }
+            files.push((format!("{folder_name}/{filename}"), entry.path()));
+        }
+    }
+    files
+}
+
+fn hash_file(path: &Path) -> std::io::Result<[u8; 20]> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha1::new();
+    let _ = std::io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().into())
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    if !from.exists() {
+        return Ok(());
+    }
+    if from.is_file() {
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(from, to)?;
+        return Ok(());
+    }
+
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry.map_err(std::io::Error::other)?;
+        let Ok(relative) = entry.path().strip_prefix(from) else {
+            continue;
+        };
+        let target = to.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_skips_synced_links() {
+        let dir = std::env::temp_dir().join(format!("pandora-snapshot-restore-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let dot_minecraft_path = dir.join(".minecraft");
+        let snapshot_dir = dir.join("snapshot");
+        let content_library_dir = dir.join("library");
+        let shared = dir.join("shared");
+        std::fs::create_dir_all(dot_minecraft_path.join("mods")).unwrap();
+        std::fs::create_dir_all(dot_minecraft_path.join("config")).unwrap();
+        std::fs::create_dir_all(&snapshot_dir).unwrap();
+        std::fs::create_dir_all(shared.join("resourcepacks")).unwrap();
+
+        std::fs::write(dot_minecraft_path.join("mods/a.jar"), "a").unwrap();
+        std::fs::write(dot_minecraft_path.join("config/mod.toml"), "current").unwrap();
+        std::fs::write(shared.join("resourcepacks/pack.zip"), "pack").unwrap();
+        std::fs::write(shared.join("options.txt"), "shared").unwrap();
+        std::fs::write(snapshot_dir.join("options.txt"), "snapshot").unwrap();
+        std::os::unix::fs::symlink(shared.join("resourcepacks"), dot_minecraft_path.join("resourcepacks")).unwrap();
+        std::os::unix::fs::symlink(shared.join("options.txt"), dot_minecraft_path.join("options.txt")).unwrap();
+
+        let mod_path = dir.join("b.jar");
+        std::fs::write(&mod_path, "b").unwrap();
+        let hash = hash_file(&mod_path).unwrap();
+        let library_path = crate::create_content_library_path(&content_library_dir, hash, Some("jar"));
+        std::fs::create_dir_all(library_path.parent().unwrap()).unwrap();
+        std::fs::rename(&mod_path, &library_path).unwrap();
+
+        let content = [
+            InstanceSnapshotFile { path: "mods/b.jar".into(), sha1: hex::encode(hash).into() },
+            // Not in the content library, but it's in a synced folder so it's never needed
+            InstanceSnapshotFile { path: "resourcepacks/old.zip".into(), sha1: hex::encode([0u8; 20]).into() },
+        ];
+        let (_, _, _, frontend_handle) = bridge::handle::create_pair();
+        let tracker = ProgressTracker::new("Restoring snapshot".into(), frontend_handle);
+
+        let skipped = restore_files(&dot_minecraft_path, &snapshot_dir, &content, &FxHashMap::default(), &content_library_dir, &tracker).unwrap();
+        assert_eq!(skipped, vec!["resourcepacks", "options.txt"]);
+
+        assert!(!dot_minecraft_path.join("mods/a.jar").exists());
+        assert_eq!(std::fs::read_to_string(dot_minecraft_path.join("mods/b.jar")).unwrap(), "b");
+        assert!(!dot_minecraft_path.join("config").exists());
+
+        assert!(is_link(&dot_minecraft_path.join("resourcepacks")));
+        assert_eq!(std::fs::read_to_string(shared.join("resourcepacks/pack.zip")).unwrap(), "pack");
+        assert!(!shared.join("resourcepacks/old.zip").exists());
+        assert!(is_link(&dot_minecraft_path.join("options.txt")));
+        assert_eq!(std::fs::read_to_string(shared.join("options.txt")).unwrap(), "shared");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use std::{io::Write, path::{Path, PathBuf}, process::Stdio};
+
+use rustc_hash::FxHashSet;
+use schema::backend_config::{JavaRuntimeInfo, JavaRuntimeSource};
+
+use crate::{directories::LauncherDirectories, launch::Launcher};
+
+pub struct JavaProbe {
+    pub major_version: u32,
+    pub version: String,
+    pub vendor: String,
+    pub arch: String,
+}
+
+// Runs the binary with the launch wrapper to read the properties of the runtime
+pub fn probe_java(launch_wrapper: &Path, binary: &Path) -> Option<JavaProbe> {
+    let mut command = std::process::Command::new(binary);
+    command.arg("-jar");
+    command.arg(launch_wrapper.as_os_str().to_os_string());
+    command.stdin(Stdio::piped());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::null());
+
+    let mut process = command.spawn().ok()?;
+
+    let mut stdin = process.stdin.take().unwrap();
+    stdin.write_all(b"printproperty\njava.specification.version\nprintproperty\njava.version\nprintproperty\njava.vendor\nprintproperty\nos.arch\nexit\n").ok()?;
+    stdin.flush().ok()?;
+    drop(stdin);
+
+    let output = process.wait_with_output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let output = str::from_utf8(&output.stdout).ok()?;
+    let mut lines = output.lines().map(str::trim);
+
+    let mut specification_version = lines.next()?;
+    if specification_version.starts_with("1.") {
+        specification_version = &specification_version[2..];
+    }
+
+    Some(JavaProbe {
+        major_version: specification_version.parse().ok()?,
+        version: lines.next().unwrap_or_default().into(),
+        vendor: lines.next().unwrap_or_default().into(),
+        arch: lines.next().unwrap_or_default().into(),
+    })
+}
+
+pub fn discover(directories: &LauncherDirectories, launch_wrapper: &Path) -> Vec<JavaRuntimeInfo> {
+    let mut candidates: Vec<(PathBuf, JavaRuntimeSource)> = Vec::new();
+
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        candidates.push((PathBuf::from(java_home), JavaRuntimeSource::JavaHome));
+    }
+
+    for system_dir in system_java_dirs() {
+        candidates.extend(list_subdirectories(&system_dir).into_iter().map(|dir| (dir, JavaRuntimeSource::System)));
+    }
+
+    let sdkman_dir = std::env::var_os("SDKMAN_DIR").map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".sdkman")));
+    if let Some(sdkman_dir) = sdkman_dir {
+        let candidates_dir = sdkman_dir.join("candidates").join("java");
+        candidates.extend(list_subdirectories(&candidates_dir).into_iter()
+            .filter(|dir| dir.file_name().is_some_and(|name| name != "current"))
+            .map(|dir| (dir, JavaRuntimeSource::Sdkman)));
+    }
+
+    // Mojang runtimes are stored as runtime/<component>/<platform>
+    for component_dir in list_subdirectories(&directories.runtime_base_dir) {
+        candidates.extend(list_subdirectories(&component_dir).into_iter().map(|dir| (dir, JavaRuntimeSource::Managed)));
+    }
+
+    let mut seen = FxHashSet::default();
+    let mut runtimes = Vec::new();
+    for (candidate, source) in candidates {
+        let Some(binary) = Launcher::search_for_java_binary(&candidate) else {
+            continue;
+        };
+        let binary = binary.canonicalize().unwrap_or(binary);
+        if !seen.insert(binary.clone()) {
+            continue;
+        }
+        let Some(probe) = probe_java(launch_wrapper, &binary) else {
+            log::debug!("Unable to probe java binary {:?}", binary);
+            continue;
+        };
+        runtimes.push(JavaRuntimeInfo {
+            path: binary.to_string_lossy().into(),
+            major_version: probe.major_version,
+            version: probe.version.into(),
+            vendor: probe.vendor.into(),
+            arch: probe.arch.into(),
+            source,
+        });
+    }
+
+    runtimes.sort_by(|a, b| b.major_version.cmp(&a.major_version).then_with(|| a.path.cmp(&b.path)));
+    runtimes
+}
+
+fn system_java_dirs() -> Vec<PathBuf> {
+    match std::env::consts::OS {
+        "linux" => vec![
+            "/usr/lib/jvm".into(),
+            "/usr/lib64/jvm".into(),
+            "/usr/java".into(),
+            "/opt/java".into(),
+        ],
+        "macos" => vec![
+            "/Library/Java/JavaVirtualMachines".into(),
+        ],
+        "windows" => {
+            let program_files = std::env::var_os("ProgramFiles").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("C:\\Program Files"));
+            ["Java", "Eclipse Adoptium", "Zulu", "Microsoft", "Amazon Corretto", "BellSoft"].iter().map(|vendor| program_files.join(vendor)).collect()
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn list_subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read_dir.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).map(|path| {
+        // macOS bundles keep the actual runtime under Contents/Home
+        let home = path.join("Contents").join("Home");
+        if home.is_dir() { home } else { path }
+    }).collect()
+}
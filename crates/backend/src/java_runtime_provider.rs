@@ -0,0 +1,278 @@
+use std::{io::Cursor, path::{Path, PathBuf}};
+
+use bridge::{handle::FrontendHandle, modal_action::{ProgressTracker, ProgressTrackerFinishType, ProgressTrackers}, safe_path::SafePath};
+use rc_zip_sync::ReadZip;
+use schema::{adoptium::AdoptiumAsset, version::MinecraftVersion};
+use sha2::{Digest, Sha256};
+use ustr::Ustr;
+
+use crate::{launch::{Launcher, LoadJavaRuntimeError, MissingArtifact, OfflineArtifacts, do_java_runtime_load, fetch_meta}, metadata::{items::{MojangJavaRuntimeComponentMetadataItem, MojangJavaRuntimesMetadataItem}, manager::MetadataManager}};
+
+// Marker written once an Adoptium runtime has been fully extracted
+const ADOPTIUM_RELEASE_MARKER: &str = ".adoptium-release";
+
+pub(crate) struct JavaRuntimeContext<'a> {
+    pub http_client: &'a reqwest::Client,
+    pub version_info: &'a MinecraftVersion,
+    pub runtime_base_dir: &'a Path,
+    pub sender: &'a FrontendHandle,
+    pub progress_trackers: &'a ProgressTrackers,
+    pub offline: Option<&'a OfflineArtifacts>,
+}
+
+pub(crate) trait JavaRuntimeProvider {
+    // Returns the path to the java binary, downloading the runtime if necessary
+    async fn load_java_runtime(&self, context: &JavaRuntimeContext<'_>) -> Result<PathBuf, LoadJavaRuntimeError>;
+}
+
+pub(crate) struct MojangJavaRuntimeProvider<'a> {
+    pub meta: &'a MetadataManager,
+}
+
+impl JavaRuntimeProvider for MojangJavaRuntimeProvider<'_> {
+    async fn load_java_runtime(&self, context: &JavaRuntimeContext<'_>) -> Result<PathBuf, LoadJavaRuntimeError> {
+        let mut platform: Ustr = match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("linux", "x86_64") => "linux".into(),
+            ("linux", "x86") => "linux-i386".into(),
+            ("macos", "x86_64") => "mac-os".into(),
+            ("macos", "aarch64") => "mac-os-arm64".into(),
+            ("windows", "aarch64") => "windows-arm64".into(),
+            ("windows", "x86_64") => "windows-x64".into(),
+            ("windows", "x86") => "windows-x86".into(),
+            ("macos", b) => format!("mac-os-{b}").into(),
+            (a, b) => format!("{a}-{b}").into(),
+        };
+
+        let jre_component = if let Some(java_version) = &context.version_info.java_version {
+            java_version.component
+        } else {
+            "jre-legacy".into()
+        };
+
+        let runtimes = fetch_meta(self.meta, &MojangJavaRuntimesMetadataItem, context.offline).await?;
+
+        let mut runtime_platform = runtimes.platforms.get(&platform).ok_or(LoadJavaRuntimeError::UnknownPlatform)?;
+        let mut runtime_components = runtime_platform.components.get(&jre_component);
+
+        // Fall back to x86 runtime on mac-os, since Rosetta exists
+        let missing_runtime_component = runtime_components.map(Vec::is_empty).unwrap_or(true);
+        if missing_runtime_component && platform == "mac-os-arm64" {
+            platform = "mac-os".into();
+            runtime_platform = runtimes.platforms.get(&platform).ok_or(LoadJavaRuntimeError::UnknownPlatform)?;
+            runtime_components = runtime_platform.components.get(&jre_component);
+        }
+
+        let Some(runtime_components) = runtime_components else {
+            return Err(LoadJavaRuntimeError::UnknownComponentForPlatform);
+        };
+        let runtime_component = runtime_components.first().ok_or(LoadJavaRuntimeError::UnknownComponentForPlatform)?;
+
+        if !crate::is_single_component_path_str(jre_component.as_str()) {
+            return Err(LoadJavaRuntimeError::InvalidComponentPath);
+        }
+        if !crate::is_single_component_path_str(&platform) {
+            return Err(LoadJavaRuntimeError::InvalidComponentPath);
+        }
+
+        let runtime_component_dir = context.runtime_base_dir.join(jre_component).join(platform);
+        let _ = std::fs::create_dir_all(&runtime_component_dir);
+        let Ok(runtime_component_dir) = runtime_component_dir.canonicalize() else {
+            return Err(LoadJavaRuntimeError::InvalidComponentPath);
+        };
+
+        let fresh_install = !runtime_component_dir.exists();
+
+        let runtime = fetch_meta(self.meta, &MojangJavaRuntimeComponentMetadataItem {
+            url: runtime_component.manifest.url,
+            cache: runtime_component_dir.join("manifest.json").into(),
+            hash: runtime_component.manifest.sha1,
+        }, context.offline).await?;
+
+        let initial_title = if fresh_install {
+            "Downloading Java Runtime"
+        } else {
+            "Verifying integrity of Java Runtime"
+        };
+
+        let java_runtime_tracker = ProgressTracker::new(initial_title.into(), context.sender.clone());
+        context.progress_trackers.push(java_runtime_tracker.clone());
+        java_runtime_tracker.notify();
+
+        let result = do_java_runtime_load(context.http_client, runtime_component_dir, fresh_install, runtime, &java_runtime_tracker, context.offline).await;
+
+        java_runtime_tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        java_runtime_tracker.notify();
+
+        result
+    }
+}
+
+pub(crate) struct AdoptiumJavaRuntimeProvider<'a> {
+    pub api_url: &'a str,
+    pub major_version: u32,
+}
+
+impl AdoptiumJavaRuntimeProvider<'_> {
+    fn platform() -> Result<(&'static str, &'static str), LoadJavaRuntimeError> {
+        let os = match std::env::consts::OS {
+            "linux" if cfg!(target_env = "musl") || Path::new("/etc/alpine-release").exists() => "alpine-linux",
+            "linux" => "linux",
+            "macos" => "mac",
+            "windows" => "windows",
+            _ => return Err(LoadJavaRuntimeError::UnknownPlatform),
+        };
+        let arch = match std::env::consts::ARCH {
+            "x86_64" => "x64",
+            "x86" => "x32",
+            "aarch64" => "aarch64",
+            "arm" => "arm",
+            "powerpc64" => "ppc64le",
+            "s390x" => "s390x",
+            "riscv64" => "riscv64",
+            _ => return Err(LoadJavaRuntimeError::UnknownPlatform),
+        };
+        Ok((os, arch))
+    }
+}
+
+impl JavaRuntimeProvider for AdoptiumJavaRuntimeProvider<'_> {
+    async fn load_java_runtime(&self, context: &JavaRuntimeContext<'_>) -> Result<PathBuf, LoadJavaRuntimeError> {
+        let (os, arch) = Self::platform()?;
+
+        let runtime_dir = context.runtime_base_dir.join(format!("adoptium-jre-{}", self.major_version)).join(format!("{os}-{arch}"));
+        if runtime_dir.join(ADOPTIUM_RELEASE_MARKER).exists() && let Some(binary) = Launcher::search_for_java_binary(&runtime_dir) {
+            return Ok(binary);
+        }
+
+        if let Some(offline) = context.offline {
+            offline.push(MissingArtifact::JavaRuntime(runtime_dir));
+            return Err(LoadJavaRuntimeError::UnableToFindBinary);
+        }
+
+        let java_runtime_tracker = ProgressTracker::new("Downloading Java Runtime from Adoptium".into(), context.sender.clone());
+        context.progress_trackers.push(java_runtime_tracker.clone());
+        java_runtime_tracker.notify();
+
+        let result = self.download_runtime(context, os, arch, &runtime_dir, &java_runtime_tracker).await;
+
+        java_runtime_tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        java_runtime_tracker.notify();
+
+        result
+    }
+}
+
+impl AdoptiumJavaRuntimeProvider<'_> {
+    async fn download_runtime(&self, context: &JavaRuntimeContext<'_>, os: &str, arch: &str, runtime_dir: &Path, tracker: &ProgressTracker) -> Result<PathBuf, LoadJavaRuntimeError> {
+        let url = format!("{}/v3/assets/latest/{}/hotspot", self.api_url.trim_end_matches('/'), self.major_version);
+        let response = context.http_client.get(url)
+            .query(&[("architecture", arch), ("image_type", "jre"), ("os", os), ("vendor", "eclipse")])
+            .send().await?
+            .error_for_status()?;
+        let assets: Vec<AdoptiumAsset> = serde_json::from_slice(&response.bytes().await?)?;
+
+        let Some(asset) = assets.into_iter().find(|asset| &*asset.binary.image_type == "jre") else {
+            return Err(LoadJavaRuntimeError::NoAdoptiumRuntime(self.major_version));
+        };
+        let package = asset.binary.package;
+
+        log::info!("Downloading {} from {}", asset.release_name, package.link);
+
+        tracker.set_total(package.size);
+        tracker.notify();
+
+        let mut response = context.http_client.get(&*package.link).send().await?.error_for_status()?;
+        let mut bytes = Vec::with_capacity(package.size);
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            tracker.set_count(bytes.len());
+            tracker.notify();
+        }
+
+        let mut expected_hash = [0u8; 32];
+        let Ok(_) = hex::decode_to_slice(&*package.checksum, &mut expected_hash) else {
+            return Err(LoadJavaRuntimeError::WrongHash);
+        };
+        let actual_hash: [u8; 32] = Sha256::digest(&bytes).into();
+        if expected_hash != actual_hash {
+            return Err(LoadJavaRuntimeError::WrongHash);
+        }
+
+        tracker.set_title("Extracting Java Runtime".into());
+        tracker.notify();
+
+        let temp_dir = runtime_dir.with_extension("partial");
+        _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir)?;
+
+        let name = package.name.clone();
+        let extract_dir = temp_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            if name.ends_with(".zip") {
+                extract_zip(&bytes, &extract_dir)
+            } else if name.ends_with(".tar.gz") {
+                extract_tar_gz(&bytes, &extract_dir)
+            } else {
+                Err(LoadJavaRuntimeError::UnsupportedArchive(name))
+            }
+        }).await.unwrap()?;
+
+        std::fs::write(temp_dir.join(ADOPTIUM_RELEASE_MARKER), asset.release_name.as_bytes())?;
+
+        _ = std::fs::remove_dir_all(runtime_dir);
+        std::fs::rename(&temp_dir, runtime_dir)?;
+
+        Launcher::search_for_java_binary(runtime_dir).ok_or(LoadJavaRuntimeError::UnableToFindBinary)
+    }
+}
+
+// Archives contain a single top-level folder (e.g. jdk-21.0.5+11-jre), which is stripped
+fn strip_top_level(name: &str) -> Option<SafePath> {
+    let (_, relative) = name.split_once('/')?;
+    SafePath::new(relative)
+}
+
+fn extract_tar_gz(bytes: &[u8], target: &Path) -> Result<(), LoadJavaRuntimeError> {
+    let gz_decoder = flate2::bufread::GzDecoder::new(Cursor::new(bytes));
+    let mut archive = tar::Archive::new(gz_decoder);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let Some(relative) = strip_top_level(&path) else {
+            continue;
+        };
+        let destination = relative.to_path(target);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&destination)?;
+    }
+
+    Ok(())
+}
+
+fn extract_zip(bytes: &[u8], target: &Path) -> Result<(), LoadJavaRuntimeError> {
+    let archive = bytes.read_zip()?;
+
+    for entry in archive.entries() {
+        let Some(relative) = strip_top_level(&entry.name) else {
+            continue;
+        };
+        let destination = relative.to_path(target);
+        match entry.kind() {
+            rc_zip_sync::rc_zip::EntryKind::Directory => {
+                std::fs::create_dir_all(&destination)?;
+            },
+            rc_zip_sync::rc_zip::EntryKind::File => {
+                if let Some(parent) = destination.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&destination, entry.bytes()?)?;
+            },
+            rc_zip_sync::rc_zip::EntryKind::Symlink => {},
+        }
+    }
+
+    Ok(())
+}
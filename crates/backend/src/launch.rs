@@ -1,17 +1,18 @@
 use std::{
-    borrow::Cow, cmp::Ordering, collections::{BTreeSet, HashMap, HashSet}, ffi::{OsStr, OsString}, fs::File, io::Write, path::{Path, PathBuf}, process::{Child, Stdio}, sync::{Arc, OnceLock, atomic::AtomicBool}
+    borrow::Cow, cmp::Ordering, collections::{BTreeSet, HashMap, HashSet}, ffi::{OsStr, OsString}, fmt::Display, fs::File, io::Write, path::{Path, PathBuf}, process::{Child, Stdio}, sync::{Arc, OnceLock, atomic::AtomicBool}
 };
 
 use bridge::{
     handle::FrontendHandle, message::{MessageToFrontend, QuickPlayLaunch}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType, ProgressTrackers}, safe_path::SafePath
 };
 use futures::{FutureExt, TryFutureExt};
+use parking_lot::Mutex;
 use rand::seq::SliceRandom;
 use rc_zip_sync::{ArchiveHandle, ReadZip};
 use regex::Regex;
 use rustc_hash::FxHashMap;
 use schema::{
-    assets_index::AssetsIndex, fabric_launch::FabricLaunch, forge::{ForgeInstallProfile, ForgeInstallProfileLegacy, ForgeSide, VersionFragment}, instance::{AUTO_LIBRARY_PATH_GLFW, AUTO_LIBRARY_PATH_OPENAL, InstanceConfiguration, InstanceWrapperCommandConfiguration}, java_runtime_component::{JavaRuntimeComponentFile, JavaRuntimeComponentManifest}, loader::Loader, maven::MavenCoordinate, version::{
+    assets_index::AssetsIndex, backend_config::{DEFAULT_ADOPTIUM_API_URL, JavaRuntimeInfo, JavaRuntimeRegistry}, fabric_launch::FabricLaunch, fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeInstallProfile, ForgeInstallProfileLegacy, ForgeSide, VersionFragment}, instance::{AUTO_LIBRARY_PATH_GLFW, AUTO_LIBRARY_PATH_OPENAL, InstanceConfiguration, InstanceEnvironmentVariablesConfiguration, InstanceSettingMode, InstanceWindowConfiguration, InstanceWrapperCommandConfiguration, WindowConfiguration}, java_runtime_component::{JavaRuntimeComponentFile, JavaRuntimeComponentManifest}, loader::Loader, maven::MavenCoordinate, quilt_launch::QuiltLaunch, quilt_loader_manifest::QuiltLoaderManifest, version::{
         GameLibrary, GameLibraryArtifact, GameLibraryDownloads, GameLibraryExtractOptions, GameLogging, LaunchArgument, LaunchArgumentValue, MinecraftVersion, OsArch, OsName, PartialMinecraftVersion, Rule, RuleAction
     }, version_manifest::MinecraftVersionManifest
 };
@@ -20,7 +21,7 @@ use sha1::{Digest, Sha1};
 use ustr::Ustr;
 
 use crate::{
-    account::MinecraftLoginInfo, directories::LauncherDirectories, launch_wrapper, metadata::{items::{AssetsIndexMetadataItem, FabricLaunchMetadataItem, FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, MinecraftVersionManifestMetadataItem, MinecraftVersionMetadataItem, MojangJavaRuntimeComponentMetadataItem, MojangJavaRuntimesMetadataItem, NeoforgeInstallerMavenMetadataItem}, manager::{
+    account::MinecraftLoginInfo, directories::LauncherDirectories, java_runtime_provider::{AdoptiumJavaRuntimeProvider, JavaRuntimeContext, JavaRuntimeProvider, MojangJavaRuntimeProvider}, launch_hooks::{LaunchHookError, LaunchHooks}, launch_wrapper, metadata::{items::{AssetsIndexMetadataItem, FabricLaunchMetadataItem, FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, MetadataItem, MinecraftVersionManifestMetadataItem, MinecraftVersionMetadataItem, NeoforgeInstallerMavenMetadataItem, QuiltLaunchMetadataItem, QuiltLoaderManifestMetadataItem}, manager::{
         MetaLoadError, MetadataManager,
     }}
 };
@@ -61,6 +62,137 @@ pub enum LaunchError {
     CancelledByUser,
     #[error("Loader supports the wrong version of Minecraft: {0}")]
     MismatchedLoaderVersions(Arc<str>),
+    #[error("Unable to launch offline, {} files are missing or corrupt:\n{}", .0.len(), format_missing_artifacts(.0))]
+    MissingOfflineArtifacts(Vec<MissingArtifact>),
+    #[error("Minecraft {0} doesn't have a dedicated server download")]
+    MissingServerDownload(Ustr),
+    #[error("Error running server installer:\n{0}")]
+    ServerInstallerError(Arc<str>),
+    #[error("Unable to find the server jar created by the installer")]
+    MissingServerLaunchTarget,
+    #[error("Pre-launch command failed:\n{0}")]
+    PreLaunchHookError(#[from] LaunchHookError),
+}
+
+#[derive(Debug)]
+pub enum MissingArtifact {
+    Metadata(Arc<Path>),
+    Library(PathBuf),
+    AssetObject(PathBuf),
+    JavaRuntime(PathBuf),
+}
+
+impl Display for MissingArtifact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Metadata(path) => write!(f, "Metadata: {}", path.display()),
+            Self::Library(path) => write!(f, "Library: {}", path.display()),
+            Self::AssetObject(path) => write!(f, "Asset: {}", path.display()),
+            Self::JavaRuntime(path) => write!(f, "Java runtime: {}", path.display()),
+        }
+    }
+}
+
+fn format_missing_artifacts(missing: &[MissingArtifact]) -> String {
+    const MAX_SHOWN: usize = 50;
+
+    let mut formatted = missing.iter().take(MAX_SHOWN).map(|artifact| artifact.to_string()).collect::<Vec<_>>().join("\n");
+    if missing.len() > MAX_SHOWN {
+        formatted.push_str(&format!("\n...and {} more (see log for the full list)", missing.len() - MAX_SHOWN));
+    }
+    formatted
+}
+
+// Collects every artifact that is missing while launching offline, so that they can all be reported at once
+// instead of failing on the first one
+#[derive(Default)]
+pub(crate) struct OfflineArtifacts {
+    missing: Mutex<Vec<MissingArtifact>>,
+}
+
+impl OfflineArtifacts {
+    pub(crate) fn push(&self, artifact: MissingArtifact) {
+        self.missing.lock().push(artifact);
+    }
+
+    fn check(&self) -> Result<(), LaunchError> {
+        let mut missing = std::mem::take(&mut *self.missing.lock());
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        missing.sort_by_key(|artifact| artifact.to_string());
+        for artifact in &missing {
+            log::error!("Missing offline artifact {}", artifact);
+        }
+        Err(LaunchError::MissingOfflineArtifacts(missing))
+    }
+}
+
+pub(crate) async fn fetch_meta<I: MetadataItem>(meta: &MetadataManager, item: &I, offline: Option<&OfflineArtifacts>) -> Result<Arc<I::T>, MetaLoadError> {
+    let Some(offline) = offline else {
+        return meta.fetch(item).await;
+    };
+
+    let result = meta.fetch_cached(item).await;
+    if let Err(MetaLoadError::NotCached(Some(path))) = &result {
+        offline.push(MissingArtifact::Metadata(path.clone()));
+    }
+    result
+}
+
+const FABRIC_SERVER_INSTALLER_VERSION: &str = "1.0.1";
+const QUILT_SERVER_INSTALLER_VERSION: &str = "0.9.2";
+
+enum ServerLaunchTarget {
+    Jar(PathBuf),
+    // Modern Forge and NeoForge servers are started with `java @libraries/.../unix_args.txt`
+    ArgsFile(PathBuf),
+}
+
+fn find_server_launch_target(server_dir: &Path, loader: Loader, loader_version: Option<&str>) -> Option<ServerLaunchTarget> {
+    let jar = |name: &str| {
+        let path = server_dir.join(name);
+        path.is_file().then_some(ServerLaunchTarget::Jar(path))
+    };
+
+    match (loader, loader_version) {
+        (Loader::Vanilla, _) => jar("server.jar"),
+        (Loader::Fabric, _) => jar("fabric-server-launch.jar"),
+        (Loader::Quilt, _) => jar("quilt-server-launch.jar"),
+        (Loader::Forge | Loader::NeoForge, Some(loader_version)) => {
+            let args_file = if cfg!(windows) { "win_args.txt" } else { "unix_args.txt" };
+            let args_path = match loader {
+                Loader::Forge => format!("libraries/net/minecraftforge/forge/{loader_version}/{args_file}"),
+                _ => format!("libraries/net/neoforged/neoforge/{loader_version}/{args_file}"),
+            };
+            if server_dir.join(&args_path).is_file() {
+                // Relative to the working directory, since that's how the installer's own run scripts reference it
+                return Some(ServerLaunchTarget::ArgsFile(PathBuf::from(args_path)));
+            }
+
+            // Older Forge installers produce a runnable jar instead
+            jar(&format!("forge-{loader_version}.jar"))
+                .or_else(|| jar(&format!("forge-{loader_version}-universal.jar")))
+                .or_else(|| jar(&format!("forge-{loader_version}-shim.jar")))
+        },
+        _ => None,
+    }
+}
+
+pub(crate) fn is_server_eula_accepted(server_dir: &Path) -> bool {
+    let Ok(eula) = std::fs::read_to_string(server_dir.join("eula.txt")) else {
+        return false;
+    };
+    eula.lines().any(|line| line.trim().eq_ignore_ascii_case("eula=true"))
+}
+
+pub(crate) fn accept_server_eula(server_dir: &Path) -> std::io::Result<()> {
+    let eula = format!(
+        "#By changing the setting below to TRUE you are indicating your agreement to our EULA (https://aka.ms/MinecraftEULA).\n#{}\neula=true\n",
+        chrono::Local::now().format("%a %b %d %H:%M:%S %Z %Y")
+    );
+    crate::write_safe(&server_dir.join("eula.txt"), eula.as_bytes())
 }
 
 #[derive(PartialEq, Eq)]
@@ -86,19 +218,31 @@ impl Launcher {
         dot_minecraft_path: Arc<Path>,
         instance_info: InstanceConfiguration,
         quick_play: Option<QuickPlayLaunch>,
+        screen_size: Option<(u32, u32)>,
         login_info: MinecraftLoginInfo,
         add_mods: Vec<PathBuf>,
+        offline: bool,
+        java_runtimes: &JavaRuntimeRegistry,
+        hooks: &LaunchHooks,
         launch_tracker: &ProgressTracker,
         modal_action: &ModalAction,
     ) -> Result<Child, LaunchError> {
         log::info!("Launching {:?}", dot_minecraft_path);
 
+        let offline_artifacts = offline.then(OfflineArtifacts::default);
+        let offline = offline_artifacts.as_ref();
+
         launch_tracker.set_total(6);
 
         log::debug!("Creating launch version");
 
         let (version_info, add_vanilla_jar) = tokio::select! {
-            result = self.create_launch_version(http_client, &modal_action.trackers, launch_tracker, &instance_info) => result?,
+            result = self.create_launch_version(http_client, &modal_action.trackers, launch_tracker, &instance_info, java_runtimes, offline) => {
+                if result.is_err() && let Some(offline) = offline {
+                    offline.check()?;
+                }
+                result?
+            },
             _ = modal_action.request_cancel.cancelled() => {
                 self.sender.send(MessageToFrontend::CloseModal);
                 return Err(LaunchError::CancelledByUser);
@@ -110,9 +254,18 @@ impl Launcher {
 
         let _ = std::fs::create_dir_all(&dot_minecraft_path);
 
+        let window = window_configuration(&instance_info);
+        let custom_resolution = if window.maximised && screen_size.is_some() {
+            screen_size
+        } else if window.custom_size {
+            Some((window.width.max(1), window.height.max(1)))
+        } else {
+            None
+        };
+
         let launch_rule_context = LaunchRuleContext {
             is_demo_user: false,
-            custom_resolution: None,
+            custom_resolution,
             quick_play,
         };
 
@@ -134,34 +287,58 @@ impl Launcher {
             });
         }
 
-        let mojang_java_binary_future = self.load_mojang_java_binary(
+        let java_binary_future = self.load_java_binary(
             &self.meta,
             http_client,
             &instance_info,
+            java_runtimes,
             &version_info,
             &modal_action.trackers,
             launch_tracker,
+            offline,
         );
         let load_assets_future =
-            self.load_assets(&self.meta, http_client, &dot_minecraft_path, &version_info, &modal_action.trackers, launch_tracker);
+            self.load_assets(&self.meta, http_client, &dot_minecraft_path, &version_info, &modal_action.trackers, launch_tracker, offline);
         let load_libraries_future =
-            self.load_libraries(http_client, &artifacts, &modal_action.trackers, launch_tracker);
-        let load_log_configuration = self.load_log_configuration(http_client, version_info.logging.as_ref());
+            self.load_libraries(http_client, &artifacts, &modal_action.trackers, launch_tracker, offline);
+        let load_log_configuration = self.load_log_configuration(http_client, version_info.logging.as_ref(), offline.is_some());
 
         log::debug!("Loading java, assets, libraries and log configuration");
 
-        let joined = futures::future::try_join4(
-            mojang_java_binary_future.map_err(LaunchError::from),
-            load_assets_future.map_err(LaunchError::from),
-            load_libraries_future.map_err(LaunchError::from),
-            load_log_configuration.map(Ok),
-        );
+        let (java_path, assets_index_name, library_paths, log_configuration) = if let Some(offline) = offline {
+            // Wait for everything to be checked, so that all the missing files can be reported together
+            let joined = futures::future::join4(
+                java_binary_future,
+                load_assets_future,
+                load_libraries_future,
+                load_log_configuration,
+            );
+
+            let (java_path, assets_index_name, library_paths, log_configuration) = tokio::select! {
+                result = joined => result,
+                _ = modal_action.request_cancel.cancelled() => {
+                    self.sender.send(MessageToFrontend::CloseModal);
+                    return Err(LaunchError::CancelledByUser);
+                }
+            };
 
-        let (java_path, assets_index_name, library_paths, log_configuration) = tokio::select! {
-            result = joined => result?,
-            _ = modal_action.request_cancel.cancelled() => {
-                self.sender.send(MessageToFrontend::CloseModal);
-                return Err(LaunchError::CancelledByUser);
+            offline.check()?;
+
+            (java_path?, assets_index_name?, library_paths?, log_configuration)
+        } else {
+            let joined = futures::future::try_join4(
+                java_binary_future.map_err(LaunchError::from),
+                load_assets_future.map_err(LaunchError::from),
+                load_libraries_future.map_err(LaunchError::from),
+                load_log_configuration.map(Ok),
+            );
+
+            tokio::select! {
+                result = joined => result?,
+                _ = modal_action.request_cancel.cancelled() => {
+                    self.sender.send(MessageToFrontend::CloseModal);
+                    return Err(LaunchError::CancelledByUser);
+                }
             }
         };
 
@@ -235,6 +412,14 @@ impl Launcher {
             return Err(LaunchError::CancelledByUser);
         }
 
+        if let Err(err) = hooks.run_pre_launch(&launch_context.java_path, &modal_action.request_cancel).await {
+            if matches!(err, LaunchHookError::Cancelled) {
+                self.sender.send(MessageToFrontend::CloseModal);
+                return Err(LaunchError::CancelledByUser);
+            }
+            return Err(err.into());
+        }
+
         log::info!("Launching game process");
         let child = launch_context.launch(&version_info)?;
 
@@ -243,19 +428,257 @@ impl Launcher {
         Ok(child)
     }
 
+    pub async fn launch_server(
+        &self,
+        http_client: &reqwest::Client,
+        server_dir: Arc<Path>,
+        instance_info: InstanceConfiguration,
+        add_mods: Vec<PathBuf>,
+        java_runtimes: &JavaRuntimeRegistry,
+        hooks: &LaunchHooks,
+        launch_tracker: &ProgressTracker,
+        modal_action: &ModalAction,
+    ) -> Result<Child, LaunchError> {
+        log::info!("Launching server {:?}", server_dir);
+
+        launch_tracker.set_total(5);
+
+        let _ = std::fs::create_dir_all(&server_dir);
+
+        let (java_path, launch_target) = tokio::select! {
+            result = self.prepare_server(http_client, &server_dir, &instance_info, java_runtimes, &modal_action.trackers, launch_tracker) => result?,
+            _ = modal_action.request_cancel.cancelled() => {
+                self.sender.send(MessageToFrontend::CloseModal);
+                return Err(LaunchError::CancelledByUser);
+            }
+        };
+
+        if modal_action.has_requested_cancel() {
+            self.sender.send(MessageToFrontend::CloseModal);
+            return Err(LaunchError::CancelledByUser);
+        }
+
+        let mut command = std::process::Command::new(&java_path);
+        command.current_dir(&server_dir);
+        command.stdin(Stdio::piped());
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
+
+        if let Some(memory) = &instance_info.memory && memory.mode == InstanceSettingMode::Override {
+            command.arg(format!("-Xms{}m", memory.min));
+            command.arg(format!("-Xmx{}m", memory.max.max(memory.min).max(128)));
+        }
+        if let Some(jvm_flags) = &instance_info.jvm_flags && jvm_flags.mode == InstanceSettingMode::Override {
+            if let Ok(split) = shell_words::split(&jvm_flags.flags) {
+                command.args(split);
+            } else {
+                command.args(jvm_flags.flags.split_whitespace());
+            }
+        }
+
+        if !add_mods.is_empty() {
+            let mods = std::env::join_paths(add_mods).unwrap();
+            match instance_info.loader {
+                Loader::Fabric => {
+                    let mut property = OsString::from("-Dfabric.addMods=");
+                    property.push(mods);
+                    command.arg(property);
+                },
+                Loader::Quilt => {
+                    let mut property = OsString::from("-Dloader.addMods=");
+                    property.push(mods);
+                    command.arg(property);
+                },
+                _ => {},
+            }
+        }
+
+        match launch_target {
+            ServerLaunchTarget::Jar(jar) => {
+                command.arg("-jar");
+                command.arg(jar);
+            },
+            ServerLaunchTarget::ArgsFile(args_file) => {
+                let mut arg = OsString::from("@");
+                arg.push(args_file);
+                command.arg(arg);
+            },
+        }
+        command.arg("nogui");
+        command.args(game_arguments(&instance_info));
+        apply_environment_variables(&mut command, &instance_info);
+
+        if let Err(err) = hooks.run_pre_launch(&java_path, &modal_action.request_cancel).await {
+            if matches!(err, LaunchHookError::Cancelled) {
+                self.sender.send(MessageToFrontend::CloseModal);
+                return Err(LaunchError::CancelledByUser);
+            }
+            return Err(err.into());
+        }
+
+        log::info!("Launching server process");
+        let child = command.spawn()?;
+
+        launch_tracker.add_count(1);
+        launch_tracker.notify();
+
+        Ok(child)
+    }
+
+    async fn prepare_server(
+        &self,
+        http_client: &reqwest::Client,
+        server_dir: &Path,
+        instance_info: &InstanceConfiguration,
+        java_runtimes: &JavaRuntimeRegistry,
+        progress_trackers: &ProgressTrackers,
+        launch_tracker: &ProgressTracker,
+    ) -> Result<(PathBuf, ServerLaunchTarget), LaunchError> {
+        let minecraft_version = instance_info.minecraft_version;
+
+        let versions = self.meta.fetch(&MinecraftVersionManifestMetadataItem).await?;
+        let Some(version_link) = versions.versions.iter().find(|v| v.id == minecraft_version) else {
+            return Err(LaunchError::CantFindVersion(minecraft_version.as_str()));
+        };
+        let version_info = self.meta.fetch(&MinecraftVersionMetadataItem(version_link)).await?;
+        let Some(server_download) = &version_info.downloads.server else {
+            return Err(LaunchError::MissingServerDownload(minecraft_version));
+        };
+
+        let loader_version = match instance_info.loader {
+            Loader::Vanilla | Loader::Unknown => None,
+            _ if instance_info.preferred_loader_version.is_some() => instance_info.preferred_loader_version,
+            Loader::Fabric => latest_fabric_loader_version(&*self.meta.fetch(&FabricLoaderManifestMetadataItem).await?),
+            Loader::Quilt => latest_quilt_loader_version(&*self.meta.fetch(&QuiltLoaderManifestMetadataItem).await?),
+            Loader::Forge => latest_forgelike_loader_version(minecraft_version.as_str(), &self.meta.fetch(&ForgeInstallerMavenMetadataItem).await?.0, false),
+            Loader::NeoForge => latest_forgelike_loader_version(minecraft_version.as_str(), &self.meta.fetch(&NeoforgeInstallerMavenMetadataItem).await?.0, true),
+        };
+        if instance_info.loader != Loader::Vanilla && loader_version.is_none() {
+            return Err(LaunchError::CantFindVersion(minecraft_version.as_str()));
+        }
+
+        launch_tracker.add_count(1);
+        launch_tracker.notify();
+
+        let mut artifacts = vec![GameLibraryArtifact {
+            path: format!("net/minecraft/{0}/minecraft-server-{0}.jar", minecraft_version).into(),
+            sha1: Some(server_download.sha1),
+            size: Some(server_download.size),
+            url: server_download.url,
+        }];
+        if let Some(loader_version) = loader_version {
+            let (path, url) = match instance_info.loader {
+                Loader::Fabric => (
+                    format!("net/fabricmc/fabric-installer/{0}/fabric-installer-{0}.jar", FABRIC_SERVER_INSTALLER_VERSION),
+                    format!("https://maven.fabricmc.net/net/fabricmc/fabric-installer/{0}/fabric-installer-{0}.jar", FABRIC_SERVER_INSTALLER_VERSION),
+                ),
+                Loader::Quilt => (
+                    format!("org/quiltmc/quilt-installer/{0}/quilt-installer-{0}.jar", QUILT_SERVER_INSTALLER_VERSION),
+                    format!("https://maven.quiltmc.org/repository/release/org/quiltmc/quilt-installer/{0}/quilt-installer-{0}.jar", QUILT_SERVER_INSTALLER_VERSION),
+                ),
+                Loader::Forge => (
+                    format!("net/minecraftforge/forge/{0}/forge-{0}-installer.jar", loader_version),
+                    format!("https://maven.minecraftforge.net/net/minecraftforge/forge/{0}/forge-{0}-installer.jar", loader_version),
+                ),
+                Loader::NeoForge => (
+                    format!("net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar", loader_version),
+                    format!("https://maven.neoforged.net/releases/net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar", loader_version),
+                ),
+                Loader::Vanilla | Loader::Unknown => unreachable!(),
+            };
+            let sha1 = Self::download_sha1(http_client, &format!("{url}.sha1")).await;
+            artifacts.push(GameLibraryArtifact {
+                path: path.into(),
+                sha1,
+                size: None,
+                url: url.into(),
+            });
+        }
+
+        let (java_path, library_paths) = futures::future::try_join(
+            self.load_java_binary(&self.meta, http_client, instance_info, java_runtimes, &version_info, progress_trackers, launch_tracker, None).map_err(LaunchError::from),
+            self.load_libraries(http_client, &artifacts, progress_trackers, launch_tracker, None).map_err(LaunchError::from),
+        ).await?;
+
+        // The marker remembers which loader was installed, so the installer only needs to run again when the version changes
+        let installed_marker_path = server_dir.join(".pandora.server_installed");
+        let installed_marker = format!("{:?} {} {}", instance_info.loader, minecraft_version, loader_version.as_deref().unwrap_or_default());
+
+        let server_jar_path = server_dir.join("server.jar");
+        let needs_install = std::fs::read_to_string(&installed_marker_path).ok().as_deref() != Some(installed_marker.as_str());
+        if needs_install || !server_jar_path.exists() {
+            std::fs::copy(&library_paths[0].1, &server_jar_path)?;
+        }
+
+        if needs_install && let Some(loader_version) = loader_version {
+            let installer_tracker = ProgressTracker::new(format!("Installing {:?} server", instance_info.loader).into(), self.sender.clone());
+            progress_trackers.push(installer_tracker.clone());
+            installer_tracker.notify();
+
+            let installer_path = &library_paths[1].1;
+            let mut command = std::process::Command::new(&java_path);
+            command.current_dir(server_dir);
+            command.arg("-jar");
+            command.arg(installer_path);
+            match instance_info.loader {
+                Loader::Fabric => {
+                    command.args(["server", "-dir", ".", "-mcversion", minecraft_version.as_str(), "-loader", loader_version.as_str()]);
+                },
+                Loader::Quilt => {
+                    command.args(["install", "server", minecraft_version.as_str(), loader_version.as_str(), "--install-dir=."]);
+                },
+                Loader::Forge | Loader::NeoForge => {
+                    command.args(["--installServer", "."]);
+                },
+                Loader::Vanilla | Loader::Unknown => unreachable!(),
+            }
+
+            let result = tokio::task::spawn_blocking(move || command.output()).await.unwrap();
+
+            installer_tracker.set_finished(ProgressTrackerFinishType::from_err(!result.as_ref().is_ok_and(|output| output.status.success())));
+            installer_tracker.notify();
+
+            let output = result?;
+            if !output.status.success() {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                log::error!("Server installer failed with {}:\n{}\n{}", output.status, stdout, stderr);
+
+                let output = if stderr.trim().is_empty() { stdout } else { stderr };
+                let last_lines = output.lines().rev().take(10).collect::<Vec<_>>().into_iter().rev().collect::<Vec<_>>().join("\n");
+                return Err(LaunchError::ServerInstallerError(last_lines.into()));
+            }
+
+            // Forge and NeoForge leave their installer log next to the server
+            let _ = std::fs::remove_file(server_dir.join("installer.log"));
+            let _ = std::fs::remove_file(server_dir.join(format!("{}.log", installer_path.file_name().unwrap_or_default().to_string_lossy())));
+        }
+
+        let launch_target = find_server_launch_target(server_dir, instance_info.loader, loader_version.as_deref()).ok_or(LaunchError::MissingServerLaunchTarget)?;
+
+        crate::write_safe(&installed_marker_path, installed_marker.as_bytes())?;
+
+        launch_tracker.add_count(1);
+        launch_tracker.notify();
+
+        Ok((java_path, launch_target))
+    }
+
     async fn create_launch_version(
         &self,
         http_client: &reqwest::Client,
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
         instance_info: &InstanceConfiguration,
+        java_runtimes: &JavaRuntimeRegistry,
+        offline: Option<&OfflineArtifacts>,
     ) -> Result<(Arc<MinecraftVersion>, AddVanillaJar), LaunchError> {
         match instance_info.loader {
             Loader::Vanilla => {
                 launch_tracker.add_total(1);
                 launch_tracker.notify();
 
-                let versions = self.meta.fetch(&MinecraftVersionManifestMetadataItem).await?;
+                let versions = fetch_meta(&self.meta, &MinecraftVersionManifestMetadataItem, offline).await?;
 
                 launch_tracker.add_count(1);
                 launch_tracker.notify();
@@ -264,22 +687,18 @@ impl Launcher {
                     return Err(LaunchError::CantFindVersion(instance_info.minecraft_version.as_str()));
                 };
 
-                Ok((self.meta.fetch(&MinecraftVersionMetadataItem(version)).await?, AddVanillaJar::Yes))
+                Ok((fetch_meta(&self.meta, &MinecraftVersionMetadataItem(version), offline).await?, AddVanillaJar::Yes))
             },
             Loader::Fabric => {
-                let versions = self.meta.fetch(&MinecraftVersionManifestMetadataItem).map_err(LaunchError::from);
+                let versions = fetch_meta(&self.meta, &MinecraftVersionManifestMetadataItem, offline).map_err(LaunchError::from);
 
                 let fabric_loader_version = async move {
                     if let Some(preferred_version) = instance_info.preferred_loader_version {
                         Ok(preferred_version)
                     } else {
-                        let manifest = self.meta.fetch(&FabricLoaderManifestMetadataItem).map_err(LaunchError::from).await?;
+                        let manifest = fetch_meta(&self.meta, &FabricLoaderManifestMetadataItem, offline).map_err(LaunchError::from).await?;
 
-                        let mut latest_loader_version = manifest.0.iter().find(|v| v.stable);
-                        if latest_loader_version.is_none() {
-                            latest_loader_version = manifest.0.first();
-                        }
-                        Ok(latest_loader_version.unwrap().version)
+                        latest_fabric_loader_version(&manifest).ok_or(LaunchError::CantFindVersion(instance_info.minecraft_version.as_str()))
                     }
                 };
 
@@ -293,10 +712,10 @@ impl Launcher {
                     launch_tracker2.add_count(1);
                     launch_tracker2.notify();
 
-                    let value = meta2.fetch(&FabricLaunchMetadataItem {
+                    let value = fetch_meta(&meta2, &FabricLaunchMetadataItem {
                         minecraft_version,
                         loader_version,
-                    }).await?;
+                    }, offline).await?;
 
                     launch_tracker2.add_count(1);
                     launch_tracker2.notify();
@@ -315,7 +734,7 @@ impl Launcher {
                         return Err(LaunchError::CantFindVersion(instance_version.as_str()));
                     };
 
-                    let value = meta3.fetch(&MinecraftVersionMetadataItem(version)).await?;
+                    let value = fetch_meta(&meta3, &MinecraftVersionMetadataItem(version), offline).await?;
 
                     launch_tracker3.add_count(1);
                     launch_tracker3.notify();
@@ -329,43 +748,11 @@ impl Launcher {
                 let mut version: MinecraftVersion = (*version).clone();
 
                 if let Some(loader) = &fabric_launch.loader {
-                    let loader_coordinate = MavenCoordinate::create(&loader.maven);
-                    let artifact_path = loader_coordinate.artifact_path();
-                    version.libraries.push(GameLibrary {
-                        downloads: GameLibraryDownloads {
-                            artifact: Some(GameLibraryArtifact {
-                                url: format!("https://maven.fabricmc.net/{}", &artifact_path).into(),
-                                path: artifact_path.into(),
-                                sha1: None,
-                                size: None,
-                            }),
-                            classifiers: None,
-                        },
-                        name: loader.maven,
-                        rules: None,
-                        natives: None,
-                        extract: None,
-                    });
+                    version.libraries.push(create_maven_library(loader.maven, "https://maven.fabricmc.net/"));
                 }
 
                 if let Some(intermediary) = &fabric_launch.intermediary {
-                    let intermediary_coordinate = MavenCoordinate::create(&intermediary.maven);
-                    let artifact_path = intermediary_coordinate.artifact_path();
-                    version.libraries.push(GameLibrary {
-                        downloads: GameLibraryDownloads {
-                            artifact: Some(GameLibraryArtifact {
-                                url: format!("https://maven.fabricmc.net/{}", &artifact_path).into(),
-                                path: artifact_path.into(),
-                                sha1: None,
-                                size: None,
-                            }),
-                            classifiers: None,
-                        },
-                        name: intermediary.maven,
-                        rules: None,
-                        natives: None,
-                        extract: None,
-                    });
+                    version.libraries.push(create_maven_library(intermediary.maven, "https://maven.fabricmc.net/"));
                 }
 
                 let libraries = &fabric_launch.launcher_meta.libraries;
@@ -393,17 +780,95 @@ impl Launcher {
 
                 Ok((Arc::new(version), AddVanillaJar::Yes))
             },
+            Loader::Quilt => {
+                let versions = fetch_meta(&self.meta, &MinecraftVersionManifestMetadataItem, offline).map_err(LaunchError::from);
+
+                let quilt_loader_version = async move {
+                    if let Some(preferred_version) = instance_info.preferred_loader_version {
+                        Ok(preferred_version)
+                    } else {
+                        let manifest = fetch_meta(&self.meta, &QuiltLoaderManifestMetadataItem, offline).map_err(LaunchError::from).await?;
+
+                        latest_quilt_loader_version(&manifest).ok_or(LaunchError::CantFindVersion(instance_info.minecraft_version.as_str()))
+                    }
+                };
+
+                launch_tracker.add_total(4);
+                launch_tracker.notify();
+
+                let launch_tracker2 = launch_tracker.clone();
+                let meta2 = Arc::clone(&self.meta);
+                let minecraft_version = instance_info.minecraft_version;
+                let quilt_launch = quilt_loader_version.and_then(async move |loader_version| {
+                    launch_tracker2.add_count(1);
+                    launch_tracker2.notify();
+
+                    let value = fetch_meta(&meta2, &QuiltLaunchMetadataItem {
+                        minecraft_version,
+                        loader_version,
+                    }, offline).await?;
+
+                    launch_tracker2.add_count(1);
+                    launch_tracker2.notify();
+
+                    Ok(value)
+                });
+
+                let launch_tracker3 = launch_tracker.clone();
+                let meta3 = Arc::clone(&self.meta);
+                let instance_version = instance_info.minecraft_version;
+                let version = versions.and_then(async move |versions| {
+                    launch_tracker3.add_count(1);
+                    launch_tracker3.notify();
+
+                    let Some(version) = versions.versions.iter().find(|v| v.id == instance_version) else {
+                        return Err(LaunchError::CantFindVersion(instance_version.as_str()));
+                    };
+
+                    let value = fetch_meta(&meta3, &MinecraftVersionMetadataItem(version), offline).await?;
+
+                    launch_tracker3.add_count(1);
+                    launch_tracker3.notify();
+
+                    Ok(value)
+                });
+
+                let (version, quilt_launch): (Arc<MinecraftVersion>, Arc<QuiltLaunch>) =
+                    futures::future::try_join(version, quilt_launch).await?;
+
+                let mut version: MinecraftVersion = (*version).clone();
+
+                if let Some(loader) = &quilt_launch.loader {
+                    version.libraries.push(create_maven_library(loader.maven, "https://maven.quiltmc.org/repository/release/"));
+                }
+
+                // Quilt is able to run on both intermediary and hashed mappings, prefer intermediary since it's what Fabric mods use
+                if let Some(intermediary) = &quilt_launch.intermediary {
+                    version.libraries.push(create_maven_library(intermediary.maven, "https://maven.fabricmc.net/"));
+                } else if let Some(hashed) = &quilt_launch.hashed {
+                    version.libraries.push(create_maven_library(hashed.maven, "https://maven.quiltmc.org/repository/release/"));
+                }
+
+                let libraries = &quilt_launch.launcher_meta.libraries;
+                for library in libraries.common.iter().chain(libraries.client.iter()) {
+                    version.libraries.push(create_maven_library(library.name, &library.url));
+                }
+
+                version.main_class = quilt_launch.launcher_meta.main_class.client;
+
+                Ok((Arc::new(version), AddVanillaJar::Yes))
+            },
             Loader::Forge => {
                 launch_tracker.add_total(7);
                 launch_tracker.notify();
 
                 // Download Minecraft manifest and neoforge installer maven
                 let (minecraft_versions, loader_versions) = futures::future::try_join(
-                    self.meta.fetch(&MinecraftVersionManifestMetadataItem),
-                    self.meta.fetch(&ForgeInstallerMavenMetadataItem)
+                    fetch_meta(&self.meta, &MinecraftVersionManifestMetadataItem, offline),
+                    fetch_meta(&self.meta, &ForgeInstallerMavenMetadataItem, offline)
                 ).await?;
 
-                self.create_forgelike_launch_version(http_client, progress_trackers, launch_tracker, instance_info,
+                self.create_forgelike_launch_version(http_client, progress_trackers, launch_tracker, instance_info, java_runtimes, offline,
                     minecraft_versions,
                     &loader_versions.0,
                     "https://maven.minecraftforge.net/net/minecraftforge/forge/{0}/forge-{0}-installer.jar.sha1",
@@ -419,11 +884,11 @@ impl Launcher {
 
                 // Download Minecraft manifest and neoforge installer maven
                 let (minecraft_versions, loader_versions) = futures::future::try_join(
-                    self.meta.fetch(&MinecraftVersionManifestMetadataItem),
-                    self.meta.fetch(&NeoforgeInstallerMavenMetadataItem)
+                    fetch_meta(&self.meta, &MinecraftVersionManifestMetadataItem, offline),
+                    fetch_meta(&self.meta, &NeoforgeInstallerMavenMetadataItem, offline)
                 ).await?;
 
-                self.create_forgelike_launch_version(http_client, progress_trackers, launch_tracker, instance_info,
+                self.create_forgelike_launch_version(http_client, progress_trackers, launch_tracker, instance_info, java_runtimes, offline,
                     minecraft_versions,
                     &loader_versions.0,
                     "https://maven.neoforged.net/releases/net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar.sha1",
@@ -443,6 +908,8 @@ impl Launcher {
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
         instance_info: &InstanceConfiguration,
+        java_runtimes: &JavaRuntimeRegistry,
+        offline: Option<&OfflineArtifacts>,
         minecraft_versions: Arc<MinecraftVersionManifest>,
         loader_versions: &[Ustr],
         installer_hash_url: &'static str,
@@ -460,49 +927,24 @@ impl Launcher {
 
         let loader_version = if let Some(preferred_loader_version) = instance_info.preferred_loader_version {
             preferred_loader_version
-        } else {
-            let mut minecraft_version_parts = VersionFragment::string_to_parts(instance_info.minecraft_version.as_str());
-            if neoforge_versioning {
-                // 1.21.5 -> 21.5
-                // 25w14craftmine -> 0.25w14craftmine
-                // 1.21 -> 21.0
-                // 26.1 -> 26.1.0
-                if minecraft_version_parts[0] == VersionFragment::String("25w14craftmine".into()) {
-                    minecraft_version_parts.insert(0, VersionFragment::Number(0))
-                } else {
-                    if minecraft_version_parts.len() < 3 {
-                        minecraft_version_parts.push(VersionFragment::Number(0))
-                    }
-                    if minecraft_version_parts[0] == VersionFragment::Number(1) {
-                        minecraft_version_parts.remove(0);
-                    }
-                }
-            }
-
-            let mut latest_loader_version = None;
-            let mut latest_loader_version_parts = Vec::new();
-            for version in loader_versions.iter() {
-                let parts = VersionFragment::string_to_parts(version);
-
-                if parts.starts_with(&minecraft_version_parts) {
-                    if parts > latest_loader_version_parts {
-                        latest_loader_version_parts = parts;
-                        latest_loader_version = Some(version.clone());
-                    }
-                }
-            }
-            let Some(latest_loader_version) = latest_loader_version else {
-                return Err(LaunchError::CantFindVersion(instance_info.minecraft_version.as_str()));
-            };
-
+        } else if let Some(latest_loader_version) = latest_forgelike_loader_version(instance_info.minecraft_version.as_str(), loader_versions, neoforge_versioning) {
             latest_loader_version
+        } else {
+            return Err(LaunchError::CantFindVersion(instance_info.minecraft_version.as_str()));
         };
 
         // Download base Minecraft version and neoforge installer hash
         let installer_hash_url = installer_hash_url.replace("{0}", &loader_version);
+        let download_installer_sha1 = async {
+            if offline.is_some() {
+                None
+            } else {
+                Self::download_sha1(http_client, &installer_hash_url).await
+            }
+        };
         let (base_version, installer_sha1) = futures::future::join(
-            self.meta.fetch(&MinecraftVersionMetadataItem(version_link)),
-            Self::download_sha1(http_client, &installer_hash_url)
+            fetch_meta(&self.meta, &MinecraftVersionMetadataItem(version_link), offline),
+            download_installer_sha1
         ).await;
         let base_version = base_version?;
 
@@ -525,20 +967,31 @@ impl Launcher {
             },
         ];
 
-        let mojang_java_binary_future = self.load_mojang_java_binary(
+        let java_binary_future = self.load_java_binary(
             &self.meta,
             http_client,
             instance_info,
+            java_runtimes,
             &base_version,
             progress_trackers,
             launch_tracker,
+            offline,
         );
-        let load_installer_library_future = self.load_libraries(http_client, artifacts, progress_trackers, launch_tracker);
-
-        let (artifact_load_result, java_load_result) = futures::future::try_join(
-            load_installer_library_future.map_err(LaunchError::from),
-            mojang_java_binary_future.map_err(LaunchError::from),
-        ).await?;
+        let load_installer_library_future = self.load_libraries(http_client, artifacts, progress_trackers, launch_tracker, offline);
+
+        let (artifact_load_result, java_load_result) = if let Some(offline) = offline {
+            let (artifact_load_result, java_load_result) = futures::future::join(
+                load_installer_library_future,
+                java_binary_future,
+            ).await;
+            offline.check()?;
+            (artifact_load_result?, java_load_result?)
+        } else {
+            futures::future::try_join(
+                load_installer_library_future.map_err(LaunchError::from),
+                java_binary_future.map_err(LaunchError::from),
+            ).await?
+        };
         let installer_path = &artifact_load_result[0].1;
         let minecraft_jar_path = &artifact_load_result[1].1;
 
@@ -559,14 +1012,14 @@ impl Launcher {
             if let Ok(install_profile_legacy) = serde_json::from_slice(&install_profile_bytes) {
                 launch_tracker.add_count(1);
                 let ret = self.create_forgelike_install_version_legacy(install_profile_legacy, installer_zip,
-                    base_version, http_client, progress_trackers, launch_tracker, instance_info, check_mirrors).await;
+                    base_version, http_client, progress_trackers, launch_tracker, instance_info, offline, check_mirrors).await;
                 return ret;
             }
         }
 
         self.create_forgelike_install_version_modern(install_profile?, installer_zip,
             installer_path, minecraft_jar_path, &java_load_result, base_version, http_client,
-            progress_trackers, launch_tracker, instance_info, check_mirrors).await
+            progress_trackers, launch_tracker, instance_info, offline, check_mirrors).await
     }
 
     async fn create_forgelike_install_version_modern(
@@ -581,6 +1034,7 @@ impl Launcher {
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
         instance_info: &InstanceConfiguration,
+        offline: Option<&OfflineArtifacts>,
         check_mirrors: bool,
     ) -> Result<(Arc<MinecraftVersion>, AddVanillaJar), LaunchError> {
         if &*install_profile.minecraft != instance_info.minecraft_version.as_str() {
@@ -598,7 +1052,7 @@ impl Launcher {
         let version: PartialMinecraftVersion = serde_json::from_slice(&version_file.bytes()?)?;
 
         // Download mirror list
-        let mirror = if check_mirrors {
+        let mirror = if check_mirrors && offline.is_none() {
             Self::download_random_mirror(http_client, &install_profile.mirror_list).await
         } else {
             None
@@ -618,7 +1072,10 @@ impl Launcher {
             Some(artifact)
         }).collect::<Vec<_>>();
 
-        self.load_libraries(http_client, &libraries, progress_trackers, launch_tracker).await?;
+        self.load_libraries(http_client, &libraries, progress_trackers, launch_tracker, offline).await?;
+        if let Some(offline) = offline {
+            offline.check()?;
+        }
 
         let forge_temp = self.directories.temp_dir.join("forge_installer");
 
@@ -794,6 +1251,7 @@ impl Launcher {
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
         instance_info: &InstanceConfiguration,
+        offline: Option<&OfflineArtifacts>,
         check_mirrors: bool,
     ) -> Result<(Arc<MinecraftVersion>, AddVanillaJar), LaunchError> {
         if &*install_profile.install.minecraft != instance_info.minecraft_version.as_str() {
@@ -815,7 +1273,7 @@ impl Launcher {
         let version: PartialMinecraftVersion = install_profile.version_info.into_partial_version(ForgeSide::Client);
 
         // Download mirror list
-        let mirror = if check_mirrors {
+        let mirror = if check_mirrors && offline.is_none() {
             Self::download_random_mirror(http_client, &install_profile.install.mirror_list).await
         } else {
             None
@@ -836,7 +1294,10 @@ impl Launcher {
                 Some(artifact)
             }).collect::<Vec<_>>();
 
-            self.load_libraries(http_client, &libraries, progress_trackers, launch_tracker).await?;
+            self.load_libraries(http_client, &libraries, progress_trackers, launch_tracker, offline).await?;
+            if let Some(offline) = offline {
+                offline.check()?;
+            }
         }
 
         Ok((Arc::new(version.apply_to(&base_version)), AddVanillaJar::Yes))
@@ -875,34 +1336,44 @@ impl Launcher {
         Some(mirror.url.clone())
     }
 
-    async fn load_mojang_java_binary(
+    async fn load_java_binary(
         &self,
         meta: &MetadataManager,
         http_client: &reqwest::Client,
         configuration: &InstanceConfiguration,
+        java_runtimes: &JavaRuntimeRegistry,
         version_info: &MinecraftVersion,
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
+        offline: Option<&OfflineArtifacts>,
     ) -> Result<PathBuf, LoadJavaRuntimeError> {
         if let Some(jvm_binary) = &configuration.jvm_binary {
-            if jvm_binary.enabled && let Some(path) = &jvm_binary.path {
+            if jvm_binary.mode == InstanceSettingMode::Override && let Some(path) = &jvm_binary.path {
                 if let Some(binary) = Self::search_for_java_binary(&path) {
                     return Ok(binary);
                 }
             }
         }
 
+        let needed_version = if let Some(java_version) = &version_info.java_version {
+            java_version.major_version
+        } else {
+            8
+        };
+
+        if let Some(path) = java_runtimes.find_for_major_version(needed_version) {
+            let path = Path::new(&**path);
+            if path.is_file() {
+                return Ok(path.to_path_buf());
+            }
+            log::warn!("Registered Java {} runtime {:?} no longer exists, falling back", needed_version, path);
+        }
+
         if let Some(force_external_java) = std::env::var_os("FORCE_EXTERNAL_JAVA") {
             let paths = std::env::split_paths(&force_external_java);
 
             let mut found_versions = BTreeSet::new();
 
-            let needed_version = if let Some(java_version) = &version_info.java_version {
-                java_version.major_version
-            } else {
-                8
-            };
-
             for path in paths {
                 let Some(binary) = Self::search_for_java_binary(&path) else {
                     continue;
@@ -922,78 +1393,28 @@ impl Launcher {
             return Err(LoadJavaRuntimeError::UnableToFindExternalBinary(needed_version, found_versions.into_iter().collect()));
         }
 
-        let mut platform: Ustr = match (std::env::consts::OS, std::env::consts::ARCH) {
-            ("linux", "x86_64") => "linux".into(),
-            ("linux", "x86") => "linux-i386".into(),
-            ("macos", "x86_64") => "mac-os".into(),
-            ("macos", "aarch64") => "mac-os-arm64".into(),
-            ("windows", "aarch64") => "windows-arm64".into(),
-            ("windows", "x86_64") => "windows-x64".into(),
-            ("windows", "x86") => "windows-x86".into(),
-            ("macos", b) => format!("mac-os-{b}").into(),
-            (a, b) => format!("{a}-{b}").into(),
-        };
-
-        let jre_component = if let Some(java_version) = &version_info.java_version {
-            java_version.component
-        } else {
-            "jre-legacy".into()
-        };
-
-        let runtimes = meta.fetch(&MojangJavaRuntimesMetadataItem).await?;
-
-        let mut runtime_platform = runtimes.platforms.get(&platform).ok_or(LoadJavaRuntimeError::UnknownPlatform)?;
-        let mut runtime_components = runtime_platform.components.get(&jre_component);
-
-        // Fall back to x86 runtime on mac-os, since Rosetta exists
-        let missing_runtime_component = runtime_components.map(Vec::is_empty).unwrap_or(true);
-        if missing_runtime_component && platform == "mac-os-arm64" {
-            platform = "mac-os".into();
-            runtime_platform = runtimes.platforms.get(&platform).ok_or(LoadJavaRuntimeError::UnknownPlatform)?;
-            runtime_components = runtime_platform.components.get(&jre_component);
-        }
-
-        let Some(runtime_components) = runtime_components else {
-            return Err(LoadJavaRuntimeError::UnknownComponentForPlatform);
-        };
-        let runtime_component = runtime_components.first().ok_or(LoadJavaRuntimeError::UnknownComponentForPlatform)?;
-
-        if !crate::is_single_component_path_str(jre_component.as_str()) {
-            return Err(LoadJavaRuntimeError::InvalidComponentPath);
-        }
-        if !crate::is_single_component_path_str(&platform) {
-            return Err(LoadJavaRuntimeError::InvalidComponentPath);
-        }
-
-        let runtime_component_dir = self.directories.runtime_base_dir.join(jre_component).join(platform);
-        let _ = std::fs::create_dir_all(&runtime_component_dir);
-        let Ok(runtime_component_dir) = runtime_component_dir.canonicalize() else {
-            return Err(LoadJavaRuntimeError::InvalidComponentPath);
+        let context = JavaRuntimeContext {
+            http_client,
+            version_info,
+            runtime_base_dir: &self.directories.runtime_base_dir,
+            sender: &self.sender,
+            progress_trackers,
+            offline,
         };
 
-        let fresh_install = !runtime_component_dir.exists();
-
-        let runtime = meta.fetch(&MojangJavaRuntimeComponentMetadataItem {
-            url: runtime_component.manifest.url,
-            cache: runtime_component_dir.join("manifest.json").into(),
-            hash: runtime_component.manifest.sha1,
-        }).await?;
-
-        let initial_title = if fresh_install {
-            "Downloading Java Runtime"
-        } else {
-            "Verifying integrity of Java Runtime"
+        let mojang = MojangJavaRuntimeProvider { meta };
+        let result = match mojang.load_java_runtime(&context).await {
+            Err(LoadJavaRuntimeError::UnknownPlatform | LoadJavaRuntimeError::UnknownComponentForPlatform) => {
+                log::info!("Mojang doesn't provide Java {} for this platform, falling back to Adoptium", needed_version);
+                let adoptium = AdoptiumJavaRuntimeProvider {
+                    api_url: java_runtimes.adoptium_api_url.as_deref().unwrap_or(DEFAULT_ADOPTIUM_API_URL),
+                    major_version: needed_version,
+                };
+                adoptium.load_java_runtime(&context).await
+            },
+            result => result,
         };
 
-        let java_runtime_tracker = ProgressTracker::new(initial_title.into(), self.sender.clone());
-        progress_trackers.push(java_runtime_tracker.clone());
-        java_runtime_tracker.notify();
-
-        let result = do_java_runtime_load(http_client, runtime_component_dir, fresh_install, runtime, &java_runtime_tracker).await;
-
-        java_runtime_tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
-        java_runtime_tracker.notify();
-
         launch_tracker.add_count(1);
         launch_tracker.notify();
 
@@ -1008,14 +1429,15 @@ impl Launcher {
         version_info: &MinecraftVersion,
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
+        offline: Option<&OfflineArtifacts>,
     ) -> Result<String, LoadAssetObjectsError> {
         let asset_index = format!("{}", version_info.assets);
 
-        let assets_index = meta.fetch(&AssetsIndexMetadataItem {
+        let assets_index = fetch_meta(meta, &AssetsIndexMetadataItem {
             url: version_info.asset_index.url,
             cache: self.directories.assets_index_dir.join(format!("{}.json", &asset_index)).into(),
             hash: version_info.asset_index.sha1,
-        }).await?;
+        }, offline).await?;
 
         let initial_title = Arc::from("Verifying integrity of game assets");
         let assets_tracker = ProgressTracker::new(initial_title, self.sender.clone());
@@ -1030,7 +1452,7 @@ impl Launcher {
             self.directories.assets_objects_dir.clone()
         };
 
-        let result = do_asset_objects_load(http_client, assets_index, assets_dir, &assets_tracker).await;
+        let result = do_asset_objects_load(http_client, assets_index, assets_dir, &assets_tracker, offline).await;
 
         assets_tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
         assets_tracker.notify();
@@ -1049,6 +1471,7 @@ impl Launcher {
         artifacts: &[GameLibraryArtifact],
         progress_trackers: &ProgressTrackers,
         launch_tracker: &ProgressTracker,
+        offline: Option<&OfflineArtifacts>,
     ) -> Result<Vec<(Ustr, PathBuf)>, LoadLibrariesError> {
         let initial_title = Arc::from("Verifying integrity of game libraries");
         let libraries_tracker = ProgressTracker::new(initial_title, self.sender.clone());
@@ -1056,7 +1479,7 @@ impl Launcher {
         libraries_tracker.notify();
 
         let result =
-            do_libraries_load(http_client, artifacts, self.directories.libraries_dir.clone(), &libraries_tracker).await;
+            do_libraries_load(http_client, artifacts, self.directories.libraries_dir.clone(), &libraries_tracker, offline).await;
 
         libraries_tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
         libraries_tracker.notify();
@@ -1071,6 +1494,7 @@ impl Launcher {
         &self,
         http_client: &reqwest::Client,
         logging: Option<&GameLogging>,
+        offline: bool,
     ) -> Option<OsString> {
         let Some(logging) = logging else {
             return None;
@@ -1104,6 +1528,11 @@ impl Launcher {
             return Some(expand_logging_argument(client.argument.as_str(), &path));
         }
 
+        if offline {
+            log::warn!("Log configuration isn't downloaded, launching without it");
+            return None;
+        }
+
         let Ok(response) = http_client.get(client.file.url.as_str()).send().await else {
             log::error!("Failed to make request to download log configuration");
             return None;
@@ -1259,7 +1688,7 @@ impl Launcher {
         }
     }
 
-    fn search_for_java_binary(path: &Path) -> Option<PathBuf> {
+    pub(crate) fn search_for_java_binary(path: &Path) -> Option<PathBuf> {
         if path.is_file() {
             return Some(path.to_path_buf());
         }
@@ -1295,31 +1724,82 @@ impl Launcher {
     }
 
     fn get_major_java_version(&self, binary: &Path) -> Option<u32> {
-        let mut command = std::process::Command::new(binary);
-        command.arg("-jar");
-        command.arg(self.launch_wrapper.as_os_str().to_os_string());
-        command.stdin(Stdio::piped());
-        command.stdout(Stdio::piped());
+        crate::java_registry::probe_java(&self.launch_wrapper, binary).map(|probe| probe.major_version)
+    }
 
-        let mut process = command.spawn().ok()?;
+    pub fn discover_java_runtimes(&self) -> Vec<JavaRuntimeInfo> {
+        crate::java_registry::discover(&self.directories, &self.launch_wrapper)
+    }
+}
 
-        let mut stdin = process.stdin.take().unwrap();
-        stdin.write_all(b"printproperty\njava.specification.version\nexit\n").ok()?;
-        stdin.flush().ok()?;
+pub(crate) fn latest_fabric_loader_version(manifest: &FabricLoaderManifest) -> Option<Ustr> {
+    let mut latest_loader_version = manifest.0.iter().find(|v| v.stable);
+    if latest_loader_version.is_none() {
+        latest_loader_version = manifest.0.first();
+    }
+    latest_loader_version.map(|v| v.version)
+}
 
-        let output = process.wait_with_output().ok()?;
+pub(crate) fn latest_quilt_loader_version(manifest: &QuiltLoaderManifest) -> Option<Ustr> {
+    let mut latest_loader_version = manifest.0.iter().find(|v| v.is_stable());
+    if latest_loader_version.is_none() {
+        latest_loader_version = manifest.0.first();
+    }
+    latest_loader_version.map(|v| v.version)
+}
 
-        if !output.status.success() {
-            return None;
+fn create_maven_library(name: Ustr, repository_url: &str) -> GameLibrary {
+    let coordinate = MavenCoordinate::create(&name);
+    let artifact_path = coordinate.artifact_path();
+    GameLibrary {
+        downloads: GameLibraryDownloads {
+            artifact: Some(GameLibraryArtifact {
+                url: format!("{}{}", repository_url, &artifact_path).into(),
+                path: artifact_path.into(),
+                sha1: None,
+                size: None,
+            }),
+            classifiers: None,
+        },
+        name,
+        rules: None,
+        natives: None,
+        extract: None,
+    }
+}
+
+pub(crate) fn latest_forgelike_loader_version(minecraft_version: &str, loader_versions: &[Ustr], neoforge_versioning: bool) -> Option<Ustr> {
+    let mut minecraft_version_parts = VersionFragment::string_to_parts(minecraft_version);
+    if neoforge_versioning {
+        // 1.21.5 -> 21.5
+        // 25w14craftmine -> 0.25w14craftmine
+        // 1.21 -> 21.0
+        // 26.1 -> 26.1.0
+        if minecraft_version_parts[0] == VersionFragment::String("25w14craftmine".into()) {
+            minecraft_version_parts.insert(0, VersionFragment::Number(0))
+        } else {
+            if minecraft_version_parts.len() < 3 {
+                minecraft_version_parts.push(VersionFragment::Number(0))
+            }
+            if minecraft_version_parts[0] == VersionFragment::Number(1) {
+                minecraft_version_parts.remove(0);
+            }
         }
+    }
+
+    let mut latest_loader_version = None;
+    let mut latest_loader_version_parts = Vec::new();
+    for version in loader_versions.iter() {
+        let parts = VersionFragment::string_to_parts(version);
 
-        let output = output.stdout.trim_ascii();
-        let mut output = str::from_utf8(output).ok()?;
-        if output.starts_with("1.") {
-            output = &output[2..];
+        if parts.starts_with(&minecraft_version_parts) {
+            if parts > latest_loader_version_parts {
+                latest_loader_version_parts = parts;
+                latest_loader_version = Some(version.clone());
+            }
         }
-        output.parse().ok()
     }
+    latest_loader_version
 }
 
 fn expand_logging_argument(argument: &str, path: &Path) -> OsString {
@@ -1399,14 +1879,23 @@ pub enum LoadJavaRuntimeError {
     UnableToFindBinary,
     #[error("Unable to find external binary, needed Java {0}, got Java {1:?}")]
     UnableToFindExternalBinary(u32, Vec<u32>),
+    #[error("Failed to deserialize data:\n{0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("Failed read zip:\n{0}")]
+    ZipError(#[from] rc_zip_sync::rc_zip::Error),
+    #[error("Adoptium has no Java {0} runtime for this platform")]
+    NoAdoptiumRuntime(u32),
+    #[error("Unsupported runtime archive: {0}")]
+    UnsupportedArchive(Arc<str>),
 }
 
-async fn do_java_runtime_load(
+pub(crate) async fn do_java_runtime_load(
     http_client: &reqwest::Client,
     runtime_component_dir: PathBuf,
     fresh_install: bool,
     runtime: Arc<JavaRuntimeComponentManifest>,
     java_runtime_tracker: &ProgressTracker,
+    offline: Option<&OfflineArtifacts>,
 ) -> Result<PathBuf, LoadJavaRuntimeError> {
     let mut links = HashMap::new();
 
@@ -1459,6 +1948,13 @@ async fn do_java_runtime_load(
                         return Ok(());
                     }
 
+                    if let Some(offline) = offline {
+                        offline.push(MissingArtifact::JavaRuntime(path));
+                        java_runtime_tracker.add_count(downloads.raw.size as usize);
+                        java_runtime_tracker.notify();
+                        return Ok(());
+                    }
+
                     let was_downloading = started_downloading.swap(true, std::sync::atomic::Ordering::Relaxed);
                     if !was_downloading {
                         java_runtime_tracker.set_title(Arc::from("Downloading Java Runtime"));
@@ -1615,6 +2111,7 @@ async fn do_asset_objects_load(
     assets_index: Arc<AssetsIndex>,
     assets_objects_dir: Arc<Path>,
     assets_tracker: &ProgressTracker,
+    offline: Option<&OfflineArtifacts>,
 ) -> Result<(), LoadAssetObjectsError> {
     // Limit max concurrent connections to 8 to avoid ratelimiting issues
     let download_semaphore = tokio::sync::Semaphore::new(8);
@@ -1662,6 +2159,13 @@ async fn do_asset_objects_load(
                 return Ok(());
             }
 
+            if let Some(offline) = offline {
+                offline.push(MissingArtifact::AssetObject(path));
+                assets_tracker.add_count(asset.size as usize);
+                assets_tracker.notify();
+                return Ok(());
+            }
+
             let was_downloading = started_downloading.swap(true, std::sync::atomic::Ordering::Relaxed);
             if !was_downloading {
                 assets_tracker.set_title(Arc::from("Downloading game assets"));
@@ -1729,6 +2233,7 @@ async fn do_libraries_load(
     artifacts: &[GameLibraryArtifact],
     libraries_dir: Arc<Path>,
     libraries_tracker: &ProgressTracker,
+    offline: Option<&OfflineArtifacts>,
 ) -> Result<Vec<(Ustr, PathBuf)>, LoadLibrariesError> {
     // Limit max concurrent connections to 8 to avoid ratelimiting issues
     let download_semaphore = tokio::sync::Semaphore::new(8);
@@ -1788,6 +2293,13 @@ async fn do_libraries_load(
                 return Ok((artifact.path, artifact_path));
             }
 
+            if let Some(offline) = offline {
+                offline.push(MissingArtifact::Library(artifact_path.clone()));
+                libraries_tracker.add_count(tracker_size as usize);
+                libraries_tracker.notify();
+                return Ok((artifact.path, artifact_path));
+            }
+
             let was_downloading = started_downloading.swap(true, std::sync::atomic::Ordering::Relaxed);
             if !was_downloading {
                 libraries_tracker.set_title(Arc::from("Downloading game libraries"));
@@ -2052,6 +2564,39 @@ impl LaunchRuleContext {
     }
 }
 
+// Applied after the launcher's own variables, so they can be overridden
+fn apply_environment_variables(command: &mut std::process::Command, configuration: &InstanceConfiguration) {
+    if let Some(environment_variables) = &configuration.environment_variables && environment_variables.mode == InstanceSettingMode::Override {
+        command.envs(InstanceEnvironmentVariablesConfiguration::parse_variables(&environment_variables.variables));
+    }
+}
+
+fn window_configuration(configuration: &InstanceConfiguration) -> WindowConfiguration {
+    match configuration.window {
+        Some(InstanceWindowConfiguration { mode: InstanceSettingMode::Override, window }) => window,
+        _ => WindowConfiguration::default(),
+    }
+}
+
+fn game_arguments(configuration: &InstanceConfiguration) -> Vec<String> {
+    let Some(game_arguments) = configuration.game_arguments.as_ref().filter(|game_arguments| game_arguments.mode == InstanceSettingMode::Override) else {
+        return Vec::new();
+    };
+    split_game_arguments(&game_arguments.arguments)
+}
+
+fn split_game_arguments(arguments: &str) -> Vec<String> {
+    match shell_words::split(arguments) {
+        Ok(split) => split,
+        Err(_) => arguments.split_whitespace().map(str::to_string).collect(),
+    }
+}
+
+// The launch wrapper reads one value per line, so a quoted line break would let an argument inject its own commands
+pub(crate) fn has_multiline_game_argument(arguments: &str) -> bool {
+    split_game_arguments(arguments).iter().any(|argument| argument.contains(['\n', '\r']))
+}
+
 pub struct LaunchContext {
     pub launch_wrapper_path: Arc<Path>,
     pub java_path: PathBuf,
@@ -2086,7 +2631,7 @@ impl LaunchContext {
             }
         }
 
-        if let Some(InstanceWrapperCommandConfiguration { enabled: true, ref flags }) = self.configuration.wrapper_command {
+        if let Some(InstanceWrapperCommandConfiguration { mode: InstanceSettingMode::Override, ref flags }) = self.configuration.wrapper_command {
             let split = &mut match shell_words::split(&flags) {
               Ok(split) => split,
               Err(_) =>  flags.split_whitespace().map(|f| f.to_string()).collect()
@@ -2117,6 +2662,8 @@ impl LaunchContext {
             }
         }
 
+        apply_environment_variables(&mut command, &self.configuration);
+
         command.current_dir(&self.game_dir);
         command.stdin(Stdio::piped());
         command.stdout(Stdio::piped());
@@ -2142,11 +2689,11 @@ impl LaunchContext {
             command.arg(log_configuration);
         }
 
-        if let Some(memory) = &self.configuration.memory && memory.enabled {
+        if let Some(memory) = &self.configuration.memory && memory.mode == InstanceSettingMode::Override {
             command.arg(format!("-Xms{}m", memory.min));
             command.arg(format!("-Xmx{}m", memory.max.max(memory.min).max(128)));
         }
-        if let Some(jvm_flags) = &self.configuration.jvm_flags && jvm_flags.enabled {
+        if let Some(jvm_flags) = &self.configuration.jvm_flags && jvm_flags.mode == InstanceSettingMode::Override {
             if let Ok(split) = shell_words::split(&jvm_flags.flags) {
                 command.args(split);
             } else {
@@ -2156,6 +2703,11 @@ impl LaunchContext {
 
         command.arg("com.moulberry.pandora.LaunchWrapper");
 
+        let custom_game_arguments = game_arguments(&self.configuration);
+        if custom_game_arguments.iter().any(|argument| argument.contains(['\n', '\r'])) {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Custom game arguments can't contain line breaks"));
+        }
+
         let mut child = command.spawn()?;
 
         let mut stdin = child.stdin.take().expect("stdin present");
@@ -2175,6 +2727,24 @@ impl LaunchContext {
                 stdin_arguments.push_str(self.expand_argument(argument).to_string_lossy().as_ref());
                 stdin_arguments.push('\n');
             }
+
+            // Only newer versions have the rule-gated resolution arguments
+            if version_info.arguments.is_none() && let Some((width, height)) = self.rule_context.custom_resolution {
+                for argument in ["--width".to_string(), width.to_string(), "--height".to_string(), height.to_string()] {
+                    stdin_arguments.push_str("arg\n");
+                    stdin_arguments.push_str(&argument);
+                    stdin_arguments.push('\n');
+                }
+            }
+        }
+        if window_configuration(&self.configuration).fullscreen {
+            stdin_arguments.push_str("arg\n");
+            stdin_arguments.push_str("--fullscreen\n");
+        }
+        for argument in custom_game_arguments {
+            stdin_arguments.push_str("arg\n");
+            stdin_arguments.push_str(&argument);
+            stdin_arguments.push('\n');
         }
 
         if !self.add_mods.is_empty() {
@@ -2188,6 +2758,14 @@ impl LaunchContext {
                     stdin_arguments.push_str(&mods.to_string_lossy());
                     stdin_arguments.push('\n');
                 },
+                Loader::Quilt => {
+                    let mods = std::env::join_paths(self.add_mods).unwrap();
+
+                    stdin_arguments.push_str("property\n");
+                    stdin_arguments.push_str("loader.addMods\n");
+                    stdin_arguments.push_str(&mods.to_string_lossy());
+                    stdin_arguments.push('\n');
+                },
                 _ => {
                     if cfg!(debug_assertions) {
                         panic!("addMods was used for unsupported loader: {:?}", self.configuration.loader);
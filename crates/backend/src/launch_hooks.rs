@@ -0,0 +1,231 @@
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::Path,
+    process::{ExitStatus, Stdio},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use bridge::{game_output::GameOutputLogLevel, handle::FrontendHandle, instance::InstanceID};
+use once_cell::sync::OnceCell;
+use schema::instance::InstanceHookCommandsConfiguration;
+use tokio_util::sync::CancellationToken;
+use ustr::Ustr;
+
+use crate::log_reader::GameOutput;
+
+// The launch waits on the pre-launch command, so one that hangs (e.g. waiting for credentials) shouldn't block it forever
+const PRE_LAUNCH_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(thiserror::Error, Debug)]
+pub enum LaunchHookError {
+    #[error("Unable to parse command:\n{0}")]
+    InvalidCommand(#[from] shell_words::ParseError),
+    #[error("Failed to run command:\n{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Command exited with {0}")]
+    Failed(ExitStatus),
+    #[error("Command was cancelled")]
+    Cancelled,
+    #[error("Command didn't finish within {} seconds", .0.as_secs())]
+    TimedOut(Duration),
+}
+
+pub struct LaunchHooks {
+    instance: InstanceID,
+    name: Ustr,
+    root_path: Arc<Path>,
+    dot_minecraft_path: Arc<Path>,
+    pre_launch: Option<Arc<str>>,
+    post_exit: Option<Arc<str>>,
+    java_path: OnceCell<Arc<str>>,
+    // Only opened once something is written to it, or when the game's own output is shown
+    output: OnceCell<GameOutput>,
+    sender: FrontendHandle,
+}
+
+impl LaunchHooks {
+    pub fn new(instance: InstanceID, name: Ustr, root_path: Arc<Path>, dot_minecraft_path: Arc<Path>, configuration: Option<&InstanceHookCommandsConfiguration>, sender: FrontendHandle) -> Self {
+        let (pre_launch, post_exit) = match configuration {
+            Some(configuration) if configuration.enabled => {
+                let non_empty = |command: &Arc<str>| (!command.trim_ascii().is_empty()).then(|| command.clone());
+                (non_empty(&configuration.pre_launch), non_empty(&configuration.post_exit))
+            },
+            _ => (None, None),
+        };
+
+        Self {
+            instance,
+            name,
+            root_path,
+            dot_minecraft_path,
+            pre_launch,
+            post_exit,
+            java_path: OnceCell::new(),
+            output: OnceCell::new(),
+            sender,
+        }
+    }
+
+    pub fn game_output(&self) -> &GameOutput {
+        self.output.get_or_init(|| GameOutput::create(self.instance, self.sender.clone()))
+    }
+
+    pub async fn run_pre_launch(&self, java_path: &Path, cancel: &CancellationToken) -> Result<(), LaunchHookError> {
+        let java_path = self.java_path.get_or_init(|| java_path.to_string_lossy().into());
+
+        let Some(command) = self.pre_launch.clone() else {
+            return Ok(());
+        };
+
+        let placeholders = self.placeholders(Some(java_path));
+        let root_path = self.root_path.clone();
+        let output = self.game_output().clone();
+        let cancel = cancel.clone();
+        tokio::task::spawn_blocking(move || {
+            run_command("pre-launch", &command, &placeholders, &root_path, &output, Some((&cancel, PRE_LAUNCH_TIMEOUT)))
+        }).await.unwrap()
+    }
+
+    pub fn into_post_exit_hook(self) -> Option<PostExitHook> {
+        let command = self.post_exit.clone()?;
+        let placeholders = self.placeholders(self.java_path.get().map(|java_path| &**java_path));
+
+        Some(PostExitHook {
+            instance: self.instance,
+            name: self.name,
+            command,
+            placeholders,
+            root_path: self.root_path,
+            output: self.output.into_inner(),
+            sender: self.sender,
+        })
+    }
+
+    fn placeholders(&self, java_path: Option<&str>) -> Vec<(&'static str, String)> {
+        vec![
+            ("INST_DIR", self.root_path.to_string_lossy().into_owned()),
+            ("INST_NAME", self.name.to_string()),
+            ("INST_MC_DIR", self.dot_minecraft_path.to_string_lossy().into_owned()),
+            ("INST_JAVA", java_path.unwrap_or_default().to_string()),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct PostExitHook {
+    instance: InstanceID,
+    name: Ustr,
+    command: Arc<str>,
+    placeholders: Vec<(&'static str, String)>,
+    root_path: Arc<Path>,
+    output: Option<GameOutput>,
+    sender: FrontendHandle,
+}
+
+impl PostExitHook {
+    pub fn run(self) {
+        tokio::task::spawn_blocking(move || {
+            // The game's output window may have been closed while playing
+            let output = match self.output {
+                Some(output) if output.is_alive() => output,
+                _ => GameOutput::create(self.instance, self.sender.clone()),
+            };
+
+            if let Err(error) = run_command("post-exit", &self.command, &self.placeholders, &self.root_path, &output, None) {
+                self.sender.send_error(format!("Post-exit command for {} failed:\n{error}", self.name));
+            }
+        });
+    }
+}
+
+fn run_command(kind: &str, command: &str, placeholders: &[(&'static str, String)], working_dir: &Path, output: &GameOutput, limits: Option<(&CancellationToken, Duration)>) -> Result<(), LaunchHookError> {
+    output.add(GameOutputLogLevel::Info, &format!("(Pandora) Running {kind} command: {command}"));
+
+    let result = run_command_inner(command, placeholders, working_dir, output, limits);
+    if let Err(error) = &result {
+        output.add(GameOutputLogLevel::Error, &format!("(Pandora) The {kind} command failed: {error}"));
+    }
+    result
+}
+
+fn run_command_inner(command: &str, placeholders: &[(&'static str, String)], working_dir: &Path, output: &GameOutput, limits: Option<(&CancellationToken, Duration)>) -> Result<(), LaunchHookError> {
+    // Split before expanding so that paths containing spaces stay a single argument
+    let split = shell_words::split(command)?;
+    let mut args = split.iter().map(|arg| expand_placeholders(arg, placeholders));
+    let Some(program) = args.next() else {
+        return Ok(());
+    };
+
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .envs(placeholders.iter().map(|(key, value)| (key, value)))
+        .current_dir(working_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stderr_thread = child.stderr.take().map(|stderr| {
+        let output = output.clone();
+        std::thread::spawn(move || forward_lines(stderr, GameOutputLogLevel::Error, &output))
+    });
+    let stdout_thread = child.stdout.take().map(|stdout| {
+        let output = output.clone();
+        std::thread::spawn(move || forward_lines(stdout, GameOutputLogLevel::Info, &output))
+    });
+
+    let status = match limits {
+        Some((cancel, timeout)) => {
+            let started = Instant::now();
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break status;
+                }
+
+                let error = if cancel.is_cancelled() {
+                    LaunchHookError::Cancelled
+                } else if started.elapsed() >= timeout {
+                    LaunchHookError::TimedOut(timeout)
+                } else {
+                    std::thread::sleep(Duration::from_millis(50));
+                    continue;
+                };
+
+                // The output threads aren't joined, processes spawned by the command may still hold the pipes open
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(error);
+            }
+        },
+        None => child.wait()?,
+    };
+
+    for thread in [stdout_thread, stderr_thread].into_iter().flatten() {
+        let _ = thread.join();
+    }
+
+    if !status.success() {
+        return Err(LaunchHookError::Failed(status));
+    }
+    Ok(())
+}
+
+fn forward_lines(reader: impl Read, level: GameOutputLogLevel, output: &GameOutput) {
+    for line in BufReader::new(reader).split(b'\n') {
+        let Ok(line) = line else {
+            break;
+        };
+        output.add(level, &String::from_utf8_lossy(&line));
+    }
+}
+
+fn expand_placeholders(arg: &str, placeholders: &[(&'static str, String)]) -> String {
+    let mut arg = arg.to_string();
+    for (key, value) in placeholders {
+        if arg.contains(key) {
+            arg = arg.replace(&format!("${{{key}}}"), value).replace(&format!("${key}"), value);
+        }
+    }
+    arg
+}
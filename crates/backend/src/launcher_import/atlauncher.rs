@@ -1,9 +1,9 @@
 use std::{path::{Path, PathBuf}, str::FromStr, sync::Arc};
 use auth::{credentials::AccountCredentials, models::{TokenWithExpiry, XstsToken}, secret::PlatformSecretStorage};
-use bridge::{import::ImportFromOtherLauncherJob, modal_action::{ModalAction, ProgressTracker}};
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, modal_action::{ModalAction, ProgressTracker}};
 use chrono::DateTime;
 use log::debug;
-use schema::{instance::{InstanceConfiguration, InstanceMemoryConfiguration,  InstanceWrapperCommandConfiguration}, loader::Loader};
+use schema::{instance::{InstanceConfiguration, InstanceMemoryConfiguration, InstanceSettingMode, InstanceWrapperCommandConfiguration}, loader::Loader};
 use serde::Deserialize;
 use uuid::Uuid;
 use crate::{BackendState, account::BackendAccount, write_safe};
@@ -242,14 +242,14 @@ struct AtLauncherDisplayClaim {
     uhs: String,
 }
 
-pub async fn import_from_atlauncher(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) {
+pub async fn import_from_atlauncher(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
     let Ok(launcher_config_bytes) = std::fs::read(import_job.root.join("configs/ATLauncher.json")) else {
-        return;
+        return ImportResult::default();
     };
     let launcher_config = serde_json::from_slice::<AtLauncherConfig>(&launcher_config_bytes).expect("Failed to parse to json");
 
     import_accounts_from_atlauncher(backend, &import_job, &launcher_config, &modal_action).await;
-    import_instances_from_atlauncher(backend, &import_job, &launcher_config, &modal_action);
+    import_instances_from_atlauncher(backend, &import_job, &launcher_config, &modal_action)
 }
 
 async fn import_accounts_from_atlauncher(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, launcher_config: &AtLauncherConfig, modal_action: &ModalAction) {
@@ -356,7 +356,7 @@ fn try_load_from_atlauncher(config_path: &Path, launcher_config: &AtLauncherConf
 
     configuration.memory = if let Some(max_memory) = instance_cfg.launcher.maximum_memory.or(launcher_config.maximum_memory) {
         Some(InstanceMemoryConfiguration {
-            enabled: true,
+            mode: InstanceSettingMode::Override,
             min: instance_cfg.launcher.required_memory as u32,
             max: max_memory as u32,
         })
@@ -365,7 +365,7 @@ fn try_load_from_atlauncher(config_path: &Path, launcher_config: &AtLauncherConf
     if let Some(enable_commands) = instance_cfg.launcher.enable_commands && enable_commands {
         configuration.wrapper_command = if let Some(wrapper_command) = instance_cfg.launcher.wrapper_command {
             Some(InstanceWrapperCommandConfiguration {
-                enabled: true,
+                mode: InstanceSettingMode::Override,
                  flags: wrapper_command.into(),
              })
         } else { None };
@@ -377,9 +377,10 @@ fn try_load_from_atlauncher(config_path: &Path, launcher_config: &AtLauncherConf
     Ok(configuration)
 }
 
-fn import_instances_from_atlauncher(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, launcher_config: &AtLauncherConfig, modal_action: &ModalAction) {
+fn import_instances_from_atlauncher(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, launcher_config: &AtLauncherConfig, modal_action: &ModalAction) -> ImportResult {
+    let mut result = ImportResult::default();
     if import_job.paths.is_empty() {
-        return;
+        return result;
     }
 
     let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
@@ -399,7 +400,8 @@ fn import_instances_from_atlauncher(backend: &BackendState, import_job: &ImportF
 
         let pandora_path = backend.directories.instances_dir.join(filename);
         if pandora_path.exists() {
-           continue;
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
+            continue;
         }
 
         let atlauncher_instance_cfg = folder.join("instance.json");
@@ -486,6 +488,7 @@ fn import_instances_from_atlauncher(backend: &BackendState, import_job: &ImportF
         let info_path = to_import.pandora_path.join("info_v1.json");
         _ = write_safe(&info_path, &configuration_bytes);
 
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
         all_tracker.add_count(1);
         all_tracker.notify();
 
@@ -494,5 +497,7 @@ fn import_instances_from_atlauncher(backend: &BackendState, import_job: &ImportF
     }
 
     all_tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Normal);
-    all_tracker.notify()
+    all_tracker.notify();
+
+    result
 }
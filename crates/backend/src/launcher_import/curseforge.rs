@@ -1,7 +1,7 @@
 use std::{path::{Path, PathBuf}, sync::Arc};
 
-use bridge::{import::ImportFromOtherLauncherJob, modal_action::{ModalAction, ProgressTracker}};
-use schema::{curseforge::CurseforgeModpackManifestJson, instance::{InstanceConfiguration, InstanceMemoryConfiguration}, loader::Loader};
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, modal_action::{ModalAction, ProgressTracker}};
+use schema::{curseforge::CurseforgeModpackManifestJson, instance::{InstanceConfiguration, InstanceMemoryConfiguration, InstanceSettingMode}, loader::Loader};
 use serde::Deserialize;
 
 use crate::{BackendState, write_safe};
@@ -23,7 +23,7 @@ fn try_load_from_curseforge(config_path: &Path) -> Option<InstanceConfiguration>
     let mut configuration = InstanceConfiguration::new(instance_cfg.manifest.minecraft.version?.into(), loader);
     if instance_cfg.is_memory_override {
         configuration.memory = Some(InstanceMemoryConfiguration {
-            enabled: true,
+            mode: InstanceSettingMode::Override,
             min: 0,
             max: instance_cfg.allocated_memory,
         })
@@ -32,8 +32,8 @@ fn try_load_from_curseforge(config_path: &Path) -> Option<InstanceConfiguration>
     Some(configuration)
 }
 
-pub fn import_from_curseforge(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) {
-    import_instances_from_curseforge(backend, &import_job, &modal_action);
+pub fn import_from_curseforge(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
+    import_instances_from_curseforge(backend, &import_job, &modal_action)
 }
 
 #[derive(Debug)]
@@ -43,9 +43,10 @@ struct CurseforgeInstanceToImport {
     folder: Arc<Path>,
 }
 
-pub fn import_instances_from_curseforge(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, modal_action: &ModalAction) {
+pub fn import_instances_from_curseforge(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, modal_action: &ModalAction) -> ImportResult {
+    let mut result = ImportResult::default();
     if import_job.paths.is_empty() {
-        return;
+        return result;
     }
 
     let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
@@ -65,6 +66,7 @@ pub fn import_instances_from_curseforge(backend: &BackendState, import_job: &Imp
 
         let pandora_path = backend.directories.instances_dir.join(filename);
         if pandora_path.exists() {
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
             continue;
         }
 
@@ -123,6 +125,7 @@ pub fn import_instances_from_curseforge(backend: &BackendState, import_job: &Imp
         let info_path = to_import.pandora_path.join("info_v1.json");
         _ = write_safe(&info_path, &configuration_bytes);
 
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
         all_tracker.add_count(1);
         all_tracker.notify();
 
@@ -130,4 +133,5 @@ pub fn import_instances_from_curseforge(backend: &BackendState, import_job: &Imp
         tracker.notify();
     }
 
+    result
 }
@@ -0,0 +1,191 @@
+use std::{io::Read, path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, instance::{ContentSummary, ContentVersionRequirement}, modal_action::{ModalAction, ProgressTracker}};
+use rustc_hash::FxHashMap;
+use schema::{instance::InstanceConfiguration, loader::Loader};
+use ustr::Ustr;
+
+use crate::{BackendState, install_content::determine_loader_from_content, metadata::items::MinecraftVersionManifestMetadataItem, write_safe};
+
+pub fn is_game_folder(path: &Path) -> bool {
+    ["options.txt", "mods", "saves", "resourcepacks", "config"].iter().any(|name| path.join(name).exists())
+}
+
+fn instance_name(folder: &Path) -> String {
+    let name = folder.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    // Use the surrounding folder for game directories that are just called .minecraft
+    let name = if name.trim_start_matches('.').eq_ignore_ascii_case("minecraft") {
+        folder.parent().and_then(Path::file_name).map(|name| name.to_string_lossy().into_owned()).unwrap_or(name)
+    } else {
+        name
+    };
+    let name = name.trim_start_matches('.');
+    let name = if name.is_empty() { "Minecraft" } else { name };
+    sanitize_filename::sanitize_with_options(name, sanitize_filename::Options { windows: true, ..Default::default() })
+}
+
+fn read_mod_summaries(backend: &BackendState, folder: &Path) -> Vec<Arc<ContentSummary>> {
+    let Ok(read_dir) = std::fs::read_dir(folder.join("mods")) else {
+        return Vec::new();
+    };
+    read_dir.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "jar"))
+        .map(|path| backend.mod_metadata_manager.get_path(&path))
+        .filter(|summary| !ContentSummary::is_unknown(summary))
+        .collect()
+}
+
+// Worlds remember the version they were last played on, so the most recently played world is the best guess
+fn version_from_worlds(folder: &Path) -> Option<Ustr> {
+    let read_dir = std::fs::read_dir(folder.join("saves")).ok()?;
+
+    let mut newest: Option<(i64, Ustr)> = None;
+    for entry in read_dir.flatten() {
+        let Some((last_played, version)) = read_world_version(&entry.path()) else {
+            continue;
+        };
+        if newest.is_none_or(|(newest_last_played, _)| last_played > newest_last_played) {
+            newest = Some((last_played, version));
+        }
+    }
+    newest.map(|(_, version)| version)
+}
+
+fn read_world_version(path: &Path) -> Option<(i64, Ustr)> {
+    let compressed = std::fs::read(path.join("level.dat")).ok()?;
+
+    let mut decoder = flate2::bufread::GzDecoder::new(compressed.as_slice());
+    let mut decompressed = Vec::new();
+    decoder.read_to_end(&mut decompressed).ok()?;
+
+    let mut nbt_data = decompressed.as_slice();
+    let result = nbt::decode::read_named(&mut nbt_data).ok()?;
+
+    let root = result.as_compound()?;
+    let data = root.find_compound("Data")?;
+    let last_played: i64 = data.find_numeric("LastPlayed").unwrap_or(0);
+    let version = data.find_compound("Version")?.find_string("Name").map(|name| Ustr::from(name.as_str()))?;
+    Some((last_played, version))
+}
+
+// Mods declare which minecraft versions they support, use the lowest bound that most mods agree on
+fn version_from_mods(summaries: &[Arc<ContentSummary>], known_versions: &[Ustr]) -> Option<Ustr> {
+    let mut counts: FxHashMap<Ustr, usize> = FxHashMap::default();
+    for summary in summaries {
+        for dependency in summary.dependencies.iter().filter(|dependency| &*dependency.id == "minecraft") {
+            let candidate = match &dependency.versions {
+                ContentVersionRequirement::Any => continue,
+                ContentVersionRequirement::Predicates(predicates) => predicates.first().map(|predicate| {
+                    predicate.split_whitespace().next().unwrap_or_default().trim_start_matches(['>', '<', '=', '~', '^'])
+                }),
+                ContentVersionRequirement::MavenRange(range) => Some(range.trim_start_matches(['[', '(']).split([',', ']', ')']).next().unwrap_or_default()),
+            };
+            let Some(candidate) = candidate else {
+                continue;
+            };
+            let candidate = candidate.trim().trim_end_matches(".x").trim_end_matches('-');
+            let Some(candidate) = known_versions.iter().find(|version| version.as_str() == candidate) else {
+                continue;
+            };
+            *counts.entry(*candidate).or_default() += 1;
+        }
+    }
+    counts.into_iter().max_by_key(|(_, count)| *count).map(|(version, _)| version)
+}
+
+async fn try_load_from_folder(backend: &BackendState, folder: &Path) -> Option<InstanceConfiguration> {
+    let summaries = read_mod_summaries(backend, folder);
+    let loader = determine_loader_from_content(summaries.iter().map(|summary| &**summary)).unwrap_or(Loader::Vanilla);
+
+    let minecraft_version = if let Some(version) = version_from_worlds(folder) {
+        version
+    } else {
+        let manifest = backend.meta.fetch(&MinecraftVersionManifestMetadataItem).await.ok()?;
+        let known_versions: Vec<Ustr> = manifest.versions.iter().map(|version| version.id).collect();
+        version_from_mods(&summaries, &known_versions).unwrap_or_else(|| {
+            log::warn!("Unable to determine minecraft version of {:?}, using the latest release", folder);
+            manifest.latest.release
+        })
+    };
+
+    Some(InstanceConfiguration::new(minecraft_version, loader))
+}
+
+struct FolderToImport {
+    pandora_path: PathBuf,
+    folder: Arc<Path>,
+}
+
+pub async fn import_from_folder(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
+    let mut result = ImportResult::default();
+    if import_job.paths.is_empty() {
+        return result;
+    }
+
+    let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
+    modal_action.trackers.push(all_tracker.clone());
+    all_tracker.notify();
+
+    let mut to_import = Vec::new();
+
+    for folder in import_job.paths.iter() {
+        if !folder.is_dir() {
+            continue;
+        }
+
+        let pandora_path = backend.directories.instances_dir.join(instance_name(folder));
+        if pandora_path.exists() {
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
+            continue;
+        }
+
+        to_import.push(FolderToImport {
+            pandora_path,
+            folder: folder.clone(),
+        });
+    }
+
+    all_tracker.set_total(to_import.len());
+
+    for to_import in to_import {
+        let title = format!("Importing {}", instance_name(&to_import.folder));
+        let tracker = ProgressTracker::new(title.into(), backend.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let Some(configuration) = try_load_from_folder(backend, &to_import.folder).await else {
+            tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Error);
+            log::error!("Failed to determine the version of {:?}", to_import.folder);
+            tracker.notify();
+            continue;
+        };
+
+        let Ok(configuration_bytes) = serde_json::to_vec(&configuration) else {
+            tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Error);
+            tracker.notify();
+            continue;
+        };
+
+        _ = std::fs::create_dir_all(&to_import.pandora_path);
+        let target_dot_minecraft = to_import.pandora_path.join(".minecraft");
+
+        _ = std::fs::create_dir_all(&target_dot_minecraft);
+        super::copy_game_folder(&to_import.folder, &target_dot_minecraft, &super::is_launcher_data, &tracker);
+
+        let info_path = to_import.pandora_path.join("info_v1.json");
+        _ = write_safe(&info_path, &configuration_bytes);
+
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
+        all_tracker.add_count(1);
+        all_tracker.notify();
+
+        tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Fast);
+        tracker.notify();
+    }
+
+    all_tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Normal);
+    all_tracker.notify();
+
+    result
+}
@@ -0,0 +1,174 @@
+use std::{path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, modal_action::{ModalAction, ProgressTracker}};
+use schema::{instance::{InstanceConfiguration, InstanceMemoryConfiguration, InstanceSettingMode}, loader::Loader};
+use serde::Deserialize;
+use ustr::Ustr;
+
+use crate::{BackendState, instance::InstanceStats, write_safe};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GDLauncherInstance {
+    loader: GDLauncherLoader,
+    java_args: Option<String>,
+    java_memory: Option<u32>,
+    #[serde(default)]
+    time_played: u64,
+    last_played: Option<i64>,
+    background: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GDLauncherLoader {
+    loader_type: String,
+    mc_version: String,
+    loader_version: Option<String>,
+}
+
+fn try_load_from_gdlauncher(config_path: &Path) -> Option<(InstanceConfiguration, InstanceStats, Option<String>)> {
+    let instance_cfg_bytes = std::fs::read(config_path).ok()?;
+    let instance_cfg = serde_json::from_slice::<GDLauncherInstance>(&instance_cfg_bytes).ok()?;
+
+    let loader = match instance_cfg.loader.loader_type.as_str() {
+        "fabric" => Loader::Fabric,
+        "quilt" => Loader::Quilt,
+        "forge" => Loader::Forge,
+        "neoforge" => Loader::NeoForge,
+        _ => Loader::Vanilla,
+    };
+
+    let mut configuration = InstanceConfiguration::new(Ustr::from(&instance_cfg.loader.mc_version), loader);
+    if loader != Loader::Vanilla {
+        // Forge versions are stored in the maven format (e.g. 1.16.5-36.2.20), which is what we expect as well
+        configuration.preferred_loader_version = instance_cfg.loader.loader_version.as_deref().map(Ustr::from);
+    }
+
+    if let Some(java_args) = &instance_cfg.java_args {
+        super::apply_java_args(&mut configuration, java_args);
+    }
+
+    if let Some(java_memory) = instance_cfg.java_memory {
+        configuration.memory = Some(InstanceMemoryConfiguration {
+            mode: InstanceSettingMode::Override,
+            min: InstanceMemoryConfiguration::DEFAULT_MIN.min(java_memory),
+            max: java_memory,
+        });
+    }
+
+    let stats = InstanceStats {
+        total_playtime_secs: instance_cfg.time_played,
+        session_count: 0,
+        last_played_unix_ms: instance_cfg.last_played.filter(|last_played| *last_played > 0),
+    };
+
+    Some((configuration, stats, instance_cfg.background))
+}
+
+pub fn import_from_gdlauncher(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
+    import_instances_from_gdlauncher(backend, &import_job, &modal_action)
+}
+
+struct GDLauncherInstanceToImport {
+    pandora_path: PathBuf,
+    config_path: PathBuf,
+    folder: Arc<Path>,
+}
+
+fn import_instances_from_gdlauncher(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, modal_action: &ModalAction) -> ImportResult {
+    let mut result = ImportResult::default();
+    if import_job.paths.is_empty() {
+        return result;
+    }
+
+    let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
+    modal_action.trackers.push(all_tracker.clone());
+    all_tracker.notify();
+
+    let mut to_import = Vec::new();
+
+    for folder in import_job.paths.iter() {
+        if !folder.is_dir() {
+            continue;
+        }
+
+        let Some(filename) = folder.file_name() else {
+            continue;
+        };
+
+        let pandora_path = backend.directories.instances_dir.join(filename);
+        if pandora_path.exists() {
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
+            continue;
+        }
+
+        let gdlauncher_config = folder.join("config.json");
+        if !gdlauncher_config.exists() {
+            continue;
+        }
+
+        to_import.push(GDLauncherInstanceToImport {
+            pandora_path,
+            config_path: gdlauncher_config,
+            folder: folder.clone(),
+        });
+    }
+
+    all_tracker.set_total(to_import.len());
+
+    for to_import in to_import {
+        let title = format!("Importing {}", to_import.folder.file_name().unwrap().to_string_lossy());
+        let tracker = ProgressTracker::new(title.into(), backend.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let Some((configuration, stats, background)) = try_load_from_gdlauncher(&to_import.config_path) else {
+            tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Error);
+            log::error!("Failed to load config path from gdlauncher for {:?}", to_import.folder.file_name().unwrap());
+            tracker.notify();
+            continue;
+        };
+
+        let Ok(configuration_bytes) = serde_json::to_vec(&configuration) else {
+            tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Error);
+            tracker.notify();
+            continue;
+        };
+
+        _ = std::fs::create_dir_all(&to_import.pandora_path);
+        let target_dot_minecraft = to_import.pandora_path.join(".minecraft");
+
+        // The instance folder is the game directory, with the launcher's config and background image next to the game files
+        _ = std::fs::create_dir_all(&target_dot_minecraft);
+        super::copy_game_folder(&to_import.folder, &target_dot_minecraft, &|name| {
+            name == "config.json" || background.as_deref() == Some(name)
+        }, &tracker);
+
+        if let Some(background) = &background {
+            _ = std::fs::copy(to_import.folder.join(background), to_import.pandora_path.join("icon.png"));
+        }
+
+        let info_path = to_import.pandora_path.join("info_v1.json");
+        _ = write_safe(&info_path, &configuration_bytes);
+
+        if stats != InstanceStats::default() {
+            let stats_path = to_import.pandora_path.join("stats_v1.json");
+            if let Ok(stats_bytes) = serde_json::to_vec(&stats) {
+                _ = write_safe(&stats_path, &stats_bytes);
+            }
+        }
+
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
+        all_tracker.add_count(1);
+        all_tracker.notify();
+
+        tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Fast);
+        tracker.notify();
+    }
+
+    all_tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Normal);
+    all_tracker.notify();
+
+    result
+}
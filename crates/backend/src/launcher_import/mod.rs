@@ -1,9 +1,9 @@
 use std::{path::Path, sync::Arc};
 
-use bridge::{import::{ImportFromOtherLauncherJob, OtherLauncher}, modal_action::ModalAction};
-use schema::instance::InstanceConfiguration;
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult, OtherLauncher}, modal_action::{ModalAction, ProgressTracker}};
+use schema::instance::{InstanceConfiguration, InstanceJvmFlagsConfiguration, InstanceMemoryConfiguration, InstanceSettingMode};
 use crate::{BackendState, launcher_import::{
-        atlauncher::import_from_atlauncher, curseforge::import_from_curseforge, modrinth::{import_instances_from_modrinth, read_profiles_from_modrinth_db}, multimc::{import_from_multimc, try_load_from_multimc}
+        atlauncher::import_from_atlauncher, curseforge::import_from_curseforge, folder::{import_from_folder, is_game_folder}, gdlauncher::import_from_gdlauncher, modrinth::{import_instances_from_modrinth, read_profiles_from_modrinth_db}, multimc::{import_from_multimc, try_load_from_multimc}, official::{import_from_official, read_game_directories}
     }
 };
 
@@ -11,6 +11,9 @@ mod multimc;
 mod modrinth;
 mod atlauncher;
 mod curseforge;
+mod official;
+mod gdlauncher;
+mod folder;
 
 pub fn get_import_from_other_launcher_job(other_launcher: OtherLauncher, path: Arc<Path>) -> Option<ImportFromOtherLauncherJob> {
     if !path.is_dir() {
@@ -65,6 +68,33 @@ pub fn get_import_from_other_launcher_job(other_launcher: OtherLauncher, path: A
                 root: path,
             })
         },
+        OtherLauncher::Official => {
+            let paths = read_game_directories(&path)?;
+            Some(ImportFromOtherLauncherJob {
+                import_accounts: false,
+                paths,
+                root: path,
+            })
+        },
+        OtherLauncher::GDLauncher => {
+            Some(ImportFromOtherLauncherJob {
+                import_accounts: false,
+                paths: collect_subfolders_matching(&path.join("instances"), &|path| {
+                    path.join("config.json").exists()
+                }),
+                root: path,
+            })
+        },
+        OtherLauncher::Folder => {
+            if !is_game_folder(&path) {
+                return None;
+            }
+            Some(ImportFromOtherLauncherJob {
+                import_accounts: false,
+                paths: vec![path.clone()],
+                root: path,
+            })
+        },
     }
 }
 
@@ -89,6 +119,92 @@ fn collect_subfolders_matching(folder: &Path, check: &dyn Fn(&Path) -> bool) ->
     paths
 }
 
+// Game data that the official launcher keeps next to the instance content, these are downloaded again on launch
+fn is_launcher_data(name: &str) -> bool {
+    matches!(name, "versions" | "libraries" | "assets" | "runtime" | "bin" | "webcache" | "webcache2")
+        || name.starts_with("launcher_")
+        || name.starts_with("treatment_tags")
+}
+
+// Copies the top level entries of a game folder one by one so that launcher data can be left behind
+fn copy_game_folder(from: &Path, to: &Path, excluded: &dyn Fn(&str) -> bool, tracker: &ProgressTracker) {
+    let Ok(read_dir) = std::fs::read_dir(from) else {
+        return;
+    };
+
+    let entries: Vec<_> = read_dir.flatten().filter(|entry| !entry.file_name().to_str().is_some_and(excluded)).collect();
+    tracker.set_total(entries.len());
+    tracker.notify();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            _ = std::fs::create_dir_all(&target);
+            _ = crate::copy_content_recursive(&path, &target, false, &|_, _| {});
+        } else if path.is_file() {
+            _ = std::fs::copy(&path, &target);
+        }
+        tracker.set_count(index + 1);
+        tracker.notify();
+    }
+}
+
+// Moves -Xms/-Xmx into the memory configuration, everything else becomes custom jvm flags
+fn apply_java_args(configuration: &mut InstanceConfiguration, java_args: &str) {
+    let Ok(args) = shell_words::split(java_args) else {
+        // Keep unparseable arguments as they are so nothing gets lost, they can be fixed in the instance settings
+        configuration.jvm_flags = Some(InstanceJvmFlagsConfiguration {
+            mode: InstanceSettingMode::Override,
+            flags: java_args.trim().into(),
+        });
+        return;
+    };
+
+    let mut min = None;
+    let mut max = None;
+    let mut flags = Vec::new();
+
+    for arg in &args {
+        if let Some(value) = arg.strip_prefix("-Xmx") && let Some(value) = parse_memory_mb(value) {
+            max = Some(value);
+        } else if let Some(value) = arg.strip_prefix("-Xms") && let Some(value) = parse_memory_mb(value) {
+            min = Some(value);
+        } else {
+            flags.push(arg);
+        }
+    }
+
+    if let Some(max) = max {
+        configuration.memory = Some(InstanceMemoryConfiguration {
+            mode: InstanceSettingMode::Override,
+            min: min.unwrap_or(InstanceMemoryConfiguration::DEFAULT_MIN).min(max),
+            max,
+        });
+    }
+
+    if !flags.is_empty() {
+        configuration.jvm_flags = Some(InstanceJvmFlagsConfiguration {
+            mode: InstanceSettingMode::Override,
+            flags: shell_words::join(flags).into(),
+        });
+    }
+}
+
+fn parse_memory_mb(value: &str) -> Option<u32> {
+    let unit_start = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(unit_start);
+    let number: u64 = number.parse().ok()?;
+    let megabytes = match unit {
+        "g" | "G" => number * 1024,
+        "m" | "M" => number,
+        "k" | "K" => number / 1024,
+        "" => number / (1024 * 1024),
+        _ => return None,
+    };
+    u32::try_from(megabytes).ok()
+}
+
 pub fn try_load_from_other_launcher_formats(folder: &Path) -> Option<InstanceConfiguration> {
     let multimc_instance_cfg = folder.join("instance.cfg");
     let multimc_mmc_pack = folder.join("mmc-pack.json");
@@ -99,22 +215,62 @@ pub fn try_load_from_other_launcher_formats(folder: &Path) -> Option<InstanceCon
     None
 }
 
-pub async fn import_from_other_launcher(backend: &BackendState, launcher: OtherLauncher, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) {
+fn pandora_instance_name(pandora_path: &Path) -> Arc<str> {
+    pandora_path.file_name().unwrap_or_default().to_string_lossy().into()
+}
+
+pub async fn import_from_other_launcher(backend: &BackendState, launcher: OtherLauncher, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
     match launcher {
         OtherLauncher::Prism | OtherLauncher::MultiMC => {
-            import_from_multimc(backend, import_job, modal_action).await;
+            import_from_multimc(backend, import_job, modal_action).await
         },
         OtherLauncher::CurseForge => {
-            import_from_curseforge(backend, import_job, modal_action);
+            import_from_curseforge(backend, import_job, modal_action)
         }
         OtherLauncher::Modrinth => {
-            if let Err(err) = import_instances_from_modrinth(backend, import_job, &modal_action) {
-                log::error!("Sqlite error while importing from modrinth: {err}");
-                modal_action.set_error_message("Sqlite error while importing from modrinth, see logs for more info".into());
+            match import_instances_from_modrinth(backend, import_job, &modal_action) {
+                Ok(result) => result,
+                Err(err) => {
+                    log::error!("Sqlite error while importing from modrinth: {err}");
+                    modal_action.set_error_message("Sqlite error while importing from modrinth, see logs for more info".into());
+                    ImportResult::default()
+                },
             }
         },
         OtherLauncher::ATLauncher => {
-            import_from_atlauncher(backend, import_job, modal_action).await;
-        }
+            import_from_atlauncher(backend, import_job, modal_action).await
+        },
+        OtherLauncher::Official => {
+            import_from_official(backend, import_job, modal_action).await
+        },
+        OtherLauncher::GDLauncher => {
+            import_from_gdlauncher(backend, import_job, modal_action)
+        },
+        OtherLauncher::Folder => {
+            import_from_folder(backend, import_job, modal_action).await
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schema::loader::Loader;
+    use ustr::Ustr;
+
+    use super::*;
+
+    #[test]
+    fn test_apply_java_args() {
+        let mut configuration = InstanceConfiguration::new(Ustr::from("1.21.1"), Loader::Vanilla);
+        apply_java_args(&mut configuration, "-Xmx4G -Xms1G -Dpath=\"C:/Program Files/foo\" -XX:+UseG1GC");
+        let memory = configuration.memory.unwrap();
+        assert_eq!((memory.min, memory.max), (1024, 4096));
+        let flags = configuration.jvm_flags.unwrap().flags;
+        assert_eq!(shell_words::split(&flags).unwrap(), vec!["-Dpath=C:/Program Files/foo", "-XX:+UseG1GC"]);
+
+        let mut configuration = InstanceConfiguration::new(Ustr::from("1.21.1"), Loader::Vanilla);
+        apply_java_args(&mut configuration, "-Xmx4G -Dname=\"unterminated");
+        assert!(configuration.memory.is_none());
+        assert_eq!(&*configuration.jvm_flags.unwrap().flags, "-Xmx4G -Dname=\"unterminated");
     }
 }
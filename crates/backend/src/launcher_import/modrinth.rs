@@ -1,6 +1,6 @@
 use std::{io::Cursor, path::{Path, PathBuf}, sync::Arc};
 
-use bridge::{import::ImportFromOtherLauncherJob, modal_action::{ModalAction, ProgressTracker}};
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, modal_action::{ModalAction, ProgressTracker}};
 use image::ImageFormat;
 use rustc_hash::FxHashMap;
 use schema::{instance::InstanceConfiguration, loader::Loader};
@@ -14,9 +14,10 @@ struct ModrinthInstanceToImport {
     minecraft_folder: Arc<Path>,
 }
 
-pub fn import_instances_from_modrinth(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: &ModalAction) -> rusqlite::Result<()> {
+pub fn import_instances_from_modrinth(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: &ModalAction) -> rusqlite::Result<ImportResult> {
+    let mut result = ImportResult::default();
     if import_job.paths.is_empty() {
-        return Ok(());
+        return Ok(result);
     }
 
     let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
@@ -25,7 +26,7 @@ pub fn import_instances_from_modrinth(backend: &BackendState, import_job: Import
 
     let app_db = import_job.root.join("app.db");
     if !app_db.exists() {
-        return Ok(());
+        return Ok(result);
     }
 
     let conn = rusqlite::Connection::open(app_db)?;
@@ -49,14 +50,15 @@ pub fn import_instances_from_modrinth(backend: &BackendState, import_job: Import
     while let Ok(Some(row)) = query.next() {
         let filename: String = row.get(0)?;
 
-        let pandora_path = backend.directories.instances_dir.join(&filename);
-        if pandora_path.exists() {
-           continue;
-        }
-
         let Some(profile) = name_to_path.get(&filename) else {
             continue;
         };
+
+        let pandora_path = backend.directories.instances_dir.join(&filename);
+        if pandora_path.exists() {
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
+            continue;
+        }
         if !profile.is_dir() {
             continue;
         }
@@ -129,6 +131,7 @@ pub fn import_instances_from_modrinth(backend: &BackendState, import_job: Import
         let info_path = to_import.pandora_path.join("info_v1.json");
         _ = crate::write_safe(&info_path, &configuration_bytes);
 
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
         all_tracker.add_count(1);
         all_tracker.notify();
 
@@ -139,7 +142,7 @@ pub fn import_instances_from_modrinth(backend: &BackendState, import_job: Import
     all_tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Normal);
     all_tracker.notify();
 
-    Ok(())
+    Ok(result)
 }
 
 pub fn read_profiles_from_modrinth_db(modrinth: &Path) -> rusqlite::Result<Option<Vec<Arc<Path>>>> {
@@ -1,9 +1,9 @@
 use std::{path::{Path, PathBuf}, sync::Arc};
 
 use auth::{credentials::AccountCredentials, models::{TokenWithExpiry, XstsToken}, secret::PlatformSecretStorage};
-use bridge::{import::ImportFromOtherLauncherJob, modal_action::{ModalAction, ProgressTracker}};
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, modal_action::{ModalAction, ProgressTracker}};
 use chrono::DateTime;
-use schema::{instance::{InstanceConfiguration, LwjglLibraryPath}, loader::Loader};
+use schema::{instance::{InstanceConfiguration, InstanceSettingMode, LwjglLibraryPath}, loader::Loader};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -39,6 +39,8 @@ pub fn try_load_from_multimc(instance_cfg: &Path, mmc_pack: &Path) -> Option<(In
             loader = Some(Loader::Forge);
         } else if &*component.uid == "net.neoforged" {
             loader = Some(Loader::NeoForge);
+        } else if &*component.uid == "org.quiltmc.quilt-loader" {
+            loader = Some(Loader::Quilt);
         }
     }
 
@@ -81,7 +83,7 @@ pub fn try_load_from_multimc(instance_cfg: &Path, mmc_pack: &Path) -> Option<(In
                         let Ok(enabled) = value.parse::<bool>() else {
                             continue;
                         };
-                        configuration.jvm_binary.get_or_insert_default().enabled = enabled;
+                        configuration.jvm_binary.get_or_insert_default().mode = InstanceSettingMode::from_enabled(enabled);
                     },
                     (Some("[General]"), "JavaPath") => {
                         configuration.jvm_binary.get_or_insert_default().path = Some(Path::new(value).into());
@@ -91,7 +93,7 @@ pub fn try_load_from_multimc(instance_cfg: &Path, mmc_pack: &Path) -> Option<(In
                         let Ok(enabled) = value.parse::<bool>() else {
                             continue;
                         };
-                        configuration.jvm_flags.get_or_insert_default().enabled = enabled;
+                        configuration.jvm_flags.get_or_insert_default().mode = InstanceSettingMode::from_enabled(enabled);
                     },
                     (Some("[General]"), "JvmArgs") => {
                         configuration.jvm_flags.get_or_insert_default().flags = value.into();
@@ -101,7 +103,7 @@ pub fn try_load_from_multimc(instance_cfg: &Path, mmc_pack: &Path) -> Option<(In
                         let Ok(enabled) = value.parse::<bool>() else {
                             continue;
                         };
-                        configuration.memory.get_or_insert_default().enabled = enabled;
+                        configuration.memory.get_or_insert_default().mode = InstanceSettingMode::from_enabled(enabled);
                     },
                     (Some("[General]"), "MinMemAlloc") => {
                         let Ok(min) = value.parse::<u32>() else {
@@ -200,10 +202,18 @@ pub fn try_load_from_multimc(instance_cfg: &Path, mmc_pack: &Path) -> Option<(In
         }
     }
 
-    if !override_native_workarounds {
+    if override_native_workarounds {
+        if let Some(system_libraries) = &mut configuration.system_libraries {
+            system_libraries.mode = InstanceSettingMode::Override;
+        }
+    } else {
         configuration.system_libraries = None;
     }
-    if !override_performance {
+    if override_performance {
+        if let Some(linux_wrapper) = &mut configuration.linux_wrapper {
+            linux_wrapper.mode = InstanceSettingMode::Override;
+        }
+    } else {
         configuration.linux_wrapper = None;
     }
 
@@ -258,9 +268,9 @@ struct MultiMCAccountTokenExtra {
     uhs: Option<Arc<str>>,
 }
 
-pub async fn import_from_multimc(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) {
+pub async fn import_from_multimc(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
     import_accounts_from_multimc(backend, &import_job, &modal_action).await;
-    import_instances_from_multimc(backend, &import_job, &modal_action);
+    import_instances_from_multimc(backend, &import_job, &modal_action)
 }
 
 async fn import_accounts_from_multimc(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, modal_action: &ModalAction) {
@@ -404,9 +414,10 @@ struct MultiMCInstanceToImport {
     folder: Arc<Path>,
 }
 
-fn import_instances_from_multimc(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, modal_action: &ModalAction) {
+fn import_instances_from_multimc(backend: &BackendState, import_job: &ImportFromOtherLauncherJob, modal_action: &ModalAction) -> ImportResult {
+    let mut result = ImportResult::default();
     if import_job.paths.is_empty() {
-        return;
+        return result;
     }
 
     let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
@@ -426,7 +437,8 @@ fn import_instances_from_multimc(backend: &BackendState, import_job: &ImportFrom
 
         let pandora_path = backend.directories.instances_dir.join(filename);
         if pandora_path.exists() {
-           continue;
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
+            continue;
         }
 
         let multimc_instance_cfg = folder.join("instance.cfg");
@@ -499,6 +511,7 @@ fn import_instances_from_multimc(backend: &BackendState, import_job: &ImportFrom
             }
         }
 
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
         all_tracker.add_count(1);
         all_tracker.notify();
 
@@ -508,4 +521,6 @@ fn import_instances_from_multimc(backend: &BackendState, import_job: &ImportFrom
 
     all_tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Normal);
     all_tracker.notify();
+
+    result
 }
@@ -0,0 +1,282 @@
+use std::{collections::HashMap, path::{Path, PathBuf}, sync::Arc};
+
+use base64::Engine;
+use bridge::{import::{ImportFromOtherLauncherJob, ImportResult}, modal_action::{ModalAction, ProgressTracker}};
+use schema::{instance::{InstanceConfiguration, InstanceJvmBinaryConfiguration, InstanceSettingMode}, loader::Loader};
+use serde::Deserialize;
+use ustr::Ustr;
+
+use crate::{BackendState, instance::InstanceStats, metadata::items::MinecraftVersionManifestMetadataItem, write_safe};
+
+#[derive(Deserialize)]
+struct LauncherProfiles {
+    #[serde(default)]
+    profiles: HashMap<String, LauncherProfile>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherProfile {
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "type")]
+    profile_type: String,
+    last_version_id: Option<String>,
+    game_dir: Option<PathBuf>,
+    java_args: Option<String>,
+    java_dir: Option<PathBuf>,
+    last_used: Option<String>,
+    icon: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionJson {
+    inherits_from: Option<String>,
+}
+
+impl LauncherProfile {
+    fn game_dir(&self, root: &Path) -> Arc<Path> {
+        match &self.game_dir {
+            Some(game_dir) => root.join(game_dir).into(),
+            None => root.into(),
+        }
+    }
+
+    fn last_used_unix_ms(&self) -> Option<i64> {
+        let last_used = self.last_used.as_ref()?;
+        chrono::DateTime::parse_from_rfc3339(last_used).ok().map(|date_time| date_time.timestamp_millis())
+    }
+}
+
+fn read_launcher_profiles(root: &Path) -> Option<Vec<LauncherProfile>> {
+    let bytes = std::fs::read(root.join("launcher_profiles.json")).ok()?;
+    let launcher_profiles = match serde_json::from_slice::<LauncherProfiles>(&bytes) {
+        Ok(launcher_profiles) => launcher_profiles,
+        Err(err) => {
+            log::error!("Unable to parse launcher_profiles.json: {err}");
+            return None;
+        },
+    };
+
+    // Most recently used first, so that profiles sharing a game directory resolve to the one that was played last
+    let mut profiles: Vec<LauncherProfile> = launcher_profiles.profiles.into_values().collect();
+    profiles.sort_by_key(|profile| std::cmp::Reverse(profile.last_used_unix_ms().unwrap_or(0)));
+    Some(profiles)
+}
+
+pub fn read_game_directories(root: &Path) -> Option<Vec<Arc<Path>>> {
+    let profiles = read_launcher_profiles(root)?;
+
+    let mut paths: Vec<Arc<Path>> = Vec::new();
+    for profile in &profiles {
+        let game_dir = profile.game_dir(root);
+        if game_dir.is_dir() && !paths.contains(&game_dir) {
+            paths.push(game_dir);
+        }
+    }
+    Some(paths)
+}
+
+fn instance_name(profile: &LauncherProfile) -> String {
+    let name = match profile.profile_type.as_str() {
+        "latest-release" | "latest-snapshot" if profile.name.is_empty() => "Minecraft",
+        _ if profile.name.trim().is_empty() => profile.last_version_id.as_deref().unwrap_or("Minecraft"),
+        _ => profile.name.trim(),
+    };
+    sanitize_filename::sanitize_with_options(name, sanitize_filename::Options { windows: true, ..Default::default() })
+}
+
+fn read_inherits_from(root: &Path, version_id: &str) -> Option<String> {
+    let bytes = std::fs::read(root.join("versions").join(version_id).join(format!("{version_id}.json"))).ok()?;
+    serde_json::from_slice::<VersionJson>(&bytes).ok()?.inherits_from
+}
+
+// Loader installers name their versions after the loader, e.g. fabric-loader-0.15.7-1.20.4, 1.20.1-forge-47.2.0 or neoforge-20.4.190
+fn parse_version_id(version_id: &str, inherits_from: Option<String>) -> Option<(Ustr, Loader, Option<Ustr>)> {
+    let fabric_like = version_id.strip_prefix("fabric-loader-").map(|rest| (rest, Loader::Fabric))
+        .or_else(|| version_id.strip_prefix("quilt-loader-").map(|rest| (rest, Loader::Quilt)));
+    if let Some((rest, loader)) = fabric_like {
+        // Quilt loader versions can contain dashes themselves, so prefer splitting off the inherited version
+        let (loader_version, minecraft_version) = inherits_from.as_deref()
+            .and_then(|minecraft_version| {
+                let loader_version = rest.strip_suffix(minecraft_version)?.strip_suffix('-')?;
+                Some((loader_version, minecraft_version))
+            })
+            .or_else(|| rest.split_once('-'))?;
+        return Some((Ustr::from(minecraft_version), loader, Some(Ustr::from(loader_version))));
+    }
+
+    if let Some(loader_version) = version_id.strip_prefix("neoforge-") {
+        let minecraft_version = inherits_from.or_else(|| {
+            // NeoForge versions are the minecraft version without the leading 1, e.g. 20.4.190 is for 1.20.4
+            let mut parts = loader_version.split('.');
+            let major = parts.next()?;
+            let minor = parts.next()?;
+            if minor == "0" {
+                Some(format!("1.{major}"))
+            } else {
+                Some(format!("1.{major}.{minor}"))
+            }
+        })?;
+        return Some((Ustr::from(&minecraft_version), Loader::NeoForge, Some(Ustr::from(loader_version))));
+    }
+
+    if let Some((minecraft_version, rest)) = version_id.split_once("-forge") {
+        let minecraft_version = inherits_from.unwrap_or_else(|| minecraft_version.to_string());
+        let rest = rest.trim_start_matches('-');
+        // Older installers already include the minecraft version, e.g. 1.12.2-forge1.12.2-14.23.5.2859
+        let loader_version = if rest.starts_with(&minecraft_version) {
+            rest.to_string()
+        } else {
+            format!("{minecraft_version}-{rest}")
+        };
+        return Some((Ustr::from(&minecraft_version), Loader::Forge, Some(Ustr::from(&loader_version))));
+    }
+
+    // Other custom versions (e.g. OptiFine) still inherit from a vanilla version
+    let minecraft_version = inherits_from.unwrap_or_else(|| version_id.to_string());
+    Some((Ustr::from(&minecraft_version), Loader::Vanilla, None))
+}
+
+async fn try_load_from_official(backend: &BackendState, root: &Path, profile: &LauncherProfile) -> Option<(InstanceConfiguration, InstanceStats)> {
+    let latest = match (profile.profile_type.as_str(), profile.last_version_id.as_deref()) {
+        ("latest-release", _) | (_, Some("latest-release")) => Some(false),
+        ("latest-snapshot", _) | (_, Some("latest-snapshot")) => Some(true),
+        _ => None,
+    };
+
+    let (minecraft_version, loader, loader_version) = if let Some(snapshot) = latest {
+        let manifest = backend.meta.fetch(&MinecraftVersionManifestMetadataItem).await.ok()?;
+        let version = if snapshot { manifest.latest.snapshot } else { manifest.latest.release };
+        (version, Loader::Vanilla, None)
+    } else {
+        let version_id = profile.last_version_id.as_deref()?;
+        parse_version_id(version_id, read_inherits_from(root, version_id))?
+    };
+
+    let mut configuration = InstanceConfiguration::new(minecraft_version, loader);
+    configuration.preferred_loader_version = loader_version;
+
+    if let Some(java_args) = &profile.java_args {
+        super::apply_java_args(&mut configuration, java_args);
+    }
+
+    if let Some(java_dir) = &profile.java_dir {
+        configuration.jvm_binary = Some(InstanceJvmBinaryConfiguration {
+            mode: InstanceSettingMode::Override,
+            path: Some(java_dir.as_path().into()),
+        });
+    }
+
+    let stats = InstanceStats {
+        last_played_unix_ms: profile.last_used_unix_ms(),
+        ..InstanceStats::default()
+    };
+
+    Some((configuration, stats))
+}
+
+struct OfficialInstanceToImport<'a> {
+    pandora_path: PathBuf,
+    profile: &'a LauncherProfile,
+    folder: Arc<Path>,
+}
+
+pub async fn import_from_official(backend: &BackendState, import_job: ImportFromOtherLauncherJob, modal_action: ModalAction) -> ImportResult {
+    let mut result = ImportResult::default();
+    if import_job.paths.is_empty() {
+        return result;
+    }
+
+    let Some(profiles) = read_launcher_profiles(&import_job.root) else {
+        modal_action.set_error_message("Unable to read launcher_profiles.json".into());
+        return result;
+    };
+
+    let all_tracker = ProgressTracker::new("Importing instances".into(), backend.send.clone());
+    modal_action.trackers.push(all_tracker.clone());
+    all_tracker.notify();
+
+    let mut to_import = Vec::new();
+
+    for folder in import_job.paths.iter() {
+        if !folder.is_dir() {
+            continue;
+        }
+
+        let Some(profile) = profiles.iter().find(|profile| profile.game_dir(&import_job.root) == *folder) else {
+            continue;
+        };
+
+        let pandora_path = backend.directories.instances_dir.join(instance_name(profile));
+        if pandora_path.exists() {
+            result.skipped.push(super::pandora_instance_name(&pandora_path));
+            continue;
+        }
+
+        to_import.push(OfficialInstanceToImport {
+            pandora_path,
+            profile,
+            folder: folder.clone(),
+        });
+    }
+
+    all_tracker.set_total(to_import.len());
+
+    for to_import in to_import {
+        let title = format!("Importing {}", instance_name(to_import.profile));
+        let tracker = ProgressTracker::new(title.into(), backend.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let Some((configuration, stats)) = try_load_from_official(backend, &import_job.root, to_import.profile).await else {
+            tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Error);
+            log::error!("Failed to load launcher profile {:?}", to_import.profile.name);
+            tracker.notify();
+            continue;
+        };
+
+        let Ok(configuration_bytes) = serde_json::to_vec(&configuration) else {
+            tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Error);
+            tracker.notify();
+            continue;
+        };
+
+        _ = std::fs::create_dir_all(&to_import.pandora_path);
+        let target_dot_minecraft = to_import.pandora_path.join(".minecraft");
+
+        _ = std::fs::create_dir_all(&target_dot_minecraft);
+        super::copy_game_folder(&to_import.folder, &target_dot_minecraft, &super::is_launcher_data, &tracker);
+
+        // Custom icons are stored inline, the built-in ones (Grass, Furnace, ...) are just names
+        if let Some(icon) = &to_import.profile.icon
+            && let Some(base64_png) = icon.strip_prefix("data:image/png;base64,")
+            && let Ok(png) = base64::engine::general_purpose::STANDARD.decode(base64_png)
+        {
+            _ = write_safe(&to_import.pandora_path.join("icon.png"), &png);
+        }
+
+        let info_path = to_import.pandora_path.join("info_v1.json");
+        _ = write_safe(&info_path, &configuration_bytes);
+
+        if stats != InstanceStats::default() {
+            let stats_path = to_import.pandora_path.join("stats_v1.json");
+            if let Ok(stats_bytes) = serde_json::to_vec(&stats) {
+                _ = write_safe(&stats_path, &stats_bytes);
+            }
+        }
+
+        result.imported.push(super::pandora_instance_name(&to_import.pandora_path));
+        all_tracker.add_count(1);
+        all_tracker.notify();
+
+        tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Fast);
+        tracker.notify();
+    }
+
+    all_tracker.set_finished(bridge::modal_action::ProgressTrackerFinishType::Normal);
+    all_tracker.notify();
+
+    result
+}
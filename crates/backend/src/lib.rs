@@ -15,11 +15,18 @@ mod backend_handler;
 
 mod account;
 mod arcfactory;
+mod crash_report;
+mod dependency_analyzer;
 mod directories;
 mod install_content;
 mod instance;
+mod instance_export;
+mod instance_snapshot;
 mod java_manifest;
+mod java_registry;
+mod java_runtime_provider;
 mod launch;
+mod launch_hooks;
 mod launch_wrapper;
 mod launcher_import;
 mod lockfile;
@@ -28,11 +35,16 @@ mod metadata;
 mod mod_metadata;
 mod id_slab;
 mod persistent;
+mod rcon;
+mod server_history;
 mod server_list_pinger;
+mod servers_dat;
 mod shortcut;
 mod skin_manager;
 mod syncing;
 mod update;
+mod world_backup;
+mod zip_writer;
 
 pub(crate) fn is_single_component_path_str(path: &str) -> bool {
     is_single_component_path(std::path::Path::new(path))
@@ -48,6 +60,39 @@ pub(crate) fn is_single_component_path(path: &Path) -> bool {
     components.count() == 1
 }
 
+// CurseForge fingerprints are a MurmurHash2 (seed 1) of the file with all whitespace bytes removed
+pub(crate) fn curseforge_fingerprint(path: &Path) -> std::io::Result<u32> {
+    const M: u32 = 0x5bd1e995;
+
+    let bytes = std::fs::read(path)?;
+    let filtered: Vec<u8> = bytes.into_iter().filter(|byte| !matches!(byte, 9 | 10 | 13 | 32)).collect();
+
+    let mut hash = 1 ^ (filtered.len() as u32);
+
+    let mut chunks = filtered.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        hash = hash.wrapping_mul(M) ^ k;
+    }
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        for (index, byte) in remainder.iter().enumerate() {
+            hash ^= (*byte as u32) << (8 * index);
+        }
+        hash = hash.wrapping_mul(M);
+    }
+
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(M);
+    hash ^= hash >> 15;
+
+    Ok(hash)
+}
+
 pub(crate) fn check_sha1_hash(path: &Path, expected_hash: [u8; 20]) -> std::io::Result<bool> {
     let mut file = std::fs::File::open(path)?;
     let mut hasher = Sha1::new();
@@ -481,3 +526,31 @@ pub fn join_windows_shell_os(args: &[&OsStr]) -> OsString {
         OsString::from_encoded_bytes_unchecked(string)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::curseforge_fingerprint;
+
+    fn fingerprint(name: &str, content: &[u8]) -> u32 {
+        let path = std::env::temp_dir().join(format!("pandora-fingerprint-test-{name}-{}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        let fingerprint = curseforge_fingerprint(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        fingerprint
+    }
+
+    // Expected values are from the reference MurmurHash2 implementation with a seed of 1
+    #[test]
+    fn test_curseforge_fingerprint() {
+        assert_eq!(fingerprint("empty", b""), 1540447798);
+        assert_eq!(fingerprint("one", b"a"), 626045324);
+        assert_eq!(fingerprint("three", b"abc"), 1621425345);
+        assert_eq!(fingerprint("four", b"abcd"), 3376380438);
+    }
+
+    #[test]
+    fn test_curseforge_fingerprint_ignores_whitespace() {
+        assert_eq!(fingerprint("whitespace", b"Hello, World!\r\n\tfoo bar\n"), 2363627489);
+        assert_eq!(fingerprint("whitespace-only", b" \t\r\n"), fingerprint("no-whitespace", b""));
+    }
+}
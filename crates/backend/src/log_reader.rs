@@ -6,7 +6,7 @@ use std::{
 };
 
 use bridge::{
-    game_output::GameOutputLogLevel, handle::FrontendHandle, keep_alive::KeepAlive, message::MessageToFrontend,
+    game_output::GameOutputLogLevel, handle::FrontendHandle, instance::InstanceID, keep_alive::{KeepAlive, KeepAliveHandle}, message::MessageToFrontend,
 };
 use chrono::Utc;
 use memchr::memchr;
@@ -39,11 +39,44 @@ pub fn replace(string: &str) -> Cow<'_, str> {
     replaced
 }
 
-pub fn start_game_output(stdout: ChildStdout, stderr: Option<ChildStderr>, sender: FrontendHandle) {
-    let id = GAME_OUTPUT_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    let keep_alive = KeepAlive::new();
-    let keep_alive_handle = keep_alive.create_handle();
-    sender.send(MessageToFrontend::CreateGameOutputWindow { id, keep_alive });
+// A game output window, shared between the game process and the instance's launch hooks
+#[derive(Clone, Debug)]
+pub struct GameOutput {
+    id: usize,
+    keep_alive_handle: KeepAliveHandle,
+    sender: FrontendHandle,
+}
+
+impl GameOutput {
+    pub fn create(instance: InstanceID, sender: FrontendHandle) -> Self {
+        let id = GAME_OUTPUT_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let keep_alive = KeepAlive::new();
+        let keep_alive_handle = keep_alive.create_handle();
+        sender.send(MessageToFrontend::CreateGameOutputWindow { id, instance, keep_alive });
+
+        Self {
+            id,
+            keep_alive_handle,
+            sender,
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.keep_alive_handle.is_alive()
+    }
+
+    pub fn add(&self, level: GameOutputLogLevel, text: &str) {
+        self.sender.send(MessageToFrontend::AddGameOutput {
+            id: self.id,
+            time: Utc::now().timestamp_millis(),
+            level,
+            text: Arc::new([replace(text).trim_end().into()]),
+        });
+    }
+}
+
+pub fn start_game_output(output: GameOutput, stdout: ChildStdout, stderr: Option<ChildStderr>) {
+    let GameOutput { id, keep_alive_handle, sender } = output;
 
     if let Some(stderr) = stderr {
         let sender = sender.clone();
@@ -4,7 +4,7 @@ use std::{
 
 use reqwest::RequestBuilder;
 use schema::{
-    assets_index::AssetsIndex, curseforge::{CURSEFORGE_SEARCH_URL, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeGetModFilesResult, CurseforgeSearchRequest, CurseforgeSearchResult, MINECRAFT_GAME_ID}, fabric_launch::FabricLaunch, fabric_loader_manifest::{FABRIC_LOADER_MANIFEST_URL, FabricLoaderManifest}, forge::{ForgeMavenManifest, NeoforgeMavenManifest, VersionFragment}, java_runtime_component::JavaRuntimeComponentManifest, java_runtimes::{JAVA_RUNTIMES_URL, JavaRuntimes}, maven::MavenMetadataXml, modrinth::{MODRINTH_PROJECT_URL, MODRINTH_SEARCH_URL, ModrinthLoader, ModrinthProjectRequest, ModrinthProjectResult, ModrinthProjectVersion, ModrinthProjectVersionsRequest, ModrinthProjectVersionsResult, ModrinthSearchRequest, ModrinthSearchResult, ModrinthVersionFileUpdateResult}, version::MinecraftVersion, version_manifest::{MOJANG_VERSION_MANIFEST_URL, MinecraftVersionLink, MinecraftVersionManifest}
+    assets_index::AssetsIndex, curseforge::{CURSEFORGE_SEARCH_URL, CurseforgeFingerprintsRequest, CurseforgeFingerprintsResult, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeGetModFilesResult, CurseforgeSearchRequest, CurseforgeSearchResult, MINECRAFT_GAME_ID}, fabric_launch::FabricLaunch, fabric_loader_manifest::{FABRIC_LOADER_MANIFEST_URL, FabricLoaderManifest}, forge::{ForgeMavenManifest, NeoforgeMavenManifest, VersionFragment}, java_runtime_component::JavaRuntimeComponentManifest, java_runtimes::{JAVA_RUNTIMES_URL, JavaRuntimes}, maven::MavenMetadataXml, modrinth::{MODRINTH_PROJECT_URL, MODRINTH_SEARCH_URL, ModrinthLoader, ModrinthProjectRequest, ModrinthProjectResult, ModrinthProjectVersion, ModrinthProjectVersionsRequest, ModrinthProjectVersionsResult, ModrinthSearchRequest, ModrinthSearchResult, ModrinthVersionFileUpdateResult}, quilt_launch::QuiltLaunch, quilt_loader_manifest::{QUILT_LOADER_MANIFEST_URL, QuiltLoaderManifest}, version::MinecraftVersion, version_manifest::{MOJANG_VERSION_MANIFEST_URL, MinecraftVersionLink, MinecraftVersionManifest}
 };
 use serde::Serialize;
 use ustr::Ustr;
@@ -250,6 +250,67 @@ impl MetadataItem for FabricLaunchMetadataItem {
     }
 }
 
+#[derive(Debug)]
+pub struct QuiltLoaderManifestMetadataItem;
+
+impl MetadataItem for QuiltLoaderManifestMetadataItem {
+    type T = QuiltLoaderManifest;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        client.get(QUILT_LOADER_MANIFEST_URL)
+    }
+
+    fn expires(&self) -> bool {
+        true
+    }
+
+    fn cache_file(&self, metadata_manager: &MetadataManager) -> Option<impl AsRef<Path> + Send + Sync + 'static> {
+        Some(Arc::clone(&metadata_manager.quilt_loader_manifest_cache))
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        states.quilt_loader_manifest.clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct QuiltLaunchMetadataItem {
+    pub minecraft_version: Ustr,
+    pub loader_version: Ustr,
+}
+
+impl MetadataItem for QuiltLaunchMetadataItem {
+    type T = QuiltLaunch;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        client.get(format!("https://meta.quiltmc.org/v3/versions/loader/{}/{}", self.minecraft_version, self.loader_version))
+    }
+
+    fn expires(&self) -> bool {
+        false
+    }
+
+    fn cache_file(&self, metadata_manager: &MetadataManager) -> Option<impl AsRef<Path> + Send + Sync + 'static> {
+        let mut path = metadata_manager.metadata_cache.join("quilt_launch");
+        path.push(self.minecraft_version.as_str());
+        path.push(self.loader_version.as_str());
+        Some(path)
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        let key = (self.minecraft_version, self.loader_version);
+        states.quilt_launch.entry(key).or_default().clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
 #[derive(Debug)]
 pub struct ModrinthSearchMetadataItem<'a>(pub &'a ModrinthSearchRequest);
 
@@ -328,6 +389,30 @@ impl MetadataItem for ModrinthVersionMetadataItem {
     }
 }
 
+#[derive(Debug)]
+pub struct ModrinthVersionFileMetadataItem(pub Arc<str>);
+
+impl MetadataItem for ModrinthVersionFileMetadataItem {
+    type T = ModrinthProjectVersion;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        let url = format!("https://api.modrinth.com/v2/version_file/{}?algorithm=sha1", self.0);
+        client.get(url)
+    }
+
+    fn expires(&self) -> bool {
+        true
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        states.modrinth_version_files.entry(self.0.clone()).or_default().clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Hash, PartialEq, Eq)]
 pub struct VersionUpdateParameters {
     pub loaders: Arc<[ModrinthLoader]>,
@@ -592,3 +677,28 @@ impl<'a> MetadataItem for CurseforgeGetFilesMetadataItem<'a> {
         Ok(serde_json::from_slice(bytes)?)
     }
 }
+
+#[derive(Debug)]
+pub struct CurseforgeFingerprintsMetadataItem<'a>(pub &'a CurseforgeFingerprintsRequest);
+
+impl<'a> MetadataItem for CurseforgeFingerprintsMetadataItem<'a> {
+    type T = CurseforgeFingerprintsResult;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        client.post(format!("https://api.curseforge.com/v1/fingerprints/{}", MINECRAFT_GAME_ID))
+            .json(self.0)
+            .header("x-api-key", "$2a$10$YXf6dyJfJZM4zeChdr.RDOvWN.L48AN0dQShQO8/cVc5ho1wA8ZbS")
+    }
+
+    fn expires(&self) -> bool {
+        true
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        states.curseforge_fingerprints.entry(self.0.clone()).or_default().clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 use bridge::keep_alive::{KeepAlive, KeepAliveHandle};
 use reqwest::StatusCode;
 use schema::{
-    assets_index::AssetsIndex, curseforge::{CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeGetModFilesResult, CurseforgeSearchRequest, CurseforgeSearchResult}, fabric_launch::FabricLaunch, fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeMavenManifest, NeoforgeMavenManifest}, java_runtime_component::JavaRuntimeComponentManifest, java_runtimes::JavaRuntimes, modrinth::{ModrinthProjectRequest, ModrinthProjectResult, ModrinthProjectVersion, ModrinthProjectVersionsRequest, ModrinthProjectVersionsResult, ModrinthSearchRequest, ModrinthSearchResult, ModrinthVersionFileUpdateResult}, version::MinecraftVersion, version_manifest::MinecraftVersionManifest
+    assets_index::AssetsIndex, curseforge::{CurseforgeFingerprintsRequest, CurseforgeFingerprintsResult, CurseforgeGetFilesRequest, CurseforgeGetModFilesRequest, CurseforgeGetModFilesResult, CurseforgeSearchRequest, CurseforgeSearchResult}, fabric_launch::FabricLaunch, fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeMavenManifest, NeoforgeMavenManifest}, java_runtime_component::JavaRuntimeComponentManifest, java_runtimes::JavaRuntimes, modrinth::{ModrinthProjectRequest, ModrinthProjectResult, ModrinthProjectVersion, ModrinthProjectVersionsRequest, ModrinthProjectVersionsResult, ModrinthSearchRequest, ModrinthSearchResult, ModrinthVersionFileUpdateResult}, quilt_launch::QuiltLaunch, quilt_loader_manifest::QuiltLoaderManifest, version::MinecraftVersion, version_manifest::MinecraftVersionManifest
 };
 use serde::Deserialize;
 use sha1::{Digest, Sha1};
@@ -26,6 +26,8 @@ pub struct MetadataManagerStates {
     pub(super) neoforge_installer_maven_manifest: MetaLoadStateWrapper<NeoforgeMavenManifest>,
     pub(super) forge_installer_maven_manifest: MetaLoadStateWrapper<ForgeMavenManifest>,
     pub(super) fabric_launch: HashMap<(Ustr, Ustr), MetaLoadStateWrapper<FabricLaunch>>,
+    pub(super) quilt_loader_manifest: MetaLoadStateWrapper<QuiltLoaderManifest>,
+    pub(super) quilt_launch: HashMap<(Ustr, Ustr), MetaLoadStateWrapper<QuiltLaunch>>,
     pub(super) version_info: HashMap<Ustr, MetaLoadStateWrapper<MinecraftVersion>>,
     pub(super) assets_index: HashMap<Ustr, MetaLoadStateWrapper<AssetsIndex>>,
     pub(super) java_runtime_manifests: HashMap<Ustr, MetaLoadStateWrapper<JavaRuntimeComponentManifest>>,
@@ -33,11 +35,13 @@ pub struct MetadataManagerStates {
     pub(super) modrinth_project_versions: HashMap<ModrinthProjectVersionsRequest, MetaLoadStateWrapper<ModrinthProjectVersionsResult>>,
     pub(super) modrinth_project: HashMap<ModrinthProjectRequest, MetaLoadStateWrapper<ModrinthProjectResult>>,
     pub(super) modrinth_versions: HashMap<Arc<str>, MetaLoadStateWrapper<ModrinthProjectVersion>>,
+    pub(super) modrinth_version_files: HashMap<Arc<str>, MetaLoadStateWrapper<ModrinthProjectVersion>>,
     pub(super) modrinth_version_v2_updates: HashMap<ModrinthVersionUpdateMetadataItem, MetaLoadStateWrapper<ModrinthVersionFileUpdateResult>>,
     pub(super) modrinth_version_v3_updates: HashMap<ModrinthV3VersionUpdateMetadataItem, MetaLoadStateWrapper<ModrinthVersionFileUpdateResult>>,
     pub(super) curseforge_search: HashMap<CurseforgeSearchRequest, MetaLoadStateWrapper<CurseforgeSearchResult>>,
     pub(super) curseforge_get_mod_files: HashMap<CurseforgeGetModFilesRequest, MetaLoadStateWrapper<CurseforgeGetModFilesResult>>,
     pub(super) curseforge_get_files: HashMap<CurseforgeGetFilesRequest, MetaLoadStateWrapper<CurseforgeGetModFilesResult>>,
+    pub(super) curseforge_fingerprints: HashMap<CurseforgeFingerprintsRequest, MetaLoadStateWrapper<CurseforgeFingerprintsResult>>,
 }
 
 pub struct MetadataManager {
@@ -47,6 +51,7 @@ pub struct MetadataManager {
     pub(super) version_manifest_cache: Arc<Path>,
     pub(super) mojang_java_runtimes_cache: Arc<Path>,
     pub(super) fabric_loader_manifest_cache: Arc<Path>,
+    pub(super) quilt_loader_manifest_cache: Arc<Path>,
     pub(super) neoforge_installer_maven_cache: Arc<Path>,
     pub(super) forge_installer_maven_cache: Arc<Path>,
 
@@ -65,6 +70,7 @@ pub enum MetaLoadError {
     Error(Arc<str>),
     ErrorWithDescription(Arc<str>, Arc<str>),
     NonOK(u16),
+    NotCached(Option<Arc<Path>>),
 }
 
 impl Display for MetaLoadError {
@@ -111,6 +117,12 @@ impl Display for MetaLoadError {
             Self::NonOK(status_code) => {
                 f.write_fmt(format_args!("Non-OK response: {}", *status_code))
             }
+            Self::NotCached(Some(path)) => {
+                f.write_fmt(format_args!("Cached metadata is missing or corrupt: {}", path.display()))
+            }
+            Self::NotCached(None) => {
+                f.write_str("Metadata isn't cached and can't be loaded offline")
+            }
             Self::TokioJoin(error) => f.debug_tuple("TokioJoin").field(error).finish(),
         }
     }
@@ -157,6 +169,7 @@ impl MetadataManager {
             version_manifest_cache: directory.join("version_manifest.json").into(),
             mojang_java_runtimes_cache: directory.join("mojang_java_runtimes.json").into(),
             fabric_loader_manifest_cache: directory.join("fabric_loader_manifest.json").into(),
+            quilt_loader_manifest_cache: directory.join("quilt_loader_manifest.json").into(),
             neoforge_installer_maven_cache: directory.join("neoforge_installer_maven.xml").into(),
             forge_installer_maven_cache: directory.join("forge_installer_maven.xml").into(),
             metadata_cache: directory,
@@ -255,6 +268,52 @@ impl MetadataManager {
         }
     }
 
+    // Loads the metadata only from memory or the cache file, without making any network requests
+    pub async fn fetch_cached<I: MetadataItem>(&self, item: &I) -> Result<Arc<<I as MetadataItem>::T>, MetaLoadError> {
+        let wrapper = item.state(&mut *self.states.lock().await);
+        let wrapper = wrapper.lock().await;
+
+        if let MetaLoadState::Loaded(value) = &wrapper.1 {
+            return Ok(Arc::clone(value));
+        }
+
+        let Some(cache_file) = item.cache_file(self) else {
+            return Err(MetaLoadError::NotCached(None));
+        };
+        let cache_file: Arc<Path> = cache_file.as_ref().into();
+
+        let expected_hash = item.data_hash().and_then(|sha1| {
+            let mut expected_hash = [0u8; 20];
+            hex::decode_to_slice(sha1.as_str(), &mut expected_hash).ok()?;
+            Some(expected_hash)
+        });
+
+        tokio::task::spawn_blocking(move || {
+            let Ok(file) = std::fs::read(&cache_file) else {
+                return Err(MetaLoadError::NotCached(Some(cache_file)));
+            };
+
+            if let Some(expected_hash) = &expected_hash {
+                let mut hasher = Sha1::new();
+                hasher.update(&file);
+                let actual_hash = hasher.finalize();
+
+                if expected_hash != &*actual_hash {
+                    log::warn!("Sha1 mismatch for cached metadata {:?}", cache_file);
+                    return Err(MetaLoadError::NotCached(Some(cache_file)));
+                }
+            }
+
+            match I::deserialize(&file) {
+                Ok(meta) => Ok(Arc::new(meta)),
+                Err(error) => {
+                    log::warn!("Error parsing cached metadata file {:?}: {}", cache_file, error);
+                    Err(MetaLoadError::NotCached(Some(cache_file)))
+                },
+            }
+        }).await?
+    }
+
     fn inner_start_loading<I: MetadataItem>(
         state: &mut MetaLoadState<I::T>,
         item: &I,
@@ -2,14 +2,14 @@ use std::{
     hash::Hash, io::{BufRead, Cursor, Read, Write}, path::{Path, PathBuf}, sync::{Arc, atomic::{AtomicBool, Ordering}}
 };
 
-use bridge::{instance::{ContentSummary, ContentType, ContentUpdateStatus, UNKNOWN_CONTENT_SUMMARY}, safe_path::SafePath};
+use bridge::{instance::{ContentDependency, ContentDependencyKind, ContentSummary, ContentType, ContentUpdateStatus, ContentVersionRequirement, UNKNOWN_CONTENT_SUMMARY}, safe_path::SafePath};
 use image::{DynamicImage, GenericImageView, imageops::FilterType};
 use indexmap::IndexMap;
 use parking_lot::{RwLock, RwLockReadGuard};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use rc_zip_sync::EntryHandle;
+use rc_zip_sync::{EntryHandle, ReadZip};
 use rustc_hash::{FxHashMap, FxHashSet};
-use schema::{content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeFile, CurseforgeModpackManifestJson}, fabric_mod::{FabricModJson, Icon, Person}, forge_mod::{JarJarMetadata, McModInfo, ModsToml}, loader::Loader, modrinth::{ModrinthFile, ModrinthSideRequirement}, mrpack::ModrinthIndexJson, resourcepack::PackMcmeta};
+use schema::{content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeFile, CurseforgeModpackManifestJson}, fabric_mod::{FabricModJson, Icon, Person}, forge_mod::{JarJarMetadata, McModInfo, ModsToml}, loader::Loader, modrinth::{ModrinthFile, ModrinthSideRequirement}, mrpack::ModrinthIndexJson, quilt_mod::{QuiltDependency, QuiltModJson, QuiltProvides}, resourcepack::PackMcmeta};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DeserializeAs};
 use sha1::{Digest, Sha1};
@@ -185,6 +185,12 @@ impl ModMetadataManager {
         }
     }
 
+    pub fn find_cached_curseforge_file_id(&self, hash: &[u8; 20]) -> Option<u32> {
+        self.cached_curseforge_info.read().iter()
+            .find(|(_, info)| info.hash == *hash)
+            .map(|(file_id, _)| *file_id)
+    }
+
     pub fn set_cached_curseforge_info(&self, file_id: u32, info: CachedCurseforgeFileInfo) {
         self.cached_curseforge_info.write().insert(file_id, info);
         self.curseforge_info_dirty.store(true, Ordering::Release);
@@ -264,6 +270,8 @@ impl ModMetadataManager {
             self.load_legacy_forge_mod(hash, &archive, file)
         } else if let Some(file) = archive.by_name("fabric.mod.json") {
             self.load_fabric_mod(hash, &archive, file)
+        } else if let Some(file) = archive.by_name("quilt.mod.json") {
+            self.load_quilt_mod(hash, &archive, file)
         } else if let Some(file) = archive.by_name("META-INF/mods.toml") {
             self.load_forge_mod(hash, &archive, file, ContentType::Forge)
         } else if let Some(file) = archive.by_name("META-INF/neoforge.mods.toml") {
@@ -328,6 +336,26 @@ impl ModMetadataManager {
             "".into()
         };
 
+        let mut provides: Vec<(Arc<str>, Arc<str>)> = fabric_mod_json.provides.iter().map(|id| (id.clone(), fabric_mod_json.version.clone())).collect();
+        for jar in &fabric_mod_json.jars {
+            load_nested_fabric_provides(archive, &jar.file, &mut provides);
+        }
+
+        let mut dependencies = Vec::new();
+        for (dependencies_map, kind) in [
+            (fabric_mod_json.depends, ContentDependencyKind::Required),
+            (fabric_mod_json.breaks, ContentDependencyKind::Incompatible),
+            (fabric_mod_json.conflicts, ContentDependencyKind::Discouraged),
+        ] {
+            for (id, predicates) in dependencies_map {
+                dependencies.push(ContentDependency {
+                    id,
+                    kind,
+                    versions: ContentVersionRequirement::Predicates(predicates.into_vec().into()),
+                });
+            }
+        }
+
         Some(Arc::new(ContentSummary {
             id: Some(fabric_mod_json.id),
             hash,
@@ -336,7 +364,85 @@ impl ModMetadataManager {
             version_str: format!("v{}", fabric_mod_json.version).into(),
             rich_description: None,
             png_icon,
-            extra: ContentType::Fabric
+            extra: ContentType::Fabric,
+            provides: provides.into(),
+            dependencies: dependencies.into(),
+        }))
+    }
+
+    fn load_quilt_mod<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], archive: &rc_zip_sync::ArchiveHandle<R>, file: EntryHandle<'_, R>) -> Option<Arc<ContentSummary>> {
+        let bytes = file.bytes().ok()?;
+
+        let quilt_mod_json: QuiltModJson = serde_json::from_slice(&bytes).inspect_err(|e| {
+            log::error!("Error parsing quilt.mod.json: {e}");
+        }).ok()?;
+
+        drop(file);
+
+        let quilt_loader = quilt_mod_json.quilt_loader;
+        let metadata = quilt_loader.metadata;
+
+        let name = metadata.name.unwrap_or_else(|| Arc::clone(&quilt_loader.id));
+
+        let icon = match metadata.icon {
+            Some(icon) => match icon {
+                Icon::Single(icon) => Some(icon),
+                Icon::Sizes(hash_map) => {
+                    const DESIRED_SIZE: usize = 64;
+                    hash_map.iter().min_by_key(|size| size.0.abs_diff(DESIRED_SIZE)).map(|e| Arc::clone(e.1))
+                },
+            },
+            None => None,
+        };
+
+        let mut png_icon: Option<Arc<[u8]>> = None;
+        if let Some(icon) = icon && let Some(icon_file) = archive.by_name(&icon) {
+            png_icon = load_icon(icon_file);
+        }
+
+        let contributors: Option<Vec<Person>> = metadata.contributors.map(|contributors| contributors.into_keys().map(Person::Name).collect());
+        let authors = if let Some(contributors) = contributors && let Some(authors) = create_authors_string(&contributors) {
+            authors.into()
+        } else {
+            "".into()
+        };
+
+        let provides: Vec<(Arc<str>, Arc<str>)> = quilt_loader.provides.into_iter().map(|provides| match provides {
+            QuiltProvides::Id(id) => (id, quilt_loader.version.clone()),
+            QuiltProvides::Object { id, version } => (id, version.unwrap_or_else(|| quilt_loader.version.clone())),
+        }).collect();
+
+        let mut dependencies = Vec::new();
+        for (quilt_dependencies, kind) in [
+            (quilt_loader.depends, ContentDependencyKind::Required),
+            (quilt_loader.breaks, ContentDependencyKind::Incompatible),
+        ] {
+            for dependency in quilt_dependencies {
+                let (id, versions) = match dependency {
+                    QuiltDependency::Id(id) => (id, Vec::new()),
+                    QuiltDependency::Object { optional: true, .. } if kind == ContentDependencyKind::Required => continue,
+                    QuiltDependency::Object { id, versions, .. } => (id, versions),
+                };
+                let versions = if versions.is_empty() {
+                    ContentVersionRequirement::Any
+                } else {
+                    ContentVersionRequirement::Predicates(versions.into())
+                };
+                dependencies.push(ContentDependency { id, kind, versions });
+            }
+        }
+
+        Some(Arc::new(ContentSummary {
+            id: Some(quilt_loader.id),
+            hash,
+            name: Some(name),
+            authors,
+            version_str: format!("v{}", quilt_loader.version).into(),
+            rich_description: None,
+            png_icon,
+            extra: ContentType::Quilt,
+            provides: provides.into(),
+            dependencies: dependencies.into(),
         }))
     }
 
@@ -380,6 +486,37 @@ impl ModMetadataManager {
             }
         }
 
+        // Additional mods in the same jar are provided with the same version
+        let provides: Vec<(Arc<str>, Arc<str>)> = mods_toml.mods.iter().skip(1)
+            .map(|other| (other.mod_id.clone(), Arc::from(&version[1..])))
+            .collect();
+
+        let mut dependencies = Vec::new();
+        for mod_dependencies in mods_toml.dependencies.values() {
+            for dependency in mod_dependencies {
+                if let Some(side) = &dependency.side && side.eq_ignore_ascii_case("server") {
+                    continue;
+                }
+                let kind = match dependency.dependency_type.as_deref().map(str::to_ascii_lowercase).as_deref() {
+                    Some("required") => ContentDependencyKind::Required,
+                    Some("incompatible") => ContentDependencyKind::Incompatible,
+                    Some("discouraged") => ContentDependencyKind::Discouraged,
+                    Some(_) => continue,
+                    None if dependency.mandatory == Some(true) => ContentDependencyKind::Required,
+                    None => continue,
+                };
+                let versions = match &dependency.version_range {
+                    Some(range) if !range.trim().is_empty() && &**range != "*" => ContentVersionRequirement::MavenRange(range.clone()),
+                    _ => ContentVersionRequirement::Any,
+                };
+                dependencies.push(ContentDependency {
+                    id: dependency.mod_id.clone(),
+                    kind,
+                    versions,
+                });
+            }
+        }
+
         Some(Arc::new(ContentSummary {
             id: Some(first.mod_id.clone()),
             hash,
@@ -389,6 +526,8 @@ impl ModMetadataManager {
             rich_description: None,
             png_icon,
             extra,
+            provides: provides.into(),
+            dependencies: dependencies.into(),
         }))
     }
 
@@ -439,6 +578,8 @@ impl ModMetadataManager {
             rich_description: None,
             png_icon,
             extra: ContentType::LegacyForge,
+            provides: [].into(),
+            dependencies: [].into(),
         }))
     }
 
@@ -541,7 +682,9 @@ impl ModMetadataManager {
                 summaries: summaries.into(),
                 overrides: overrides.into_iter().collect(),
                 dependencies: modrinth_index_json.dependencies,
-            }
+            },
+            provides: [].into(),
+            dependencies: [].into(),
         }))
     }
 
@@ -630,7 +773,9 @@ impl ModMetadataManager {
                 summaries: summaries.into(),
                 overrides: overrides.into_iter().collect(),
                 minecraft: manifest_json.minecraft,
-            }
+            },
+            provides: [].into(),
+            dependencies: [].into(),
         }))
     }
 
@@ -700,7 +845,9 @@ impl ModMetadataManager {
             version_str: version.unwrap_or_default(),
             rich_description: None,
             png_icon: None,
-            extra: ContentType::JavaModule
+            extra: ContentType::JavaModule,
+            provides: [].into(),
+            dependencies: [].into(),
         }))
     }
 
@@ -726,7 +873,9 @@ impl ModMetadataManager {
             version_str: "".into(),
             rich_description: Some(Arc::new(pack_mcmeta.pack.description)),
             png_icon,
-            extra: ContentType::ResourcePack
+            extra: ContentType::ResourcePack,
+            provides: [].into(),
+            dependencies: [].into(),
         }))
     }
 
@@ -745,12 +894,40 @@ impl ModMetadataManager {
             version_str: "".into(),
             rich_description: Some(Arc::new(pack_mcmeta.pack.description)),
             png_icon,
-            extra: ContentType::ResourcePack
+            extra: ContentType::ResourcePack,
+            provides: [].into(),
+            dependencies: [].into(),
         }))
     }
 }
 
 
+// Adds the ids of the fabric mod inside a jar-in-jar, only a single level of nesting is checked
+fn load_nested_fabric_provides<R: rc_zip_sync::HasCursor>(archive: &rc_zip_sync::ArchiveHandle<R>, path: &str, provides: &mut Vec<(Arc<str>, Arc<str>)>) {
+    let Some(file) = archive.by_name(path) else {
+        return;
+    };
+    let Ok(bytes) = file.bytes() else {
+        return;
+    };
+    let nested_bytes: &[u8] = &bytes;
+    let Ok(nested) = nested_bytes.read_zip() else {
+        return;
+    };
+    let Some(nested_file) = nested.by_name("fabric.mod.json") else {
+        return;
+    };
+    let Ok(nested_json) = nested_file.bytes() else {
+        return;
+    };
+    let Ok(nested_mod) = serde_json::from_slice::<FabricModJson>(&nested_json) else {
+        return;
+    };
+
+    provides.push((nested_mod.id.clone(), nested_mod.version.clone()));
+    provides.extend(nested_mod.provides.iter().map(|id| (id.clone(), nested_mod.version.clone())));
+}
+
 fn load_icon<R: rc_zip_sync::HasCursor>(icon_file: rc_zip_sync::EntryHandle<R>) -> Option<Arc<[u8]>> {
     let Ok(icon_bytes) = icon_file.bytes() else {
         return None;
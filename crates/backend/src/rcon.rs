@@ -0,0 +1,262 @@
+use std::{net::Ipv4Addr, path::Path, sync::Arc, time::Duration};
+
+use tokio::{io::{AsyncRead, AsyncReadExt, AsyncWriteExt}, net::{TcpStream, ToSocketAddrs}};
+
+const DEFAULT_RCON_PORT: u16 = 25575;
+const TIMEOUT: Duration = Duration::from_secs(10);
+// Minecraft drops the connection for commands longer than this
+const MAX_COMMAND_LENGTH: usize = 1446;
+// Responses are split into packets of at most 4096 UTF-16 characters, which can take up to 3 bytes each when encoded as UTF-8
+const MAX_PACKET_LENGTH: i32 = 4096 * 3 + 10;
+
+const PACKET_TYPE_RESPONSE: i32 = 0;
+const PACKET_TYPE_COMMAND: i32 = 2;
+const PACKET_TYPE_AUTH: i32 = 3;
+
+#[derive(thiserror::Error, Debug)]
+pub enum RconError {
+    #[error("RCON is not enabled, set enable-rcon=true and rcon.password in server.properties")]
+    NotEnabled,
+    #[error("Authentication failed, check rcon.password in server.properties")]
+    AuthenticationFailed,
+    #[error("Command is too long ({0} bytes, the maximum is {MAX_COMMAND_LENGTH})")]
+    CommandTooLong(usize),
+    #[error("Received an invalid packet from the server")]
+    InvalidPacket,
+    #[error("Timed out waiting for the server")]
+    Timeout,
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+struct RconPacket {
+    id: i32,
+    payload: Vec<u8>,
+}
+
+pub struct RconClient {
+    stream: TcpStream,
+    next_id: i32,
+}
+
+impl RconClient {
+    pub async fn connect(address: impl ToSocketAddrs, password: &str) -> Result<Self, RconError> {
+        let stream = tokio::time::timeout(TIMEOUT, TcpStream::connect(address)).await.map_err(|_| RconError::Timeout)??;
+
+        let mut client = Self {
+            stream,
+            next_id: 1,
+        };
+
+        let id = client.send(PACKET_TYPE_AUTH, password).await?;
+        let response = client.receive().await?;
+        if response.id != id {
+            // The server responds with an id of -1 when the password is wrong
+            return Err(RconError::AuthenticationFailed);
+        }
+
+        Ok(client)
+    }
+
+    pub async fn command(&mut self, command: &str) -> Result<String, RconError> {
+        if command.len() > MAX_COMMAND_LENGTH {
+            return Err(RconError::CommandTooLong(command.len()));
+        }
+
+        let id = self.send(PACKET_TYPE_COMMAND, command).await?;
+
+        // Long responses are split over multiple packets without saying how many there are. Requests are handled
+        // in order, so the server answering this (invalid) request means the command's response is complete
+        let end_id = self.send(PACKET_TYPE_RESPONSE, "").await?;
+
+        let mut response = Vec::new();
+        loop {
+            let packet = self.receive().await?;
+            if packet.id == end_id {
+                break;
+            } else if packet.id == id {
+                response.extend_from_slice(&packet.payload);
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&response).into_owned())
+    }
+
+    async fn send(&mut self, packet_type: i32, payload: &str) -> Result<i32, RconError> {
+        let id = self.next_id;
+        self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+
+        let packet = encode_packet(id, packet_type, payload);
+        tokio::time::timeout(TIMEOUT, self.stream.write_all(&packet)).await.map_err(|_| RconError::Timeout)??;
+        Ok(id)
+    }
+
+    async fn receive(&mut self) -> Result<RconPacket, RconError> {
+        tokio::time::timeout(TIMEOUT, read_packet(&mut self.stream)).await.map_err(|_| RconError::Timeout)?
+    }
+}
+
+fn encode_packet(id: i32, packet_type: i32, payload: &str) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(payload.len() + 14);
+    packet.extend_from_slice(&(payload.len() as i32 + 10).to_le_bytes());
+    packet.extend_from_slice(&id.to_le_bytes());
+    packet.extend_from_slice(&packet_type.to_le_bytes());
+    packet.extend_from_slice(payload.as_bytes());
+    packet.extend_from_slice(&[0, 0]);
+    packet
+}
+
+async fn read_packet(stream: &mut (impl AsyncRead + Unpin)) -> Result<RconPacket, RconError> {
+    let length = stream.read_i32_le().await?;
+    if !(10..=MAX_PACKET_LENGTH).contains(&length) {
+        return Err(RconError::InvalidPacket);
+    }
+
+    let mut buffer = vec![0_u8; length as usize];
+    stream.read_exact(&mut buffer).await?;
+
+    // id, type, payload, two null bytes
+    let id = i32::from_le_bytes(buffer[0..4].try_into().unwrap());
+    let payload = buffer[8..buffer.len() - 2].to_vec();
+    Ok(RconPacket { id, payload })
+}
+
+fn read_local_settings(server_dir: &Path) -> Option<(u16, String)> {
+    let properties = std::fs::read_to_string(server_dir.join("server.properties")).ok()?;
+
+    let mut enabled = false;
+    let mut port = DEFAULT_RCON_PORT;
+    let mut password = None;
+    for line in properties.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "enable-rcon" => enabled = value.trim() == "true",
+            "rcon.port" => port = value.trim().parse().unwrap_or(DEFAULT_RCON_PORT),
+            "rcon.password" => password = Some(value.trim().to_string()),
+            _ => {},
+        }
+    }
+
+    if !enabled {
+        return None;
+    }
+    password.filter(|password| !password.is_empty()).map(|password| (port, password))
+}
+
+pub async fn run_local_command(server_dir: &Path, command: &str) -> Result<Arc<str>, RconError> {
+    let Some((port, password)) = read_local_settings(server_dir) else {
+        return Err(RconError::NotEnabled);
+    };
+
+    let mut client = RconClient::connect((Ipv4Addr::LOCALHOST, port), &password).await?;
+    let response = client.command(command.trim().trim_start_matches('/')).await?;
+    Ok(response.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+    use super::{encode_packet, read_packet, RconClient, RconError, PACKET_TYPE_AUTH, PACKET_TYPE_COMMAND, PACKET_TYPE_RESPONSE};
+
+    #[test]
+    fn test_encode_packet() {
+        assert_eq!(encode_packet(7, PACKET_TYPE_COMMAND, "list"), [
+            14, 0, 0, 0,
+            7, 0, 0, 0,
+            2, 0, 0, 0,
+            b'l', b'i', b's', b't',
+            0, 0,
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_decode_packet() {
+        let mut data = encode_packet(3, PACKET_TYPE_RESPONSE, "There are 0 of a max of 20 players online");
+        data.extend_from_slice(&encode_packet(-1, PACKET_TYPE_RESPONSE, ""));
+        let mut reader = data.as_slice();
+
+        let packet = read_packet(&mut reader).await.unwrap();
+        assert_eq!(packet.id, 3);
+        assert_eq!(packet.payload, b"There are 0 of a max of 20 players online");
+
+        let packet = read_packet(&mut reader).await.unwrap();
+        assert_eq!(packet.id, -1);
+        assert!(packet.payload.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_decode_multibyte_packet() {
+        // A full packet from the server is 4096 characters, which is more than 4096 bytes
+        let payload = "é".repeat(4096);
+        let data = encode_packet(1, PACKET_TYPE_RESPONSE, &payload);
+        let packet = read_packet(&mut data.as_slice()).await.unwrap();
+        assert_eq!(packet.payload, payload.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_decode_invalid_packet() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&5_i32.to_le_bytes());
+        data.extend_from_slice(&[0; 5]);
+        assert!(matches!(read_packet(&mut data.as_slice()).await, Err(RconError::InvalidPacket)));
+    }
+
+    #[tokio::test]
+    async fn test_split_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let first = "a".repeat(4096);
+        let second = "ü".repeat(4096);
+        let third = "end";
+        let expected = format!("{first}{second}{third}");
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let auth = read_packet(&mut stream).await.unwrap();
+            assert_eq!(auth.payload, b"password");
+            stream.write_all(&encode_packet(auth.id, PACKET_TYPE_COMMAND, "")).await.unwrap();
+
+            let command = read_packet(&mut stream).await.unwrap();
+            assert_eq!(command.payload, b"help");
+            let end = read_packet(&mut stream).await.unwrap();
+
+            for chunk in [first.as_str(), second.as_str(), third] {
+                stream.write_all(&encode_packet(command.id, PACKET_TYPE_RESPONSE, chunk)).await.unwrap();
+            }
+            stream.write_all(&encode_packet(end.id, PACKET_TYPE_RESPONSE, "Unknown request 0")).await.unwrap();
+        });
+
+        let mut client = RconClient::connect(address, "password").await.unwrap();
+        let response = client.command("help").await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(response, expected);
+    }
+
+    #[tokio::test]
+    async fn test_authentication_failed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let auth = read_packet(&mut stream).await.unwrap();
+            assert_ne!(auth.id, -1);
+            stream.write_all(&encode_packet(-1, PACKET_TYPE_AUTH, "")).await.unwrap();
+        });
+
+        let result = RconClient::connect(address, "wrong").await;
+        server.await.unwrap();
+
+        assert!(matches!(result, Err(RconError::AuthenticationFailed)));
+    }
+}
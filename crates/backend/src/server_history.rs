@@ -0,0 +1,324 @@
+use std::{collections::VecDeque, io::{BufRead, Write}, path::Path, sync::Arc, time::Duration};
+
+use bridge::{instance::ServerStatusTrend, message::{BridgeNotificationType, MessageToFrontend}};
+use futures::StreamExt;
+use rustc_hash::FxHashMap;
+use schema::server_history::{ServerHistory, ServerHistoryEntry, ServerStatusSample, ServerStatusSampleRecord};
+
+use crate::{persistent::Persistent, BackendState};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// A week of samples at the poll interval
+const MAX_SAMPLES: usize = 7 * 24 * 12;
+const MAX_CONCURRENT_PINGS: usize = 8;
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+const WEEK_MS: i64 = 7 * DAY_MS;
+
+// Samples are appended to a JSON lines file instead of rewriting the whole history on every poll
+pub struct ServerSampleLog {
+    path: Arc<Path>,
+    samples: FxHashMap<Arc<str>, VecDeque<ServerStatusSample>>,
+    lines: usize,
+}
+
+impl ServerSampleLog {
+    pub fn load(path: Arc<Path>, server_history: &mut Persistent<ServerHistory>) -> Self {
+        let mut log = Self {
+            path,
+            samples: FxHashMap::default(),
+            lines: 0,
+        };
+
+        if let Ok(file) = std::fs::File::open(&log.path) {
+            for line in std::io::BufReader::new(file).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                log.lines += 1;
+                // A partially written line is left behind if the launcher was closed while appending
+                if let Ok(record) = serde_json::from_str::<ServerStatusSampleRecord>(&line) {
+                    log.push(record);
+                }
+            }
+        }
+
+        // Move samples from older versions of server_history.json into the log
+        let legacy: Vec<ServerStatusSampleRecord> = server_history.get().servers.iter()
+            .flat_map(|(address, entry)| entry.samples.iter().map(|sample| ServerStatusSampleRecord {
+                address: address.clone(),
+                sample: sample.clone(),
+            }))
+            .collect();
+        if !legacy.is_empty() && log.append(legacy) {
+            server_history.modify(|server_history| {
+                for entry in server_history.servers.values_mut() {
+                    entry.samples.clear();
+                }
+            });
+        }
+
+        log
+    }
+
+    pub fn samples(&self, address: &str) -> Option<&VecDeque<ServerStatusSample>> {
+        self.samples.get(address)
+    }
+
+    fn push(&mut self, record: ServerStatusSampleRecord) {
+        let samples = self.samples.entry(record.address).or_default();
+        samples.push_back(record.sample);
+        while samples.len() > MAX_SAMPLES {
+            samples.pop_front();
+        }
+    }
+
+    fn append(&mut self, records: Vec<ServerStatusSampleRecord>) -> bool {
+        let mut content = Vec::new();
+        for record in &records {
+            if serde_json::to_writer(&mut content, record).is_ok() {
+                content.push(b'\n');
+            }
+        }
+
+        let result = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)
+            .and_then(|mut file| file.write_all(&content));
+        if let Err(err) = &result {
+            log::warn!("Unable to append server status samples to {:?}: {}", self.path, err);
+        }
+
+        self.lines += records.len();
+        for record in records {
+            self.push(record);
+        }
+
+        result.is_ok()
+    }
+
+    fn retain(&mut self, func: impl Fn(&str) -> bool) {
+        self.samples.retain(|address, _| func(address));
+    }
+
+    // Rewrite the log once most of its lines are samples that have been dropped
+    fn compact_if_needed(&mut self) {
+        let retained: usize = self.samples.values().map(VecDeque::len).sum();
+        if self.lines <= retained.max(MAX_SAMPLES) * 2 {
+            return;
+        }
+
+        let mut content = Vec::new();
+        for (address, samples) in &self.samples {
+            for sample in samples {
+                let record = ServerStatusSampleRecord {
+                    address: address.clone(),
+                    sample: sample.clone(),
+                };
+                if serde_json::to_writer(&mut content, &record).is_ok() {
+                    content.push(b'\n');
+                }
+            }
+        }
+
+        match crate::write_safe(&self.path, &content) {
+            Ok(()) => self.lines = retained,
+            Err(err) => log::warn!("Unable to compact server status samples in {:?}: {}", self.path, err),
+        }
+    }
+}
+
+pub async fn poll_server_history(backend: Arc<BackendState>) {
+    let message = create_trends_message(backend.server_history.write().get(), &backend.server_history_samples.read());
+    backend.send.send(message);
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+
+        let poll = {
+            let mut config = backend.config.write();
+            let config = config.get();
+            !config.offline_mode && !config.dont_poll_server_history
+        };
+        if poll {
+            poll_all(&backend).await;
+        }
+    }
+}
+
+// Servers are tracked once they've been seen in any instance's servers.dat
+pub fn register_servers(backend: &BackendState, addresses: &[Arc<str>]) {
+    let mut server_history = backend.server_history.write();
+    if addresses.iter().all(|address| server_history.get().servers.contains_key(address)) {
+        return;
+    }
+
+    server_history.modify(|server_history| {
+        for address in addresses {
+            server_history.servers.entry(address.clone()).or_default();
+        }
+    });
+}
+
+pub fn set_favourite(backend: &BackendState, address: Arc<str>, favourite: bool) {
+    let mut server_history = backend.server_history.write();
+    server_history.modify(|server_history| {
+        server_history.servers.entry(address).or_default().favourite = favourite;
+    });
+    backend.send.send(create_trends_message(server_history.get(), &backend.server_history_samples.read()));
+}
+
+async fn poll_all(backend: &Arc<BackendState>) {
+    let addresses: Vec<Arc<str>> = backend.server_history.write().get().servers.keys().cloned().collect();
+    if addresses.is_empty() {
+        return;
+    }
+
+    let results: Vec<_> = futures::stream::iter(addresses).map(|address| {
+        let backend = backend.clone();
+        async move {
+            let result = backend.server_list_pinger.request_status_for_address(&address).await;
+            let time_unix_ms = chrono::Utc::now().timestamp_millis();
+            let motd: Option<Arc<str>> = result.as_ref().ok().map(|(status, _)| status.description.content.trim().into());
+            let sample = match result {
+                Ok((status, ping)) => ServerStatusSample {
+                    time_unix_ms,
+                    online: true,
+                    players: status.players.as_ref().map(|players| players.online),
+                    max_players: status.players.as_ref().map(|players| players.max),
+                    latency_ms: ping.map(|ping| ping.as_millis() as u32),
+                    version: status.version.as_ref().map(|version| version.name.clone()),
+                },
+                Err(_) => ServerStatusSample {
+                    time_unix_ms,
+                    online: false,
+                    players: None,
+                    max_players: None,
+                    latency_ms: None,
+                    version: None,
+                },
+            };
+            (address, sample, motd)
+        }
+    }).buffer_unordered(MAX_CONCURRENT_PINGS).collect().await;
+
+    let backend = backend.clone();
+    _ = tokio::task::spawn_blocking(move || record_results(&backend, results)).await;
+}
+
+fn record_results(backend: &BackendState, results: Vec<(Arc<str>, ServerStatusSample, Option<Arc<str>>)>) {
+    let mut notifications = Vec::new();
+
+    let mut server_history = backend.server_history.write();
+    let mut sample_log = backend.server_history_samples.write();
+
+    let mut servers = server_history.get().servers.clone();
+    let mut records = Vec::new();
+    for (address, sample, motd) in results {
+        // The server may have been removed while polling
+        let Some(entry) = servers.get_mut(&address) else {
+            continue;
+        };
+        let was_online = sample_log.samples(&address).and_then(VecDeque::back).map(|sample| sample.online);
+        record_sample(&address, entry, &sample, motd, was_online, &mut notifications);
+        records.push(ServerStatusSampleRecord { address, sample });
+    }
+    sample_log.append(records);
+
+    let now = chrono::Utc::now().timestamp_millis();
+    servers.retain(|address, entry| entry.favourite || !is_abandoned(sample_log.samples(address), now));
+    sample_log.retain(|address| servers.contains_key(address));
+    sample_log.compact_if_needed();
+
+    if servers != server_history.get().servers {
+        server_history.modify(|server_history| server_history.servers = servers);
+    }
+
+    for message in notifications {
+        backend.send.send(MessageToFrontend::AddNotification {
+            notification_type: BridgeNotificationType::Info,
+            message: message.into(),
+        });
+    }
+
+    backend.send.send(create_trends_message(server_history.get(), &sample_log));
+}
+
+fn record_sample(address: &str, entry: &mut ServerHistoryEntry, sample: &ServerStatusSample, motd: Option<Arc<str>>, was_online: Option<bool>, notifications: &mut Vec<String>) {
+    if entry.favourite {
+        if sample.online && was_online == Some(false) {
+            notifications.push(format!("{address} is back online"));
+        }
+        if let Some(motd) = &motd && let Some(old_motd) = &entry.motd && old_motd != motd {
+            notifications.push(format!("{address} changed its MOTD to: {motd}"));
+        }
+        if let Some(version) = &sample.version && let Some(old_version) = &entry.version && old_version != version {
+            notifications.push(format!("{address} changed its version from {old_version} to {version}"));
+        }
+    }
+
+    // Keep the last known MOTD and version while the server is offline
+    if sample.online {
+        entry.motd = motd;
+        entry.version = sample.version.clone();
+    }
+}
+
+// Servers that haven't been online for a week are probably gone or were removed from servers.dat
+fn is_abandoned(samples: Option<&VecDeque<ServerStatusSample>>, now: i64) -> bool {
+    let Some(samples) = samples else {
+        return false;
+    };
+    let Some(oldest) = samples.front() else {
+        return false;
+    };
+    now - oldest.time_unix_ms > WEEK_MS && !samples.iter().any(|sample| sample.online && now - sample.time_unix_ms <= WEEK_MS)
+}
+
+fn create_trends_message(server_history: &ServerHistory, sample_log: &ServerSampleLog) -> MessageToFrontend {
+    let now = chrono::Utc::now().timestamp_millis();
+    let empty = VecDeque::new();
+    let trends = server_history.servers.iter()
+        .map(|(address, entry)| create_trend(address, entry, sample_log.samples(address).unwrap_or(&empty), now))
+        .collect();
+    MessageToFrontend::ServerStatusTrendsUpdated { trends }
+}
+
+fn create_trend(address: &Arc<str>, entry: &ServerHistoryEntry, samples: &VecDeque<ServerStatusSample>, now: i64) -> ServerStatusTrend {
+    let day = || samples.iter().filter(move |sample| now - sample.time_unix_ms <= DAY_MS);
+    let week = || samples.iter().filter(move |sample| now - sample.time_unix_ms <= WEEK_MS);
+
+    let hourly_uptime = (0..24).rev().map(|hours_ago| {
+        let end = now - hours_ago * HOUR_MS;
+        let start = end - HOUR_MS;
+        uptime(samples.iter().filter(|sample| sample.time_unix_ms > start && sample.time_unix_ms <= end))
+    }).collect();
+
+    ServerStatusTrend {
+        address: address.clone(),
+        favourite: entry.favourite,
+        online: samples.back().map(|sample| sample.online),
+        uptime_day: uptime(day()),
+        uptime_week: uptime(week()),
+        average_players_day: average(day().filter_map(|sample| sample.players).map(|players| players as f32)),
+        average_ping_day: average(day().filter_map(|sample| sample.latency_ms).map(|latency| latency as f32))
+            .map(|latency| Duration::from_millis(latency as u64)),
+        version: entry.version.clone(),
+        hourly_uptime,
+    }
+}
+
+fn uptime<'a>(samples: impl Iterator<Item = &'a ServerStatusSample>) -> Option<f32> {
+    average(samples.map(|sample| if sample.online { 1.0 } else { 0.0 }))
+}
+
+fn average(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f32)
+    }
+}
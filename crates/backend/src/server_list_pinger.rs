@@ -115,14 +115,9 @@ impl ServerListPinger {
 
         let backend = backend.clone();
         tokio::spawn(async move {
-            let (host, port) = if let Some((host, port)) = server.split_once(':') {
-                let Ok(port) = port.parse::<u16>() else {
-                    backend.server_list_pinger.data.write().insert(key.clone(), PingEntry::Failed);
-                    return;
-                };
-                (host, Some(port))
-            } else {
-                (&*server, None)
+            let Some((host, port)) = split_address(&server) else {
+                backend.server_list_pinger.data.write().insert(key.clone(), PingEntry::Failed);
+                return;
             };
             let status = backend.server_list_pinger.request_status(host, port, 774).await;
             let Ok((status, ping)) = status else {
@@ -146,6 +141,14 @@ impl ServerListPinger {
         PingResult::Pinging
     }
 
+    // Used for background polling, where there's no instance to take the protocol version from
+    pub(crate) async fn request_status_for_address(&self, server: &str) -> std::io::Result<(ServerStatus, Option<Duration>)> {
+        let Some((host, port)) = split_address(server) else {
+            return Err(Error::new(ErrorKind::InvalidInput, "Invalid server address"));
+        };
+        self.request_status(host, port, FALLBACK_PROTOCOL_VERSION).await
+    }
+
     pub async fn request_status_as_string(&self, host: &str, port: Option<u16>, protocol: i32) -> std::io::Result<(String, Option<Duration>)> {
         let port = port.unwrap_or(MINECRAFT_PORT);
         let (host, port) = if port == MINECRAFT_PORT && let Some(result) = self.srv_lookup(host).await {
@@ -239,6 +242,14 @@ impl ServerListPinger {
     }
 }
 
+fn split_address(server: &str) -> Option<(&str, Option<u16>)> {
+    if let Some((host, port)) = server.split_once(':') {
+        Some((host, Some(port.parse::<u16>().ok()?)))
+    } else {
+        Some((server, None))
+    }
+}
+
 const VAR_INT_SECTION_BITS: u32 = 0x7F;
 const VAR_INT_SECTION_CONTINUE_BIT: u32 = 0x80;
 
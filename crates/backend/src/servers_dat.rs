@@ -0,0 +1,204 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::instance::{InstanceID, ServerEntryRef, ServerResourcePackPolicy};
+use nbt::{CompoundRefMut, NBT};
+
+use crate::BackendState;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ServersDatError {
+    #[error("Instance no longer exists")]
+    MissingInstance,
+    #[error("Can't modify servers while the instance is running")]
+    InstanceRunning,
+    #[error("Server no longer exists")]
+    MissingServer,
+    #[error("Server address can't be empty")]
+    EmptyAddress,
+    #[error("{0} is already in the server list")]
+    AlreadyExists(Arc<str>),
+    #[error("Unable to read servers.dat:\n{0}")]
+    InvalidServersDat(#[from] anyhow::Error),
+    #[error("I/O error:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+pub fn add_server(backend: &Arc<BackendState>, id: InstanceID, name: &str, ip: &str, resource_packs: ServerResourcePackPolicy) -> Result<(), ServersDatError> {
+    let ip = ip.trim();
+    if ip.is_empty() {
+        return Err(ServersDatError::EmptyAddress);
+    }
+
+    modify_servers(backend, id, |servers| {
+        let normalized = crate::syncing::normalize_server_ip(ip);
+        if servers.iter().any(|server| find_ip(server).is_some_and(|existing| crate::syncing::normalize_server_ip(existing) == normalized)) {
+            return Err(ServersDatError::AlreadyExists(ip.into()));
+        }
+
+        let mut server = NBT::new();
+        if let Some(mut compound) = server.as_compound_mut() {
+            set_fields(&mut compound, name, ip, resource_packs);
+        }
+        servers.push(server);
+        Ok(())
+    })
+}
+
+pub fn edit_server(backend: &Arc<BackendState>, id: InstanceID, server: &ServerEntryRef, name: &str, ip: &str, resource_packs: ServerResourcePackPolicy) -> Result<(), ServersDatError> {
+    let ip = ip.trim();
+    if ip.is_empty() {
+        return Err(ServersDatError::EmptyAddress);
+    }
+
+    modify_servers(backend, id, |servers| {
+        let index = file_index(servers, server)?;
+        if let Some(mut compound) = servers[index].as_compound_mut() {
+            set_fields(&mut compound, name, ip, resource_packs);
+        }
+        Ok(())
+    })
+}
+
+pub fn remove_server(backend: &Arc<BackendState>, id: InstanceID, server: &ServerEntryRef) -> Result<(), ServersDatError> {
+    modify_servers(backend, id, |servers| {
+        let index = file_index(servers, server)?;
+        servers.remove(index);
+        Ok(())
+    })
+}
+
+pub fn move_server(backend: &Arc<BackendState>, id: InstanceID, from: &ServerEntryRef, to: &ServerEntryRef) -> Result<(), ServersDatError> {
+    modify_servers(backend, id, |servers| {
+        let from = file_index(servers, from)?;
+        // Hidden entries stay where they are, the target is the position of the visible entry we're moving onto
+        let to = file_index(servers, to)?;
+        let server = servers.remove(from);
+        servers.insert(to, server);
+        Ok(())
+    })
+}
+
+pub fn set_resource_pack_policy(backend: &Arc<BackendState>, id: InstanceID, server: &ServerEntryRef, policy: ServerResourcePackPolicy) -> Result<(), ServersDatError> {
+    modify_servers(backend, id, |servers| {
+        let index = file_index(servers, server)?;
+        if let Some(mut compound) = servers[index].as_compound_mut() {
+            set_resource_pack_fields(&mut compound, policy);
+        }
+        Ok(())
+    })
+}
+
+pub fn resource_pack_policy(accept_textures: Option<i8>) -> ServerResourcePackPolicy {
+    match accept_textures {
+        None => ServerResourcePackPolicy::Prompt,
+        Some(0) => ServerResourcePackPolicy::Disabled,
+        Some(_) => ServerResourcePackPolicy::Enabled,
+    }
+}
+
+fn modify_servers(backend: &Arc<BackendState>, id: InstanceID, modify: impl FnOnce(&mut Vec<NBT>) -> Result<(), ServersDatError>) -> Result<(), ServersDatError> {
+    let server_dat_path = {
+        let mut instance_state = backend.instance_state.write();
+        let Some(instance) = instance_state.instances.get_mut(id) else {
+            return Err(ServersDatError::MissingInstance);
+        };
+        // The game writes its own copy of the server list when it exits
+        if !instance.processes.is_empty() {
+            return Err(ServersDatError::InstanceRunning);
+        }
+        instance.server_dat_path.clone()
+    };
+
+    let mut servers = read_servers(&server_dat_path)?;
+    modify(&mut servers)?;
+    write_servers(&server_dat_path, &servers)?;
+
+    if let Some(instance) = backend.instance_state.write().instances.get_mut(id) {
+        instance.mark_servers_dirty(backend, true);
+    }
+
+    Ok(())
+}
+
+fn read_servers(path: &Path) -> Result<Vec<NBT>, ServersDatError> {
+    let raw = match std::fs::read(path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let result = nbt::decode::read_named(&mut raw.as_slice())?;
+    let Some(servers) = result.as_compound().and_then(|root| root.find_list("servers", nbt::TAG_COMPOUND_ID)) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(servers.iter().filter_map(|server| server.as_compound().map(|server| server.clone_nbt())).collect())
+}
+
+fn write_servers(path: &Path, servers: &[NBT]) -> Result<(), ServersDatError> {
+    let mut result = NBT::new();
+    if let Some(mut root) = result.as_compound_mut() {
+        let mut list = root.create_list("servers", nbt::TAG_COMPOUND_ID);
+        for server in servers {
+            if let Some(server) = server.as_compound() {
+                server.clone_into(list.create_compound());
+            }
+        }
+    }
+    let bytes = nbt::encode::write_named(&result);
+
+    // servers.dat can be a symlink into the synced folder, replacing it would break syncing
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    crate::write_safe(&path, &bytes)?;
+    Ok(())
+}
+
+fn find_ip(server: &NBT) -> Option<&str> {
+    server.as_compound()?.find_string("ip").map(String::as_str)
+}
+
+// Indices match the entries shown by `load_servers_summary`, which skips hidden entries and entries without an address.
+// servers.dat may have changed since then (e.g. the game rewrote it), so the entry at the index must still have the expected address
+fn file_index(servers: &[NBT], server: &ServerEntryRef) -> Result<usize, ServersDatError> {
+    let visible: Vec<(usize, &str)> = servers.iter()
+        .enumerate()
+        .filter_map(|(index, server)| {
+            let compound = server.as_compound()?;
+            let hidden = compound.find_byte("hidden").is_some_and(|hidden| *hidden != 0);
+            if hidden {
+                return None;
+            }
+            Some((index, find_ip(server)?))
+        })
+        .collect();
+
+    if let Some((index, ip)) = visible.get(server.index) && *ip == &*server.ip {
+        return Ok(*index);
+    }
+
+    // The entry moved, fall back to looking it up by its address
+    visible.iter()
+        .find(|(_, ip)| *ip == &*server.ip)
+        .map(|(index, _)| *index)
+        .ok_or(ServersDatError::MissingServer)
+}
+
+fn set_fields(compound: &mut CompoundRefMut, name: &str, ip: &str, resource_packs: ServerResourcePackPolicy) {
+    let name = name.trim();
+    let name = if name.is_empty() { "Minecraft Server" } else { name };
+
+    compound.remove("name");
+    compound.insert_string("name", name.to_string());
+    compound.remove("ip");
+    compound.insert_string("ip", ip.to_string());
+    set_resource_pack_fields(compound, resource_packs);
+}
+
+fn set_resource_pack_fields(compound: &mut CompoundRefMut, policy: ServerResourcePackPolicy) {
+    compound.remove("acceptTextures");
+    match policy {
+        ServerResourcePackPolicy::Prompt => {},
+        ServerResourcePackPolicy::Enabled => compound.insert_byte("acceptTextures", 1),
+        ServerResourcePackPolicy::Disabled => compound.insert_byte("acceptTextures", 0),
+    }
+}
@@ -3,14 +3,40 @@ use std::{collections::BTreeMap, path::{Path, PathBuf}, sync::Arc, time::SystemT
 use bridge::{message::{SyncState, SyncTargetState}, safe_path::SafePath};
 use once_cell::sync::Lazy;
 use relative_path::PathExt;
-use rustc_hash::FxHashMap;
-use schema::backend_config::SyncTargets;
+use indexmap::IndexMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use schema::backend_config::{SyncMergeStrategy, SyncTargets};
 
 use crate::{directories::LauncherDirectories, BackendStateInstances};
 
-pub fn apply_to_instance(sync_targets: &SyncTargets, directories: &LauncherDirectories, dot_minecraft: Arc<Path>) {
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 64 && !name.contains(['/', '\\']) && SafePath::new(name).is_some()
+}
+
+pub fn synced_dir_for(profile: Option<&str>, directories: &LauncherDirectories) -> Option<PathBuf> {
+    match profile {
+        Some(profile) if is_valid_profile_name(profile) => Some(directories.sync_profiles_dir.join(profile)),
+        Some(_) => None,
+        None => Some(directories.synced_dir.to_path_buf()),
+    }
+}
+
+// The synced folders of every profile, links into any of them are managed by syncing
+fn all_synced_dirs(directories: &LauncherDirectories) -> Vec<PathBuf> {
+    let mut synced_dirs = vec![directories.synced_dir.to_path_buf()];
+    if let Ok(read_dir) = std::fs::read_dir(&directories.sync_profiles_dir) {
+        synced_dirs.extend(read_dir.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()));
+    }
+    synced_dirs
+}
+
+// `members` are the .minecraft folders of every instance using the same profile, files are merged from these
+pub fn apply_to_instance(sync_targets: &SyncTargets, synced_dir: &Path, members: &[Arc<Path>], directories: &LauncherDirectories, dot_minecraft: Arc<Path>) {
     _ = std::fs::create_dir_all(&dot_minecraft);
 
+    let all_synced_dirs = all_synced_dirs(directories);
+    let is_synced_link = |target: &Path| all_synced_dirs.iter().any(|synced_dir| target.starts_with(synced_dir));
+
     let mut dir_iterator = walkdir::WalkDir::new(&dot_minecraft).into_iter();
     while let Some(Ok(entry)) = dir_iterator.next() {
         if entry.file_type().is_dir() {
@@ -26,8 +52,7 @@ pub fn apply_to_instance(sync_targets: &SyncTargets, directories: &LauncherDirec
                 dir_iterator.skip_current_dir();
                 continue;
             };
-            let target_dir = safe_relative.to_path(&directories.synced_dir);
-            if !target_dir.is_dir() {
+            if !all_synced_dirs.iter().any(|synced_dir| safe_relative.to_path(synced_dir).is_dir()) {
                 dir_iterator.skip_current_dir();
                 continue;
             }
@@ -38,7 +63,7 @@ pub fn apply_to_instance(sync_targets: &SyncTargets, directories: &LauncherDirec
                     continue;
                 };
 
-                if target.starts_with(&directories.synced_dir) {
+                if is_synced_link(&target) {
                     dir_iterator.skip_current_dir();
                     _ = junction::delete(entry.path());
                     continue;
@@ -58,31 +83,49 @@ pub fn apply_to_instance(sync_targets: &SyncTargets, directories: &LauncherDirec
                 continue;
             };
 
-            if target.starts_with(&directories.synced_dir) {
+            if is_synced_link(&target) {
                 _ = std::fs::remove_file(entry.path());
             }
         }
     }
 
     for file_target in sync_targets.files.iter() {
-        if &**file_target == "options.txt" {
-            let fallback = &directories.synced_dir.join("fallback_options.txt");
-            let target = dot_minecraft.join("options.txt");
-            let combined = create_combined_options_txt(fallback, &target, directories);
-            _ = crate::write_safe(&fallback, combined.as_bytes());
-            _ = crate::write_safe(&target, combined.as_bytes());
-        } else if let Some(path) = SafePath::new(file_target) {
-            if let Some(latest) = find_latest(&path, directories) {
-                let target = path.to_path(&dot_minecraft);
-                if latest != target {
+        let Some(path) = SafePath::new(file_target) else {
+            log::warn!("Skipping file sync target because it is not a safe path: {}", file_target);
+            continue;
+        };
+
+        let target = path.to_path(&dot_minecraft);
+
+        match sync_targets.strategy_for(file_target) {
+            SyncMergeStrategy::Latest => {
+                if let Some(latest) = find_latest(&path, members) && latest != target {
                     if let Some(parent) = target.parent() {
                         _ = std::fs::create_dir_all(parent);
                     }
                     _ = std::fs::copy(latest, target);
                 }
-            }
-        } else {
-            log::warn!("Skipping file sync target because it is not a safe path: {}", file_target);
+            },
+            SyncMergeStrategy::KeyValue => {
+                if &**file_target == "options.txt" {
+                    let fallback = &synced_dir.join("fallback_options.txt");
+                    let combined = create_combined_options_txt(fallback, &target, &path, members);
+                    _ = crate::write_safe(fallback, combined.as_bytes());
+                    _ = crate::write_safe(&target, combined.as_bytes());
+                } else if let Some(combined) = create_combined_key_value(&path, key_value_separator(file_target), members) {
+                    _ = crate::write_safe(&target, combined.as_bytes());
+                }
+            },
+            SyncMergeStrategy::ServersUnion => {
+                if let Some(combined) = create_combined_servers_dat(&path, members) {
+                    _ = crate::write_safe(&target, &combined);
+                }
+            },
+            SyncMergeStrategy::JsonDeepMerge => {
+                if let Some(combined) = create_combined_json(&path, members) {
+                    _ = crate::write_safe(&target, &combined);
+                }
+            },
         }
     }
 
@@ -92,7 +135,7 @@ pub fn apply_to_instance(sync_targets: &SyncTargets, directories: &LauncherDirec
             continue;
         };
 
-        let target_dir = path.to_path(&directories.synced_dir);
+        let target_dir = path.to_path(synced_dir);
         let path = path.to_path(&dot_minecraft);
 
         if !path.exists() || std::fs::remove_dir(&path).is_ok() {
@@ -105,18 +148,12 @@ pub fn apply_to_instance(sync_targets: &SyncTargets, directories: &LauncherDirec
     }
 }
 
-fn find_latest(filename: &SafePath, directories: &LauncherDirectories) -> Option<PathBuf> {
+fn find_latest(filename: &SafePath, members: &[Arc<Path>]) -> Option<PathBuf> {
     let mut latest_time = SystemTime::UNIX_EPOCH;
     let mut latest_path = None;
 
-    let read_dir = std::fs::read_dir(&directories.instances_dir).ok()?;
-
-    for entry in read_dir {
-        let Ok(entry) = entry else {
-            continue;
-        };
-
-        let path = filename.to_path(&entry.path().join(".minecraft"));
+    for dot_minecraft in members {
+        let path = filename.to_path(dot_minecraft);
 
         if let Ok(metadata) = std::fs::metadata(&path) {
             let mut time = SystemTime::UNIX_EPOCH;
@@ -138,41 +175,39 @@ fn find_latest(filename: &SafePath, directories: &LauncherDirectories) -> Option
     latest_path
 }
 
-fn create_combined_options_txt(fallback: &Path, current: &Path, directories: &LauncherDirectories) -> String {
-    let mut values = read_options_txt(fallback);
-
-    let Ok(read_dir) = std::fs::read_dir(&directories.instances_dir) else {
-        return create_options_txt(values);
-    };
-
+// Returns every existing copy of the file across the members, oldest first
+fn find_all_by_time(filename: &SafePath, members: &[Arc<Path>]) -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
-    for entry in read_dir {
-        let Ok(entry) = entry else {
+    for dot_minecraft in members {
+        let path = filename.to_path(dot_minecraft);
+
+        let Ok(metadata) = std::fs::metadata(&path) else {
             continue;
         };
-
-        let mut path = entry.path();
-        path.push(".minecraft");
-        path.push("options.txt");
+        if !metadata.is_file() {
+            continue;
+        }
 
         let mut time = SystemTime::UNIX_EPOCH;
-
-        if let Ok(metadata) = std::fs::metadata(&path) {
-            if let Ok(created) = metadata.created() {
-                time = time.max(created);
-            }
-            if let Ok(modified) = metadata.modified() {
-                time = time.max(modified);
-            }
+        if let Ok(created) = metadata.created() {
+            time = time.max(created);
+        }
+        if let Ok(modified) = metadata.modified() {
+            time = time.max(modified);
         }
 
         paths.push((time, path));
     }
 
     paths.sort_by_key(|(time, _)| *time);
+    paths.into_iter().map(|(_, path)| path).collect()
+}
+
+fn create_combined_options_txt(fallback: &Path, current: &Path, filename: &SafePath, members: &[Arc<Path>]) -> String {
+    let mut values = read_options_txt(fallback);
 
-    for (_, path) in paths {
+    for path in find_all_by_time(filename, members) {
         let mut new_values = read_options_txt(&path);
 
         if path != current {
@@ -188,6 +223,149 @@ fn create_combined_options_txt(fallback: &Path, current: &Path, directories: &La
     create_options_txt(values)
 }
 
+fn key_value_separator(filename: &str) -> char {
+    if filename.ends_with(".properties") || filename.ends_with(".ini") {
+        '='
+    } else {
+        ':'
+    }
+}
+
+fn create_combined_key_value(filename: &SafePath, separator: char, members: &[Arc<Path>]) -> Option<String> {
+    let paths = find_all_by_time(filename, members);
+    if paths.is_empty() {
+        return None;
+    }
+
+    // Newer files override the values of older ones
+    let mut values: IndexMap<String, String> = IndexMap::new();
+    let mut base_lines = Vec::new();
+
+    for path in paths {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        base_lines.clear();
+        for line in content.lines() {
+            let line = line.trim_ascii();
+            if line.starts_with('#') || line.starts_with('!') {
+                base_lines.push(line.to_string());
+            } else if let Some((key, value)) = line.split_once(separator) {
+                base_lines.push(line.to_string());
+                values.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+
+    // The newest file is used as the base so its comments and key order are kept, keys only found in older files go at the end
+    let mut combined = String::new();
+    for line in base_lines {
+        if let Some((key, _)) = line.split_once(separator) && !line.starts_with('#') && !line.starts_with('!') {
+            values.shift_remove(key);
+        }
+        combined.push_str(&line);
+        combined.push('\n');
+    }
+    for (key, value) in values {
+        combined.push_str(&key);
+        combined.push(separator);
+        combined.push_str(&value);
+        combined.push('\n');
+    }
+    Some(combined)
+}
+
+fn create_combined_servers_dat(filename: &SafePath, members: &[Arc<Path>]) -> Option<Vec<u8>> {
+    let mut files = Vec::new();
+
+    // Newest first, so the newest entry for a server wins and its list order is kept
+    for path in find_all_by_time(filename, members).into_iter().rev() {
+        let Ok(raw) = std::fs::read(&path) else {
+            continue;
+        };
+        match nbt::decode::read_named(&mut raw.as_slice()) {
+            Ok(nbt) => files.push(nbt),
+            Err(error) => log::warn!("Skipping {:?} while merging servers because it could not be read: {error}", path),
+        }
+    }
+
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut combined = nbt::NBT::new();
+    let mut seen = FxHashSet::default();
+
+    let mut root = combined.as_compound_mut()?;
+    let mut combined_servers = root.create_list("servers", nbt::TAG_COMPOUND_ID);
+    for file in &files {
+        let Some(servers) = file.as_compound().and_then(|root| root.find_list("servers", nbt::TAG_COMPOUND_ID)) else {
+            continue;
+        };
+        for server in servers.iter() {
+            let Some(server) = server.as_compound() else {
+                continue;
+            };
+            let Some(ip) = server.find_string("ip") else {
+                continue;
+            };
+            if seen.insert(normalize_server_ip(ip)) {
+                server.clone_into(combined_servers.create_compound());
+            }
+        }
+    }
+
+    Some(nbt::encode::write_named(&combined))
+}
+
+pub(crate) fn normalize_server_ip(ip: &str) -> String {
+    let ip = ip.trim().to_ascii_lowercase();
+    match ip.strip_suffix(":25565") {
+        Some(without_port) => without_port.to_string(),
+        None => ip,
+    }
+}
+
+fn create_combined_json(filename: &SafePath, members: &[Arc<Path>]) -> Option<Vec<u8>> {
+    let mut combined: Option<serde_json::Value> = None;
+
+    for path in find_all_by_time(filename, members) {
+        let Ok(raw) = std::fs::read(&path) else {
+            continue;
+        };
+        let value = match serde_json::from_slice::<serde_json::Value>(&raw) {
+            Ok(value) => value,
+            Err(error) => {
+                log::warn!("Skipping {:?} while merging json because it could not be parsed: {error}", path);
+                continue;
+            },
+        };
+        match &mut combined {
+            Some(combined) => deep_merge_json(combined, value),
+            None => combined = Some(value),
+        }
+    }
+
+    serde_json::to_vec_pretty(&combined?).ok()
+}
+
+// Objects are merged recursively, any other value is replaced
+fn deep_merge_json(into: &mut serde_json::Value, from: serde_json::Value) {
+    match (into, from) {
+        (serde_json::Value::Object(into), serde_json::Value::Object(from)) => {
+            for (key, value) in from {
+                match into.get_mut(&key) {
+                    Some(existing) => deep_merge_json(existing, value),
+                    None => {
+                        into.insert(key, value);
+                    },
+                }
+            }
+        },
+        (into, from) => *into = from,
+    }
+}
+
 fn create_options_txt(values: FxHashMap<String, String>) -> String {
     let mut options = String::new();
 
@@ -216,15 +394,20 @@ fn read_options_txt(path: &Path) -> FxHashMap<String, String> {
     values
 }
 
-pub fn get_sync_state(sync_targets: &SyncTargets, instances: &mut BackendStateInstances, directories: &LauncherDirectories) -> std::io::Result<SyncState> {
-    let mut dot_minecraft_paths = Vec::new();
+pub fn profile_members(profile: Option<&str>, instances: &mut BackendStateInstances) -> Vec<Arc<Path>> {
+    let mut members = Vec::new();
 
     for instance in instances.instances.iter_mut() {
-        if !instance.configuration.get().disable_file_syncing {
-            dot_minecraft_paths.push(instance.dot_minecraft_path.clone());
+        let configuration = instance.configuration.get();
+        if !configuration.disable_file_syncing && configuration.sync_profile.as_deref() == profile {
+            members.push(instance.dot_minecraft_path.clone());
         }
     }
 
+    members
+}
+
+pub fn get_sync_state(sync_targets: &SyncTargets, profile: Option<Arc<str>>, profiles: Arc<[Arc<str>]>, synced_dir: Arc<Path>, dot_minecraft_paths: &[Arc<Path>]) -> std::io::Result<SyncState> {
     let total = dot_minecraft_paths.len();
     let mut entries = BTreeMap::default();
 
@@ -232,7 +415,7 @@ pub fn get_sync_state(sync_targets: &SyncTargets, instances: &mut BackendStateIn
         if let Some(safe_file_target) = SafePath::new(file_target) {
             let mut cannot_sync_count = 0;
 
-            for dot_minecraft in &dot_minecraft_paths {
+            for dot_minecraft in dot_minecraft_paths {
                 let target = safe_file_target.to_path(dot_minecraft);
                 if target.is_dir() {
                     cannot_sync_count += 1;
@@ -244,6 +427,7 @@ pub fn get_sync_state(sync_targets: &SyncTargets, instances: &mut BackendStateIn
                 is_file: true,
                 sync_count: total.saturating_sub(cannot_sync_count),
                 cannot_sync_count,
+                strategy: sync_targets.strategy_for(file_target),
             });
         } else {
             entries.insert(file_target.clone(), SyncTargetState {
@@ -251,6 +435,7 @@ pub fn get_sync_state(sync_targets: &SyncTargets, instances: &mut BackendStateIn
                 is_file: true,
                 sync_count: 0,
                 cannot_sync_count: total,
+                strategy: sync_targets.strategy_for(file_target),
             });
         }
     }
@@ -272,16 +457,17 @@ pub fn get_sync_state(sync_targets: &SyncTargets, instances: &mut BackendStateIn
                 is_file: false,
                 sync_count: 0,
                 cannot_sync_count: total,
+                strategy: SyncMergeStrategy::Latest,
             });
             continue;
         };
 
-        let target_dir = safe_path.to_path(&directories.synced_dir);
+        let target_dir = safe_path.to_path(&synced_dir);
 
         let mut sync_count = 0;
         let mut cannot_sync_count = 0;
 
-        for dot_minecraft in &dot_minecraft_paths {
+        for dot_minecraft in dot_minecraft_paths {
             let path = safe_path.to_path(dot_minecraft);
 
             if linking::is_targeting(&target_dir, &path) {
@@ -296,11 +482,14 @@ pub fn get_sync_state(sync_targets: &SyncTargets, instances: &mut BackendStateIn
             is_file: false,
             sync_count,
             cannot_sync_count,
+            strategy: SyncMergeStrategy::Latest,
         });
     }
 
     Ok(SyncState {
-        sync_folder: directories.synced_dir.clone(),
+        profile,
+        profiles,
+        sync_folder: synced_dir,
         targets: entries,
         total_count: total,
     })
@@ -334,7 +523,7 @@ static DEFAULT_FOLDERS: Lazy<Vec<Arc<str>>> = Lazy::new(|| {
     ].into_iter().map(Arc::from).collect()
 });
 
-pub fn enable_all(name: &str, is_file: bool, instances: &mut BackendStateInstances, directories: &LauncherDirectories) -> std::io::Result<bool> {
+pub fn enable_all(name: &str, is_file: bool, synced_dir: &Path, members: &[Arc<Path>]) -> std::io::Result<bool> {
     if is_file {
         return Ok(true);
     }
@@ -344,14 +533,9 @@ pub fn enable_all(name: &str, is_file: bool, instances: &mut BackendStateInstanc
         return Ok(false);
     };
 
-    let mut paths = Vec::new();
-    for instance in instances.instances.iter_mut() {
-        if !instance.configuration.get().disable_file_syncing {
-            paths.push(safe_path.to_path(&instance.dot_minecraft_path));
-        }
-    }
+    let mut paths: Vec<PathBuf> = members.iter().map(|dot_minecraft| safe_path.to_path(dot_minecraft)).collect();
 
-    let target_dir = safe_path.to_path(&directories.synced_dir);
+    let target_dir = safe_path.to_path(synced_dir);
 
     // Exclude links that already point to target_dir
     paths.retain(|path| {
@@ -375,7 +559,7 @@ pub fn enable_all(name: &str, is_file: bool, instances: &mut BackendStateInstanc
     Ok(true)
 }
 
-pub fn disable_all(name: &str, is_file: bool, directories: &LauncherDirectories) -> std::io::Result<()> {
+pub fn disable_all(name: &str, is_file: bool, synced_dir: &Path, directories: &LauncherDirectories) -> std::io::Result<()> {
     if is_file {
         return Ok(());
     }
@@ -391,7 +575,7 @@ pub fn disable_all(name: &str, is_file: bool, directories: &LauncherDirectories)
         paths.push(safe_path.to_path(&entry?.path().join(".minecraft")));
     }
 
-    let target_dir = safe_path.to_path(&directories.synced_dir);
+    let target_dir = safe_path.to_path(synced_dir);
 
     for path in &paths {
         linking::unlink_dir_if_targeting(&target_dir, path)?;
@@ -457,3 +641,118 @@ mod linking {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{path::{Path, PathBuf}, sync::Arc, time::{Duration, SystemTime}};
+
+    use bridge::safe_path::SafePath;
+    use schema::backend_config::SyncMergeStrategy;
+
+    use super::{create_combined_json, create_combined_key_value, create_combined_servers_dat, key_value_separator};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pandora-syncing-test-{name}-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Members are written with increasing modification times, so later members are newer
+    fn create_members(dir: &Path, filename: &str, contents: &[Vec<u8>]) -> Vec<Arc<Path>> {
+        contents.iter().enumerate().map(|(index, content)| {
+            let dot_minecraft = dir.join(format!("instance{index}"));
+            std::fs::create_dir_all(&dot_minecraft).unwrap();
+            let path = dot_minecraft.join(filename);
+            std::fs::write(&path, content).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::now() + Duration::from_secs(3600 * (index as u64 + 1))).unwrap();
+            dot_minecraft.into()
+        }).collect()
+    }
+
+    fn create_servers_dat(servers: &[(&str, &str)]) -> Vec<u8> {
+        let mut nbt = nbt::NBT::new();
+        {
+            let mut root = nbt.as_compound_mut().unwrap();
+            let mut list = root.create_list("servers", nbt::TAG_COMPOUND_ID);
+            for (name, ip) in servers {
+                let mut server = list.create_compound();
+                server.insert_string("name", name.to_string());
+                server.insert_string("ip", ip.to_string());
+            }
+        }
+        nbt::encode::write_named(&nbt)
+    }
+
+    #[test]
+    fn test_default_strategies() {
+        assert_eq!(SyncMergeStrategy::default_for("options.txt"), SyncMergeStrategy::KeyValue);
+        // Merging would bring back servers that were removed
+        assert_eq!(SyncMergeStrategy::default_for("servers.dat"), SyncMergeStrategy::Latest);
+        assert_eq!(SyncMergeStrategy::default_for("config/sodium-options.json"), SyncMergeStrategy::Latest);
+    }
+
+    #[test]
+    fn test_merge_key_value() {
+        let dir = temp_dir("key-value");
+        let members = create_members(&dir, "server.properties", &[
+            b"# old\na=1\nb=2\n".to_vec(),
+            b"b=3\nc=4\n! comment\n".to_vec(),
+        ]);
+
+        let filename = SafePath::new("server.properties").unwrap();
+        let combined = create_combined_key_value(&filename, key_value_separator("server.properties"), &members);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(combined.as_deref(), Some("b=3\nc=4\n! comment\na=1\n"));
+    }
+
+    #[test]
+    fn test_merge_servers() {
+        let dir = temp_dir("servers");
+        let members = create_members(&dir, "servers.dat", &[
+            create_servers_dat(&[("Old name", "play.example.com"), ("Other", "other.example.com")]),
+            create_servers_dat(&[("New name", "Play.Example.com:25565"), ("Third", "third.example.com")]),
+        ]);
+
+        let filename = SafePath::new("servers.dat").unwrap();
+        let combined = create_combined_servers_dat(&filename, &members).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let nbt = nbt::decode::read_named(&mut combined.as_slice()).unwrap();
+        let servers = nbt.as_compound().unwrap().find_list("servers", nbt::TAG_COMPOUND_ID).unwrap();
+        let servers: Vec<(String, String)> = servers.iter().map(|server| {
+            let server = server.as_compound().unwrap();
+            (server.find_string("name").unwrap().to_string(), server.find_string("ip").unwrap().to_string())
+        }).collect();
+
+        assert_eq!(servers, [
+            ("New name".to_string(), "Play.Example.com:25565".to_string()),
+            ("Third".to_string(), "third.example.com".to_string()),
+            ("Other".to_string(), "other.example.com".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_merge_json() {
+        let dir = temp_dir("json");
+        let members = create_members(&dir, "settings.json", &[
+            br#"{"a": {"x": 1, "y": 2}, "b": [1, 2], "c": "old"}"#.to_vec(),
+            b"not json".to_vec(),
+            br#"{"a": {"y": 3}, "b": [3], "d": null}"#.to_vec(),
+        ]);
+
+        let filename = SafePath::new("settings.json").unwrap();
+        let combined = create_combined_json(&filename, &members).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let combined: serde_json::Value = serde_json::from_slice(&combined).unwrap();
+        assert_eq!(combined, serde_json::json!({
+            "a": { "x": 1, "y": 3 },
+            "b": [3],
+            "c": "old",
+            "d": null,
+        }));
+    }
+}
@@ -0,0 +1,400 @@
+use std::{path::{Path, PathBuf}, sync::Arc, time::{Duration, SystemTime}};
+
+use bridge::{instance::{InstanceID, WorldBackupSummary}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}, safe_path::SafePath};
+use rc_zip_sync::ReadZip;
+use schema::instance::{InstanceWorldBackupConfiguration, WorldBackupSchedule};
+
+use crate::BackendState;
+
+const BACKUP_TIME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+const AUTOMATIC_SUFFIX: &str = "_auto";
+
+#[derive(thiserror::Error, Debug)]
+pub enum WorldBackupError {
+    #[error("Instance no longer exists")]
+    MissingInstance,
+    #[error("Can't restore a world while the instance is running")]
+    InstanceRunning,
+    #[error("World doesn't exist")]
+    MissingWorld,
+    #[error("Backup doesn't exist")]
+    MissingBackup,
+    #[error("Backup is not a valid zip:\n{0}")]
+    ZipError(#[from] rc_zip_sync::rc_zip::error::Error),
+    #[error("I/O error:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+struct BackupSource {
+    root_path: Arc<Path>,
+    saves_path: Arc<Path>,
+    running: bool,
+    configuration: InstanceWorldBackupConfiguration,
+}
+
+impl BackupSource {
+    fn read(backend: &BackendState, id: InstanceID) -> Result<Self, WorldBackupError> {
+        let mut instance_state = backend.instance_state.write();
+        let Some(instance) = instance_state.instances.get_mut(id) else {
+            return Err(WorldBackupError::MissingInstance);
+        };
+
+        Ok(Self {
+            root_path: instance.root_path.clone(),
+            saves_path: instance.saves_path.clone(),
+            running: !instance.processes.is_empty(),
+            configuration: instance.configuration.get().world_backups.unwrap_or_default(),
+        })
+    }
+}
+
+fn world_backups_dir(root_path: &Path, world: &str) -> PathBuf {
+    root_path.join("backups").join(world)
+}
+
+pub async fn backup_world(backend: &Arc<BackendState>, id: InstanceID, world: Arc<str>, modal_action: ModalAction) {
+    let result = try_backup_world(backend, id, &world, &modal_action).await;
+
+    match result {
+        Ok(path) => {
+            backend.send.send_success(format!("Backed up {world} to {}", path.display()));
+        },
+        Err(err) => {
+            log::error!("Error backing up world {world}: {err}");
+            modal_action.set_error_message(format!("{err}").into());
+        },
+    }
+    modal_action.set_finished();
+}
+
+async fn try_backup_world(backend: &Arc<BackendState>, id: InstanceID, world: &str, modal_action: &ModalAction) -> Result<PathBuf, WorldBackupError> {
+    if !crate::is_single_component_path_str(world) {
+        return Err(WorldBackupError::MissingWorld);
+    }
+
+    let source = BackupSource::read(backend, id)?;
+
+    let tracker = ProgressTracker::new(format!("Backing up {world}").into(), backend.send.clone());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let world = world.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        write_backup(&source.root_path, &source.saves_path, &world, false)
+    }).await.unwrap();
+
+    tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+    tracker.notify();
+
+    result
+}
+
+// Called before launching, backs up every world that is due according to the instance's schedule
+pub async fn backup_before_launch(backend: &Arc<BackendState>, id: InstanceID, modal_action: &ModalAction) {
+    let source = match BackupSource::read(backend, id) {
+        Ok(source) => source,
+        Err(_) => return,
+    };
+    if !source.configuration.enabled {
+        return;
+    }
+
+    let tracker = ProgressTracker::new("Backing up worlds".into(), backend.send.clone());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let tracker2 = tracker.clone();
+    let errors = tokio::task::spawn_blocking(move || {
+        let worlds = list_worlds(&source.saves_path);
+        tracker2.set_total(worlds.len());
+        tracker2.notify();
+
+        let mut errors = Vec::new();
+        for world in worlds {
+            if is_backup_due(&source.root_path, &source.saves_path, &world, source.configuration.schedule) {
+                if let Err(err) = write_backup(&source.root_path, &source.saves_path, &world, true) {
+                    errors.push(format!("{world}: {err}"));
+                }
+                prune_backups(&source.root_path, &world, source.configuration.keep_last as usize);
+            }
+            tracker2.add_count(1);
+            tracker2.notify();
+        }
+        errors
+    }).await.unwrap();
+
+    tracker.set_finished(ProgressTrackerFinishType::from_err(!errors.is_empty()));
+    tracker.notify();
+
+    if !errors.is_empty() {
+        log::error!("Unable to back up worlds before launch:\n{}", errors.join("\n"));
+        backend.send.send_error(format!("Unable to back up worlds before launch:\n{}", errors.join("\n")));
+    }
+}
+
+fn list_worlds(saves_path: &Path) -> Vec<String> {
+    let Ok(read_dir) = std::fs::read_dir(saves_path) else {
+        return Vec::new();
+    };
+
+    let mut worlds = Vec::new();
+    for entry in read_dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.path().join("level.dat").is_file() {
+            continue;
+        }
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        worlds.push(name);
+    }
+    worlds
+}
+
+fn is_backup_due(root_path: &Path, saves_path: &Path, world: &str, schedule: WorldBackupSchedule) -> bool {
+    let backups = list_backups_in(&world_backups_dir(root_path, world));
+    let Some(latest) = backups.first() else {
+        return true;
+    };
+
+    // The world hasn't been saved since the last backup, so there's nothing new to back up
+    let level_modified = std::fs::metadata(saves_path.join(world).join("level.dat")).and_then(|metadata| metadata.modified());
+    if let Ok(level_modified) = level_modified && let Ok(since_epoch) = level_modified.duration_since(SystemTime::UNIX_EPOCH)
+        && (since_epoch.as_millis() as i64) < latest.created
+    {
+        return false;
+    }
+
+    let Some(latest_automatic) = backups.iter().find(|backup| backup.automatic) else {
+        return true;
+    };
+    let interval = match schedule {
+        WorldBackupSchedule::EveryLaunch => return true,
+        WorldBackupSchedule::Daily => Duration::from_secs(60 * 60 * 24),
+        WorldBackupSchedule::Weekly => Duration::from_secs(60 * 60 * 24 * 7),
+    };
+    chrono::Utc::now().timestamp_millis() - latest_automatic.created >= interval.as_millis() as i64
+}
+
+fn write_backup(root_path: &Path, saves_path: &Path, world: &str, automatic: bool) -> Result<PathBuf, WorldBackupError> {
+    let world_path = saves_path.join(world);
+    if !world_path.join("level.dat").is_file() {
+        return Err(WorldBackupError::MissingWorld);
+    }
+
+    let backup_path = unique_backup_path(&world_backups_dir(root_path, world), automatic);
+
+    crate::zip_writer::write_zip_safe(&backup_path, |zip| {
+        for entry in walkdir::WalkDir::new(&world_path) {
+            let entry = entry.map_err(std::io::Error::other)?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            // Held open by the game while the world is loaded, and not needed to restore it
+            if entry.file_name() == "session.lock" {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(&world_path) else {
+                continue;
+            };
+            let Some(relative) = relative.to_str() else {
+                log::warn!("Skipping non-utf8 path {:?} during world backup", entry.path());
+                continue;
+            };
+            zip.write_file_from_path(&format!("{world}/{}", relative.replace('\\', "/")), entry.path())?;
+        }
+        Ok(())
+    })?;
+
+    Ok(backup_path)
+}
+
+// Several backups can be taken within the same second (e.g. restoring backs up the current world first), so a counter is added when needed
+fn unique_backup_path(backups_dir: &Path, automatic: bool) -> PathBuf {
+    let time = chrono::Local::now().format(BACKUP_TIME_FORMAT).to_string();
+    let suffix = if automatic { AUTOMATIC_SUFFIX } else { "" };
+
+    let mut path = backups_dir.join(format!("{time}{suffix}.zip"));
+    let mut counter = 2;
+    while path.exists() || path.with_added_extension("new").exists() {
+        path = backups_dir.join(format!("{time}_{counter}{suffix}.zip"));
+        counter += 1;
+    }
+    path
+}
+
+// Only automatic backups are pruned, manual backups are kept until the user deletes them
+fn prune_backups(root_path: &Path, world: &str, keep_last: usize) {
+    let backups_dir = world_backups_dir(root_path, world);
+    let automatic = list_backups_in(&backups_dir).into_iter().filter(|backup| backup.automatic);
+    for backup in automatic.skip(keep_last.max(1)) {
+        if let Err(err) = std::fs::remove_file(backups_dir.join(&*backup.name)) {
+            log::warn!("Unable to remove old world backup {}: {err}", backup.name);
+        }
+    }
+}
+
+pub fn list_backups(root_path: &Path, world: &str) -> Vec<WorldBackupSummary> {
+    if !crate::is_single_component_path_str(world) {
+        return Vec::new();
+    }
+    list_backups_in(&world_backups_dir(root_path, world))
+}
+
+// Returns the backups in the folder, newest first
+fn list_backups_in(backups_dir: &Path) -> Vec<WorldBackupSummary> {
+    let Ok(read_dir) = std::fs::read_dir(backups_dir) else {
+        return Vec::new();
+    };
+
+    let mut backups = Vec::new();
+    for entry in read_dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        let Some(stem) = name.strip_suffix(".zip") else {
+            continue;
+        };
+        let (stem, automatic) = match stem.strip_suffix(AUTOMATIC_SUFFIX) {
+            Some(stem) => (stem, true),
+            None => (stem, false),
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        let (time, counter) = stem.rsplit_once('_')
+            .and_then(|(time, counter)| Some((time, counter.parse::<u32>().ok()?)))
+            .unwrap_or((stem, 1));
+
+        let created = chrono::NaiveDateTime::parse_from_str(time, BACKUP_TIME_FORMAT).ok()
+            .and_then(|time| time.and_local_timezone(chrono::Local).earliest())
+            .map(|time| time.timestamp_millis())
+            .or_else(|| {
+                let modified = metadata.modified().ok()?;
+                Some(modified.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_millis() as i64)
+            })
+            .unwrap_or_default();
+
+        backups.push((WorldBackupSummary {
+            name: name.into(),
+            created,
+            size: metadata.len(),
+            automatic,
+        }, counter));
+    }
+
+    // The counter orders backups taken within the same second
+    backups.sort_by_key(|(backup, counter)| std::cmp::Reverse((backup.created, *counter)));
+    backups.into_iter().map(|(backup, _)| backup).collect()
+}
+
+pub async fn restore_backup(backend: &Arc<BackendState>, id: InstanceID, world: Arc<str>, backup: Arc<str>, modal_action: ModalAction) {
+    let result = try_restore_backup(backend, id, &world, &backup, &modal_action).await;
+
+    match result {
+        Ok(()) => {
+            backend.send.send_success(format!("Restored {world} from {backup}"));
+        },
+        Err(err) => {
+            log::error!("Error restoring world backup {backup}: {err}");
+            modal_action.set_error_message(format!("{err}").into());
+        },
+    }
+    modal_action.set_finished();
+}
+
+async fn try_restore_backup(backend: &Arc<BackendState>, id: InstanceID, world: &str, backup: &str, modal_action: &ModalAction) -> Result<(), WorldBackupError> {
+    if !crate::is_single_component_path_str(world) {
+        return Err(WorldBackupError::MissingWorld);
+    }
+    if !crate::is_single_component_path_str(backup) {
+        return Err(WorldBackupError::MissingBackup);
+    }
+
+    let source = BackupSource::read(backend, id)?;
+    if source.running {
+        return Err(WorldBackupError::InstanceRunning);
+    }
+
+    let backup_path = world_backups_dir(&source.root_path, world).join(backup);
+    if !backup_path.is_file() {
+        return Err(WorldBackupError::MissingBackup);
+    }
+
+    let tracker = ProgressTracker::new(format!("Restoring {world}").into(), backend.send.clone());
+    tracker.notify();
+    modal_action.trackers.push(tracker.clone());
+
+    let world = world.to_string();
+    let tracker2 = tracker.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let world_path = source.saves_path.join(&world);
+
+        // Keep the current state of the world, in case the wrong backup was picked
+        if world_path.join("level.dat").is_file() {
+            write_backup(&source.root_path, &source.saves_path, &world, false)?;
+        }
+
+        let temp_path = source.root_path.join("backups").join(format!(".restore-{world}"));
+        if temp_path.exists() {
+            std::fs::remove_dir_all(&temp_path)?;
+        }
+
+        let result = extract_backup(&backup_path, &temp_path, &tracker2);
+        if let Err(err) = result {
+            _ = std::fs::remove_dir_all(&temp_path);
+            return Err(err);
+        }
+
+        if world_path.exists() {
+            std::fs::remove_dir_all(&world_path)?;
+        }
+        std::fs::create_dir_all(&source.saves_path)?;
+        std::fs::rename(&temp_path, &world_path)?;
+        Ok(())
+    }).await.unwrap();
+
+    tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+    tracker.notify();
+
+    result
+}
+
+// Backups contain a single top-level folder named after the world, its contents are extracted into the target
+fn extract_backup(backup_path: &Path, target: &Path, tracker: &ProgressTracker) -> Result<(), WorldBackupError> {
+    let file = std::fs::File::open(backup_path)?;
+    let archive = file.read_zip()?;
+
+    let entries: Vec<_> = archive.entries().filter(|entry| entry.kind() == rc_zip_sync::rc_zip::EntryKind::File).collect();
+    tracker.set_total(entries.len());
+    tracker.notify();
+
+    for entry in entries {
+        let Some(path) = SafePath::new(&entry.name) else {
+            continue;
+        };
+        let Some((_, relative)) = path.as_str().split_once('/') else {
+            continue;
+        };
+        let Some(relative) = SafePath::new(relative) else {
+            continue;
+        };
+
+        let destination = relative.to_path(target);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut entry.reader(), &mut std::fs::File::create(&destination)?)?;
+
+        tracker.add_count(1);
+        tracker.notify();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,487 @@
+use std::{io::{BufReader, Error, ErrorKind, Read, Write}, path::Path};
+
+use chrono::{Datelike, Timelike};
+use flate2::{Compression, write::DeflateEncoder};
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x08074b50;
+const CENTRAL_DIRECTORY_HEADER_SIGNATURE: u32 = 0x02014b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06064b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+
+const VERSION_NEEDED: u16 = 20;
+const VERSION_NEEDED_ZIP64: u16 = 45;
+const FLAG_DATA_DESCRIPTOR: u16 = 1 << 3;
+const FLAG_UTF8: u16 = 1 << 11;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+const ZIP64_EXTRA_FIELD_ID: u16 = 0x0001;
+
+// Streamed entries within this much of the zip64 threshold get zip64 sizes up front, since deflate can make data slightly larger
+const ZIP64_STREAMED_HEADROOM: u64 = 1 << 24;
+
+struct ZipEntry {
+    name: Box<str>,
+    flags: u16,
+    method: u16,
+    crc32: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    local_header_offset: u64,
+}
+
+// Zip writer supporting zip64, files added from a path are streamed instead of being read into memory
+pub struct ZipWriter<W: Write> {
+    writer: W,
+    offset: u64,
+    entries: Vec<ZipEntry>,
+    dos_time: u16,
+    dos_date: u16,
+    // Sizes and offsets from this value on are moved to zip64 fields, only lowered by tests
+    zip64_threshold: u64,
+}
+
+impl<W: Write> ZipWriter<W> {
+    pub fn new(writer: W) -> Self {
+        let now = chrono::Local::now();
+        let dos_time = ((now.hour() << 11) | (now.minute() << 5) | (now.second() / 2)) as u16;
+        let dos_date = if now.year() < 1980 {
+            (1 << 5) | 1
+        } else {
+            ((((now.year() - 1980) as u32).min(127) << 9) | (now.month() << 5) | now.day()) as u16
+        };
+
+        Self {
+            writer,
+            offset: 0,
+            entries: Vec::new(),
+            dos_time,
+            dos_date,
+            zip64_threshold: u32::MAX as u64,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_zip64_threshold(writer: W, zip64_threshold: u64) -> Self {
+        Self {
+            zip64_threshold,
+            ..Self::new(writer)
+        }
+    }
+
+    fn needs_zip64(&self, value: u64) -> bool {
+        value >= self.zip64_threshold
+    }
+
+    pub fn write_file(&mut self, name: &str, data: &[u8]) -> std::io::Result<()> {
+        check_name(name)?;
+
+        let crc32 = crc32fast::hash(data);
+
+        let compressed;
+        let (method, written_data) = if should_compress(name) {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data)?;
+            compressed = encoder.finish()?;
+            if compressed.len() < data.len() {
+                (METHOD_DEFLATED, compressed.as_slice())
+            } else {
+                (METHOD_STORED, data)
+            }
+        } else {
+            (METHOD_STORED, data)
+        };
+
+        let entry = ZipEntry {
+            name: name.into(),
+            flags: FLAG_UTF8,
+            method,
+            crc32,
+            compressed_size: written_data.len() as u64,
+            uncompressed_size: data.len() as u64,
+            local_header_offset: self.offset,
+        };
+        let zip64 = self.needs_zip64(entry.compressed_size) || self.needs_zip64(entry.uncompressed_size);
+
+        self.write_local_header(&entry, zip64)?;
+        self.write_raw(written_data)?;
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    pub fn write_file_from_path(&mut self, name: &str, path: &Path) -> std::io::Result<()> {
+        check_name(name)?;
+
+        if should_compress(name) {
+            let size = std::fs::metadata(path)?.len();
+            let zip64 = self.needs_zip64(size.saturating_add(ZIP64_STREAMED_HEADROOM));
+            self.write_deflated_streamed(name, BufReader::new(std::fs::File::open(path)?), zip64)
+        } else {
+            self.write_stored_streamed(name, path)
+        }
+    }
+
+    // Stored entries read the file twice, so that the crc is known before the header is written
+    fn write_stored_streamed(&mut self, name: &str, path: &Path) -> std::io::Result<()> {
+        let mut hasher = crc32fast::Hasher::new();
+        let size = std::io::copy(&mut BufReader::new(std::fs::File::open(path)?), &mut HashingWriter(&mut hasher))?;
+
+        let entry = ZipEntry {
+            name: name.into(),
+            flags: FLAG_UTF8,
+            method: METHOD_STORED,
+            crc32: hasher.finalize(),
+            compressed_size: size,
+            uncompressed_size: size,
+            local_header_offset: self.offset,
+        };
+        self.write_local_header(&entry, self.needs_zip64(size))?;
+
+        let copied = std::io::copy(&mut std::fs::File::open(path)?.take(size), &mut self.writer)?;
+        self.offset += copied;
+        if copied != size {
+            return Err(Error::other(format!("{name} changed while it was being written to the zip")));
+        }
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    // Deflated entries are followed by a data descriptor, since the sizes and crc are only known once the data has been written
+    fn write_deflated_streamed(&mut self, name: &str, mut reader: impl Read, zip64: bool) -> std::io::Result<()> {
+        let mut entry = ZipEntry {
+            name: name.into(),
+            flags: FLAG_UTF8 | FLAG_DATA_DESCRIPTOR,
+            method: METHOD_DEFLATED,
+            crc32: 0,
+            compressed_size: 0,
+            uncompressed_size: 0,
+            local_header_offset: self.offset,
+        };
+        self.write_local_header(&entry, zip64)?;
+
+        let mut hasher = crc32fast::Hasher::new();
+        let mut counting = CountingWriter { inner: &mut self.writer, count: 0 };
+        let mut encoder = DeflateEncoder::new(&mut counting, Compression::default());
+        let mut buffer = vec![0_u8; 64 * 1024];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            hasher.update(&buffer[..read]);
+            entry.uncompressed_size += read as u64;
+            encoder.write_all(&buffer[..read])?;
+        }
+        encoder.finish()?;
+        entry.compressed_size = counting.count;
+        entry.crc32 = hasher.finalize();
+        self.offset += entry.compressed_size;
+
+        if !zip64 && (self.needs_zip64(entry.compressed_size) || self.needs_zip64(entry.uncompressed_size)) {
+            return Err(Error::new(ErrorKind::FileTooLarge, format!("{name} grew while it was being written to the zip")));
+        }
+
+        let mut descriptor = Vec::with_capacity(24);
+        descriptor.extend_from_slice(&DATA_DESCRIPTOR_SIGNATURE.to_le_bytes());
+        descriptor.extend_from_slice(&entry.crc32.to_le_bytes());
+        if zip64 {
+            descriptor.extend_from_slice(&entry.compressed_size.to_le_bytes());
+            descriptor.extend_from_slice(&entry.uncompressed_size.to_le_bytes());
+        } else {
+            descriptor.extend_from_slice(&(entry.compressed_size as u32).to_le_bytes());
+            descriptor.extend_from_slice(&(entry.uncompressed_size as u32).to_le_bytes());
+        }
+        self.write_raw(&descriptor)?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    fn write_local_header(&mut self, entry: &ZipEntry, zip64: bool) -> std::io::Result<()> {
+        let streamed = entry.flags & FLAG_DATA_DESCRIPTOR != 0;
+
+        let mut extra = Vec::new();
+        let (compressed_size, uncompressed_size) = if zip64 {
+            extra.extend_from_slice(&ZIP64_EXTRA_FIELD_ID.to_le_bytes());
+            extra.extend_from_slice(&16_u16.to_le_bytes());
+            extra.extend_from_slice(&entry.uncompressed_size.to_le_bytes());
+            extra.extend_from_slice(&entry.compressed_size.to_le_bytes());
+            (u32::MAX, u32::MAX)
+        } else if streamed {
+            (0, 0)
+        } else {
+            (entry.compressed_size as u32, entry.uncompressed_size as u32)
+        };
+
+        let mut header = Vec::with_capacity(30 + entry.name.len() + extra.len());
+        header.extend_from_slice(&LOCAL_FILE_HEADER_SIGNATURE.to_le_bytes());
+        header.extend_from_slice(&(if zip64 { VERSION_NEEDED_ZIP64 } else { VERSION_NEEDED }).to_le_bytes());
+        header.extend_from_slice(&entry.flags.to_le_bytes());
+        header.extend_from_slice(&entry.method.to_le_bytes());
+        header.extend_from_slice(&self.dos_time.to_le_bytes());
+        header.extend_from_slice(&self.dos_date.to_le_bytes());
+        header.extend_from_slice(&entry.crc32.to_le_bytes());
+        header.extend_from_slice(&compressed_size.to_le_bytes());
+        header.extend_from_slice(&uncompressed_size.to_le_bytes());
+        header.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        header.extend_from_slice(entry.name.as_bytes());
+        header.extend_from_slice(&extra);
+
+        self.write_raw(&header)
+    }
+
+    fn write_raw(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(data)?;
+        self.offset += data.len() as u64;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let central_directory_offset = self.offset;
+
+        let zip64_threshold = self.zip64_threshold;
+        let mut cd = Vec::new();
+        for entry in &self.entries {
+            // Values that don't fit are replaced with 0xFFFFFFFF and moved to the zip64 extra field, in this order
+            let mut extra = Vec::new();
+            let mut zip64_value = |value: u64| {
+                if value >= zip64_threshold {
+                    extra.extend_from_slice(&value.to_le_bytes());
+                    u32::MAX
+                } else {
+                    value as u32
+                }
+            };
+            let uncompressed_size = zip64_value(entry.uncompressed_size);
+            let compressed_size = zip64_value(entry.compressed_size);
+            let local_header_offset = zip64_value(entry.local_header_offset);
+
+            let version_needed = if extra.is_empty() { VERSION_NEEDED } else { VERSION_NEEDED_ZIP64 };
+            let extra_length = if extra.is_empty() { 0 } else { extra.len() as u16 + 4 };
+
+            cd.extend_from_slice(&CENTRAL_DIRECTORY_HEADER_SIGNATURE.to_le_bytes());
+            cd.extend_from_slice(&version_needed.to_le_bytes());
+            cd.extend_from_slice(&version_needed.to_le_bytes());
+            cd.extend_from_slice(&entry.flags.to_le_bytes());
+            cd.extend_from_slice(&entry.method.to_le_bytes());
+            cd.extend_from_slice(&self.dos_time.to_le_bytes());
+            cd.extend_from_slice(&self.dos_date.to_le_bytes());
+            cd.extend_from_slice(&entry.crc32.to_le_bytes());
+            cd.extend_from_slice(&compressed_size.to_le_bytes());
+            cd.extend_from_slice(&uncompressed_size.to_le_bytes());
+            cd.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            cd.extend_from_slice(&extra_length.to_le_bytes());
+            cd.extend_from_slice(&0_u16.to_le_bytes()); // comment length
+            cd.extend_from_slice(&0_u16.to_le_bytes()); // disk number
+            cd.extend_from_slice(&0_u16.to_le_bytes()); // internal attributes
+            cd.extend_from_slice(&0_u32.to_le_bytes()); // external attributes
+            cd.extend_from_slice(&local_header_offset.to_le_bytes());
+            cd.extend_from_slice(entry.name.as_bytes());
+            if !extra.is_empty() {
+                cd.extend_from_slice(&ZIP64_EXTRA_FIELD_ID.to_le_bytes());
+                cd.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+                cd.extend_from_slice(&extra);
+            }
+        }
+        self.write_raw(&cd)?;
+
+        let entry_count = self.entries.len() as u64;
+        let central_directory_size = cd.len() as u64;
+
+        let zip64_size = self.needs_zip64(central_directory_size);
+        let zip64_offset = self.needs_zip64(central_directory_offset);
+        if entry_count >= u16::MAX as u64 || zip64_size || zip64_offset {
+            let zip64_end_offset = self.offset;
+
+            let mut end = Vec::with_capacity(56 + 20);
+            end.extend_from_slice(&ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+            end.extend_from_slice(&44_u64.to_le_bytes()); // size of the remaining record
+            end.extend_from_slice(&VERSION_NEEDED_ZIP64.to_le_bytes());
+            end.extend_from_slice(&VERSION_NEEDED_ZIP64.to_le_bytes());
+            end.extend_from_slice(&0_u32.to_le_bytes()); // disk number
+            end.extend_from_slice(&0_u32.to_le_bytes()); // disk with central directory
+            end.extend_from_slice(&entry_count.to_le_bytes());
+            end.extend_from_slice(&entry_count.to_le_bytes());
+            end.extend_from_slice(&central_directory_size.to_le_bytes());
+            end.extend_from_slice(&central_directory_offset.to_le_bytes());
+
+            end.extend_from_slice(&ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIGNATURE.to_le_bytes());
+            end.extend_from_slice(&0_u32.to_le_bytes()); // disk with zip64 end of central directory
+            end.extend_from_slice(&zip64_end_offset.to_le_bytes());
+            end.extend_from_slice(&1_u32.to_le_bytes()); // total disks
+            self.write_raw(&end)?;
+        }
+
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        end.extend_from_slice(&0_u16.to_le_bytes()); // disk number
+        end.extend_from_slice(&0_u16.to_le_bytes()); // disk with central directory
+        end.extend_from_slice(&(entry_count.min(u16::MAX as u64) as u16).to_le_bytes());
+        end.extend_from_slice(&(entry_count.min(u16::MAX as u64) as u16).to_le_bytes());
+        end.extend_from_slice(&(if zip64_size { u32::MAX } else { central_directory_size as u32 }).to_le_bytes());
+        end.extend_from_slice(&(if zip64_offset { u32::MAX } else { central_directory_offset as u32 }).to_le_bytes());
+        end.extend_from_slice(&0_u16.to_le_bytes()); // comment length
+        self.write_raw(&end)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+struct CountingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+struct HashingWriter<'a>(&'a mut crc32fast::Hasher);
+
+impl Write for HashingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn check_name(name: &str) -> std::io::Result<()> {
+    if name.len() > u16::MAX as usize {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("{name} is too long to be written to a zip")));
+    }
+    Ok(())
+}
+
+fn should_compress(name: &str) -> bool {
+    let Some((_, extension)) = name.rsplit_once('.') else {
+        return true;
+    };
+    !matches!(extension.to_ascii_lowercase().as_str(), "jar" | "zip" | "mrpack" | "png" | "jpg" | "ogg" | "gz" | "disabled")
+}
+
+// Writes to a temporary file first and renames it into place once the zip has been finished
+pub(crate) fn write_zip_safe(path: &Path, write: impl FnOnce(&mut ZipWriter<std::io::BufWriter<std::fs::File>>) -> std::io::Result<()>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let mut temp = path.to_path_buf();
+    temp.add_extension("new");
+
+    let result = (|| {
+        let file = std::fs::File::create(&temp)?;
+        let mut zip = ZipWriter::new(std::io::BufWriter::new(file));
+        write(&mut zip)?;
+        let writer = zip.finish()?;
+        let file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()
+    })();
+
+    if let Err(err) = result {
+        _ = std::fs::remove_file(&temp);
+        return Err(err);
+    }
+
+    if let Err(err) = std::fs::rename(&temp, path) {
+        _ = std::fs::remove_file(&temp);
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rc_zip_sync::ReadZip;
+
+    use super::{ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE, ZipWriter};
+
+    #[test]
+    fn test_round_trip() {
+        let dir = std::env::temp_dir().join(format!("pandora-zip-writer-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let text_path = dir.join("level.dat_old");
+        let jar_path = dir.join("mod.jar");
+        let text: Vec<u8> = b"streamed and deflated ".repeat(10000);
+        let jar: Vec<u8> = (0..100000_u32).map(|i| (i * 7919 % 251) as u8).collect();
+        std::fs::write(&text_path, &text).unwrap();
+        std::fs::write(&jar_path, &jar).unwrap();
+
+        let mut zip = ZipWriter::new(Vec::new());
+        zip.write_file("in_memory.txt", b"hello hello hello hello").unwrap();
+        zip.write_file_from_path("world/level.dat_old", &text_path).unwrap();
+        zip.write_file_from_path("mods/mod.jar", &jar_path).unwrap();
+        let data = zip.finish().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let archive = data.read_zip().unwrap();
+        assert_eq!(archive.entries().count(), 3);
+        assert_eq!(archive.by_name("in_memory.txt").unwrap().bytes().unwrap(), b"hello hello hello hello");
+        assert_eq!(archive.by_name("world/level.dat_old").unwrap().bytes().unwrap(), text);
+        assert_eq!(archive.by_name("mods/mod.jar").unwrap().bytes().unwrap(), jar);
+    }
+
+    #[test]
+    fn test_zip64_entry_count() {
+        let mut zip = ZipWriter::new(Vec::new());
+        for i in 0..70000 {
+            zip.write_file(&format!("{i}.txt"), i.to_string().as_bytes()).unwrap();
+        }
+        let data = zip.finish().unwrap();
+
+        let archive = data.read_zip().unwrap();
+        assert_eq!(archive.entries().count(), 70000);
+        assert_eq!(archive.by_name("69999.txt").unwrap().bytes().unwrap(), b"69999");
+    }
+
+    // Lowering the threshold makes small entries take the same zip64 paths as entries over 4GiB
+    #[test]
+    fn test_zip64_sizes_and_offsets() {
+        let dir = std::env::temp_dir().join(format!("pandora-zip-writer-zip64-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let text_path = dir.join("latest.log");
+        let jar_path = dir.join("mod.jar");
+        let text: Vec<u8> = b"zip64 streamed and deflated ".repeat(10000);
+        let jar: Vec<u8> = (0..100000_u32).map(|i| (i * 7919 % 251) as u8).collect();
+        std::fs::write(&text_path, &text).unwrap();
+        std::fs::write(&jar_path, &jar).unwrap();
+
+        let mut zip = ZipWriter::with_zip64_threshold(Vec::new(), 1024);
+        zip.write_file("small.txt", b"before the threshold").unwrap();
+        zip.write_file("in_memory.bin", &jar).unwrap();
+        zip.write_file_from_path("logs/latest.log", &text_path).unwrap();
+        zip.write_file_from_path("mods/mod.jar", &jar_path).unwrap();
+        zip.write_file("after.txt", b"local header offset past the threshold").unwrap();
+        let data = zip.finish().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(data.windows(4).any(|window| window == ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes()));
+
+        let archive = data.read_zip().unwrap();
+        assert_eq!(archive.entries().count(), 5);
+        assert_eq!(archive.by_name("small.txt").unwrap().bytes().unwrap(), b"before the threshold");
+        assert_eq!(archive.by_name("in_memory.bin").unwrap().bytes().unwrap(), jar);
+        assert_eq!(archive.by_name("logs/latest.log").unwrap().bytes().unwrap(), text);
+        assert_eq!(archive.by_name("mods/mod.jar").unwrap().bytes().unwrap(), jar);
+        assert_eq!(archive.by_name("after.txt").unwrap().bytes().unwrap(), b"local header offset past the threshold");
+    }
+}
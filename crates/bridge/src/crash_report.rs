@@ -0,0 +1,21 @@
+use std::{path::Path, sync::Arc};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrashReportKind {
+    // crash-reports/crash-*.txt written by the game
+    Game,
+    // hs_err_pid*.log written by the JVM
+    Jvm,
+    // The game exited abnormally without leaving a report behind
+    Unknown,
+}
+
+#[derive(Clone, Debug)]
+pub struct CrashReportAnalysis {
+    pub kind: CrashReportKind,
+    pub exit_code: Option<i32>,
+    pub report_path: Option<Arc<Path>>,
+    pub description: Option<Arc<str>>,
+    pub stack_trace: Arc<[Arc<str>]>,
+    pub suspected_mods: Arc<[Arc<str>]>,
+}
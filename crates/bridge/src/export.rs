@@ -0,0 +1,36 @@
+use strum::{Display, EnumIter};
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum InstanceExportFormat {
+    Modrinth,
+    CurseForge,
+    #[strum(to_string = "Prism/MultiMC")]
+    Prism,
+}
+
+impl InstanceExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            InstanceExportFormat::Modrinth => "mrpack",
+            InstanceExportFormat::CurseForge => "zip",
+            InstanceExportFormat::Prism => "zip",
+        }
+    }
+}
+
+// Top-level entries of .minecraft that shouldn't be included in an export unless explicitly selected
+pub const EXCLUDED_BY_DEFAULT: &[&str] = &[
+    "saves",
+    "logs",
+    "crash-reports",
+    "screenshots",
+    "downloads",
+    "backups",
+    "servers.dat",
+    "servers.dat_old",
+    "usercache.json",
+    "usernamecache.json",
+    "command_history.txt",
+    ".fabric",
+    ".cache",
+];
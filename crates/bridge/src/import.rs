@@ -8,6 +8,13 @@ pub struct ImportFromOtherLauncherJob {
     pub paths: Vec<Arc<Path>>,
 }
 
+// Names of the instances an import created, and of those skipped because an instance with that name already exists
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub imported: Vec<Arc<str>>,
+    pub skipped: Vec<Arc<str>>,
+}
+
 #[derive(Debug, Display, Clone, Copy, enum_map::Enum, EnumIter)]
 pub enum OtherLauncher {
     Prism,
@@ -15,6 +22,11 @@ pub enum OtherLauncher {
     Modrinth,
     MultiMC,
     ATLauncher,
+    #[strum(to_string = "Minecraft Launcher")]
+    Official,
+    GDLauncher,
+    #[strum(to_string = ".minecraft Folder")]
+    Folder,
 }
 
 impl OtherLauncher {
@@ -27,6 +39,16 @@ impl OtherLauncher {
             OtherLauncher::Modrinth => data_dir.join("ModrinthApp").into(),
             OtherLauncher::MultiMC => data_dir.join("multimc").into(),
             OtherLauncher::ATLauncher => data_dir.join("atlauncher").into(),
+            OtherLauncher::Official | OtherLauncher::Folder => {
+                if cfg!(target_os = "linux") {
+                    directories.home_dir().join(".minecraft").into()
+                } else if cfg!(target_os = "macos") {
+                    data_dir.join("minecraft").into()
+                } else {
+                    data_dir.join(".minecraft").into()
+                }
+            },
+            OtherLauncher::GDLauncher => data_dir.join("gdlauncher_next").into(),
         }
     }
 }
@@ -2,7 +2,8 @@ use std::{path::Path, sync::Arc, time::Duration};
 
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
-use schema::{auxiliary::AuxDisabledChildren, content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeModpackFile, CurseforgeModpackMinecraft}, loader::Loader, modification::ModrinthModpackFileDownload, server_status::ServerStatus, text_component::FlatTextComponent};
+use schema::{auxiliary::AuxDisabledChildren, content::ContentSource, curseforge::{CachedCurseforgeFileInfo, CurseforgeModpackFile, CurseforgeModpackMinecraft}, instance_snapshot::InstanceSnapshotReason, loader::Loader, modification::ModrinthModpackFileDownload, server_status::ServerStatus, text_component::FlatTextComponent};
+use ustr::Ustr;
 
 use crate::safe_path::SafePath;
 
@@ -59,16 +60,75 @@ pub struct InstanceWorldSummary {
     pub png_icon: Option<Arc<[u8]>>,
 }
 
+#[derive(Debug, Clone)]
+pub struct WorldBackupSummary {
+    pub name: Arc<str>,
+    pub created: i64,
+    pub size: u64,
+    pub automatic: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceSnapshotSummary {
+    pub name: Arc<str>,
+    pub created: i64,
+    pub reason: InstanceSnapshotReason,
+    pub minecraft_version: Ustr,
+    pub loader: Loader,
+    pub content_count: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct InstanceServerSummary {
+    // Position in the list of visible servers, used to refer to the entry when modifying servers.dat
+    pub index: usize,
     pub name: Arc<str>,
     pub ip: Arc<str>,
+    pub resource_packs: ServerResourcePackPolicy,
     pub png_icon: Option<Arc<[u8]>>,
     pub pinging: bool,
     pub status: Option<Arc<ServerStatus>>,
     pub ping: Option<Duration>,
 }
 
+impl InstanceServerSummary {
+    pub fn entry_ref(&self) -> ServerEntryRef {
+        ServerEntryRef {
+            index: self.index,
+            ip: self.ip.clone(),
+        }
+    }
+}
+
+// Refers to an entry in servers.dat, the address is checked so a stale index can't modify a different server
+#[derive(Debug, Clone)]
+pub struct ServerEntryRef {
+    pub index: usize,
+    pub ip: Arc<str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ServerResourcePackPolicy {
+    #[default]
+    Prompt,
+    Enabled,
+    Disabled,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerStatusTrend {
+    pub address: Arc<str>,
+    pub favourite: bool,
+    pub online: Option<bool>,
+    pub uptime_day: Option<f32>,
+    pub uptime_week: Option<f32>,
+    pub average_players_day: Option<f32>,
+    pub average_ping_day: Option<Duration>,
+    pub version: Option<Arc<str>>,
+    // Oldest first, one entry per hour of the last day, None if the server wasn't polled in that hour
+    pub hourly_uptime: Arc<[Option<f32>]>,
+}
+
 #[derive(Debug, Clone)]
 pub struct InstanceContentSummary {
     pub content_summary: Arc<ContentSummary>,
@@ -94,6 +154,9 @@ pub struct ContentSummary {
     pub authors: Arc<str>,
     pub png_icon: Option<Arc<[u8]>>,
     pub extra: ContentType,
+    // Other ids this content can satisfy dependencies for (e.g. bundled jars), along with their version
+    pub provides: Arc<[(Arc<str>, Arc<str>)]>,
+    pub dependencies: Arc<[ContentDependency]>,
 }
 
 impl ContentSummary {
@@ -112,13 +175,73 @@ pub static UNKNOWN_CONTENT_SUMMARY: Lazy<Arc<ContentSummary>> = Lazy::new(|| {
         rich_description: None,
         png_icon: None,
         extra: ContentType::Unknown,
+        provides: [].into(),
+        dependencies: [].into(),
     })
 });
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentDependencyKind {
+    Required,
+    Incompatible,
+    Discouraged,
+}
+
+#[derive(Debug, Clone)]
+pub enum ContentVersionRequirement {
+    Any,
+    // Fabric/Quilt style predicates, any of them must match
+    Predicates(Arc<[Arc<str>]>),
+    // Forge/NeoForge style maven version range
+    MavenRange(Arc<str>),
+}
+
+impl std::fmt::Display for ContentVersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentVersionRequirement::Any => f.write_str("*"),
+            ContentVersionRequirement::Predicates(predicates) => f.write_str(&predicates.join(" || ")),
+            ContentVersionRequirement::MavenRange(range) => f.write_str(range),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentDependency {
+    pub id: Arc<str>,
+    pub kind: ContentDependencyKind,
+    pub versions: ContentVersionRequirement,
+}
+
+#[derive(Debug, Clone)]
+pub enum ContentDependencyIssueKind {
+    Missing {
+        modrinth_project_id: Option<Arc<str>>,
+    },
+    VersionMismatch {
+        installed_version: Arc<str>,
+    },
+    Incompatible {
+        installed_version: Arc<str>,
+    },
+    Discouraged {
+        installed_version: Arc<str>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentDependencyIssue {
+    pub content_name: Arc<str>,
+    pub dependency_id: Arc<str>,
+    pub requirement: Arc<str>,
+    pub kind: ContentDependencyIssueKind,
+}
+
 #[derive(Debug, Clone)]
 pub enum ContentType {
     Unknown,
     Fabric,
+    Quilt,
     LegacyForge,
     Forge,
     NeoForge,
@@ -141,7 +264,7 @@ pub enum ContentType {
 impl ContentType {
     pub fn content_folder(&self) -> Option<&'static str> {
         match self {
-            Self::Fabric | Self::Forge | Self::LegacyForge | Self::NeoForge | Self::JavaModule | Self::ModrinthModpack { .. } | Self::CurseforgeModpack { .. } => {
+            Self::Fabric | Self::Quilt | Self::Forge | Self::LegacyForge | Self::NeoForge | Self::JavaModule | Self::ModrinthModpack { .. } | Self::CurseforgeModpack { .. } => {
                 Some("mods")
             },
             ContentType::ResourcePack => {
@@ -163,6 +286,7 @@ impl ContentType {
     pub fn is_strict_loader(&self) -> bool {
         match self {
             Self::Fabric => true,
+            Self::Quilt => true,
             Self::LegacyForge => true,
             Self::Forge => true,
             Self::NeoForge => true,
@@ -186,6 +310,7 @@ impl ContentUpdateStatus {
     pub fn can_update(&self) -> bool {
         match self {
             ContentUpdateStatus::Modrinth => true,
+            ContentUpdateStatus::Curseforge => true,
             _ => false,
         }
     }
@@ -1,4 +1,6 @@
 pub mod account;
+pub mod crash_report;
+pub mod export;
 pub mod game_output;
 pub mod handle;
 pub mod import;
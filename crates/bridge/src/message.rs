@@ -3,18 +3,18 @@ use std::{
 };
 
 use schema::{
-    backend_config::{BackendConfig, ProxyConfig}, instance::{
-        InstanceConfiguration, InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration,
-        InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration, InstanceSystemLibrariesConfiguration, InstanceWrapperCommandConfiguration,
+    backend_config::{BackendConfig, InstanceDefaults, JavaRuntimeInfo, ProxyConfig, SyncMergeStrategy}, instance::{
+        InstanceConfiguration, InstanceEnvironmentVariablesConfiguration, InstanceGameArgumentsConfiguration, InstanceHookCommandsConfiguration, InstanceJvmBinaryConfiguration, InstanceKind, InstanceJvmFlagsConfiguration,
+        InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration, InstanceSystemLibrariesConfiguration, InstanceWindowConfiguration, InstanceWorldBackupConfiguration, InstanceWrapperCommandConfiguration,
     }, loader::Loader, minecraft_profile::{MinecraftProfileCape, SkinVariant}, pandora_update::UpdatePrompt
 };
 use ustr::Ustr;
 use uuid::Uuid;
 
 use crate::{
-    account::Account, game_output::GameOutputLogLevel, import::{ImportFromOtherLauncherJob, OtherLauncher}, install::ContentInstall, instance::{
-        InstanceContentID, InstanceContentSummary, InstanceID, InstancePlaytime, InstanceServerSummary, InstanceStatus,
-        InstanceWorldSummary,
+    account::Account, crash_report::CrashReportAnalysis, export::InstanceExportFormat, game_output::GameOutputLogLevel, import::{ImportFromOtherLauncherJob, ImportResult, OtherLauncher}, install::ContentInstall, instance::{
+        ContentDependencyIssue, InstanceContentID, InstanceContentSummary, InstanceID, InstancePlaytime, InstanceServerSummary, InstanceSnapshotSummary, InstanceStatus,
+        InstanceWorldSummary, ServerEntryRef, ServerResourcePackPolicy, ServerStatusTrend, WorldBackupSummary,
     }, keep_alive::{KeepAlive, KeepAliveHandle}, meta::{MetadataRequest, MetadataResult}, modal_action::ModalAction
 };
 
@@ -34,7 +34,13 @@ pub enum MessageToBackend {
         name: Ustr,
         version: Ustr,
         loader: Loader,
+        kind: InstanceKind,
         icon: Option<EmbeddedOrRaw>,
+        channel: Option<tokio::sync::oneshot::Sender<Result<Arc<Path>, Arc<str>>>>,
+    },
+    // Replies once every message sent before it has been handled
+    Flush {
+        channel: tokio::sync::oneshot::Sender<()>,
     },
     DeleteInstance {
         id: InstanceID,
@@ -63,18 +69,54 @@ pub enum MessageToBackend {
         id: InstanceID,
         disable_file_syncing: bool,
     },
+    SetInstanceSyncProfile {
+        id: InstanceID,
+        profile: Option<Arc<str>>,
+    },
     SetInstanceMemory {
         id: InstanceID,
         memory: InstanceMemoryConfiguration,
     },
+    SetInstanceWorldBackups {
+        id: InstanceID,
+        world_backups: InstanceWorldBackupConfiguration,
+    },
     SetInstanceWrapperCommand {
         id: InstanceID,
         wrapper_command: InstanceWrapperCommandConfiguration,
     },
+    SetInstanceHookCommands {
+        id: InstanceID,
+        hook_commands: InstanceHookCommandsConfiguration,
+    },
     SetInstanceJvmFlags {
         id: InstanceID,
         jvm_flags: InstanceJvmFlagsConfiguration,
     },
+    SetInstanceEnvironmentVariables {
+        id: InstanceID,
+        environment_variables: InstanceEnvironmentVariablesConfiguration,
+    },
+    SetInstanceGameArguments {
+        id: InstanceID,
+        game_arguments: InstanceGameArgumentsConfiguration,
+    },
+    SetInstanceWindow {
+        id: InstanceID,
+        window: InstanceWindowConfiguration,
+    },
+    SetInstanceGroup {
+        id: InstanceID,
+        group: Option<Arc<str>>,
+    },
+    SetInstanceTags {
+        id: InstanceID,
+        tags: Vec<Arc<str>>,
+    },
+    SetInstanceFavourite {
+        id: InstanceID,
+        favourite: bool,
+    },
     SetInstanceJvmBinary {
         id: InstanceID,
         jvm_binary: InstanceJvmBinaryConfiguration,
@@ -97,8 +139,22 @@ pub enum MessageToBackend {
     StartInstance {
         id: InstanceID,
         quick_play: Option<QuickPlayLaunch>,
+        // Size of the display the launcher is on, used when the game window should start maximised
+        screen_size: Option<(u32, u32)>,
         modal_action: ModalAction,
     },
+    AcceptServerEula {
+        id: InstanceID,
+    },
+    SendGameInput {
+        id: InstanceID,
+        line: Arc<str>,
+    },
+    RunRconCommand {
+        id: InstanceID,
+        command: Arc<str>,
+        channel: tokio::sync::oneshot::Sender<Result<Arc<str>, Arc<str>>>,
+    },
     RequestLoadWorlds {
         id: InstanceID,
     },
@@ -144,7 +200,7 @@ pub enum MessageToBackend {
     },
     UpdateContent {
         instance: InstanceID,
-        content_id: InstanceContentID,
+        content_ids: Arc<[InstanceContentID]>,
         modal_action: ModalAction,
     },
     Sleep5s,
@@ -156,22 +212,67 @@ pub enum MessageToBackend {
         instance: InstanceID,
         channel: tokio::sync::oneshot::Sender<LogFiles>,
     },
+    GetInstanceSnapshots {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<Arc<[InstanceSnapshotSummary]>>,
+    },
+    RestoreInstanceSnapshot {
+        id: InstanceID,
+        snapshot: Arc<str>,
+        modal_action: ModalAction,
+    },
+    AnalyzeContentDependencies {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<Arc<[ContentDependencyIssue]>>,
+    },
+    BackupWorld {
+        id: InstanceID,
+        world: Arc<str>,
+        modal_action: ModalAction,
+    },
+    GetWorldBackups {
+        id: InstanceID,
+        world: Arc<str>,
+        channel: tokio::sync::oneshot::Sender<Arc<[WorldBackupSummary]>>,
+    },
+    RestoreWorldBackup {
+        id: InstanceID,
+        world: Arc<str>,
+        backup: Arc<str>,
+        modal_action: ModalAction,
+    },
     GetImportFromOtherLauncherJob {
         channel: tokio::sync::oneshot::Sender<Option<ImportFromOtherLauncherJob>>,
         launcher: OtherLauncher,
         path: Arc<Path>,
     },
     GetSyncState {
+        profile: Option<Arc<str>>,
         channel: tokio::sync::oneshot::Sender<SyncState>,
     },
+    GetSyncProfiles {
+        channel: tokio::sync::oneshot::Sender<Arc<[Arc<str>]>>,
+    },
+    CreateSyncProfile {
+        name: Arc<str>,
+    },
+    DeleteSyncProfile {
+        name: Arc<str>,
+    },
     GetBackendConfiguration {
         channel: tokio::sync::oneshot::Sender<BackendConfigWithPassword>,
     },
     SetSyncing {
+        profile: Option<Arc<str>>,
         target: Arc<str>,
         is_file: bool,
         value: bool,
     },
+    SetSyncStrategy {
+        profile: Option<Arc<str>>,
+        target: Arc<str>,
+        strategy: SyncMergeStrategy,
+    },
     CleanupOldLogFiles {
         instance: InstanceID,
     },
@@ -195,10 +296,29 @@ pub enum MessageToBackend {
     SetOpenGameOutputAfterLaunching {
         value: bool,
     },
+    SetOfflineMode {
+        value: bool,
+    },
+    SetPollServerHistory {
+        value: bool,
+    },
     SetProxyConfiguration {
         config: ProxyConfig,
         password: Option<String>,
     },
+    RefreshJavaRuntimes {
+        channel: tokio::sync::oneshot::Sender<Arc<[JavaRuntimeInfo]>>,
+    },
+    SetDefaultJavaRuntime {
+        major_version: u32,
+        path: Option<Arc<str>>,
+    },
+    SetPreferDiscoveredJavaRuntimes {
+        value: bool,
+    },
+    SetInstanceDefaults {
+        defaults: InstanceDefaults,
+    },
     CreateInstanceShortcut {
         id: InstanceID,
         path: PathBuf
@@ -207,6 +327,13 @@ pub enum MessageToBackend {
         id: InstanceID,
         path: PathBuf
     },
+    ExportInstance {
+        id: InstanceID,
+        format: InstanceExportFormat,
+        path: PathBuf,
+        include: Vec<Arc<str>>,
+        modal_action: ModalAction,
+    },
     InstallUpdate {
         update: UpdatePrompt,
         modal_action: ModalAction,
@@ -215,6 +342,7 @@ pub enum MessageToBackend {
         launcher: OtherLauncher,
         import_job: ImportFromOtherLauncherJob,
         modal_action: ModalAction,
+        channel: Option<tokio::sync::oneshot::Sender<ImportResult>>,
     },
     GetAccountSkin {
         account: Uuid,
@@ -233,6 +361,37 @@ pub enum MessageToBackend {
         account: Uuid,
         cape: Option<Uuid>,
     },
+    AddServer {
+        ids: Arc<[InstanceID]>,
+        name: Arc<str>,
+        ip: Arc<str>,
+        resource_packs: ServerResourcePackPolicy,
+    },
+    EditServer {
+        id: InstanceID,
+        server: ServerEntryRef,
+        name: Arc<str>,
+        ip: Arc<str>,
+        resource_packs: ServerResourcePackPolicy,
+    },
+    RemoveServer {
+        id: InstanceID,
+        server: ServerEntryRef,
+    },
+    MoveServer {
+        id: InstanceID,
+        from: ServerEntryRef,
+        to: ServerEntryRef,
+    },
+    SetServerResourcePackPolicy {
+        id: InstanceID,
+        server: ServerEntryRef,
+        policy: ServerResourcePackPolicy,
+    },
+    SetServerFavourite {
+        address: Arc<str>,
+        favourite: bool,
+    },
     RequestSkinLibrary,
     AddToSkinLibrary {
         source: UrlOrFile,
@@ -283,6 +442,9 @@ pub enum MessageToFrontend {
         id: InstanceID,
         servers: Arc<[InstanceServerSummary]>,
     },
+    ServerStatusTrendsUpdated {
+        trends: Arc<[ServerStatusTrend]>,
+    },
     InstanceModsUpdated {
         id: InstanceID,
         mods: Arc<[InstanceContentSummary]>,
@@ -291,8 +453,18 @@ pub enum MessageToFrontend {
         id: InstanceID,
         resource_packs: Arc<[InstanceContentSummary]>,
     },
+    InstanceCrashed {
+        id: InstanceID,
+        name: Ustr,
+        analysis: CrashReportAnalysis,
+    },
+    ServerEulaRequired {
+        id: InstanceID,
+        name: Ustr,
+    },
     CreateGameOutputWindow {
         id: usize,
+        instance: InstanceID,
         keep_alive: KeepAlive,
     },
     AddGameOutput {
@@ -339,10 +511,13 @@ pub struct SyncTargetState {
     pub is_file: bool,
     pub sync_count: usize,
     pub cannot_sync_count: usize,
+    pub strategy: SyncMergeStrategy,
 }
 
 #[derive(Debug)]
 pub struct SyncState {
+    pub profile: Option<Arc<str>>,
+    pub profiles: Arc<[Arc<str>]>,
     pub sync_folder: Arc<Path>,
     pub targets: BTreeMap<Arc<str>, SyncTargetState>,
     pub total_count: usize,
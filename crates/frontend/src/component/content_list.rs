@@ -175,18 +175,20 @@ impl ContentListDelegate {
                                 let mut updating = updating.lock();
                                 let delegate = this.delegate_mut();
                                 if delegate.is_selected(element_id) {
+                                    let mut content_ids = Vec::new();
                                     for summary in &delegate.content {
                                         if delegate.is_selected(summary.filename_hash) && summary.update.can_update(delegate.for_loader, delegate.for_version.as_str()) {
                                             updating.insert(summary.filename_hash);
-                                            crate::root::update_single_mod(id, summary.id, &backend_handle, window, cx);
+                                            content_ids.push(summary.id);
                                         }
                                     }
+                                    crate::root::update_content(id, content_ids.into(), &backend_handle, window, cx);
                                     delegate.selected.clear();
                                     delegate.selected_range.clear();
                                     delegate.last_clicked_non_range = None;
                                 } else {
                                     updating.insert(element_id);
-                                    crate::root::update_single_mod(id, content_id, &backend_handle, window, cx);
+                                    crate::root::update_content(id, [content_id].into(), &backend_handle, window, cx);
                                 }
                             })
                         })
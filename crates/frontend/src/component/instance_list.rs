@@ -1,18 +1,48 @@
+use std::sync::Arc;
+
 use bridge::{handle::BackendHandle, instance::InstanceStatus, message::MessageToBackend};
 use gpui::{prelude::*, *};
 use gpui_component::{
     button::{Button, ButtonVariants}, h_flex, table::{Column, ColumnSort, TableDelegate, TableState}, v_flex, ActiveTheme, Icon, Sizable
 };
+use schema::instance::InstanceConfiguration;
 
 use crate::{
     entity::{
         instance::{InstanceAddedEvent, InstanceEntry, InstanceModifiedEvent, InstanceRemovedEvent}, DataEntities
-    }, png_render_cache, ts, root, ui
+    }, icon::PandoraIcon, interface_config::InterfaceConfig, png_render_cache, ts, root, ui
 };
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InstanceFilter {
+    #[default]
+    All,
+    Favourites,
+    Tag(Arc<str>),
+}
+
+impl InstanceFilter {
+    fn matches(&self, configuration: &InstanceConfiguration) -> bool {
+        match self {
+            InstanceFilter::All => true,
+            InstanceFilter::Favourites => configuration.favourite,
+            InstanceFilter::Tag(tag) => configuration.tags.iter().any(|instance_tag| instance_tag.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum InstanceListRow {
+    // An empty name is used for instances without a group
+    Group { name: Arc<str>, count: usize, collapsed: bool },
+    Instance(usize),
+}
+
 pub struct InstanceList {
     columns: Vec<Column>,
     items: Vec<InstanceEntry>,
+    rows: Vec<InstanceListRow>,
+    filter: InstanceFilter,
     backend_handle: BackendHandle,
     _instance_added_subscription: Subscription,
     _instance_removed_subscription: Subscription,
@@ -26,17 +56,20 @@ impl InstanceList {
         cx.new(|cx| {
             let _instance_added_subscription = cx.subscribe::<_, InstanceAddedEvent>(&instances, |table: &mut TableState<InstanceList>, _, event, cx| {
                 table.delegate_mut().items.insert(0, event.instance.clone());
+                table.delegate_mut().refresh_rows(cx);
                 cx.notify();
             });
             let _instance_removed_subscription = cx.subscribe::<_, InstanceRemovedEvent>(&instances, |table, _, event, cx| {
                 table.delegate_mut().items.retain(|instance| {
                     instance.id != event.id
                 });
+                table.delegate_mut().refresh_rows(cx);
                 cx.notify();
             });
             let _instance_modified_subscription = cx.subscribe::<_, InstanceModifiedEvent>(&instances, |table, _, event, cx| {
                 if let Some(entry) = table.delegate_mut().items.iter_mut().find(|entry| entry.id == event.instance.id) {
                     *entry = event.instance.clone();
+                    table.delegate_mut().refresh_rows(cx);
                     cx.notify();
                 }
             });
@@ -61,17 +94,79 @@ impl InstanceList {
                         .width(150.)
                         .fixed_left()
                         .resizable(true),
+                    Column::new("tags", ts!("instance.tags.label"))
+                        .width(200.)
+                        .resizable(true),
                 ],
                 items,
+                rows: Vec::new(),
+                filter: InstanceFilter::All,
                 backend_handle: data.backend_handle.clone(),
                 _instance_added_subscription,
                 _instance_removed_subscription,
                 _instance_modified_subscription,
             };
-            TableState::new(instance_list, window, cx)
+            let mut table = TableState::new(instance_list, window, cx);
+            table.delegate_mut().refresh_rows(cx);
+            table
         })
     }
 
+    pub fn rows(&self) -> &[InstanceListRow] {
+        &self.rows
+    }
+
+    pub fn filter(&self) -> &InstanceFilter {
+        &self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: InstanceFilter, cx: &App) {
+        self.filter = filter;
+        self.refresh_rows(cx);
+    }
+
+    pub fn toggle_group_collapsed(&mut self, name: &Arc<str>, cx: &mut App) {
+        let collapsed_groups = &mut InterfaceConfig::get_mut(cx).collapsed_instance_groups;
+        if !collapsed_groups.remove(name) {
+            collapsed_groups.insert(name.clone());
+        }
+        self.refresh_rows(cx);
+    }
+
+    fn refresh_rows(&mut self, cx: &App) {
+        let mut groups: Vec<(Arc<str>, Vec<usize>)> = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            if !self.filter.matches(&item.configuration) {
+                continue;
+            }
+            let name = item.configuration.group.clone().unwrap_or_default();
+            match groups.iter_mut().find(|(group, _)| *group == name) {
+                Some((_, indices)) => indices.push(index),
+                None => groups.push((name, vec![index])),
+            }
+        }
+
+        // Named groups first, ungrouped instances at the end
+        groups.sort_by(|(a, _), (b, _)| a.is_empty().cmp(&b.is_empty()).then_with(|| lexical_sort::natural_lexical_cmp(a, b)));
+
+        // Headers are only useful once at least one instance has been put in a group
+        let show_headers = groups.iter().any(|(name, _)| !name.is_empty());
+        let collapsed_groups = &InterfaceConfig::get(cx).collapsed_instance_groups;
+
+        self.rows.clear();
+        for (name, mut indices) in groups {
+            indices.sort_by_key(|index| !self.items[*index].configuration.favourite);
+
+            let collapsed = show_headers && collapsed_groups.contains(&name);
+            if show_headers {
+                self.rows.push(InstanceListRow::Group { name, count: indices.len(), collapsed });
+            }
+            if !collapsed {
+                self.rows.extend(indices.into_iter().map(InstanceListRow::Instance));
+            }
+        }
+    }
+
     pub fn render_card(&self, index: usize, cx: &mut App) -> Div {
         let item = &self.items[index];
         let loader_and_version = format!(
@@ -92,6 +187,7 @@ impl InstanceList {
         };
 
         let play_button = render_play_button(item, index, self.backend_handle.clone());
+        let favourite_button = render_favourite_button(item, index, self.backend_handle.clone());
 
         let theme = cx.theme();
         v_flex()
@@ -112,7 +208,12 @@ impl InstanceList {
                     .w_full()
                     .child(item.name.clone())
                     .child(loader_and_version)
+                    .when(!item.configuration.tags.is_empty(), |this| this.child(div()
+                        .text_sm()
+                        .text_color(theme.muted_foreground)
+                        .child(item.configuration.tags.join(", "))))
                 )
+                .child(favourite_button.small())
             ).child(h_flex()
                 .gap_2()
                 .child(play_button.flex_1().small())
@@ -133,7 +234,7 @@ impl TableDelegate for InstanceList {
     }
 
     fn rows_count(&self, _cx: &App) -> usize {
-        self.items.len()
+        self.rows.len()
     }
 
     fn column(&self, col_ix: usize, _cx: &App) -> gpui_component::table::Column {
@@ -145,7 +246,7 @@ impl TableDelegate for InstanceList {
         col_ix: usize,
         sort: gpui_component::table::ColumnSort,
         _window: &mut Window,
-        _cx: &mut Context<TableState<Self>>,
+        cx: &mut Context<TableState<Self>>,
     ) {
         if let Some(col) = self.columns.get_mut(col_ix) {
             match col.key.as_ref() {
@@ -160,23 +261,46 @@ impl TableDelegate for InstanceList {
                 _ => {},
             }
         }
+        self.refresh_rows(cx);
     }
 
-    fn render_td(&mut self, row_ix: usize, col_ix: usize, _window: &mut Window, _cx: &mut Context<TableState<Self>>) -> impl IntoElement {
-        let item = &self.items[row_ix];
+    fn render_td(&mut self, row_ix: usize, col_ix: usize, _window: &mut Window, cx: &mut Context<TableState<Self>>) -> impl IntoElement {
+        let index = match &self.rows[row_ix] {
+            InstanceListRow::Group { name, count, collapsed } => {
+                if self.columns.get(col_ix).is_some_and(|col| col.key.as_ref() == "controls") {
+                    let name = name.clone();
+                    return render_group_header(row_ix, &name, *count, *collapsed)
+                        .small()
+                        .on_click(cx.listener(move |table, _, _, cx| {
+                            table.delegate_mut().toggle_group_collapsed(&name, cx);
+                            cx.notify();
+                        }))
+                        .into_any_element();
+                }
+                return div().into_any_element();
+            },
+            InstanceListRow::Instance(index) => *index,
+        };
+
+        let item = &self.items[index];
         if let Some(col) = self.columns.get(col_ix) {
             match col.key.as_ref() {
-                "name" => item.name.clone().into_any_element(),
+                "name" => h_flex()
+                    .gap_1()
+                    .child(render_favourite_button(item, index, self.backend_handle.clone()).small())
+                    .child(item.name.clone())
+                    .into_any_element(),
                 "version" => item.configuration.minecraft_version.as_str().into_any_element(),
+                "tags" => item.configuration.tags.join(", ").into_any_element(),
                 "controls" => {
-                    let play_button = render_play_button(item, row_ix, self.backend_handle.clone());
+                    let play_button = render_play_button(item, index, self.backend_handle.clone());
 
                     h_flex()
                         .size_full()
                         .gap_2()
                         .border_r_4()
                         .child(play_button.w_1_2().small())
-                        .child(Button::new("view").w_1_2().small().info().label(ts!("instance.view")).on_click({
+                        .child(Button::new(("view", index)).w_1_2().small().info().label(ts!("instance.view")).on_click({
                             let name = item.name.clone();
                             move |_, window, cx| {
                                 root::switch_page(ui::PageType::InstancePage { name: name.clone() },
@@ -194,6 +318,31 @@ impl TableDelegate for InstanceList {
     }
 }
 
+pub fn render_group_header(index: usize, name: &Arc<str>, count: usize, collapsed: bool) -> Button {
+    let name = if name.is_empty() {
+        ts!("instance.groups.ungrouped")
+    } else {
+        SharedString::from(name.to_string())
+    };
+
+    Button::new(("group", index))
+        .ghost()
+        .icon(if collapsed { PandoraIcon::ChevronRight } else { PandoraIcon::ChevronDown })
+        .label(format!("{name} ({count})"))
+}
+
+fn render_favourite_button(item: &InstanceEntry, index: usize, backend_handle: BackendHandle) -> Button {
+    let id = item.id;
+    let favourite = item.configuration.favourite;
+    Button::new(("favourite", index))
+        .icon(if favourite { PandoraIcon::Star } else { PandoraIcon::StarOff })
+        .ghost()
+        .tooltip(if favourite { ts!("instance.favourite.remove") } else { ts!("instance.favourite.add") })
+        .on_click(move |_, _, _| {
+            backend_handle.send(MessageToBackend::SetInstanceFavourite { id, favourite: !favourite });
+        })
+}
+
 fn render_play_button(item: &InstanceEntry, index: usize, backend_handle: BackendHandle) -> Button {
     let name = item.name.clone();
     let id = item.id;
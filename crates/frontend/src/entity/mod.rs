@@ -1,6 +1,6 @@
 use std::{path::Path, sync::Arc};
 
-use bridge::{handle::BackendHandle, message::{MessageToBackend, SkinLibrary}, serial::AtomicOptionSerial};
+use bridge::{handle::BackendHandle, instance::ServerStatusTrend, message::{MessageToBackend, SkinLibrary}, serial::AtomicOptionSerial};
 use gpui::{App, Entity, Global};
 use parking_lot::RwLock;
 
@@ -34,6 +34,12 @@ struct SkinLibraryWrapper {
 
 impl Global for SkinLibraryWrapper {}
 
+struct ServerStatusTrendsWrapper {
+    trends: Arc<[ServerStatusTrend]>,
+}
+
+impl Global for ServerStatusTrendsWrapper {}
+
 impl DataEntities {
     pub fn use_skin_library<'a>(&self, cx: &'a mut App) -> Option<&'a SkinLibrary> {
         let wrapper = cx.global::<SkinLibraryWrapper>();
@@ -57,10 +63,22 @@ impl DataEntities {
         cx.refresh_windows();
     }
 
+    pub fn server_status_trend<'a>(address: &str, cx: &'a App) -> Option<&'a ServerStatusTrend> {
+        cx.global::<ServerStatusTrendsWrapper>().trends.iter().find(|trend| &*trend.address == address)
+    }
+
+    pub fn set_server_status_trends(&self, trends: Arc<[ServerStatusTrend]>, cx: &mut App) {
+        cx.global_mut::<ServerStatusTrendsWrapper>().trends = trends;
+        cx.refresh_windows();
+    }
+
     pub fn init_globals(cx: &mut App) {
         cx.set_global(SkinLibraryWrapper {
             skin_library: None,
             serial: AtomicOptionSerial::default(),
         });
+        cx.set_global(ServerStatusTrendsWrapper {
+            trends: Arc::from([]),
+        });
     }
 }
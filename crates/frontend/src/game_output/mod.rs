@@ -8,7 +8,7 @@ use gpui_component::{
 use lru::LruCache;
 use rustc_hash::FxBuildHasher;
 
-use bridge::{game_output::GameOutputLogLevel, keep_alive::KeepAlive};
+use bridge::{game_output::GameOutputLogLevel, handle::BackendHandle, instance::InstanceID, keep_alive::KeepAlive, message::MessageToBackend};
 
 use crate::{CloseWindow, icon::PandoraIcon, ts};
 
@@ -831,6 +831,12 @@ pub struct GameOutputRoot {
     search_state: Entity<InputState>,
     _search_task: Task<()>,
     _search_input_subscription: Subscription,
+    input_state: Entity<InputState>,
+    _input_subscription: Subscription,
+    // Only servers read commands from stdin
+    accepts_input: bool,
+    instance: InstanceID,
+    backend_handle: BackendHandle,
     focus_handle: FocusHandle,
 }
 
@@ -939,6 +945,9 @@ impl GameOutputRoot {
     pub fn new(
         keep_alive: KeepAlive,
         game_output: Entity<GameOutput>,
+        instance: InstanceID,
+        accepts_input: bool,
+        backend_handle: BackendHandle,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -948,6 +957,9 @@ impl GameOutputRoot {
 
         let _search_input_subscription = cx.subscribe_in(&search_state, window, Self::on_search_input_event);
 
+        let input_state = cx.new(|cx| InputState::new(window, cx).placeholder(ts!("system.game_output_input")));
+        let _input_subscription = cx.subscribe_in(&input_state, window, Self::on_input_event);
+
         let focus_handle = cx.focus_handle();
         focus_handle.focus(window, cx);
 
@@ -958,10 +970,50 @@ impl GameOutputRoot {
             search_state,
             _search_task: Task::ready(()),
             _search_input_subscription,
+            input_state,
+            _input_subscription,
+            accepts_input,
+            instance,
+            backend_handle,
             focus_handle,
         }
     }
 
+    fn on_input_event(
+        &mut self,
+        state: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let InputEvent::PressEnter { secondary: false } = event else {
+            return;
+        };
+
+        let line = state.read(cx).value();
+        if line.trim().is_empty() {
+            return;
+        }
+
+        self.backend_handle.send(MessageToBackend::SendGameInput {
+            id: self.instance,
+            line: line.as_str().into(),
+        });
+
+        // Echo the input, since the process doesn't
+        let text: Arc<[Arc<str>]> = Arc::new([format!("> {}", line).into()]);
+        self.game_output.update(cx, |game_output, _| {
+            game_output.add(chrono::Utc::now().timestamp_millis(), GameOutputLogLevel::Other, text);
+        });
+        {
+            let mut scroll_state = self.scroll_handler.state.borrow_mut();
+            scroll_state.scrolling = GameOutputScrolling::Bottom;
+        }
+
+        state.update(cx, |input, cx| input.set_value("", window, cx));
+        cx.notify();
+    }
+
     fn on_search_input_event(
         &mut self,
         state: &Entity<InputState>,
@@ -1097,6 +1149,9 @@ impl Render for GameOutputRoot {
                             .child(Scrollbar::vertical(&self.scroll_handler)),
                     ),
             )
+            .when(self.accepts_input, |this| {
+                this.child(Input::new(&self.input_state).prefix(Icon::new(PandoraIcon::ChevronRight).small()))
+            })
             .on_scroll_wheel(cx.listener(|root, event: &ScrollWheelEvent, _, cx| {
                 let state = root.scroll_handler.state.borrow();
                 let delta = event.delta.pixel_delta(state.line_height).y;
@@ -1,4 +1,4 @@
-use std::{io::Write, path::Path, sync::Arc, time::Duration};
+use std::{collections::BTreeSet, io::Write, path::Path, sync::Arc, time::Duration};
 
 use gpui::{App, SharedString, Task};
 use rand::RngCore;
@@ -55,6 +55,9 @@ pub struct InterfaceConfig {
     pub instance_subpage: InstanceSubpageType,
     #[serde(default, deserialize_with = "schema::try_deserialize")]
     pub collapse_capes_in_skins_page: bool,
+    // Groups on the instances page that are collapsed, an empty name is used for ungrouped instances
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub collapsed_instance_groups: BTreeSet<Arc<str>>,
 }
 
 fn default_modrinth_project_type() -> ModrinthProjectType {
@@ -87,6 +90,7 @@ impl Default for InterfaceConfig {
             instances_view_mode: Default::default(),
             instance_subpage: Default::default(),
             collapse_capes_in_skins_page: false,
+            collapsed_instance_groups: Default::default(),
         }
     }
 }
@@ -358,6 +358,13 @@ pub(crate) fn labelled(label: impl Into<SharedString>, element: impl IntoElement
     gpui_component::v_flex().gap_0p5().child(div().text_sm().font_medium().child(label.into())).child(element)
 }
 
+pub(crate) fn open_file(path: &Path, window: &mut Window, cx: &mut App) {
+    if let Err(err) = open::that_detached(path) {
+        let notification: Notification = (NotificationType::Error, ts!("file_system.open_file.error", err = err)).into();
+        window.push_notification(notification.autohide(false), cx);
+    }
+}
+
 pub(crate) fn open_folder(path: &Path, window: &mut Window, cx: &mut App) {
     let mut is_dir = path.is_dir();
     if !is_dir && !path.exists() {
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use bridge::{
+    handle::BackendHandle, install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget}, instance::{ContentDependencyIssue, ContentDependencyIssueKind, InstanceID}, message::MessageToBackend
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme, WindowExt, button::{Button, ButtonVariants}, h_flex, v_flex
+};
+use schema::{content::ContentSource, loader::Loader};
+use ustr::Ustr;
+
+use crate::ts;
+
+pub fn open_content_dependencies(
+    instance: InstanceID,
+    instance_loader: Loader,
+    instance_version: Ustr,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    backend_handle.send(MessageToBackend::AnalyzeContentDependencies {
+        id: instance,
+        channel: send,
+    });
+
+    window.spawn(cx, async move |cx| {
+        let Ok(issues) = recv.await else {
+            return;
+        };
+        _ = cx.update(move |window, cx| {
+            show_dependencies_dialog(instance, instance_loader, instance_version, issues, backend_handle, window, cx);
+        });
+    }).detach();
+}
+
+fn show_dependencies_dialog(
+    instance: InstanceID,
+    instance_loader: Loader,
+    instance_version: Ustr,
+    issues: Arc<[ContentDependencyIssue]>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let rows = issues.iter().enumerate().map(|(index, issue)| {
+            let description = issue_text(issue);
+
+            let row = h_flex()
+                .gap_3()
+                .justify_between()
+                .child(v_flex()
+                    .child(issue.content_name.clone())
+                    .child(div().text_sm().text_color(cx.theme().muted_foreground).child(description)));
+
+            if let ContentDependencyIssueKind::Missing { modrinth_project_id: Some(project_id) } = &issue.kind {
+                row.child(Button::new(("fix", index)).label(ts!("instance.content.dependencies.fix")).success().on_click({
+                    let backend_handle = backend_handle.clone();
+                    let project_id = project_id.clone();
+                    move |_, window, cx| {
+                        let content_install = ContentInstall {
+                            target: InstallTarget::Instance(instance),
+                            loader_hint: instance_loader,
+                            version_hint: Some(instance_version.into()),
+                            files: [ContentInstallFile {
+                                replace_old: None,
+                                path: ContentInstallPath::Automatic,
+                                download: ContentDownload::Modrinth {
+                                    project_id: project_id.clone(),
+                                    version_id: None,
+                                    install_dependencies: true,
+                                },
+                                content_source: ContentSource::ModrinthProject {
+                                    project_id: project_id.clone(),
+                                },
+                            }].into(),
+                        };
+                        window.close_dialog(cx);
+                        crate::root::start_install(content_install, &backend_handle, window, cx);
+                    }
+                }))
+            } else {
+                row
+            }
+        }).collect::<Vec<_>>();
+
+        let content = if rows.is_empty() {
+            v_flex().child(ts!("instance.content.dependencies.no_issues"))
+        } else {
+            v_flex().id("dependency-issues").gap_2().max_h_96().overflow_y_scroll().children(rows)
+        };
+
+        dialog
+            .title(ts!("instance.content.dependencies.title"))
+            .child(content)
+    });
+}
+
+fn issue_text(issue: &ContentDependencyIssue) -> SharedString {
+    let id = issue.dependency_id.clone();
+    let requirement = issue.requirement.clone();
+    match &issue.kind {
+        ContentDependencyIssueKind::Missing { .. } => ts!("instance.content.dependencies.missing", id = id, requirement = requirement),
+        ContentDependencyIssueKind::VersionMismatch { installed_version } => ts!("instance.content.dependencies.version_mismatch", id = id, requirement = requirement, installed = installed_version),
+        ContentDependencyIssueKind::Incompatible { installed_version } => ts!("instance.content.dependencies.incompatible", id = id, installed = installed_version),
+        ContentDependencyIssueKind::Discouraged { installed_version } => ts!("instance.content.dependencies.discouraged", id = id, installed = installed_version),
+    }
+}
@@ -0,0 +1,76 @@
+use bridge::{crash_report::{CrashReportAnalysis, CrashReportKind}, handle::BackendHandle};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme, WindowExt, button::{Button, ButtonVariants}, h_flex, v_flex
+};
+use ustr::Ustr;
+
+use crate::ts;
+
+pub fn show_crash_report(
+    instance_name: Ustr,
+    analysis: CrashReportAnalysis,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = ts!("instance.crash.title", name = instance_name);
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let summary = match (analysis.kind, analysis.exit_code) {
+            (CrashReportKind::Game, _) => ts!("instance.crash.game_report"),
+            (CrashReportKind::Jvm, _) => ts!("instance.crash.jvm_report"),
+            (CrashReportKind::Unknown, Some(code)) => ts!("instance.crash.no_report_code", code = code),
+            (CrashReportKind::Unknown, None) => ts!("instance.crash.no_report"),
+        };
+
+        let mut content = v_flex()
+            .gap_3()
+            .child(div().text_sm().text_color(cx.theme().muted_foreground).child(summary));
+
+        if let Some(description) = &analysis.description {
+            content = content.child(crate::labelled(ts!("instance.crash.description"), description.clone()));
+        }
+
+        if !analysis.suspected_mods.is_empty() {
+            content = content.child(crate::labelled(ts!("instance.crash.suspected_mods"), analysis.suspected_mods.join(", ")));
+        }
+
+        if !analysis.stack_trace.is_empty() {
+            let stack_trace = v_flex()
+                .id("stack-trace")
+                .max_h_64()
+                .overflow_scroll()
+                .p_2()
+                .rounded(cx.theme().radius)
+                .bg(cx.theme().secondary)
+                .font_family("monospace")
+                .text_xs()
+                .children(analysis.stack_trace.iter().map(|line| div().whitespace_nowrap().child(line.clone())));
+            content = content.child(crate::labelled(ts!("instance.crash.stack_trace"), stack_trace));
+        }
+
+        if let Some(report_path) = &analysis.report_path {
+            content = content.child(h_flex()
+                .gap_2()
+                .child(Button::new("open").label(ts!("instance.crash.open_report")).on_click({
+                    let report_path = report_path.clone();
+                    move |_, window, cx| {
+                        crate::open_file(&report_path, window, cx);
+                    }
+                }))
+                .child(Button::new("upload").success().label(ts!("instance.crash.upload_report")).on_click({
+                    let report_path = report_path.clone();
+                    let backend_handle = backend_handle.clone();
+                    move |_, window, cx| {
+                        window.close_dialog(cx);
+                        crate::root::upload_log_file(report_path.clone(), &backend_handle, window, cx);
+                    }
+                })));
+        }
+
+        dialog
+            .title(title.clone())
+            .child(content)
+    });
+}
@@ -5,7 +5,7 @@ use gpui::{prelude::*, *};
 use gpui_component::{
     ActiveTheme, Icon, Selectable, WindowExt, alert::Alert, button::{Button, ButtonGroup, ButtonVariants}, checkbox::Checkbox, dialog::Dialog, h_flex, input::{Input, InputEvent, InputState}, select::{Select, SelectState}, skeleton::Skeleton, v_flex
 };
-use schema::{loader::Loader, version_manifest::{MinecraftVersionManifest, MinecraftVersionType}};
+use schema::{instance::InstanceKind, loader::Loader, version_manifest::{MinecraftVersionManifest, MinecraftVersionType}};
 
 use crate::{entity::{instance::InstanceEntries, metadata::{AsMetadataResult, FrontendMetadata, FrontendMetadataResult, FrontendMetadataState}}, icon::PandoraIcon, interface_config::InterfaceConfig, pages::instances_page::VersionList, png_render_cache, ts};
 
@@ -16,6 +16,7 @@ struct CreateInstanceModalState {
     minecraft_version_dropdown: Entity<SelectState<VersionList>>,
     name_input_state: Entity<InputState>,
     selected_loader: Loader,
+    dedicated_server: bool,
     loaded_versions: bool,
     error_loading_versions: Option<SharedString>,
     name_invalid: bool,
@@ -74,6 +75,7 @@ impl CreateInstanceModalState {
             minecraft_version_dropdown,
             name_input_state,
             selected_loader: Loader::Vanilla,
+            dedicated_server: false,
             loaded_versions: false,
             error_loading_versions: None,
             name_invalid: false,
@@ -261,12 +263,18 @@ impl CreateInstanceModalState {
                         .label(ts!("modrinth.category.neoforge"))
                         .selected(self.selected_loader == Loader::NeoForge),
                 )
+                .child(
+                    Button::new("loader-quilt")
+                        .label(ts!("modrinth.category.quilt"))
+                        .selected(self.selected_loader == Loader::Quilt),
+                )
                 .on_click(cx.listener(move |this, selected: &Vec<usize>, _, _| {
                     match selected.first() {
                         Some(0) => this.selected_loader = Loader::Vanilla,
                         Some(1) => this.selected_loader = Loader::Fabric,
                         Some(2) => this.selected_loader = Loader::Forge,
                         Some(3) => this.selected_loader = Loader::NeoForge,
+                        Some(4) => this.selected_loader = Loader::Quilt,
                         _ => {},
                     };
                 }))
@@ -281,6 +289,12 @@ impl CreateInstanceModalState {
             ))
             .child(crate::labelled(ts!("instance.version"), v_flex().gap_2().child(version_dropdown).child(show_snapshots_button)))
             .child(crate::labelled(ts!("instance.modloader"), loader_button_group))
+            .child(Checkbox::new("dedicated_server")
+                .checked(self.dedicated_server)
+                .label(ts!("instance.dedicated_server"))
+                .on_click(cx.listener(move |this, checked, _, _| {
+                    this.dedicated_server = *checked;
+                })))
             .child(h_flex().gap_2().child(Button::new("icon").icon(PandoraIcon::Plus).label(ts!("instance.select_icon")).on_click({
                 let entity = cx.entity();
                 move |_, window, cx| {
@@ -339,7 +353,9 @@ impl CreateInstanceModalState {
                                 name: name.as_str().into(),
                                 version: selected_version.as_str().into(),
                                 loader: this.selected_loader,
+                                kind: if this.dedicated_server { InstanceKind::Server } else { InstanceKind::Client },
                                 icon: this.icon.clone(),
+                                channel: None,
                             });
                             window.close_dialog(cx);
                         }))))
@@ -587,6 +587,7 @@ impl InstallDialog {
                                     CurseforgeModLoaderType::Fabric => loader_hint = Loader::Fabric,
                                     CurseforgeModLoaderType::Forge => loader_hint = Loader::Forge,
                                     CurseforgeModLoaderType::NeoForge => loader_hint = Loader::NeoForge,
+                                    CurseforgeModLoaderType::Quilt => loader_hint = Loader::Quilt,
                                     _ => {}
                                 }
                             }
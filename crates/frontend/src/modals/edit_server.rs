@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use bridge::{handle::BackendHandle, instance::{InstanceID, InstanceServerSummary, ServerEntryRef, ServerResourcePackPolicy}, message::MessageToBackend};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    Selectable, WindowExt, button::{Button, ButtonGroup, ButtonVariants}, checkbox::Checkbox, input::{Input, InputState}, v_flex
+};
+use parking_lot::RwLock;
+
+use crate::{entity::instance::InstanceEntries, ts};
+
+const RESOURCE_PACK_POLICIES: [ServerResourcePackPolicy; 3] = [
+    ServerResourcePackPolicy::Prompt,
+    ServerResourcePackPolicy::Enabled,
+    ServerResourcePackPolicy::Disabled,
+];
+
+struct ServerDialogState {
+    resource_packs: ServerResourcePackPolicy,
+    // Other instances the server can be added to at the same time
+    targets: Vec<(InstanceID, SharedString, bool)>,
+}
+
+pub fn resource_pack_policy_name(policy: ServerResourcePackPolicy) -> SharedString {
+    match policy {
+        ServerResourcePackPolicy::Prompt => ts!("instance.server_dialog.resource_packs_prompt"),
+        ServerResourcePackPolicy::Enabled => ts!("instance.server_dialog.resource_packs_enabled"),
+        ServerResourcePackPolicy::Disabled => ts!("instance.server_dialog.resource_packs_disabled"),
+    }
+}
+
+pub fn open_add_server(
+    instance: InstanceID,
+    instances: &Entity<InstanceEntries>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let targets = instances.read(cx).entries.iter()
+        .filter(|(id, _)| **id != instance)
+        .map(|(id, entry)| (*id, entry.read(cx).name.clone(), false))
+        .collect();
+
+    open_server_dialog(instance, None, "".into(), "".into(), ServerResourcePackPolicy::Prompt, targets, backend_handle, window, cx);
+}
+
+pub fn open_edit_server(
+    instance: InstanceID,
+    server: &InstanceServerSummary,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let name = SharedString::new(server.name.clone());
+    let ip = SharedString::new(server.ip.clone());
+    open_server_dialog(instance, Some(server.entry_ref()), name, ip, server.resource_packs, Vec::new(), backend_handle, window, cx);
+}
+
+fn open_server_dialog(
+    instance: InstanceID,
+    server: Option<ServerEntryRef>,
+    name: SharedString,
+    ip: SharedString,
+    resource_packs: ServerResourcePackPolicy,
+    targets: Vec<(InstanceID, SharedString, bool)>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = match &server {
+        Some(_) => ts!("instance.server_dialog.edit_title", name = name),
+        None => ts!("instance.server_dialog.add_title"),
+    };
+
+    let name_input = cx.new(|cx| InputState::new(window, cx).placeholder("Minecraft Server").default_value(name));
+    let ip_input = cx.new(|cx| InputState::new(window, cx).placeholder("mc.example.com").default_value(ip));
+
+    let state = Arc::new(RwLock::new(ServerDialogState {
+        resource_packs,
+        targets,
+    }));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let selected_policy = state.read().resource_packs;
+
+        let mut policy_group = ButtonGroup::new("resource_packs").outline();
+        for (policy_index, policy) in RESOURCE_PACK_POLICIES.iter().enumerate() {
+            policy_group = policy_group.child(Button::new(("policy", policy_index))
+                .label(resource_pack_policy_name(*policy))
+                .selected(*policy == selected_policy));
+        }
+        let policy_group = policy_group.on_click({
+            let state = state.clone();
+            move |selected: &Vec<usize>, _, _| {
+                if let Some(index) = selected.first() && let Some(policy) = RESOURCE_PACK_POLICIES.get(*index) {
+                    state.write().resource_packs = *policy;
+                }
+            }
+        });
+
+        let checkboxes = state.read().targets.iter().enumerate().map(|(target_index, (_, name, checked))| {
+            Checkbox::new(("target", target_index)).label(name.clone()).checked(*checked).on_click({
+                let state = state.clone();
+                move |value: &bool, _, _| {
+                    if let Some(target) = state.write().targets.get_mut(target_index) {
+                        target.2 = *value;
+                    }
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        let confirm_label = if server.is_some() {
+            ts!("instance.server_dialog.save")
+        } else {
+            ts!("instance.server_dialog.add")
+        };
+
+        let content = v_flex()
+            .gap_3()
+            .child(crate::labelled(ts!("instance.server_dialog.name"), Input::new(&name_input)))
+            .child(crate::labelled(ts!("instance.server_dialog.address"), Input::new(&ip_input)))
+            .child(crate::labelled(ts!("instance.server_dialog.resource_packs"), policy_group))
+            .when(!checkboxes.is_empty(), |this| {
+                this.child(crate::labelled(ts!("instance.server_dialog.also_add_to"),
+                    v_flex().id("targets").gap_1().max_h_64().overflow_y_scroll().children(checkboxes)))
+            })
+            .child(Button::new("confirm").success().label(confirm_label).on_click({
+                let server = server.clone();
+                let state = state.clone();
+                let backend_handle = backend_handle.clone();
+                let name_input = name_input.clone();
+                let ip_input = ip_input.clone();
+                move |_, window, cx| {
+                    let name: Arc<str> = name_input.read(cx).value().trim().into();
+                    let ip: Arc<str> = ip_input.read(cx).value().trim().into();
+                    if ip.is_empty() {
+                        return;
+                    }
+
+                    let state = state.read();
+                    if let Some(server) = &server {
+                        backend_handle.send(MessageToBackend::EditServer {
+                            id: instance,
+                            server: server.clone(),
+                            name,
+                            ip,
+                            resource_packs: state.resource_packs,
+                        });
+                    } else {
+                        let ids = std::iter::once(instance)
+                            .chain(state.targets.iter().filter(|(_, _, checked)| *checked).map(|(id, _, _)| *id))
+                            .collect();
+                        backend_handle.send(MessageToBackend::AddServer {
+                            ids,
+                            name,
+                            ip,
+                            resource_packs: state.resource_packs,
+                        });
+                    }
+
+                    window.close_dialog(cx);
+                }
+            }));
+
+        dialog
+            .title(title.clone())
+            .child(content)
+    });
+}
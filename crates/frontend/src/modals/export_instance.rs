@@ -0,0 +1,137 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::{export::{EXCLUDED_BY_DEFAULT, InstanceExportFormat}, handle::BackendHandle, instance::InstanceID, message::MessageToBackend, modal_action::ModalAction};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    Selectable, WindowExt, button::{Button, ButtonGroup, ButtonVariants}, checkbox::Checkbox, v_flex
+};
+use parking_lot::RwLock;
+use strum::IntoEnumIterator;
+
+use crate::ts;
+
+struct ExportState {
+    format: InstanceExportFormat,
+    entries: Vec<(Arc<str>, bool)>,
+}
+
+pub fn open_export_instance(
+    instance: InstanceID,
+    instance_name: SharedString,
+    dot_minecraft_folder: Arc<Path>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let mut entries = Vec::new();
+    if let Ok(read_dir) = std::fs::read_dir(&dot_minecraft_folder) {
+        for entry in read_dir {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.starts_with(".pandora.") {
+                continue;
+            }
+            let included = !EXCLUDED_BY_DEFAULT.contains(&name.as_str());
+            entries.push((Arc::<str>::from(name), included));
+        }
+    }
+    entries.sort_by(|(a, _), (b, _)| lexical_sort::natural_lexical_cmp(a, b));
+
+    let state = Arc::new(RwLock::new(ExportState {
+        format: InstanceExportFormat::Modrinth,
+        entries,
+    }));
+    let export_task = Arc::new(RwLock::new(Task::ready(())));
+
+    let title = ts!("instance.export_dialog.title", name = instance_name);
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let selected_format = state.read().format;
+
+        let mut format_group = ButtonGroup::new("format").outline();
+        for format in InstanceExportFormat::iter() {
+            format_group = format_group.child(Button::new(SharedString::from(format!("format-{format}")))
+                .label(format!("{format} (.{})", format.extension()))
+                .selected(format == selected_format));
+        }
+        let format_group = format_group
+            .on_click({
+                let state = state.clone();
+                move |selected: &Vec<usize>, _, _| {
+                    if let Some(index) = selected.first() && let Some(format) = InstanceExportFormat::iter().nth(*index) {
+                        state.write().format = format;
+                    }
+                }
+            });
+
+        let checkboxes = state.read().entries.iter().enumerate().map(|(index, (name, included))| {
+            Checkbox::new(("entry", index)).label(SharedString::new(name.clone())).checked(*included).on_click({
+                let state = state.clone();
+                move |value: &bool, _, _| {
+                    if let Some(entry) = state.write().entries.get_mut(index) {
+                        entry.1 = *value;
+                    }
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        let content = v_flex()
+            .gap_3()
+            .child(crate::labelled(ts!("instance.export_dialog.format"), format_group))
+            .child(crate::labelled(ts!("instance.export_dialog.include"),
+                v_flex().id("entries").gap_1().max_h_96().overflow_y_scroll().children(checkboxes)))
+            .child(Button::new("export").success().label(ts!("instance.export_dialog.export")).on_click({
+                let state = state.clone();
+                let export_task = export_task.clone();
+                let backend_handle = backend_handle.clone();
+                let instance_name = instance_name.clone();
+                move |_, window, cx| {
+                    let (format, include) = {
+                        let state = state.read();
+                        let include: Vec<Arc<str>> = state.entries.iter()
+                            .filter(|(_, included)| *included)
+                            .map(|(name, _)| name.clone())
+                            .collect();
+                        (state.format, include)
+                    };
+
+                    let user_dirs = directories::UserDirs::new();
+                    let directory = user_dirs.as_ref()
+                        .and_then(directories::UserDirs::download_dir).unwrap_or(Path::new("."));
+                    let suggested_name = format!("{instance_name}.{}", format.extension());
+                    let receiver = cx.prompt_for_new_path(directory, Some(&suggested_name));
+
+                    let backend_handle = backend_handle.clone();
+                    let instance_name = instance_name.clone();
+                    *export_task.write() = window.spawn(cx, async move |cx| {
+                        let Ok(Ok(Some(path))) = receiver.await else {
+                            return;
+                        };
+
+                        let modal_action = ModalAction::default();
+                        backend_handle.send(MessageToBackend::ExportInstance {
+                            id: instance,
+                            format,
+                            path,
+                            include,
+                            modal_action: modal_action.clone(),
+                        });
+
+                        _ = cx.update(move |window, cx| {
+                            window.close_dialog(cx);
+                            let title = ts!("instance.export_dialog.exporting", name = instance_name);
+                            crate::modals::generic::show_modal(window, cx, title, ts!("instance.export_dialog.error"), modal_action);
+                        });
+                    });
+                }
+            }));
+
+        dialog
+            .title(title.clone())
+            .child(content)
+    });
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use bridge::{handle::BackendHandle, instance::{InstanceID, InstanceSnapshotSummary}, message::MessageToBackend, modal_action::ModalAction};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme, WindowExt, button::{Button, ButtonVariants}, h_flex, v_flex
+};
+use schema::instance_snapshot::InstanceSnapshotReason;
+
+use crate::ts;
+
+pub fn open_instance_snapshots(
+    instance: InstanceID,
+    instance_name: SharedString,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    backend_handle.send(MessageToBackend::GetInstanceSnapshots {
+        id: instance,
+        channel: send,
+    });
+
+    window.spawn(cx, async move |cx| {
+        let Ok(snapshots) = recv.await else {
+            return;
+        };
+        _ = cx.update(move |window, cx| {
+            show_snapshots_dialog(instance, instance_name, snapshots, backend_handle, window, cx);
+        });
+    }).detach();
+}
+
+fn show_snapshots_dialog(
+    instance: InstanceID,
+    instance_name: SharedString,
+    snapshots: Arc<[InstanceSnapshotSummary]>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = ts!("instance.snapshots_dialog.title", name = instance_name);
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let rows = snapshots.iter().enumerate().map(|(index, snapshot)| {
+            let created = chrono::DateTime::from_timestamp_millis(snapshot.created).unwrap_or_default().with_timezone(&chrono::Local);
+            let created = SharedString::from(format!("{}", created.format("%Y-%m-%d %H:%M:%S")));
+            let details = format!("{} {}, {}", snapshot.loader.name(), snapshot.minecraft_version, ts!("instance.snapshots_dialog.content_count", count = snapshot.content_count));
+
+            h_flex()
+                .gap_3()
+                .justify_between()
+                .child(v_flex()
+                    .child(format!("{created} - {}", reason_text(snapshot.reason)))
+                    .child(div().text_sm().text_color(cx.theme().muted_foreground).child(details)))
+                .child(Button::new(("restore", index)).label(ts!("instance.snapshots_dialog.restore")).warning().on_click({
+                    let backend_handle = backend_handle.clone();
+                    let name = snapshot.name.clone();
+                    let created = created.clone();
+                    move |_, window, cx| {
+                        let modal_action = ModalAction::default();
+                        backend_handle.send(MessageToBackend::RestoreInstanceSnapshot {
+                            id: instance,
+                            snapshot: name.clone(),
+                            modal_action: modal_action.clone(),
+                        });
+
+                        window.close_dialog(cx);
+                        let title = ts!("instance.snapshots_dialog.restoring", name = created.clone());
+                        crate::modals::generic::show_modal(window, cx, title, ts!("instance.snapshots_dialog.error"), modal_action);
+                    }
+                }))
+        }).collect::<Vec<_>>();
+
+        let content = v_flex()
+            .gap_3()
+            .child(div().text_sm().text_color(cx.theme().muted_foreground).child(ts!("instance.snapshots_dialog.description")));
+
+        let content = if rows.is_empty() {
+            content.child(ts!("instance.snapshots_dialog.empty"))
+        } else {
+            content.child(v_flex().id("snapshots").gap_2().max_h_96().overflow_y_scroll().children(rows))
+        };
+
+        dialog
+            .title(title.clone())
+            .child(content)
+    });
+}
+
+fn reason_text(reason: InstanceSnapshotReason) -> SharedString {
+    match reason {
+        InstanceSnapshotReason::UpdateCheck => ts!("instance.snapshots_dialog.reason.update_check"),
+        InstanceSnapshotReason::UpdateContent => ts!("instance.snapshots_dialog.reason.update_content"),
+        InstanceSnapshotReason::LoaderChange => ts!("instance.snapshots_dialog.reason.loader_change"),
+        InstanceSnapshotReason::VersionChange => ts!("instance.snapshots_dialog.reason.version_change"),
+        InstanceSnapshotReason::BeforeRestore => ts!("instance.snapshots_dialog.reason.before_restore"),
+    }
+}
@@ -1,8 +1,15 @@
+pub mod content_dependencies;
+pub mod crash_report;
 pub mod create_instance;
 pub mod curseforge_install;
 pub mod delete_instance;
+pub mod edit_server;
+pub mod export_instance;
 pub mod generic;
+pub mod instance_snapshots;
 pub mod modrinth_install;
 pub mod select_icon;
+pub mod server_eula;
 pub mod settings;
 pub mod update_prompt;
+pub mod world_backups;
@@ -666,6 +666,7 @@ impl InstallDialog {
                                     ModrinthLoader::Fabric => loader_hint = Loader::Fabric,
                                     ModrinthLoader::Forge => loader_hint = Loader::Forge,
                                     ModrinthLoader::NeoForge => loader_hint = Loader::NeoForge,
+                                    ModrinthLoader::Quilt => loader_hint = Loader::Quilt,
                                     _ => {}
                                 }
                             }
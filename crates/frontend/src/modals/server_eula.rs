@@ -0,0 +1,51 @@
+use bridge::{handle::BackendHandle, instance::InstanceID, message::MessageToBackend};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    WindowExt, button::{Button, ButtonVariants}, h_flex, v_flex
+};
+
+use crate::ts;
+
+const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+
+pub fn open_server_eula(
+    id: InstanceID,
+    name: SharedString,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    // Replaces the launch modal, which has nothing left to show
+    window.close_all_dialogs(cx);
+
+    let description = ts!("instance.server_eula.description", name = name);
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let buttons = h_flex()
+            .w_full()
+            .gap_2()
+            .child(Button::new("agree").flex_1().label(ts!("instance.server_eula.agree")).success().on_click({
+                let backend_handle = backend_handle.clone();
+                let name = name.clone();
+                move |_, window, cx| {
+                    backend_handle.send(MessageToBackend::AcceptServerEula { id });
+                    window.close_all_dialogs(cx);
+                    crate::root::start_instance(id, name.clone(), None, &backend_handle, window, cx);
+                }
+            }))
+            .child(Button::new("cancel").flex_1().label(ts!("common.cancel")).on_click(|_, window, cx| {
+                window.close_all_dialogs(cx);
+            }));
+
+        dialog
+            .title(ts!("instance.server_eula.title"))
+            .overlay_closable(false)
+            .child(v_flex()
+                .gap_2()
+                .child(description.clone())
+                .child(Button::new("view").label(ts!("instance.server_eula.view")).on_click(|_, _, cx| {
+                    cx.open_url(EULA_URL);
+                }))
+                .child(buttons))
+    });
+}
@@ -13,15 +13,17 @@ use gpui_component::{
     tab::{Tab, TabBar},
     v_flex, ActiveTheme, Disableable, Sizable, ThemeRegistry,
 };
-use schema::backend_config::{BackendConfig, ProxyConfig, ProxyProtocol};
+use schema::{backend_config::{BackendConfig, InstanceDefaults, ProxyConfig, ProxyProtocol}, instance::{InstanceConfiguration, InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration, InstanceMemoryConfiguration, InstanceSettingMode, InstanceWrapperCommandConfiguration, WindowConfiguration}};
 
-use crate::{entity::DataEntities, icon::PandoraIcon, interface_config::InterfaceConfig, ts};
+use crate::{entity::{DataEntities, instance::InstanceEntries}, icon::PandoraIcon, interface_config::InterfaceConfig, ts};
 
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 enum SettingsTab {
     #[default]
     Interface,
     Network,
+    Java,
+    InstanceDefaults,
 }
 
 struct Settings {
@@ -32,6 +34,7 @@ struct Settings {
     pending_request: bool,
     backend_config: Option<BackendConfig>,
     get_configuration_task: Option<Task<()>>,
+    java_scan_task: Option<Task<()>>,
     // Proxy settings state
     proxy_enabled: bool,
     proxy_protocol_select: Entity<SelectState<Vec<&'static str>>>,
@@ -41,6 +44,17 @@ struct Settings {
     proxy_username_input: Entity<InputState>,
     proxy_password_input: Entity<InputState>,
     proxy_password_changed: bool,
+    // Instance defaults state
+    instances: Entity<InstanceEntries>,
+    default_memory_min_input: Entity<InputState>,
+    default_memory_max_input: Entity<InputState>,
+    default_jvm_flags_input: Entity<InputState>,
+    default_wrapper_command_input: Entity<InputState>,
+    default_jvm_binary_input: Entity<InputState>,
+    default_environment_variables_input: Entity<InputState>,
+    default_game_arguments_input: Entity<InputState>,
+    default_window_width_input: Entity<InputState>,
+    default_window_height_input: Entity<InputState>,
 }
 
 pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut App) -> impl Fn(Sheet, &mut Window, &mut App) -> Sheet + 'static {
@@ -85,6 +99,16 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
             state
         });
 
+        let default_memory_min_input = cx.new(|cx| InputState::new(window, cx).default_value(InstanceMemoryConfiguration::DEFAULT_MIN.to_string()));
+        let default_memory_max_input = cx.new(|cx| InputState::new(window, cx).default_value(InstanceMemoryConfiguration::DEFAULT_MAX.to_string()));
+        let default_jvm_flags_input = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8));
+        let default_wrapper_command_input = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8));
+        let default_jvm_binary_input = cx.new(|cx| InputState::new(window, cx).placeholder("/usr/lib/jvm/java-21/bin/java"));
+        let default_environment_variables_input = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8).placeholder("__GL_SYNC_TO_VBLANK=0"));
+        let default_game_arguments_input = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8));
+        let default_window_width_input = cx.new(|cx| InputState::new(window, cx).default_value(WindowConfiguration::DEFAULT_WIDTH.to_string()));
+        let default_window_height_input = cx.new(|cx| InputState::new(window, cx).default_value(WindowConfiguration::DEFAULT_HEIGHT.to_string()));
+
         let mut settings = Settings {
             selected_tab: SettingsTab::Interface,
             theme_folder,
@@ -93,6 +117,7 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
             pending_request: false,
             backend_config: None,
             get_configuration_task: None,
+            java_scan_task: None,
             proxy_enabled: false,
             proxy_protocol_select,
             proxy_host_input,
@@ -101,6 +126,16 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
             proxy_username_input,
             proxy_password_input,
             proxy_password_changed: false,
+            instances: data.instances.clone(),
+            default_memory_min_input,
+            default_memory_max_input,
+            default_jvm_flags_input,
+            default_wrapper_command_input,
+            default_jvm_binary_input,
+            default_environment_variables_input,
+            default_game_arguments_input,
+            default_window_width_input,
+            default_window_height_input,
         };
 
         cx.subscribe(&settings.proxy_protocol_select, Settings::on_proxy_protocol_changed).detach();
@@ -108,6 +143,16 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
         cx.subscribe(&settings.proxy_port_input, Settings::on_proxy_input_changed).detach();
         cx.subscribe(&settings.proxy_username_input, Settings::on_proxy_input_changed).detach();
         cx.subscribe(&settings.proxy_password_input, Settings::on_proxy_password_changed).detach();
+        cx.subscribe(&settings.default_memory_min_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_memory_max_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_jvm_flags_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_wrapper_command_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_jvm_binary_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_environment_variables_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_game_arguments_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_window_width_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.subscribe(&settings.default_window_height_input, Settings::on_instance_defaults_input_changed).detach();
+        cx.observe(&settings.instances, |_, _, cx| cx.notify()).detach();
 
         settings.update_backend_configuration(window, cx);
 
@@ -154,6 +199,35 @@ impl Settings {
                 settings.proxy_protocol_select.update(cx, |select, cx| {
                     select.set_selected_value(&result.config.proxy.protocol.name(), window, cx);
                 });
+                let defaults = &result.config.instance_defaults;
+                let memory = defaults.memory.unwrap_or_default();
+                settings.default_memory_min_input.update(cx, |input, cx| {
+                    input.set_value(memory.min.to_string(), window, cx);
+                });
+                settings.default_memory_max_input.update(cx, |input, cx| {
+                    input.set_value(memory.max.to_string(), window, cx);
+                });
+                settings.default_jvm_flags_input.update(cx, |input, cx| {
+                    input.set_value(defaults.jvm_flags.as_ref().map(|jvm_flags| jvm_flags.flags.to_string()).unwrap_or_default(), window, cx);
+                });
+                settings.default_wrapper_command_input.update(cx, |input, cx| {
+                    input.set_value(defaults.wrapper_command.as_ref().map(|wrapper_command| wrapper_command.flags.to_string()).unwrap_or_default(), window, cx);
+                });
+                settings.default_jvm_binary_input.update(cx, |input, cx| {
+                    input.set_value(defaults.jvm_binary.as_ref().and_then(|jvm_binary| jvm_binary.path.as_ref()).map(|path| path.to_string_lossy().into_owned()).unwrap_or_default(), window, cx);
+                });
+                settings.default_environment_variables_input.update(cx, |input, cx| {
+                    input.set_value(result.config.instance_defaults.environment_variables.to_string(), window, cx);
+                });
+                settings.default_game_arguments_input.update(cx, |input, cx| {
+                    input.set_value(result.config.instance_defaults.game_arguments.to_string(), window, cx);
+                });
+                settings.default_window_width_input.update(cx, |input, cx| {
+                    input.set_value(result.config.instance_defaults.window.width.to_string(), window, cx);
+                });
+                settings.default_window_height_input.update(cx, |input, cx| {
+                    input.set_value(result.config.instance_defaults.window.height.to_string(), window, cx);
+                });
                 if let Some(ref password) = result.proxy_password {
                     settings.proxy_password_input.update(cx, |input, cx| {
                         input.set_value(password, window, cx);
@@ -346,14 +420,438 @@ impl Settings {
         div
     }
 
+    fn on_instance_defaults_input_changed(
+        &mut self,
+        _state: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Blur = event && let Some(defaults) = self.get_instance_defaults(cx) {
+            self.save_instance_defaults(defaults);
+        }
+    }
+
+    fn get_instance_defaults(&self, cx: &App) -> Option<InstanceDefaults> {
+        let defaults = &self.backend_config.as_ref()?.instance_defaults;
+        let window = defaults.window;
+        let width = self.default_window_width_input.read(cx).value().parse::<u32>().unwrap_or(window.width);
+        let height = self.default_window_height_input.read(cx).value().parse::<u32>().unwrap_or(window.height);
+        let min = self.default_memory_min_input.read(cx).value().parse::<u32>().unwrap_or(InstanceMemoryConfiguration::DEFAULT_MIN);
+        let max = self.default_memory_max_input.read(cx).value().parse::<u32>().unwrap_or(InstanceMemoryConfiguration::DEFAULT_MAX);
+        let jvm_binary = self.default_jvm_binary_input.read(cx).value();
+        let jvm_binary = jvm_binary.trim();
+
+        Some(InstanceDefaults {
+            memory: Some(InstanceMemoryConfiguration {
+                mode: InstanceSettingMode::of(&defaults.memory),
+                min,
+                max,
+            }),
+            wrapper_command: Some(InstanceWrapperCommandConfiguration {
+                mode: InstanceSettingMode::of(&defaults.wrapper_command),
+                flags: self.default_wrapper_command_input.read(cx).value().into(),
+            }),
+            jvm_flags: Some(InstanceJvmFlagsConfiguration {
+                mode: InstanceSettingMode::of(&defaults.jvm_flags),
+                flags: self.default_jvm_flags_input.read(cx).value().into(),
+            }),
+            jvm_binary: Some(InstanceJvmBinaryConfiguration {
+                mode: InstanceSettingMode::of(&defaults.jvm_binary),
+                path: (!jvm_binary.is_empty()).then(|| Path::new(jvm_binary).into()),
+            }),
+            linux_wrapper: defaults.linux_wrapper,
+            system_libraries: defaults.system_libraries.clone(),
+            environment_variables: self.default_environment_variables_input.read(cx).value().into(),
+            game_arguments: self.default_game_arguments_input.read(cx).value().into(),
+            window: WindowConfiguration {
+                width,
+                height,
+                ..window
+            },
+        })
+    }
+
+    fn save_instance_defaults(&mut self, defaults: InstanceDefaults) {
+        let Some(backend_config) = &mut self.backend_config else {
+            return;
+        };
+        if backend_config.instance_defaults == defaults {
+            return;
+        }
+        backend_config.instance_defaults = defaults.clone();
+
+        self.backend_handle.send(MessageToBackend::SetInstanceDefaults {
+            defaults,
+        });
+    }
+
+    fn render_instance_defaults_tab(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(backend_config) = &self.backend_config else {
+            return v_flex().px_4().py_3().child(Spinner::new().large());
+        };
+        let defaults = &backend_config.instance_defaults;
+        let window = defaults.window;
+        let memory_enabled = InstanceSettingMode::of(&defaults.memory) == InstanceSettingMode::Override;
+        let jvm_flags_enabled = InstanceSettingMode::of(&defaults.jvm_flags) == InstanceSettingMode::Override;
+        let wrapper_command_enabled = InstanceSettingMode::of(&defaults.wrapper_command) == InstanceSettingMode::Override;
+        let jvm_binary_enabled = InstanceSettingMode::of(&defaults.jvm_binary) == InstanceSettingMode::Override;
+        let system_libraries = defaults.system_libraries.clone().unwrap_or_default();
+        let system_libraries_enabled = system_libraries.mode == InstanceSettingMode::Override;
+        #[cfg(target_os = "linux")]
+        let linux_wrapper = defaults.linux_wrapper.unwrap_or_default();
+
+        let content = v_flex()
+            .px_4()
+            .py_3()
+            .gap_3()
+            .child(div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(ts!("settings.instance_defaults.note")))
+            .child(crate::labelled(
+                ts!("instance.memory"),
+                v_flex().gap_2()
+                    .child(Self::render_default_toggle("default-memory", memory_enabled, |defaults, mode| {
+                        defaults.memory.get_or_insert_default().mode = mode;
+                    }, cx))
+                    .child(h_flex().gap_1()
+                        .child(NumberInput::new(&self.default_memory_min_input).small().suffix("MiB").disabled(!memory_enabled))
+                        .child(NumberInput::new(&self.default_memory_max_input).small().suffix("MiB").disabled(!memory_enabled)))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.memory) == InstanceSettingMode::Inherit, cx))
+            ))
+            .child(crate::labelled(
+                ts!("instance.jvm_flags"),
+                v_flex().gap_2()
+                    .child(Self::render_default_toggle("default-jvm-flags", jvm_flags_enabled, |defaults, mode| {
+                        defaults.jvm_flags.get_or_insert_default().mode = mode;
+                    }, cx))
+                    .child(Input::new(&self.default_jvm_flags_input).disabled(!jvm_flags_enabled))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.jvm_flags) == InstanceSettingMode::Inherit, cx))
+            ))
+            .child(crate::labelled(
+                ts!("instance.jvm_binary"),
+                v_flex().gap_2()
+                    .child(Self::render_default_toggle("default-jvm-binary", jvm_binary_enabled, |defaults, mode| {
+                        defaults.jvm_binary.get_or_insert_default().mode = mode;
+                    }, cx))
+                    .child(Input::new(&self.default_jvm_binary_input).disabled(!jvm_binary_enabled))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.jvm_binary) == InstanceSettingMode::Inherit, cx))
+            ))
+            .child(crate::labelled(
+                ts!("instance.wrapper_command"),
+                v_flex().gap_2()
+                    .child(Self::render_default_toggle("default-wrapper-command", wrapper_command_enabled, |defaults, mode| {
+                        defaults.wrapper_command.get_or_insert_default().mode = mode;
+                    }, cx))
+                    .child(Input::new(&self.default_wrapper_command_input).disabled(!wrapper_command_enabled))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.wrapper_command) == InstanceSettingMode::Inherit, cx))
+            ))
+            .child(crate::labelled(
+                ts!("instance.system_libraries"),
+                v_flex().gap_2()
+                    .child(Self::render_default_toggle("default-system-libraries", system_libraries_enabled, |defaults, mode| {
+                        defaults.system_libraries.get_or_insert_default().mode = mode;
+                    }, cx))
+                    .child(Checkbox::new("default-system-glfw")
+                        .label(ts!("instance.glfw_lib"))
+                        .checked(system_libraries.override_glfw)
+                        .disabled(!system_libraries_enabled)
+                        .on_click(cx.listener(|settings, value, _, cx| {
+                            if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                                defaults.system_libraries.get_or_insert_default().override_glfw = *value;
+                                settings.save_instance_defaults(defaults);
+                                cx.notify();
+                            }
+                        })))
+                    .child(Checkbox::new("default-system-openal")
+                        .label(ts!("instance.openal_lib"))
+                        .checked(system_libraries.override_openal)
+                        .disabled(!system_libraries_enabled)
+                        .on_click(cx.listener(|settings, value, _, cx| {
+                            if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                                defaults.system_libraries.get_or_insert_default().override_openal = *value;
+                                settings.save_instance_defaults(defaults);
+                                cx.notify();
+                            }
+                        })))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.system_libraries) == InstanceSettingMode::Inherit, cx))
+            ));
+
+        #[cfg(target_os = "linux")]
+        let content = content.child(crate::labelled(
+            ts!("instance.linux.label"),
+            v_flex().gap_2()
+                .child(Self::render_default_toggle("default-linux-wrapper", linux_wrapper.mode == InstanceSettingMode::Override, |defaults, mode| {
+                    defaults.linux_wrapper.get_or_insert_default().mode = mode;
+                }, cx))
+                .child(Checkbox::new("default-use-mangohud")
+                    .label(ts!("instance.linux.use_mangohud"))
+                    .checked(linux_wrapper.use_mangohud)
+                    .disabled(linux_wrapper.mode != InstanceSettingMode::Override)
+                    .on_click(cx.listener(|settings, value, _, cx| {
+                        if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                            defaults.linux_wrapper.get_or_insert_default().use_mangohud = *value;
+                            settings.save_instance_defaults(defaults);
+                            cx.notify();
+                        }
+                    })))
+                .child(Checkbox::new("default-use-gamemode")
+                    .label(ts!("instance.linux.use_gamemode"))
+                    .checked(linux_wrapper.use_gamemode)
+                    .disabled(linux_wrapper.mode != InstanceSettingMode::Override)
+                    .on_click(cx.listener(|settings, value, _, cx| {
+                        if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                            defaults.linux_wrapper.get_or_insert_default().use_gamemode = *value;
+                            settings.save_instance_defaults(defaults);
+                            cx.notify();
+                        }
+                    })))
+                .child(Checkbox::new("default-use-discrete-gpu")
+                    .label(ts!("instance.linux.use_discrete_gpu"))
+                    .checked(linux_wrapper.use_discrete_gpu)
+                    .disabled(linux_wrapper.mode != InstanceSettingMode::Override)
+                    .on_click(cx.listener(|settings, value, _, cx| {
+                        if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                            defaults.linux_wrapper.get_or_insert_default().use_discrete_gpu = *value;
+                            settings.save_instance_defaults(defaults);
+                            cx.notify();
+                        }
+                    })))
+                .child(Checkbox::new("default-disable-gl-threaded-optimizations")
+                    .label(ts!("instance.linux.disable_gl_threaded_optimizations"))
+                    .checked(linux_wrapper.disable_gl_threaded_optimizations)
+                    .disabled(linux_wrapper.mode != InstanceSettingMode::Override)
+                    .on_click(cx.listener(|settings, value, _, cx| {
+                        if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                            defaults.linux_wrapper.get_or_insert_default().disable_gl_threaded_optimizations = *value;
+                            settings.save_instance_defaults(defaults);
+                            cx.notify();
+                        }
+                    })))
+                .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.linux_wrapper) == InstanceSettingMode::Inherit, cx))
+        ));
+
+        content
+            .child(crate::labelled(
+                ts!("instance.environment_variables.label"),
+                v_flex().gap_2()
+                    .child(Input::new(&self.default_environment_variables_input))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.environment_variables) != InstanceSettingMode::Disable, cx))
+            ))
+            .child(crate::labelled(
+                ts!("instance.game_arguments.label"),
+                v_flex().gap_2()
+                    .child(Input::new(&self.default_game_arguments_input))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.game_arguments) != InstanceSettingMode::Disable, cx))
+            ))
+            .child(crate::labelled(
+                ts!("instance.window.label"),
+                v_flex().gap_2()
+                    .child(Checkbox::new("default-window-custom-size")
+                        .label(ts!("instance.window.custom_size"))
+                        .checked(window.custom_size)
+                        .on_click(cx.listener(|settings, value, _, cx| {
+                            if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                                defaults.window.custom_size = *value;
+                                settings.save_instance_defaults(defaults);
+                                cx.notify();
+                            }
+                        })))
+                    .child(h_flex().gap_1()
+                        .child(NumberInput::new(&self.default_window_width_input).small().disabled(!window.custom_size))
+                        .child("×")
+                        .child(NumberInput::new(&self.default_window_height_input).small().disabled(!window.custom_size)))
+                    .child(Checkbox::new("default-window-maximised")
+                        .label(ts!("instance.window.maximised"))
+                        .checked(window.maximised)
+                        .on_click(cx.listener(|settings, value, _, cx| {
+                            if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                                defaults.window.maximised = *value;
+                                settings.save_instance_defaults(defaults);
+                                cx.notify();
+                            }
+                        })))
+                    .child(Checkbox::new("default-window-fullscreen")
+                        .label(ts!("instance.window.fullscreen"))
+                        .checked(window.fullscreen)
+                        .on_click(cx.listener(|settings, value, _, cx| {
+                            if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                                defaults.window.fullscreen = *value;
+                                settings.save_instance_defaults(defaults);
+                                cx.notify();
+                            }
+                        })))
+                    .child(self.render_affected_instances(|configuration| InstanceSettingMode::of(&configuration.window) == InstanceSettingMode::Inherit, cx))
+            ))
+    }
+
+    fn render_default_toggle(id: &'static str, enabled: bool, set_mode: fn(&mut InstanceDefaults, InstanceSettingMode), cx: &mut Context<Self>) -> Checkbox {
+        Checkbox::new(id)
+            .label(ts!("settings.instance_defaults.enabled"))
+            .checked(enabled)
+            .on_click(cx.listener(move |settings, value: &bool, _, cx| {
+                if let Some(mut defaults) = settings.get_instance_defaults(cx) {
+                    set_mode(&mut defaults, InstanceSettingMode::from_enabled(*value));
+                    settings.save_instance_defaults(defaults);
+                    cx.notify();
+                }
+            }))
+    }
+
+    // Lists the instances that use the default, so it's clear what changing it affects
+    fn render_affected_instances(&self, uses_default: fn(&InstanceConfiguration) -> bool, cx: &App) -> Div {
+        let names: Vec<String> = self.instances.read(cx).entries.values()
+            .map(|entry| entry.read(cx))
+            .filter(|entry| uses_default(&entry.configuration))
+            .map(|entry| entry.name.to_string())
+            .collect();
+
+        const MAX_NAMES: usize = 5;
+        let text = if names.is_empty() {
+            ts!("settings.instance_defaults.affected.none")
+        } else if names.len() <= MAX_NAMES {
+            ts!("settings.instance_defaults.affected.some", names = names.join(", "))
+        } else {
+            ts!("settings.instance_defaults.affected.more", names = names[..MAX_NAMES].join(", "), count = names.len() - MAX_NAMES)
+        };
+
+        div().text_sm().text_color(cx.theme().muted_foreground).child(text)
+    }
+
+    fn scan_java_runtimes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.java_scan_task.is_some() {
+            return;
+        }
+
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.java_scan_task = Some(cx.spawn_in(window, async move |page, cx| {
+            _ = recv.await;
+            let _ = page.update_in(cx, move |settings, window, cx| {
+                settings.java_scan_task = None;
+                settings.update_backend_configuration(window, cx);
+                cx.notify();
+            });
+        }));
+
+        self.backend_handle.send(MessageToBackend::RefreshJavaRuntimes {
+            channel: send,
+        });
+        cx.notify();
+    }
+
+    fn render_java_tab(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(backend_config) = &self.backend_config else {
+            return v_flex().px_4().py_3().child(Spinner::new().large());
+        };
+        let java_runtimes = &backend_config.java_runtimes;
+        let scanning = self.java_scan_task.is_some();
+
+        let mut runtimes = v_flex().gap_2();
+        if java_runtimes.discovered.is_empty() {
+            runtimes = runtimes.child(div().text_sm().text_color(cx.theme().muted_foreground).child(ts!("settings.java.none_found")));
+        }
+        for (index, runtime) in java_runtimes.discovered.iter().enumerate() {
+            let is_default = java_runtimes.defaults.get(&runtime.major_version) == Some(&runtime.path);
+            let details = format!("{} ({}, {})", runtime.version, runtime.vendor, runtime.arch);
+            runtimes = runtimes.child(v_flex()
+                .child(Checkbox::new(("java-default", index))
+                    .label(ts!("settings.java.default_for", version = runtime.major_version, details = details))
+                    .checked(is_default)
+                    .on_click(cx.listener({
+                        let major_version = runtime.major_version;
+                        let path = runtime.path.clone();
+                        move |settings, value: &bool, window, cx| {
+                            settings.backend_handle.send(MessageToBackend::SetDefaultJavaRuntime {
+                                major_version,
+                                path: value.then(|| path.clone()),
+                            });
+                            settings.update_backend_configuration(window, cx);
+                        }
+                    })))
+                .child(div().text_sm().text_color(cx.theme().muted_foreground).child(runtime.path.clone())));
+        }
+
+        v_flex()
+            .px_4()
+            .py_3()
+            .gap_3()
+            .child(crate::labelled(
+                ts!("settings.java.title"),
+                v_flex().gap_2()
+                    .child(Checkbox::new("prefer-discovered-java")
+                        .label(ts!("settings.java.prefer_discovered"))
+                        .checked(java_runtimes.prefer_discovered)
+                        .on_click(cx.listener(|settings, value, window, cx| {
+                            settings.backend_handle.send(MessageToBackend::SetPreferDiscoveredJavaRuntimes {
+                                value: *value
+                            });
+                            settings.update_backend_configuration(window, cx);
+                        })))
+                    .child(Button::new("scan-java").info().label(if scanning { ts!("settings.java.scanning") } else { ts!("settings.java.scan") })
+                        .disabled(scanning)
+                        .on_click(cx.listener(|settings, _, window, cx| {
+                            settings.scan_java_runtimes(window, cx);
+                        })))
+                    .child(div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(ts!("settings.java.note")))
+            ))
+            .child(crate::labelled(ts!("settings.java.discovered"), runtimes))
+    }
+
     fn render_network_tab(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let proxy_enabled = self.proxy_enabled;
         let proxy_auth_enabled = self.proxy_auth_enabled;
+        let offline_mode = self.backend_config.as_ref().map(|config| config.offline_mode);
+        let poll_server_history = self.backend_config.as_ref().map(|config| !config.dont_poll_server_history);
 
         v_flex()
             .px_4()
             .py_3()
             .gap_3()
+            .child(crate::labelled(
+                ts!("settings.offline.title"),
+                v_flex().gap_2()
+                    .child(Checkbox::new("offline-mode")
+                        .label(ts!("settings.offline.enabled"))
+                        .checked(offline_mode.unwrap_or(false))
+                        .disabled(offline_mode.is_none())
+                        .on_click(cx.listener({
+                            let backend_handle = self.backend_handle.clone();
+                            move |settings, value, window, cx| {
+                                backend_handle.send(MessageToBackend::SetOfflineMode {
+                                    value: *value
+                                });
+                                settings.update_backend_configuration(window, cx);
+                            }
+                        })))
+                    .child(div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(ts!("settings.offline.note")))
+            ))
+            .child(crate::labelled(
+                ts!("settings.server_history.title"),
+                v_flex().gap_2()
+                    .child(Checkbox::new("poll-server-history")
+                        .label(ts!("settings.server_history.enabled"))
+                        .checked(poll_server_history.unwrap_or(false))
+                        .disabled(poll_server_history.is_none())
+                        .on_click(cx.listener({
+                            let backend_handle = self.backend_handle.clone();
+                            move |settings, value, window, cx| {
+                                backend_handle.send(MessageToBackend::SetPollServerHistory {
+                                    value: *value
+                                });
+                                settings.update_backend_configuration(window, cx);
+                            }
+                        })))
+                    .child(div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(ts!("settings.server_history.note")))
+            ))
             .child(crate::labelled(
                 ts!("settings.proxy.title"),
                 v_flex().gap_2()
@@ -418,14 +916,20 @@ impl Render for Settings {
             .selected_index(match selected_tab {
                 SettingsTab::Interface => 0,
                 SettingsTab::Network => 1,
+                SettingsTab::Java => 2,
+                SettingsTab::InstanceDefaults => 3,
             })
             .underline()
             .child(Tab::new().label(ts!("settings.interface")))
             .child(Tab::new().label(ts!("settings.network")))
+            .child(Tab::new().label(ts!("settings.java.tab")))
+            .child(Tab::new().label(ts!("settings.instance_defaults.tab")))
             .on_click(cx.listener(|settings, index, _window, cx| {
                 settings.selected_tab = match index {
                     0 => SettingsTab::Interface,
                     1 => SettingsTab::Network,
+                    2 => SettingsTab::Java,
+                    3 => SettingsTab::InstanceDefaults,
                     _ => SettingsTab::Interface,
                 };
                 cx.notify();
@@ -434,6 +938,8 @@ impl Render for Settings {
         let content = match selected_tab {
             SettingsTab::Interface => self.render_interface_tab(window, cx).into_any_element(),
             SettingsTab::Network => self.render_network_tab(window, cx).into_any_element(),
+            SettingsTab::Java => self.render_java_tab(window, cx).into_any_element(),
+            SettingsTab::InstanceDefaults => self.render_instance_defaults_tab(window, cx).into_any_element(),
         };
 
         v_flex()
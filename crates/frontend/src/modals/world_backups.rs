@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use bridge::{handle::BackendHandle, instance::{InstanceID, WorldBackupSummary}, message::MessageToBackend, modal_action::ModalAction};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme, WindowExt, button::{Button, ButtonVariants}, h_flex, v_flex
+};
+
+use crate::ts;
+
+pub fn open_world_backups(
+    instance: InstanceID,
+    world: Arc<str>,
+    world_title: SharedString,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    backend_handle.send(MessageToBackend::GetWorldBackups {
+        id: instance,
+        world: world.clone(),
+        channel: send,
+    });
+
+    window.spawn(cx, async move |cx| {
+        let Ok(backups) = recv.await else {
+            return;
+        };
+        _ = cx.update(move |window, cx| {
+            show_backups_dialog(instance, world, world_title, backups, backend_handle, window, cx);
+        });
+    }).detach();
+}
+
+fn show_backups_dialog(
+    instance: InstanceID,
+    world: Arc<str>,
+    world_title: SharedString,
+    backups: Arc<[WorldBackupSummary]>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = ts!("instance.world_backups.title", name = world_title);
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let rows = backups.iter().enumerate().map(|(index, backup)| {
+            let created = chrono::DateTime::from_timestamp_millis(backup.created).unwrap_or_default().with_timezone(&chrono::Local);
+            let created = SharedString::from(format!("{}", created.format("%Y-%m-%d %H:%M:%S")));
+            let kind = if backup.automatic {
+                ts!("instance.world_backups.automatic")
+            } else {
+                ts!("instance.world_backups.manual")
+            };
+            let details = format!("{kind}, {:.1} MiB", backup.size as f64 / (1024.0 * 1024.0));
+
+            h_flex()
+                .gap_3()
+                .justify_between()
+                .child(v_flex()
+                    .child(created.clone())
+                    .child(div().text_sm().text_color(cx.theme().muted_foreground).child(details)))
+                .child(Button::new(("restore", index)).label(ts!("instance.world_backups.restore")).warning().on_click({
+                    let backend_handle = backend_handle.clone();
+                    let world = world.clone();
+                    let world_title = world_title.clone();
+                    let name = backup.name.clone();
+                    move |_, window, cx| {
+                        let modal_action = ModalAction::default();
+                        backend_handle.send(MessageToBackend::RestoreWorldBackup {
+                            id: instance,
+                            world: world.clone(),
+                            backup: name.clone(),
+                            modal_action: modal_action.clone(),
+                        });
+
+                        window.close_dialog(cx);
+                        let title = ts!("instance.world_backups.restoring", name = world_title.clone());
+                        crate::modals::generic::show_modal(window, cx, title, ts!("instance.world_backups.restore_error"), modal_action);
+                    }
+                }))
+        }).collect::<Vec<_>>();
+
+        let content = v_flex()
+            .gap_3()
+            .child(Button::new("backup").success().label(ts!("instance.world_backups.backup_now")).on_click({
+                let backend_handle = backend_handle.clone();
+                let world = world.clone();
+                let world_title = world_title.clone();
+                move |_, window, cx| {
+                    let modal_action = ModalAction::default();
+                    backend_handle.send(MessageToBackend::BackupWorld {
+                        id: instance,
+                        world: world.clone(),
+                        modal_action: modal_action.clone(),
+                    });
+
+                    window.close_dialog(cx);
+                    let title = ts!("instance.world_backups.backing_up", name = world_title.clone());
+                    crate::modals::generic::show_modal(window, cx, title, ts!("instance.world_backups.backup_error"), modal_action);
+                }
+            }));
+
+        let content = if rows.is_empty() {
+            content.child(ts!("instance.world_backups.empty"))
+        } else {
+            content.child(v_flex().id("backups").gap_2().max_h_96().overflow_y_scroll().children(rows))
+        };
+
+        dialog
+            .title(title.clone())
+            .child(content)
+    });
+}
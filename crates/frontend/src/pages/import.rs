@@ -288,7 +288,8 @@ impl Render for ImportPage {
                                         && !page.disabled_manually.contains(&*path)
                                 }).collect()
                             },
-                            modal_action: modal_action.clone()
+                            modal_action: modal_action.clone(),
+                            channel: None,
                         });
 
                         let title = SharedString::new(label.clone());
@@ -194,7 +194,7 @@ impl InstanceSubpageType {
     ) -> InstanceSubpage {
         match self {
             InstanceSubpageType::Quickplay => InstanceSubpage::Quickplay(cx.new(|cx| {
-                InstanceQuickplaySubpage::new(instance, backend_handle, window, cx)
+                InstanceQuickplaySubpage::new(instance, data, backend_handle, window, cx)
             })),
             InstanceSubpageType::Logs => InstanceSubpage::Logs(cx.new(|cx| {
                 InstanceLogsSubpage::new(instance, backend_handle, window, cx)
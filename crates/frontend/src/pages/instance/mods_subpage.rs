@@ -106,6 +106,15 @@ impl Render for InstanceModsSubpage {
                     crate::root::start_update_check(instance_id, &backend_handle, window, cx);
                 }
             }))
+            .child(Button::new("dependencies").label(ts!("instance.content.dependencies.label")).compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance_id = self.instance;
+                let instance_loader = self.instance_loader;
+                let instance_version = self.instance_version;
+                move |_, window, cx| {
+                    crate::modals::content_dependencies::open_content_dependencies(instance_id, instance_loader, instance_version, backend_handle.clone(), window, cx);
+                }
+            }))
             .child(Button::new("addmr").label(ts!("instance.content.install.from_modrinth")).success().compact().small().on_click({
                 let instance_name = self.instance_name.clone();
                 move |_, window, cx| {
@@ -2,21 +2,22 @@ use std::{ffi::OsString, sync::Arc};
 
 use bridge::{
     handle::BackendHandle,
-    instance::{InstanceID, InstanceServerSummary, InstanceWorldSummary},
+    instance::{InstanceID, InstanceServerSummary, InstanceWorldSummary, ServerResourcePackPolicy},
     message::{BridgeDataLoadState, MessageToBackend, QuickPlayLaunch},
     serial::AtomicOptionSerial,
 };
 use gpui::{prelude::*, *};
 use gpui_component::{
-    ActiveTheme as _, Colorize, IndexPath, Theme, button::{Button, ButtonVariants}, h_flex, list::{ListDelegate, ListItem, ListState}, v_flex
+    ActiveTheme as _, Colorize, Disableable, IndexPath, Theme, button::{Button, ButtonVariants}, h_flex, list::{ListDelegate, ListItem, ListState}, tooltip::Tooltip, v_flex
 };
 
 use crate::{
-    entity::instance::InstanceEntry, icon::PandoraIcon, interface_config::InterfaceConfig, png_render_cache, root, ts,
+    entity::{DataEntities, instance::{InstanceEntries, InstanceEntry}}, icon::PandoraIcon, interface_config::InterfaceConfig, modals::edit_server::resource_pack_policy_name, png_render_cache, root, ts,
 };
 
 pub struct InstanceQuickplaySubpage {
     instance: Entity<InstanceEntry>,
+    instances: Entity<InstanceEntries>,
     backend_handle: BackendHandle,
     worlds_state: BridgeDataLoadState,
     world_list: Entity<ListState<WorldsListDelegate>>,
@@ -29,6 +30,7 @@ pub struct InstanceQuickplaySubpage {
 impl InstanceQuickplaySubpage {
     pub fn new(
         instance: &Entity<InstanceEntry>,
+        data: &DataEntities,
         backend_handle: BackendHandle,
         mut window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
@@ -93,6 +95,7 @@ impl InstanceQuickplaySubpage {
 
         Self {
             instance: instance_entity,
+            instances: data.instances.clone(),
             backend_handle,
             worlds_state,
             world_list,
@@ -124,7 +127,18 @@ impl Render for InstanceQuickplaySubpage {
         }
 
         let worlds_header = div().mb_1().ml_1().text_lg().child(ts!("instance.worlds"));
-        let servers_header = div().mb_1().ml_1().text_lg().child(ts!("instance.servers"));
+        let servers_header = h_flex()
+            .mb_1()
+            .ml_1()
+            .justify_between()
+            .child(div().text_lg().child(ts!("instance.servers")))
+            .child(Button::new("add_server").small().ghost().icon(PandoraIcon::Plus).label(ts!("instance.server_dialog.add_title")).on_click({
+                let instances = self.instances.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    crate::modals::edit_server::open_add_server(instance_id, &instances, backend_handle.clone(), window, cx);
+                }
+            }));
         let total_playtime = format_playtime(playtime.total_secs);
         let current_session = if playtime.current_session_secs > 0 {
             format_playtime(playtime.current_session_secs)
@@ -251,6 +265,14 @@ impl ListDelegate for WorldsListDelegate {
         let name = self.name.clone();
         let backend_handle = self.backend_handle.clone();
         let target = summary.level_path.file_name().unwrap().to_owned();
+        let world: Arc<str> = target.to_string_lossy().into();
+        let world_title = SharedString::from(summary.title.clone());
+        let backups_button = Button::new(("backups", ix.row)).icon(PandoraIcon::Archive).tooltip(ts!("instance.world_backups.open")).on_click({
+            let backend_handle = backend_handle.clone();
+            move |_, window, cx| {
+                crate::modals::world_backups::open_world_backups(id, world.clone(), world_title.clone(), backend_handle.clone(), window, cx);
+            }
+        });
         let item = ListItem::new(ix).p_1().child(
             h_flex()
                 .gap_1()
@@ -269,7 +291,8 @@ impl ListDelegate for WorldsListDelegate {
                         .px_2(),
                 )
                 .child(icon.size_16().min_w_16().min_h_16())
-                .child(description),
+                .child(description.flex_1())
+                .child(div().child(backups_button).px_2()),
         );
 
         Some(item)
@@ -385,6 +408,103 @@ impl ListDelegate for ServersListDelegate {
                     .child("Unable to get server status"))
             });
 
+        let trend = DataEntities::server_status_trend(&summary.ip, cx);
+        let favourite = trend.is_some_and(|trend| trend.favourite);
+        let uptime = trend.and_then(|trend| {
+            let uptime_day = trend.uptime_day?;
+            let bars = trend.hourly_uptime.iter().map(|uptime| {
+                let color = match uptime {
+                    Some(uptime) => theme.success.mix_oklab(theme.danger, *uptime),
+                    None => theme.muted,
+                };
+                div().w(px(3.0)).h(px(12.0)).rounded_xs().bg(color)
+            });
+            let tooltip: SharedString = ts!("instance.server_status.uptime_tooltip", week = format!("{:.1}", trend.uptime_week.unwrap_or(uptime_day) * 100.0));
+            Some(v_flex()
+                .id(("uptime", ix.row))
+                .gap_1()
+                .items_end()
+                .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+                .child(h_flex().gap_px().children(bars))
+                .child(div()
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child(ts!("instance.server_status.uptime", percent = format!("{:.1}", uptime_day * 100.0)))))
+        });
+
+        let favourite_button = Button::new(("favourite", ix.row))
+            .icon(if favourite { PandoraIcon::Star } else { PandoraIcon::StarOff })
+            .when(!favourite, |this| this.ghost())
+            .tooltip(if favourite { ts!("instance.server_status.unfavourite") } else { ts!("instance.server_status.favourite") })
+            .on_click({
+                let backend_handle = self.backend_handle.clone();
+                let address = summary.ip.clone();
+                move |_, _, _| {
+                    backend_handle.send(MessageToBackend::SetServerFavourite { address: address.clone(), favourite: !favourite });
+                }
+            });
+
+        let index = summary.index;
+        let entry_ref = summary.entry_ref();
+        let previous_entry_ref = index.checked_sub(1).and_then(|previous| self.servers.get(previous)).map(InstanceServerSummary::entry_ref);
+        let next_entry_ref = self.servers.get(index + 1).map(InstanceServerSummary::entry_ref);
+        let next_policy = match summary.resource_packs {
+            ServerResourcePackPolicy::Prompt => ServerResourcePackPolicy::Enabled,
+            ServerResourcePackPolicy::Enabled => ServerResourcePackPolicy::Disabled,
+            ServerResourcePackPolicy::Disabled => ServerResourcePackPolicy::Prompt,
+        };
+        let resource_packs_tooltip = ts!("instance.server_actions.resource_packs", policy = resource_pack_policy_name(summary.resource_packs));
+        let actions = h_flex()
+            .gap_0p5()
+            .child(Button::new(("move_up", ix.row)).small().ghost().icon(PandoraIcon::ArrowUp).disabled(index == 0)
+                .tooltip(ts!("instance.server_actions.move_up")).on_click({
+                    let backend_handle = self.backend_handle.clone();
+                    let id = self.id;
+                    let entry_ref = entry_ref.clone();
+                    move |_, _, _| {
+                        if let Some(previous_entry_ref) = &previous_entry_ref {
+                            backend_handle.send(MessageToBackend::MoveServer { id, from: entry_ref.clone(), to: previous_entry_ref.clone() });
+                        }
+                    }
+                }))
+            .child(Button::new(("move_down", ix.row)).small().ghost().icon(PandoraIcon::ArrowDown).disabled(index + 1 >= self.servers.len())
+                .tooltip(ts!("instance.server_actions.move_down")).on_click({
+                    let backend_handle = self.backend_handle.clone();
+                    let id = self.id;
+                    let entry_ref = entry_ref.clone();
+                    move |_, _, _| {
+                        if let Some(next_entry_ref) = &next_entry_ref {
+                            backend_handle.send(MessageToBackend::MoveServer { id, from: entry_ref.clone(), to: next_entry_ref.clone() });
+                        }
+                    }
+                }))
+            .child(Button::new(("resource_packs", ix.row)).small().ghost().icon(PandoraIcon::Palette)
+                .tooltip(resource_packs_tooltip).on_click({
+                    let backend_handle = self.backend_handle.clone();
+                    let id = self.id;
+                    let entry_ref = entry_ref.clone();
+                    move |_, _, _| {
+                        backend_handle.send(MessageToBackend::SetServerResourcePackPolicy { id, server: entry_ref.clone(), policy: next_policy });
+                    }
+                }))
+            .child(Button::new(("edit", ix.row)).small().ghost().icon(PandoraIcon::Settings2)
+                .tooltip(ts!("instance.server_actions.edit")).on_click({
+                    let backend_handle = self.backend_handle.clone();
+                    let id = self.id;
+                    let summary = summary.clone();
+                    move |_, window, cx| {
+                        crate::modals::edit_server::open_edit_server(id, &summary, backend_handle.clone(), window, cx);
+                    }
+                }))
+            .child(Button::new(("remove", ix.row)).small().ghost().icon(PandoraIcon::Trash2)
+                .tooltip(ts!("instance.server_actions.remove")).on_click({
+                    let backend_handle = self.backend_handle.clone();
+                    let id = self.id;
+                    move |_, _, _| {
+                        backend_handle.send(MessageToBackend::RemoveServer { id, server: entry_ref.clone() });
+                    }
+                }));
+
         let id = self.id;
         let name = self.name.clone();
         let backend_handle = self.backend_handle.clone();
@@ -407,7 +527,11 @@ impl ListDelegate for ServersListDelegate {
                         .px_2(),
                 )
                 .child(icon.size_16().min_w_16().min_h_16())
-                .child(description),
+                .child(description)
+                .child(div().flex_1())
+                .children(uptime)
+                .child(div().child(favourite_button).px_2())
+                .child(actions),
         );
 
         Some(item)
@@ -5,9 +5,9 @@ use bridge::{
 };
 use gpui::{prelude::*, *};
 use gpui_component::{
-    ActiveTheme as _, Disableable, Icon, IndexPath, Sizable, WindowExt, button::{Button, ButtonVariants}, checkbox::Checkbox, h_flex, input::{Input, InputEvent, InputState, NumberInput, NumberInputEvent}, notification::{Notification, NotificationType}, select::{SearchableVec, Select, SelectEvent, SelectState}, skeleton::Skeleton, v_flex
+    ActiveTheme as _, Disableable, Icon, IndexPath, Selectable, Sizable, WindowExt, button::{Button, ButtonGroup, ButtonVariants}, checkbox::Checkbox, h_flex, input::{Input, InputEvent, InputState, NumberInput, NumberInputEvent}, notification::{Notification, NotificationType}, select::{SearchableVec, Select, SelectEvent, SelectState}, skeleton::Skeleton, v_flex
 };
-use schema::{fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeMavenManifest, NeoforgeMavenManifest}, instance::{AUTO_LIBRARY_PATH_GLFW, AUTO_LIBRARY_PATH_OPENAL, InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration, InstanceSystemLibrariesConfiguration, InstanceWrapperCommandConfiguration, LwjglLibraryPath}, loader::Loader, version_manifest::MinecraftVersionManifest};
+use schema::{fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeMavenManifest, NeoforgeMavenManifest}, instance::{AUTO_LIBRARY_PATH_GLFW, AUTO_LIBRARY_PATH_OPENAL, InstanceEnvironmentVariablesConfiguration, InstanceGameArgumentsConfiguration, InstanceHookCommandsConfiguration, InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration, InstanceSettingMode, InstanceSystemLibrariesConfiguration, InstanceWindowConfiguration, InstanceWorldBackupConfiguration, InstanceWrapperCommandConfiguration, LwjglLibraryPath, WindowConfiguration, WorldBackupSchedule}, loader::Loader, quilt_loader_manifest::QuiltLoaderManifest, version_manifest::MinecraftVersionManifest};
 use strum::IntoEnumIterator;
 use uuid::Uuid;
 
@@ -29,6 +29,9 @@ pub struct InstanceSettingsSubpage {
     instance: Entity<InstanceEntry>,
     instance_id: InstanceID,
     new_name_input_state: Entity<InputState>,
+    group_input_state: Entity<InputState>,
+    tags_input_state: Entity<InputState>,
+    favourite: bool,
     version_state: TypelessFrontendMetadataResult,
     version_select_state: Entity<SelectState<VersionList>>,
     account_items: Entity<SelectState<NamedDropdown<Uuid>>>,
@@ -37,24 +40,44 @@ pub struct InstanceSettingsSubpage {
     loader_versions_state: TypelessFrontendMetadataResult,
     loader_version_select_state: Entity<SelectState<SearchableVec<&'static str>>>,
     disable_file_syncing: bool,
+    sync_profile_select_state: Entity<SelectState<NamedDropdown<Option<Arc<str>>>>>,
 
-    memory_override_enabled: bool,
+    memory_mode: InstanceSettingMode,
     memory_min_input_state: Entity<InputState>,
     memory_max_input_state: Entity<InputState>,
-    wrapper_command_enabled: bool,
+    window_mode: InstanceSettingMode,
+    window_custom_size: bool,
+    window_maximised: bool,
+    window_fullscreen: bool,
+    window_width_input_state: Entity<InputState>,
+    window_height_input_state: Entity<InputState>,
+    world_backups_enabled: bool,
+    world_backups_schedule: WorldBackupSchedule,
+    world_backups_keep_input_state: Entity<InputState>,
+    wrapper_command_mode: InstanceSettingMode,
     wrapper_command_input_state: Entity<InputState>,
-    jvm_flags_enabled: bool,
+    hook_commands_enabled: bool,
+    pre_launch_command_input_state: Entity<InputState>,
+    post_exit_command_input_state: Entity<InputState>,
+    jvm_flags_mode: InstanceSettingMode,
     jvm_flags_input_state: Entity<InputState>,
-    jvm_binary_enabled: bool,
+    environment_variables_mode: InstanceSettingMode,
+    environment_variables_input_state: Entity<InputState>,
+    game_arguments_mode: InstanceSettingMode,
+    game_arguments_input_state: Entity<InputState>,
+    jvm_binary_mode: InstanceSettingMode,
     jvm_binary_path: Option<PathLabel>,
 
     instance_root_label: PathLabel,
 
+    system_libraries_mode: InstanceSettingMode,
     override_glfw_enabled: bool,
     override_glfw_path: Option<PathLabel>,
     override_openal_enabled: bool,
     override_openal_path: Option<PathLabel>,
 
+    #[cfg(target_os = "linux")]
+    linux_wrapper_mode: InstanceSettingMode,
     #[cfg(target_os = "linux")]
     use_mangohud: bool,
     #[cfg(target_os = "linux")]
@@ -89,10 +112,19 @@ impl InstanceSettingsSubpage {
         let preferred_loader_version = entry.configuration.preferred_loader_version.map(|s| s.as_str()).unwrap_or("Latest");
         let account = entry.configuration.preferred_account;
         let disable_file_syncing = entry.configuration.disable_file_syncing;
+        let sync_profile = entry.configuration.sync_profile.clone();
+        let group = entry.configuration.group.clone().unwrap_or_default();
+        let tags = entry.configuration.tags.join(", ");
+        let favourite = entry.configuration.favourite;
 
         let memory = entry.configuration.memory.unwrap_or_default();
+        let world_backups = entry.configuration.world_backups.unwrap_or_default();
+        let window_configuration = entry.configuration.window.unwrap_or_default();
         let wrapper_command = entry.configuration.wrapper_command.clone().unwrap_or_default();
+        let hook_commands = entry.configuration.hook_commands.clone().unwrap_or_default();
         let jvm_flags = entry.configuration.jvm_flags.clone().unwrap_or_default();
+        let environment_variables = entry.configuration.environment_variables.clone().unwrap_or_default();
+        let game_arguments = entry.configuration.game_arguments.clone().unwrap_or_default();
         let jvm_binary = entry.configuration.jvm_binary.clone().unwrap_or_default();
         #[cfg(target_os = "linux")]
         let linux_wrapper = entry.configuration.linux_wrapper.unwrap_or_default();
@@ -147,6 +179,15 @@ impl InstanceSettingsSubpage {
         }).detach();
         cx.subscribe(&account_items, Self::on_account_selected).detach();
 
+        let sync_profile_select_state = cx.new(|cx| {
+            let items = vec![NamedDropdownItem {
+                name: ts!("instance.sync.profile.default"),
+                item: None,
+            }];
+            SelectState::new(NamedDropdown::new(items), Some(IndexPath::default()), window, cx)
+        });
+        cx.subscribe(&sync_profile_select_state, Self::on_sync_profile_selected).detach();
+
         let loader_select_state = cx.new(|cx| {
             let loaders = Loader::iter()
                 .filter(|l| *l != Loader::Unknown)
@@ -194,21 +235,68 @@ impl InstanceSettingsSubpage {
         cx.subscribe_in(&memory_max_input_state, window, Self::on_memory_step).detach();
         cx.subscribe(&memory_max_input_state, Self::on_memory_changed).detach();
 
+        let window_width_input_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(window_configuration.window.width.to_string())
+        });
+        cx.subscribe(&window_width_input_state, Self::on_window_size_changed).detach();
+        let window_height_input_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(window_configuration.window.height.to_string())
+        });
+        cx.subscribe(&window_height_input_state, Self::on_window_size_changed).detach();
+
+        let world_backups_keep_input_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(world_backups.keep_last.to_string())
+        });
+        cx.subscribe(&world_backups_keep_input_state, Self::on_world_backups_keep_changed).detach();
+
         let wrapper_command_input_state = cx.new(|cx| {
             InputState::new(window, cx).auto_grow(1, 8).default_value(wrapper_command.flags)
         });
         cx.subscribe(&wrapper_command_input_state, Self::on_wrapper_command_changed).detach();
 
+        let pre_launch_command_input_state = cx.new(|cx| {
+            InputState::new(window, cx).auto_grow(1, 8).placeholder("git -C $INST_MC_DIR/config pull").default_value(hook_commands.pre_launch)
+        });
+        cx.subscribe(&pre_launch_command_input_state, Self::on_hook_commands_changed).detach();
+
+        let post_exit_command_input_state = cx.new(|cx| {
+            InputState::new(window, cx).auto_grow(1, 8).default_value(hook_commands.post_exit)
+        });
+        cx.subscribe(&post_exit_command_input_state, Self::on_hook_commands_changed).detach();
+
         let jvm_flags_input_state = cx.new(|cx| {
             InputState::new(window, cx).auto_grow(1, 8).default_value(jvm_flags.flags)
         });
         cx.subscribe(&jvm_flags_input_state, Self::on_jvm_flags_changed).detach();
 
+        let environment_variables_input_state = cx.new(|cx| {
+            InputState::new(window, cx).auto_grow(1, 8).placeholder("__GL_SYNC_TO_VBLANK=0").default_value(environment_variables.variables)
+        });
+        cx.subscribe(&environment_variables_input_state, Self::on_environment_variables_changed).detach();
+
+        let game_arguments_input_state = cx.new(|cx| {
+            InputState::new(window, cx).auto_grow(1, 8).default_value(game_arguments.arguments)
+        });
+        cx.subscribe(&game_arguments_input_state, Self::on_game_arguments_changed).detach();
+
+        let group_input_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(ts!("instance.organisation.group_placeholder")).default_value(group)
+        });
+        cx.subscribe(&group_input_state, Self::on_group_changed).detach();
+
+        let tags_input_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(ts!("instance.organisation.tags_placeholder")).default_value(tags)
+        });
+        cx.subscribe(&tags_input_state, Self::on_tags_changed).detach();
+
         let mut page = Self {
             data: data.clone(),
             instance: instance.clone(),
             instance_id,
             new_name_input_state,
+            group_input_state,
+            tags_input_state,
+            favourite,
             version_state: TypelessFrontendMetadataResult::Loading,
             version_select_state,
             account_items,
@@ -216,21 +304,41 @@ impl InstanceSettingsSubpage {
             loader_select_state,
             loader_version_select_state,
             disable_file_syncing,
-            memory_override_enabled: memory.enabled,
+            sync_profile_select_state,
+            memory_mode: memory.mode,
             memory_min_input_state,
             memory_max_input_state,
-            wrapper_command_enabled: wrapper_command.enabled,
+            window_mode: window_configuration.mode,
+            window_custom_size: window_configuration.window.custom_size,
+            window_maximised: window_configuration.window.maximised,
+            window_fullscreen: window_configuration.window.fullscreen,
+            window_width_input_state,
+            window_height_input_state,
+            world_backups_enabled: world_backups.enabled,
+            world_backups_schedule: world_backups.schedule,
+            world_backups_keep_input_state,
+            wrapper_command_mode: wrapper_command.mode,
             wrapper_command_input_state,
-            jvm_flags_enabled: jvm_flags.enabled,
+            hook_commands_enabled: hook_commands.enabled,
+            pre_launch_command_input_state,
+            post_exit_command_input_state,
+            jvm_flags_mode: jvm_flags.mode,
             jvm_flags_input_state,
-            jvm_binary_enabled: jvm_binary.enabled,
+            environment_variables_mode: environment_variables.mode,
+            environment_variables_input_state,
+            game_arguments_mode: game_arguments.mode,
+            game_arguments_input_state,
+            jvm_binary_mode: jvm_binary.mode,
             jvm_binary_path: jvm_binary.path.clone().map(|path| PathLabel::new(path, false)),
+            system_libraries_mode: system_libraries.mode,
             override_glfw_enabled: system_libraries.override_glfw,
             override_glfw_path: glfw_path.map(|path| PathLabel::new(path, false)),
             override_openal_enabled: system_libraries.override_openal,
             override_openal_path: openal_path.map(|path| PathLabel::new(path, false)),
             instance_root_label,
             #[cfg(target_os = "linux")]
+            linux_wrapper_mode: linux_wrapper.mode,
+            #[cfg(target_os = "linux")]
             use_mangohud: linux_wrapper.use_mangohud,
             #[cfg(target_os = "linux")]
             use_gamemode: linux_wrapper.use_gamemode,
@@ -251,6 +359,7 @@ impl InstanceSettingsSubpage {
         };
         page.update_minecraft_versions(minecraft_versions, window, cx);
         page.update_loader_versions(window, cx);
+        page.load_sync_profiles(sync_profile, window, cx);
         page
     }
 }
@@ -320,6 +429,13 @@ impl InstanceSettingsSubpage {
                         .collect()
                 }, window, cx)
             },
+            Loader::Quilt => {
+                self.update_loader_versions_for_loader(MetadataRequest::QuiltLoaderManifest, |manifest: &QuiltLoaderManifest| {
+                    std::iter::once("Latest")
+                        .chain(manifest.0.iter().map(|s| s.version.as_str()))
+                        .collect()
+                }, window, cx)
+            },
             Loader::Forge => {
                 self.update_loader_versions_for_loader(MetadataRequest::ForgeMavenManifest, |manifest: &ForgeMavenManifest| {
                     std::iter::once("Latest")
@@ -392,6 +508,41 @@ impl InstanceSettingsSubpage {
         })
     }
 
+    fn load_sync_profiles(&mut self, selected: Option<Arc<str>>, window: &mut Window, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::GetSyncProfiles {
+            channel: send,
+        });
+
+        let this_entity = cx.entity();
+        window.spawn(cx, async move |cx| {
+            let Ok(profiles) = recv.await else {
+                return;
+            };
+            _ = cx.update_window_entity(&this_entity, move |this, window, cx| {
+                let mut items = vec![NamedDropdownItem {
+                    name: ts!("instance.sync.profile.default"),
+                    item: None,
+                }];
+                let mut selected_index = 0;
+                for profile in profiles.iter() {
+                    if selected.as_ref() == Some(profile) {
+                        selected_index = items.len();
+                    }
+                    items.push(NamedDropdownItem {
+                        name: SharedString::from(profile.clone()),
+                        item: Some(profile.clone()),
+                    });
+                }
+
+                this.sync_profile_select_state.update(cx, |state, cx| {
+                    state.set_items(NamedDropdown::new(items), window, cx);
+                    state.set_selected_index(Some(IndexPath::new(selected_index)), window, cx);
+                });
+            });
+        }).detach();
+    }
+
     pub fn on_new_name_input(
         &mut self,
         state: Entity<InputState>,
@@ -452,6 +603,23 @@ impl InstanceSettingsSubpage {
 		});
     }
 
+    pub fn on_sync_profile_selected(
+        &mut self,
+        _state: Entity<SelectState<NamedDropdown<Option<Arc<str>>>>>,
+        event: &SelectEvent<NamedDropdown<Option<Arc<str>>>>,
+        _cx: &mut Context<Self>,
+    ) {
+        let SelectEvent::Confirm(value) = event;
+        let Some(value) = value else {
+            return;
+        };
+
+        self.backend_handle.send(MessageToBackend::SetInstanceSyncProfile {
+            id: self.instance_id,
+            profile: value.item.clone(),
+        });
+    }
+
     pub fn on_loader_selected(
         &mut self,
         _state: &Entity<SelectState<Vec<&'static str>>>,
@@ -548,12 +716,96 @@ impl InstanceSettingsSubpage {
         let max = self.memory_max_input_state.read(cx).value().parse::<u32>().unwrap_or(0);
 
         InstanceMemoryConfiguration {
-            enabled: self.memory_override_enabled,
+            mode: self.memory_mode,
             min,
             max
         }
     }
 
+    pub fn on_window_size_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceWindow {
+                id: self.instance_id,
+                window: self.get_window_configuration(cx)
+            });
+        }
+    }
+
+    fn get_window_configuration(&self, cx: &App) -> InstanceWindowConfiguration {
+        let width = self.window_width_input_state.read(cx).value().parse::<u32>().unwrap_or(WindowConfiguration::DEFAULT_WIDTH);
+        let height = self.window_height_input_state.read(cx).value().parse::<u32>().unwrap_or(WindowConfiguration::DEFAULT_HEIGHT);
+
+        InstanceWindowConfiguration {
+            mode: self.window_mode,
+            window: WindowConfiguration {
+                custom_size: self.window_custom_size,
+                width,
+                height,
+                maximised: self.window_maximised,
+                fullscreen: self.window_fullscreen,
+            },
+        }
+    }
+
+    pub fn on_world_backups_keep_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceWorldBackups {
+                id: self.instance_id,
+                world_backups: self.get_world_backup_configuration(cx)
+            });
+        }
+    }
+
+    fn get_world_backup_configuration(&self, cx: &App) -> InstanceWorldBackupConfiguration {
+        let keep_last = self.world_backups_keep_input_state.read(cx).value().parse::<u32>().unwrap_or(InstanceWorldBackupConfiguration::DEFAULT_KEEP_LAST);
+
+        InstanceWorldBackupConfiguration {
+            enabled: self.world_backups_enabled,
+            schedule: self.world_backups_schedule,
+            keep_last: keep_last.max(1),
+        }
+    }
+
+    pub fn on_group_changed(
+        &mut self,
+        state: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            let group = state.read(cx).value();
+            self.backend_handle.send(MessageToBackend::SetInstanceGroup {
+                id: self.instance_id,
+                group: (!group.trim().is_empty()).then(|| group.as_str().into()),
+            });
+        }
+    }
+
+    pub fn on_tags_changed(
+        &mut self,
+        state: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            let tags = state.read(cx).value();
+            self.backend_handle.send(MessageToBackend::SetInstanceTags {
+                id: self.instance_id,
+                tags: tags.split(',').map(Arc::from).collect(),
+            });
+        }
+    }
+
     pub fn on_wrapper_command_changed(
         &mut self,
         _: Entity<InputState>,
@@ -572,11 +824,36 @@ impl InstanceSettingsSubpage {
         let flags = self.wrapper_command_input_state.read(cx).value();
 
         InstanceWrapperCommandConfiguration {
-            enabled: self.wrapper_command_enabled,
+            mode: self.wrapper_command_mode,
             flags: flags.into(),
         }
     }
 
+    pub fn on_hook_commands_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceHookCommands {
+                id: self.instance_id,
+                hook_commands: self.get_hook_commands_configuration(cx)
+            });
+        }
+    }
+
+    fn get_hook_commands_configuration(&self, cx: &App) -> InstanceHookCommandsConfiguration {
+        let pre_launch = self.pre_launch_command_input_state.read(cx).value();
+        let post_exit = self.post_exit_command_input_state.read(cx).value();
+
+        InstanceHookCommandsConfiguration {
+            enabled: self.hook_commands_enabled,
+            pre_launch: pre_launch.into(),
+            post_exit: post_exit.into(),
+        }
+    }
+
     pub fn on_jvm_flags_changed(
         &mut self,
         _: Entity<InputState>,
@@ -595,20 +872,67 @@ impl InstanceSettingsSubpage {
         let flags = self.jvm_flags_input_state.read(cx).value();
 
         InstanceJvmFlagsConfiguration {
-            enabled: self.jvm_flags_enabled,
+            mode: self.jvm_flags_mode,
             flags: flags.into(),
         }
     }
 
+    pub fn on_environment_variables_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceEnvironmentVariables {
+                id: self.instance_id,
+                environment_variables: self.get_environment_variables_configuration(cx)
+            });
+        }
+    }
+
+    fn get_environment_variables_configuration(&self, cx: &App) -> InstanceEnvironmentVariablesConfiguration {
+        let variables = self.environment_variables_input_state.read(cx).value();
+
+        InstanceEnvironmentVariablesConfiguration {
+            mode: self.environment_variables_mode,
+            variables: variables.into(),
+        }
+    }
+
+    pub fn on_game_arguments_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceGameArguments {
+                id: self.instance_id,
+                game_arguments: self.get_game_arguments_configuration(cx)
+            });
+        }
+    }
+
+    fn get_game_arguments_configuration(&self, cx: &App) -> InstanceGameArgumentsConfiguration {
+        let arguments = self.game_arguments_input_state.read(cx).value();
+
+        InstanceGameArgumentsConfiguration {
+            mode: self.game_arguments_mode,
+            arguments: arguments.into(),
+        }
+    }
+
     fn get_jvm_binary_configuration(&self) -> InstanceJvmBinaryConfiguration {
         InstanceJvmBinaryConfiguration {
-            enabled: self.jvm_binary_enabled,
+            mode: self.jvm_binary_mode,
             path: self.jvm_binary_path.as_ref().map(PathLabel::path),
         }
     }
 
     fn get_system_libraries_configuration(&self) -> InstanceSystemLibrariesConfiguration {
         InstanceSystemLibrariesConfiguration {
+            mode: self.system_libraries_mode,
             override_glfw: self.override_glfw_enabled,
             glfw: Self::create_lwjgl_library_path(&self.override_glfw_path.as_ref().map(PathLabel::path), &*AUTO_LIBRARY_PATH_GLFW),
             override_openal: self.override_openal_enabled,
@@ -631,6 +955,7 @@ impl InstanceSettingsSubpage {
     #[cfg(target_os = "linux")]
     fn get_linux_wrapper_configuration(&self) -> InstanceLinuxWrapperConfiguration {
         InstanceLinuxWrapperConfiguration {
+            mode: self.linux_wrapper_mode,
             use_mangohud: self.use_mangohud,
             use_gamemode: self.use_gamemode,
             use_discrete_gpu: self.use_discrete_gpu,
@@ -638,6 +963,35 @@ impl InstanceSettingsSubpage {
         }
     }
 
+    fn render_setting_mode(
+        id: &'static str,
+        label: SharedString,
+        mode: InstanceSettingMode,
+        on_change: fn(&mut Self, InstanceSettingMode, &mut Context<Self>),
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .justify_between()
+            .child(label)
+            .child(ButtonGroup::new(id)
+                .outline()
+                .child(Button::new("inherit").label(ts!("instance.setting_mode.inherit")).selected(mode == InstanceSettingMode::Inherit))
+                .child(Button::new("override").label(ts!("instance.setting_mode.override")).selected(mode == InstanceSettingMode::Override))
+                .child(Button::new("disable").label(ts!("instance.setting_mode.disable")).selected(mode == InstanceSettingMode::Disable))
+                .on_click(cx.listener(move |page, selected: &Vec<usize>, _, cx| {
+                    let new_mode = match selected.first() {
+                        Some(1) => InstanceSettingMode::Override,
+                        Some(2) => InstanceSettingMode::Disable,
+                        _ => InstanceSettingMode::Inherit,
+                    };
+                    if new_mode != mode {
+                        on_change(page, new_mode, cx);
+                        cx.notify();
+                    }
+                })))
+    }
+
     #[cfg(target_os = "linux")]
     fn is_command_available(command: &str) -> bool {
         std::process::Command::new("sh")
@@ -693,10 +1047,15 @@ impl Render for InstanceSettingsSubpage {
             .ml_1()
             .child(div().text_lg().child(ts!("settings.title")));
 
-        let memory_override_enabled = self.memory_override_enabled;
-        let wrapper_command_enabled = self.wrapper_command_enabled;
-        let jvm_flags_enabled = self.jvm_flags_enabled;
-        let jvm_binary_enabled = self.jvm_binary_enabled;
+        let memory_override_enabled = self.memory_mode == InstanceSettingMode::Override;
+        let window_override_enabled = self.window_mode == InstanceSettingMode::Override;
+        let wrapper_command_enabled = self.wrapper_command_mode == InstanceSettingMode::Override;
+        let hook_commands_enabled = self.hook_commands_enabled;
+        let jvm_flags_enabled = self.jvm_flags_mode == InstanceSettingMode::Override;
+        let environment_variables_enabled = self.environment_variables_mode == InstanceSettingMode::Override;
+        let game_arguments_enabled = self.game_arguments_mode == InstanceSettingMode::Override;
+        let jvm_binary_enabled = self.jvm_binary_mode == InstanceSettingMode::Override;
+        let system_libraries_enabled = self.system_libraries_mode == InstanceSettingMode::Override;
 
         let icon_element: Option<AnyElement> = self.icon.clone().map(|icon| match icon {
             EmbeddedOrRaw::Embedded(path) => {
@@ -763,6 +1122,21 @@ impl Render for InstanceSettingsSubpage {
                     }
                     row
                 }
+            ))
+            .child(crate::labelled(
+                ts!("instance.organisation.label"),
+                v_flex()
+                    .gap_2()
+                    .child(Input::new(&self.group_input_state))
+                    .child(Input::new(&self.tags_input_state))
+                    .child(Checkbox::new("favourite").label(ts!("instance.organisation.favourite")).checked(self.favourite).on_click(cx.listener(|page, value, _, cx| {
+                        page.favourite = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceFavourite {
+                            id: page.instance_id,
+                            favourite: *value
+                        });
+                        cx.notify();
+                    })))
             ));
 
         let mut version_content = v_flex().gap_2();
@@ -791,6 +1165,7 @@ impl Render for InstanceSettingsSubpage {
                         Loader::Fabric => format!("{}: ", ts!("instance.loader_version", loader = ts!("modrinth.category.fabric"))),
                         Loader::Forge => format!("{}: ", ts!("instance.loader_version", loader = ts!("modrinth.category.forge"))),
                         Loader::NeoForge => format!("{}: ", ts!("instance.loader_version", loader = ts!("modrinth.category.neoforge"))),
+                        Loader::Quilt => format!("{}: ", ts!("instance.loader_version", loader = ts!("modrinth.category.quilt"))),
                         Loader::Vanilla | Loader::Unknown => format!("{}: ", ts!("instance.loader_version", loader = ts!("instance.loader"))),
                     }).w_full())
                 },
@@ -811,15 +1186,59 @@ impl Render for InstanceSettingsSubpage {
                 .gap_2()
                 .child(Select::new(&self.account_items).placeholder("No override").cleanable(true))
             ))
+            .child(crate::labelled(
+                ts!("instance.world_backups.label"),
+                v_flex()
+                    .gap_2()
+                    .child(Checkbox::new("world_backups").label(ts!("instance.world_backups.before_launch")).checked(self.world_backups_enabled).on_click(cx.listener(|page, value, _, cx| {
+                        if page.world_backups_enabled != *value {
+                            page.world_backups_enabled = *value;
+                            page.backend_handle.send(MessageToBackend::SetInstanceWorldBackups {
+                                id: page.instance_id,
+                                world_backups: page.get_world_backup_configuration(cx)
+                            });
+                            cx.notify();
+                        }
+                    })))
+                    .child(ButtonGroup::new("world_backups_schedule")
+                        .outline()
+                        .child(Button::new("every_launch").label(ts!("instance.world_backups.schedule.every_launch")).selected(self.world_backups_schedule == WorldBackupSchedule::EveryLaunch))
+                        .child(Button::new("daily").label(ts!("instance.world_backups.schedule.daily")).selected(self.world_backups_schedule == WorldBackupSchedule::Daily))
+                        .child(Button::new("weekly").label(ts!("instance.world_backups.schedule.weekly")).selected(self.world_backups_schedule == WorldBackupSchedule::Weekly))
+                        .on_click(cx.listener(|page, selected: &Vec<usize>, _, cx| {
+                            let schedule = match selected.first() {
+                                Some(1) => WorldBackupSchedule::Daily,
+                                Some(2) => WorldBackupSchedule::Weekly,
+                                _ => WorldBackupSchedule::EveryLaunch,
+                            };
+                            if page.world_backups_schedule != schedule {
+                                page.world_backups_schedule = schedule;
+                                page.backend_handle.send(MessageToBackend::SetInstanceWorldBackups {
+                                    id: page.instance_id,
+                                    world_backups: page.get_world_backup_configuration(cx)
+                                });
+                                cx.notify();
+                            }
+                        })))
+                    .child(h_flex()
+                        .gap_2()
+                        .child(NumberInput::new(&self.world_backups_keep_input_state).small().disabled(!self.world_backups_enabled))
+                        .child(ts!("instance.world_backups.keep_last")))
+            ))
             .child(crate::labelled(
                 ts!("instance.sync.label"),
-                Checkbox::new("syncing").label(ts!("instance.sync.disable_syncing")).checked(self.disable_file_syncing).on_click(cx.listener(|page, value, _, _| {
-                    page.disable_file_syncing = *value;
-                    page.backend_handle.send(MessageToBackend::SetInstanceDisableFileSyncing {
-                        id: page.instance_id,
-                        disable_file_syncing: *value
-                    });
-                }))
+                v_flex()
+                    .gap_2()
+                    .child(Checkbox::new("syncing").label(ts!("instance.sync.disable_syncing")).checked(self.disable_file_syncing).on_click(cx.listener(|page, value, _, _| {
+                        page.disable_file_syncing = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceDisableFileSyncing {
+                            id: page.instance_id,
+                            disable_file_syncing: *value
+                        });
+                    })))
+                    .child(Select::new(&self.sync_profile_select_state)
+                        .title_prefix(format!("{}: ", ts!("instance.sync.profile.label")))
+                        .disabled(self.disable_file_syncing))
             ));
 
         let runtime_content = v_flex()
@@ -827,16 +1246,13 @@ impl Render for InstanceSettingsSubpage {
             .size_full()
             .child(v_flex()
                 .gap_1()
-                .child(Checkbox::new("memory").label(ts!("instance.memory")).checked(memory_override_enabled).on_click(cx.listener(|page, value, _, cx| {
-                    if page.memory_override_enabled != *value {
-                        page.memory_override_enabled = *value;
-                        page.backend_handle.send(MessageToBackend::SetInstanceMemory {
-                            id: page.instance_id,
-                            memory: page.get_memory_configuration(cx)
-                        });
-                        cx.notify();
-                    }
-                })))
+                .child(Self::render_setting_mode("memory", ts!("instance.memory"), self.memory_mode, |page, mode, cx| {
+                    page.memory_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceMemory {
+                        id: page.instance_id,
+                        memory: page.get_memory_configuration(cx)
+                    });
+                }, cx))
                 .child(h_flex()
                     .gap_1()
                     .child(v_flex()
@@ -851,32 +1267,96 @@ impl Render for InstanceSettingsSubpage {
                         .child(ts!("common.min"))
                         .child(ts!("common.max")))
                 )
-            ).child(v_flex()
+            )
+            .child(v_flex()
                 .gap_1()
-                .child(Checkbox::new("jvm_flags").label(ts!("instance.jvm_flags")).checked(jvm_flags_enabled).on_click(cx.listener(|page, value, _, cx| {
-                    if page.jvm_flags_enabled != *value {
-                        page.jvm_flags_enabled = *value;
-                        page.backend_handle.send(MessageToBackend::SetInstanceJvmFlags {
+                .child(Self::render_setting_mode("window", ts!("instance.window.label"), self.window_mode, |page, mode, cx| {
+                    page.window_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceWindow {
+                        id: page.instance_id,
+                        window: page.get_window_configuration(cx)
+                    });
+                }, cx))
+                .child(Checkbox::new("window_custom_size").label(ts!("instance.window.custom_size")).checked(self.window_custom_size).disabled(!window_override_enabled).on_click(cx.listener(|page, value, _, cx| {
+                    if page.window_custom_size != *value {
+                        page.window_custom_size = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceWindow {
                             id: page.instance_id,
-                            jvm_flags: page.get_jvm_flags_configuration(cx)
+                            window: page.get_window_configuration(cx)
                         });
                         cx.notify();
                     }
                 })))
-                .child(Input::new(&self.jvm_flags_input_state).disabled(!jvm_flags_enabled))
-            )
-            .child(v_flex()
-                .gap_1()
-                .child(Checkbox::new("jvm_binary").label(ts!("instance.jvm_binary")).checked(jvm_binary_enabled).on_click(cx.listener(|page, value, _, cx| {
-                    if page.jvm_binary_enabled != *value {
-                        page.jvm_binary_enabled = *value;
-                        page.backend_handle.send(MessageToBackend::SetInstanceJvmBinary {
+                .child(h_flex()
+                    .gap_1()
+                    .child(NumberInput::new(&self.window_width_input_state).small().disabled(!window_override_enabled || !self.window_custom_size))
+                    .child("×")
+                    .child(NumberInput::new(&self.window_height_input_state).small().disabled(!window_override_enabled || !self.window_custom_size))
+                )
+                .child(Checkbox::new("window_maximised").label(ts!("instance.window.maximised")).checked(self.window_maximised).disabled(!window_override_enabled).on_click(cx.listener(|page, value, _, cx| {
+                    if page.window_maximised != *value {
+                        page.window_maximised = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceWindow {
+                            id: page.instance_id,
+                            window: page.get_window_configuration(cx)
+                        });
+                        cx.notify();
+                    }
+                })))
+                .child(Checkbox::new("window_fullscreen").label(ts!("instance.window.fullscreen")).checked(self.window_fullscreen).disabled(!window_override_enabled).on_click(cx.listener(|page, value, _, cx| {
+                    if page.window_fullscreen != *value {
+                        page.window_fullscreen = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceWindow {
                             id: page.instance_id,
-                            jvm_binary: page.get_jvm_binary_configuration()
+                            window: page.get_window_configuration(cx)
                         });
                         cx.notify();
                     }
                 })))
+            ).child(v_flex()
+                .gap_1()
+                .child(Self::render_setting_mode("jvm_flags", ts!("instance.jvm_flags"), self.jvm_flags_mode, |page, mode, cx| {
+                    page.jvm_flags_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceJvmFlags {
+                        id: page.instance_id,
+                        jvm_flags: page.get_jvm_flags_configuration(cx)
+                    });
+                }, cx))
+                .child(Input::new(&self.jvm_flags_input_state).disabled(!jvm_flags_enabled))
+            )
+            .child(v_flex()
+                .gap_1()
+                .child(Self::render_setting_mode("environment_variables", ts!("instance.environment_variables.label"), self.environment_variables_mode, |page, mode, cx| {
+                    page.environment_variables_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceEnvironmentVariables {
+                        id: page.instance_id,
+                        environment_variables: page.get_environment_variables_configuration(cx)
+                    });
+                }, cx))
+                .child(Input::new(&self.environment_variables_input_state).disabled(!environment_variables_enabled))
+                .child(div().text_sm().text_color(cx.theme().muted_foreground).child(ts!("instance.environment_variables.note")))
+            )
+            .child(v_flex()
+                .gap_1()
+                .child(Self::render_setting_mode("game_arguments", ts!("instance.game_arguments.label"), self.game_arguments_mode, |page, mode, cx| {
+                    page.game_arguments_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceGameArguments {
+                        id: page.instance_id,
+                        game_arguments: page.get_game_arguments_configuration(cx)
+                    });
+                }, cx))
+                .child(Input::new(&self.game_arguments_input_state).disabled(!game_arguments_enabled))
+                .child(div().text_sm().text_color(cx.theme().muted_foreground).child(ts!("instance.game_arguments.note")))
+            )
+            .child(v_flex()
+                .gap_1()
+                .child(Self::render_setting_mode("jvm_binary", ts!("instance.jvm_binary"), self.jvm_binary_mode, |page, mode, cx| {
+                    page.jvm_binary_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceJvmBinary {
+                        id: page.instance_id,
+                        jvm_binary: page.get_jvm_binary_configuration()
+                    });
+                }, cx))
                 .child(PathLabel::button_opt(&self.jvm_binary_path, "select_jvm_binary").disabled(!jvm_binary_enabled).on_click(cx.listener(|this, _, window, cx| {
                     this.select_file(ts!("instance.select_jvm_binary"), |this, path| {
                         this.jvm_binary_path = path.map(|path| PathLabel::new(path, false));
@@ -889,7 +1369,14 @@ impl Render for InstanceSettingsSubpage {
             )
             .child(v_flex()
                 .gap_1()
-                .child(Checkbox::new("system_glfw").label(ts!("instance.glfw_lib")).checked(self.override_glfw_enabled).on_click(cx.listener(|page, value, _, cx| {
+                .child(Self::render_setting_mode("system_libraries", ts!("instance.system_libraries"), self.system_libraries_mode, |page, mode, cx| {
+                    page.system_libraries_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceSystemLibraries {
+                        id: page.instance_id,
+                        system_libraries: page.get_system_libraries_configuration()
+                    });
+                }, cx))
+                .child(Checkbox::new("system_glfw").label(ts!("instance.glfw_lib")).checked(self.override_glfw_enabled).disabled(!system_libraries_enabled).on_click(cx.listener(|page, value, _, cx| {
                     if page.override_glfw_enabled != *value {
                         page.override_glfw_enabled = *value;
                         page.backend_handle.send(MessageToBackend::SetInstanceSystemLibraries {
@@ -899,7 +1386,7 @@ impl Render for InstanceSettingsSubpage {
                         cx.notify();
                     }
                 })))
-                .child(PathLabel::button_opt(&self.override_glfw_path, "select_glfw").disabled(!self.override_glfw_enabled).on_click(cx.listener(|this, _, window, cx| {
+                .child(PathLabel::button_opt(&self.override_glfw_path, "select_glfw").disabled(!system_libraries_enabled || !self.override_glfw_enabled).on_click(cx.listener(|this, _, window, cx| {
                     this.select_file(ts!("instance.select_glfw_lib"), |this, path| {
                         this.override_glfw_path = path.map(|path| PathLabel::new(path, false));
                         this.backend_handle.send(MessageToBackend::SetInstanceSystemLibraries {
@@ -910,7 +1397,7 @@ impl Render for InstanceSettingsSubpage {
                 })))
             ).child(v_flex()
                 .gap_1()
-                .child(Checkbox::new("system_openal").label(ts!("instance.openal_lib")).checked(self.override_openal_enabled).on_click(cx.listener(|page, value, _, cx| {
+                .child(Checkbox::new("system_openal").label(ts!("instance.openal_lib")).checked(self.override_openal_enabled).disabled(!system_libraries_enabled).on_click(cx.listener(|page, value, _, cx| {
                     if page.override_openal_enabled != *value {
                         page.override_openal_enabled = *value;
                         page.backend_handle.send(MessageToBackend::SetInstanceSystemLibraries {
@@ -921,7 +1408,7 @@ impl Render for InstanceSettingsSubpage {
 
                     }
                 })))
-                .child(PathLabel::button_opt(&self.override_openal_path, "select_openal").disabled(!self.override_openal_enabled).on_click(cx.listener(|this, _, window, cx| {
+                .child(PathLabel::button_opt(&self.override_openal_path, "select_openal").disabled(!system_libraries_enabled || !self.override_openal_enabled).on_click(cx.listener(|this, _, window, cx| {
                     this.select_file(ts!("instance.select_openal_lib"), |this, path| {
                         this.override_openal_path = path.map(|path| PathLabel::new(path, false));
                         this.backend_handle.send(MessageToBackend::SetInstanceSystemLibraries {
@@ -932,24 +1419,46 @@ impl Render for InstanceSettingsSubpage {
                 })))
             ).child(v_flex()
                 .gap_1()
-                .child(Checkbox::new("wrapper_command").label(ts!("instance.wrapper_command")).checked(wrapper_command_enabled).on_click(cx.listener(|page, value, _, cx| {
-                    if page.wrapper_command_enabled != *value {
-                        page.wrapper_command_enabled = *value;
-                        page.backend_handle.send(MessageToBackend::SetInstanceWrapperCommand {
+                .child(Self::render_setting_mode("wrapper_command", ts!("instance.wrapper_command"), self.wrapper_command_mode, |page, mode, cx| {
+                    page.wrapper_command_mode = mode;
+                    page.backend_handle.send(MessageToBackend::SetInstanceWrapperCommand {
+                        id: page.instance_id,
+                        wrapper_command: page.get_wrapper_command_configuration(cx)
+                    });
+                }, cx))
+                .child(Input::new(&self.wrapper_command_input_state).disabled(!wrapper_command_enabled))
+            ).child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("hook_commands").label(ts!("instance.hook_commands.label")).checked(hook_commands_enabled).on_click(cx.listener(|page, value, _, cx| {
+                    if page.hook_commands_enabled != *value {
+                        page.hook_commands_enabled = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceHookCommands {
                             id: page.instance_id,
-                            wrapper_command: page.get_wrapper_command_configuration(cx)
+                            hook_commands: page.get_hook_commands_configuration(cx)
                         });
                         cx.notify();
                     }
                 })))
-                .child(Input::new(&self.wrapper_command_input_state).disabled(!wrapper_command_enabled))
+                .child(ts!("instance.hook_commands.pre_launch"))
+                .child(Input::new(&self.pre_launch_command_input_state).disabled(!hook_commands_enabled))
+                .child(ts!("instance.hook_commands.post_exit"))
+                .child(Input::new(&self.post_exit_command_input_state).disabled(!hook_commands_enabled))
+                .child(div().text_sm().text_color(cx.theme().muted_foreground).child(ts!("instance.hook_commands.placeholders")))
             );
 
+        #[cfg(target_os = "linux")]
+        let linux_wrapper_enabled = self.linux_wrapper_mode == InstanceSettingMode::Override;
         #[cfg(target_os = "linux")]
         let runtime_content = runtime_content.child(v_flex()
             .gap_1()
-            .child(ts!("instance.linux.label"))
-            .child(Checkbox::new("use_mangohud").label(ts!("instance.linux.use_mangohud")).checked(self.use_mangohud).disabled(!self.mangohud_available).on_click(cx.listener(|page, value, _, cx| {
+            .child(Self::render_setting_mode("linux_wrapper", ts!("instance.linux.label"), self.linux_wrapper_mode, |page, mode, cx| {
+                page.linux_wrapper_mode = mode;
+                page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
+                    id: page.instance_id,
+                    linux_wrapper: page.get_linux_wrapper_configuration()
+                });
+            }, cx))
+            .child(Checkbox::new("use_mangohud").label(ts!("instance.linux.use_mangohud")).checked(self.use_mangohud).disabled(!linux_wrapper_enabled || !self.mangohud_available).on_click(cx.listener(|page, value, _, cx| {
                 if page.use_mangohud != *value {
                     page.use_mangohud = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -959,7 +1468,7 @@ impl Render for InstanceSettingsSubpage {
                     cx.notify();
                 }
             })))
-            .child(Checkbox::new("use_gamemode").label(ts!("instance.linux.use_gamemode")).checked(self.use_gamemode).disabled(!self.gamemode_available).on_click(cx.listener(|page, value, _, cx| {
+            .child(Checkbox::new("use_gamemode").label(ts!("instance.linux.use_gamemode")).checked(self.use_gamemode).disabled(!linux_wrapper_enabled || !self.gamemode_available).on_click(cx.listener(|page, value, _, cx| {
                 if page.use_gamemode != *value {
                     page.use_gamemode = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -969,7 +1478,7 @@ impl Render for InstanceSettingsSubpage {
                     cx.notify();
                 }
             })))
-            .child(Checkbox::new("use_discrete_gpu").label(ts!("instance.linux.use_discrete_gpu")).checked(self.use_discrete_gpu).on_click(cx.listener(|page, value, _, cx| {
+            .child(Checkbox::new("use_discrete_gpu").label(ts!("instance.linux.use_discrete_gpu")).checked(self.use_discrete_gpu).disabled(!linux_wrapper_enabled).on_click(cx.listener(|page, value, _, cx| {
                 if page.use_discrete_gpu != *value {
                     page.use_discrete_gpu = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -979,7 +1488,7 @@ impl Render for InstanceSettingsSubpage {
                     cx.notify();
                 }
             })))
-            .child(Checkbox::new("disable_gl_threaded_optimizations").label(ts!("instance.linux.disable_gl_threaded_optimizations")).checked(self.disable_gl_threaded_optimizations).on_click(cx.listener(|page, value, _, cx| {
+            .child(Checkbox::new("disable_gl_threaded_optimizations").label(ts!("instance.linux.disable_gl_threaded_optimizations")).checked(self.disable_gl_threaded_optimizations).disabled(!linux_wrapper_enabled).on_click(cx.listener(|page, value, _, cx| {
                 if page.disable_gl_threaded_optimizations != *value {
                     page.disable_gl_threaded_optimizations = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -1050,6 +1559,27 @@ impl Render for InstanceSettingsSubpage {
                     }).detach();
                 }
             }))
+            .child(Button::new("export").label(ts!("instance.export")).overflow_x_hidden().success().on_click({
+                let instance = self.instance.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_: &ClickEvent, window, cx| {
+                    let instance = instance.read(cx);
+                    let id = instance.id;
+                    let name = instance.name.clone();
+                    let dot_minecraft_folder = instance.dot_minecraft_folder.clone();
+                    crate::modals::export_instance::open_export_instance(id, name, dot_minecraft_folder, backend_handle.clone(), window, cx);
+                }
+            }))
+            .child(Button::new("snapshots").label(ts!("instance.snapshots")).overflow_x_hidden().on_click({
+                let instance = self.instance.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_: &ClickEvent, window, cx| {
+                    let instance = instance.read(cx);
+                    let id = instance.id;
+                    let name = instance.name.clone();
+                    crate::modals::instance_snapshots::open_instance_snapshots(id, name, backend_handle.clone(), window, cx);
+                }
+            }))
             .child(Button::new("delete").label(ts!("instance.delete")).overflow_x_hidden().danger().on_click({
                 let instance = self.instance.clone();
                 let backend_handle = self.backend_handle.clone();
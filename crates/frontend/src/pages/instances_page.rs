@@ -1,17 +1,20 @@
+use std::sync::Arc;
+
 use bridge::handle::BackendHandle;
 use gpui::{prelude::*, *};
 use gpui_component::{
-    IndexPath, button::{Button, ButtonVariants}, h_flex, select::{Select, SelectDelegate, SelectEvent, SelectItem, SelectState}, table::{DataTable, TableDelegate, TableState}
+    IndexPath, button::{Button, ButtonVariants}, h_flex, v_flex, select::{Select, SelectDelegate, SelectEvent, SelectItem, SelectState}, table::{DataTable, TableState}
 };
 use strum::IntoEnumIterator;
 
 use crate::{
-    component::{instance_list::InstanceList, named_dropdown::{NamedDropdown, NamedDropdownItem}, responsive_grid::ResponsiveGrid}, entity::{DataEntities, instance::InstanceEntries, metadata::FrontendMetadata}, icon::PandoraIcon, interface_config::{InstancesViewMode, InterfaceConfig}, pages::page::Page, ts
+    component::{instance_list::{render_group_header, InstanceFilter, InstanceList, InstanceListRow}, named_dropdown::{NamedDropdown, NamedDropdownItem}, responsive_grid::ResponsiveGrid}, entity::{DataEntities, instance::{InstanceAddedEvent, InstanceEntries, InstanceModifiedEvent, InstanceRemovedEvent}, metadata::FrontendMetadata}, icon::PandoraIcon, interface_config::{InstancesViewMode, InterfaceConfig}, pages::page::Page, ts
 };
 
 pub struct InstancesPage {
     instance_table: Entity<TableState<InstanceList>>,
     view_dropdown: Entity<SelectState<NamedDropdown<InstancesViewMode>>>,
+    filter_dropdown: Entity<SelectState<NamedDropdown<InstanceFilter>>>,
 
     metadata: Entity<FrontendMetadata>,
     instances: Entity<InstanceEntries>,
@@ -40,14 +43,85 @@ impl InstancesPage {
             InterfaceConfig::get_mut(cx).instances_view_mode = view;
         }).detach();
 
+        cx.observe(&instance_table, |_, _, cx| cx.notify()).detach();
+
+        let filter_dropdown = cx.new(|cx| {
+            let delegate = NamedDropdown::new(Self::filter_items(&data.instances, cx));
+            SelectState::new(delegate, Some(IndexPath::new(0)), window, cx)
+        });
+        cx.subscribe(&filter_dropdown, |this, _, event: &SelectEvent<NamedDropdown<InstanceFilter>>, cx| {
+            let SelectEvent::Confirm(Some(value)) = event else {
+                return;
+            };
+            let filter = value.item.clone();
+
+            this.instance_table.update(cx, |table, cx| {
+                table.delegate_mut().set_filter(filter, cx);
+                cx.notify();
+            });
+        }).detach();
+
+        // Tags can be added or removed from any instance, so the filter options need to follow
+        cx.subscribe_in::<_, InstanceAddedEvent>(&data.instances, window, |this, _, _, window, cx| {
+            this.update_filter_items(window, cx);
+        }).detach();
+        cx.subscribe_in::<_, InstanceModifiedEvent>(&data.instances, window, |this, _, _, window, cx| {
+            this.update_filter_items(window, cx);
+        }).detach();
+        cx.subscribe_in::<_, InstanceRemovedEvent>(&data.instances, window, |this, _, _, window, cx| {
+            this.update_filter_items(window, cx);
+        }).detach();
+
         Self {
             instance_table,
             view_dropdown,
+            filter_dropdown,
             metadata: data.metadata.clone(),
             instances: data.instances.clone(),
             backend_handle: data.backend_handle.clone(),
         }
     }
+
+    fn filter_items(instances: &Entity<InstanceEntries>, cx: &App) -> Vec<NamedDropdownItem<InstanceFilter>> {
+        let mut tags: Vec<Arc<str>> = Vec::new();
+        for entry in instances.read(cx).entries.values() {
+            for tag in &entry.read(cx).configuration.tags {
+                if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+        tags.sort_by(|a, b| lexical_sort::natural_lexical_cmp(a, b));
+
+        let mut items = vec![
+            NamedDropdownItem { name: ts!("instance.filter.all"), item: InstanceFilter::All },
+            NamedDropdownItem { name: ts!("instance.filter.favourites"), item: InstanceFilter::Favourites },
+        ];
+        items.extend(tags.into_iter().map(|tag| NamedDropdownItem {
+            name: ts!("instance.filter.tag", tag = tag),
+            item: InstanceFilter::Tag(tag),
+        }));
+        items
+    }
+
+    fn update_filter_items(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let items = Self::filter_items(&self.instances, cx);
+        let current_filter = self.instance_table.read(cx).delegate().filter().clone();
+
+        let selected = items.iter().position(|item| item.item == current_filter);
+        if selected.is_none() {
+            // The tag being filtered by no longer exists on any instance
+            self.instance_table.update(cx, |table, cx| {
+                table.delegate_mut().set_filter(InstanceFilter::All, cx);
+                cx.notify();
+            });
+        }
+
+        self.filter_dropdown.update(cx, |dropdown, cx| {
+            dropdown.set_items(NamedDropdown::new(items), window, cx);
+            dropdown.set_selected_index(Some(IndexPath::new(selected.unwrap_or(0))), window, cx);
+        });
+    }
 }
 
 impl Page for InstancesPage {
@@ -63,8 +137,10 @@ impl Page for InstancesPage {
         // wrapping in div makes it not take up the full space of the titlebar
         let select_view = div()
             .child(Select::new(&self.view_dropdown).title_prefix(format!("{}: ", ts!("instance.view"))));
+        let select_filter = div()
+            .child(Select::new(&self.filter_dropdown).title_prefix(format!("{}: ", ts!("instance.filter.label"))));
 
-        h_flex().gap_3().child(create_instance).child(select_view)
+        h_flex().gap_3().child(create_instance).child(select_filter).child(select_view)
     }
 
     fn scrollable(&self, cx: &App) -> bool {
@@ -79,17 +155,44 @@ impl Render for InstancesPage {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         match InterfaceConfig::get(cx).instances_view_mode {
             InstancesViewMode::Cards => {
-                let cards = self.instance_table.update(cx, |table, cx| {
-                    let rows = table.delegate().rows_count(cx);
-                    (0..rows).map(|i| table.delegate().render_card(i, cx)).collect::<Vec<_>>()
-                });
-
                 let size = Size::new(
                     gpui::AvailableSpace::MinContent,
                     gpui::AvailableSpace::MinContent
                 );
 
-                div().p_4().child(ResponsiveGrid::new(size).size_full().gap_4().children(cards)).into_any_element()
+                let instance_table = self.instance_table.clone();
+                let sections = self.instance_table.update(cx, |table, cx| {
+                    let table = table.delegate();
+                    let mut sections = Vec::new();
+                    let mut cards = Vec::new();
+                    for (row_ix, row) in table.rows().iter().enumerate() {
+                        match row {
+                            InstanceListRow::Group { name, count, collapsed } => {
+                                if !cards.is_empty() {
+                                    sections.push(ResponsiveGrid::new(size).w_full().gap_4().children(cards.drain(..)).into_any_element());
+                                }
+
+                                let name = name.clone();
+                                let instance_table = instance_table.clone();
+                                sections.push(render_group_header(row_ix, &name, *count, *collapsed)
+                                    .on_click(move |_, _, cx| {
+                                        instance_table.update(cx, |table, cx| {
+                                            table.delegate_mut().toggle_group_collapsed(&name, cx);
+                                            cx.notify();
+                                        });
+                                    })
+                                    .into_any_element());
+                            },
+                            InstanceListRow::Instance(index) => cards.push(table.render_card(*index, cx)),
+                        }
+                    }
+                    if !cards.is_empty() {
+                        sections.push(ResponsiveGrid::new(size).w_full().gap_4().children(cards).into_any_element());
+                    }
+                    sections
+                });
+
+                v_flex().p_4().gap_4().size_full().children(sections).into_any_element()
             },
             InstancesViewMode::List => {
                 DataTable::new(&self.instance_table).bordered(false).into_any_element()
@@ -718,17 +718,14 @@ impl PrimaryAction {
             PrimaryAction::ErrorCheckingForUpdates => {},
             PrimaryAction::UpToDate => {},
             PrimaryAction::Update(ids) => {
-                for id in ids {
-                    let modal_action = ModalAction::default();
-                    data.backend_handle.send(MessageToBackend::UpdateContent {
-                        instance: install_for.unwrap(),
-                        content_id: *id,
-                        modal_action: modal_action.clone()
-                    });
-                    crate::modals::generic::show_notification(window, cx,
-                        ts!("instance.content.update.error"), modal_action);
-                }
-
+                let modal_action = ModalAction::default();
+                data.backend_handle.send(MessageToBackend::UpdateContent {
+                    instance: install_for.unwrap(),
+                    content_ids: ids.as_slice().into(),
+                    modal_action: modal_action.clone()
+                });
+                crate::modals::generic::show_notification(window, cx,
+                    ts!("instance.content.update.error"), modal_action);
             },
         }
     }
@@ -848,11 +845,13 @@ impl Render for ModrinthSearchPage {
                 .child(Button::new("fabric").label(ts!("modrinth.category.fabric")).selected(self.filter_loaders.contains(Loader::Fabric)))
                 .child(Button::new("forge").label(ts!("modrinth.category.forge")).selected(self.filter_loaders.contains(Loader::Forge)))
                 .child(Button::new("neoforge").label(ts!("modrinth.category.neoforge")).selected(self.filter_loaders.contains(Loader::NeoForge)))
+                .child(Button::new("quilt").label(ts!("modrinth.category.quilt")).selected(self.filter_loaders.contains(Loader::Quilt)))
                 .on_click(cx.listener(|page, clicked: &Vec<usize>, window, cx| {
                     page.set_filter_loaders(clicked.iter().filter_map(|index| match index {
                         0 => Some(Loader::Fabric),
                         1 => Some(Loader::Forge),
                         2 => Some(Loader::NeoForge),
+                        3 => Some(Loader::Quilt),
                         _ => None
                     }).collect(), window, cx);
                 })))
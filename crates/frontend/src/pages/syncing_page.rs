@@ -3,19 +3,23 @@ use std::{collections::HashSet, sync::Arc};
 use bridge::{handle::BackendHandle, message::{MessageToBackend, SyncState}, safe_path::SafePath};
 use gpui::{prelude::*, *};
 use gpui_component::{
-    button::{Button, ButtonVariants}, checkbox::Checkbox, h_flex, input::{Input, InputState}, spinner::Spinner, tooltip::Tooltip, v_flex, ActiveTheme as _, Disableable, Sizable
+    button::{Button, ButtonGroup, ButtonVariants}, checkbox::Checkbox, h_flex, input::{Input, InputState}, spinner::Spinner, tooltip::Tooltip, v_flex, ActiveTheme as _, Disableable, Selectable, Sizable
 };
 use once_cell::sync::Lazy;
 use rustc_hash::FxHashSet;
+use schema::backend_config::SyncMergeStrategy;
+use strum::IntoEnumIterator;
 
 use crate::{entity::DataEntities, icon::PandoraIcon, pages::page::Page, ts};
 
 pub struct SyncingPage {
     backend_handle: BackendHandle,
+    profile: Option<Arc<str>>,
     sync_state: Option<SyncState>,
     pending: FxHashSet<Arc<str>>,
     loading: FxHashSet<Arc<str>>,
     custom_input_state: Entity<InputState>,
+    profile_input_state: Entity<InputState>,
     _get_sync_state_task: Task<()>,
 }
 
@@ -23,10 +27,12 @@ impl SyncingPage {
     pub fn new(data: &DataEntities, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let mut page = Self {
             backend_handle: data.backend_handle.clone(),
+            profile: None,
             sync_state: None,
             pending: FxHashSet::default(),
             loading: FxHashSet::default(),
             custom_input_state: cx.new(|cx| InputState::new(window, cx)),
+            profile_input_state: cx.new(|cx| InputState::new(window, cx).placeholder(ts!("instance.sync.profile.new_placeholder"))),
             _get_sync_state_task: Task::ready(()),
         };
 
@@ -41,6 +47,12 @@ impl SyncingPage {
         let (send, recv) = tokio::sync::oneshot::channel();
         self._get_sync_state_task = cx.spawn(async move |page, cx| {
             let Ok(result): Result<SyncState, _> = recv.await else {
+                // The profile no longer exists, go back to the default one
+                let _ = page.update(cx, |page, cx| {
+                    if page.profile.is_some() {
+                        page.select_profile(None, cx);
+                    }
+                });
                 return;
             };
             let _ = page.update(cx, move |page, cx| {
@@ -57,27 +69,90 @@ impl SyncingPage {
         });
 
         self.backend_handle.send(MessageToBackend::GetSyncState {
+            profile: self.profile.clone(),
             channel: send,
         });
     }
 
+    fn select_profile(&mut self, profile: Option<Arc<str>>, cx: &mut Context<Self>) {
+        self.profile = profile;
+        self.sync_state = None;
+        self.loading.clear();
+        self.pending.clear();
+        self.update_sync_state(cx);
+        cx.notify();
+    }
+
+    fn create_profile_controls(&self, sync_state: &SyncState, cx: &mut Context<Self>) -> Div {
+        let profiles: Vec<Option<Arc<str>>> = std::iter::once(None).chain(sync_state.profiles.iter().cloned().map(Some)).collect();
+
+        let mut group = ButtonGroup::new("profiles").outline();
+        for (index, profile) in profiles.iter().enumerate() {
+            let label = match profile {
+                Some(profile) => SharedString::from(profile.clone()),
+                None => ts!("instance.sync.profile.default"),
+            };
+            group = group.child(Button::new(("profile", index)).label(label).selected(*profile == self.profile));
+        }
+        let group = group.on_click(cx.listener(move |page, selected: &Vec<usize>, _, cx| {
+            if let Some(profile) = selected.first().and_then(|index| profiles.get(*index)) && *profile != page.profile {
+                page.select_profile(profile.clone(), cx);
+            }
+        }));
+
+        let mut controls = h_flex()
+            .w_full()
+            .gap_2()
+            .child(group)
+            .child(Input::new(&self.profile_input_state).max_w_64())
+            .child(Button::new("create_profile").label(ts!("instance.sync.profile.create")).on_click(cx.listener(|page, _, window, cx| {
+                let input = page.profile_input_state.read(cx).value();
+                let input = input.as_str().trim_ascii();
+                if input.is_empty() || input.contains(['/', '\\']) || SafePath::new(input).is_none() {
+                    return;
+                }
+
+                let name: Arc<str> = input.into();
+                page.backend_handle.send(MessageToBackend::CreateSyncProfile {
+                    name: name.clone(),
+                });
+                page.profile_input_state.update(cx, |state, cx| state.set_value("", window, cx));
+                page.select_profile(Some(name), cx);
+            })));
+
+        if let Some(profile) = self.profile.clone() {
+            controls = controls.child(Button::new("delete_profile").danger().label(ts!("instance.sync.profile.delete")).on_click(cx.listener(move |page, _, _, cx| {
+                page.backend_handle.send(MessageToBackend::DeleteSyncProfile {
+                    name: profile.clone(),
+                });
+                page.select_profile(None, cx);
+            })));
+        }
+
+        controls
+    }
+
     pub fn create_entry(&self, sync_state: &SyncState, name: Arc<str>, is_file: bool, label: SharedString, warning: Hsla, info: Hsla, cx: &mut Context<Self>) -> Div {
         let synced_count;
         let cannot_sync_count;
         let enabled;
+        let strategy;
         if let Some(sync_target_state) = sync_state.targets.get(&name) && sync_target_state.is_file == is_file {
             synced_count = sync_target_state.sync_count;
             cannot_sync_count = sync_target_state.cannot_sync_count;
             enabled = sync_target_state.enabled;
+            strategy = sync_target_state.strategy;
         } else {
             synced_count = 0;
             cannot_sync_count = 0;
             enabled = false;
+            strategy = SyncMergeStrategy::default_for(&name);
         }
         let disabled = !enabled && cannot_sync_count > 0;
         let is_loading = self.loading.contains(&name);
 
         let disable_tooltip = ts!("instance.sync.already_exists", num = cannot_sync_count, name = name);
+        let strategy_group = (enabled && is_file).then(|| self.create_strategy_group(name.clone(), strategy, cx));
         let backend_handle = self.backend_handle.clone();
         let checkbox = Checkbox::new(name.clone())
             .label(label)
@@ -89,6 +164,7 @@ impl SyncingPage {
             .on_click(cx.listener(move |page, value, _, cx| {
 
             backend_handle.send(MessageToBackend::SetSyncing {
+                profile: page.profile.clone(),
                 target: name.clone(),
                 is_file,
                 value: *value,
@@ -101,7 +177,7 @@ impl SyncingPage {
             }
         }));
 
-        let mut base = h_flex().line_height(relative(1.0)).gap_2p5().child(checkbox);
+        let mut base = h_flex().line_height(relative(1.0)).gap_2p5().child(checkbox).children(strategy_group);
 
         if is_loading {
             base = base.child(Spinner::new());
@@ -122,6 +198,45 @@ impl SyncingPage {
 
         base
     }
+
+    fn create_strategy_group(&self, name: Arc<str>, selected: SyncMergeStrategy, cx: &mut Context<Self>) -> ButtonGroup {
+        // Server list merging only understands servers.dat
+        let strategies: Vec<SyncMergeStrategy> = SyncMergeStrategy::iter()
+            .filter(|strategy| *strategy != SyncMergeStrategy::ServersUnion || name.ends_with("servers.dat"))
+            .collect();
+
+        let mut group = ButtonGroup::new(SharedString::from(format!("strategy-{name}"))).outline().small();
+        for strategy in &strategies {
+            let label = match strategy {
+                SyncMergeStrategy::Latest => ts!("instance.sync.strategy.latest"),
+                SyncMergeStrategy::KeyValue => ts!("instance.sync.strategy.key_value"),
+                SyncMergeStrategy::ServersUnion => ts!("instance.sync.strategy.servers_union"),
+                SyncMergeStrategy::JsonDeepMerge => ts!("instance.sync.strategy.json_deep_merge"),
+            };
+            group = group.child(Button::new(SharedString::from(format!("strategy-{name}-{strategy:?}")))
+                .label(label)
+                .selected(*strategy == selected));
+        }
+
+        let backend_handle = self.backend_handle.clone();
+        group.on_click(cx.listener(move |page, selected: &Vec<usize>, _, cx| {
+            let Some(strategy) = selected.first().and_then(|index| strategies.get(*index)) else {
+                return;
+            };
+
+            backend_handle.send(MessageToBackend::SetSyncStrategy {
+                profile: page.profile.clone(),
+                target: name.clone(),
+                strategy: *strategy,
+            });
+
+            page.loading.insert(name.clone());
+            if page.pending.is_empty() {
+                page.pending.insert(name.clone());
+                page.update_sync_state(cx);
+            }
+        }))
+    }
 }
 
 impl Page for SyncingPage {
@@ -150,6 +265,7 @@ impl Render for SyncingPage {
         let info = cx.theme().blue;
         let content = v_flex().size_full().p_3().gap_3()
             .child(ts!("instance.sync.description"))
+            .child(self.create_profile_controls(sync_state, cx))
             .child(Button::new("open").info().icon(PandoraIcon::FolderOpen).label(ts!("instance.sync.open_folder")).on_click(move |_, window, cx| {
                 crate::open_folder(&sync_folder, window, cx);
             }).w_72())
@@ -193,6 +309,7 @@ impl Render for SyncingPage {
                     if SafePath::new(input).is_some() {
                         let name: Arc<str> = input.into();
                         page.backend_handle.send(MessageToBackend::SetSyncing {
+                            profile: page.profile.clone(),
                             target: name.clone(),
                             is_file: true,
                             value: true,
@@ -213,6 +330,7 @@ impl Render for SyncingPage {
                     if SafePath::new(input).is_some() {
                         let name: Arc<str> = input.into();
                         page.backend_handle.send(MessageToBackend::SetSyncing {
+                            profile: page.profile.clone(),
                             target: name.clone(),
                             is_file: false,
                             value: true,
@@ -3,6 +3,7 @@ use std::{collections::HashMap, sync::{Arc, atomic::AtomicBool}};
 use bridge::{instance::InstanceStatus, message::{BridgeNotificationType, MessageToFrontend}};
 use gpui::{AnyWindowHandle, App, AppContext, Entity, SharedString, TitlebarOptions, Window, WindowDecorations, WindowHandle, WindowOptions, px, size};
 use gpui_component::{notification::{Notification, NotificationType}, Root, WindowExt};
+use schema::instance::InstanceKind;
 
 use crate::{entity::{DataEntities, account::AccountEntries, instance::InstanceEntries, metadata::FrontendMetadata}, game_output::{GameOutput, GameOutputRoot}, interface_config::InterfaceConfig, root::LauncherRoot, ts};
 
@@ -178,7 +179,7 @@ impl Processor {
                     window.close_all_dialogs(cx);
                 });
             },
-            MessageToFrontend::CreateGameOutputWindow { id, keep_alive } => {
+            MessageToFrontend::CreateGameOutputWindow { id, instance, keep_alive } => {
                 let options = WindowOptions {
                     app_id: Some("PandoraLauncher".into()),
                     window_min_size: Some(size(px(360.0), px(240.0))),
@@ -189,10 +190,13 @@ impl Processor {
                     window_decorations: Some(WindowDecorations::Server),
                     ..Default::default()
                 };
+                let backend_handle = self.data.backend_handle.clone();
+                let accepts_input = self.data.instances.read(cx).entries.get(&instance)
+                    .is_some_and(|entry| entry.read(cx).configuration.kind == InstanceKind::Server);
                 _ = cx.open_window(options, |window, cx| {
                     let game_output = cx.new(|_| GameOutput::default());
                     let game_output_root = cx
-                        .new(|cx| GameOutputRoot::new(keep_alive, game_output.clone(), window, cx));
+                        .new(|cx| GameOutputRoot::new(keep_alive, game_output.clone(), instance, accepts_input, backend_handle, window, cx));
                     window.activate_window();
                     let window_handle = window.window_handle().downcast::<Root>().unwrap();
                     self.game_output_windows.insert(id, (window_handle, game_output.clone()));
@@ -220,9 +224,32 @@ impl Processor {
             MessageToFrontend::MetadataResult { request, result, keep_alive_handle } => {
                 FrontendMetadata::set(&self.data.metadata, request, result, keep_alive_handle, cx);
             },
+            MessageToFrontend::ServerStatusTrendsUpdated { trends } => {
+                self.data.set_server_status_trends(trends, cx);
+            },
             MessageToFrontend::SkinLibraryUpdated { skin_library } => {
                 self.data.set_skin_library(skin_library, cx);
             },
+            MessageToFrontend::InstanceCrashed { .. } => {
+                self.with_main_window(message, cx, |processor, message, window, cx| {
+                    let MessageToFrontend::InstanceCrashed { name, analysis, .. } = message else {
+                        unreachable!();
+                    };
+
+                    let backend_handle = processor.data.backend_handle.clone();
+                    crate::modals::crash_report::show_crash_report(name, analysis, backend_handle, window, cx);
+                });
+            },
+            MessageToFrontend::ServerEulaRequired { .. } => {
+                self.with_main_window(message, cx, |processor, message, window, cx| {
+                    let MessageToFrontend::ServerEulaRequired { id, name } = message else {
+                        unreachable!();
+                    };
+
+                    let backend_handle = processor.data.backend_handle.clone();
+                    crate::modals::server_eula::open_server_eula(id, name.as_str().into(), backend_handle, window, cx);
+                });
+            },
             MessageToFrontend::UpdateAvailable { .. } => {
                 self.with_main_window(message, cx, |_, message, window, cx| {
                     let MessageToFrontend::UpdateAvailable { update } = message else {
@@ -146,9 +146,15 @@ pub fn start_instance(
 ) {
     let modal_action = ModalAction::default();
 
+    let screen_size = window.display(cx).or_else(|| cx.primary_display()).map(|display| {
+        let size = display.bounds().size;
+        (size.width.as_f32() as u32, size.height.as_f32() as u32)
+    });
+
     backend_handle.send(MessageToBackend::StartInstance {
         id,
         quick_play,
+        screen_size,
         modal_action: modal_action.clone(),
     });
 
@@ -189,9 +195,9 @@ pub fn start_update_check(
     modals::generic::show_modal(window, cx, title, ts!("instance.content.update.check.error"), modal_action);
 }
 
-pub fn update_single_mod(
+pub fn update_content(
     instance: InstanceID,
-    mod_id: InstanceContentID,
+    content_ids: Arc<[InstanceContentID]>,
     backend_handle: &BackendHandle,
     window: &mut Window,
     cx: &mut App,
@@ -200,7 +206,7 @@ pub fn update_single_mod(
 
     backend_handle.send(MessageToBackend::UpdateContent {
         instance,
-        content_id: mod_id,
+        content_ids,
         modal_action: modal_action.clone(),
     });
 
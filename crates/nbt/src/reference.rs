@@ -208,7 +208,7 @@ impl<'a> CompoundRef<'a> {
         nbt
     }
 
-    fn clone_into(&self, mut into: CompoundRefMut<'_>) {
+    pub fn clone_into(&self, mut into: CompoundRefMut<'_>) {
         for (key, entry) in self.entries() {
             match entry {
                 NBTRef::Byte(v) => into.insert_byte(key, *v),
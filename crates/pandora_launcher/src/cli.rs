@@ -0,0 +1,622 @@
+use std::{
+    path::{Path, PathBuf}, sync::{Arc, mpsc}, time::{Duration, Instant}
+};
+
+use bridge::{
+    handle::{BackendHandle, FrontendReceiver}, import::{ImportFromOtherLauncherJob, OtherLauncher}, install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget}, instance::{InstanceContentSummary, InstanceID}, message::{BridgeNotificationType, MessageToBackend, MessageToFrontend}, modal_action::ModalAction
+};
+use clap::Subcommand;
+use schema::{content::ContentSource, instance::{InstanceConfiguration, InstanceKind}, loader::Loader};
+use serde_json::json;
+use strum::IntoEnumIterator;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List all instances
+    List,
+    /// Create a new instance
+    Create {
+        name: String,
+        /// Minecraft version, e.g. 1.21.1
+        #[arg(long)]
+        version: String,
+        /// Mod loader (vanilla, fabric, quilt, forge, neoforge)
+        #[arg(long, default_value = "vanilla", value_parser = parse_loader)]
+        loader: Loader,
+        /// Create a dedicated server instead of a client
+        #[arg(long)]
+        server: bool,
+    },
+    /// Launch an instance
+    Launch {
+        instance: String,
+        /// Agree to the Minecraft EULA (https://aka.ms/MinecraftEULA), required to start servers
+        #[arg(long)]
+        accept_eula: bool,
+    },
+    /// Install a mod, resource pack or modpack into an instance
+    #[command(group(clap::ArgGroup::new("source").required(true).args(["modrinth", "curseforge", "file"])))]
+    InstallContent {
+        instance: String,
+        /// Modrinth project id
+        #[arg(long)]
+        modrinth: Option<String>,
+        /// Modrinth version id, defaults to the latest compatible version
+        #[arg(long, requires = "modrinth")]
+        version: Option<String>,
+        /// CurseForge project id
+        #[arg(long)]
+        curseforge: Option<u32>,
+        /// Local file
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Don't install required dependencies
+        #[arg(long)]
+        no_dependencies: bool,
+    },
+    /// Check for updates and update all content of an instance
+    Update {
+        instance: String,
+        /// Only list available updates
+        #[arg(long)]
+        check_only: bool,
+    },
+    /// File syncing
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommand,
+    },
+    /// Run a command on a running server instance over RCON
+    Rcon {
+        instance: String,
+        /// Command to run, e.g. `say hello`
+        #[arg(required = true, trailing_var_arg = true)]
+        command: Vec<String>,
+    },
+    /// Import instances from another launcher
+    Import {
+        /// Launcher to import from (prism, curseforge, modrinth, multimc, atlauncher, official, gdlauncher, folder)
+        #[arg(value_parser = parse_launcher)]
+        launcher: OtherLauncher,
+        /// Launcher directory, defaults to the launcher's default location
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Also import accounts
+        #[arg(long)]
+        accounts: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SyncCommand {
+    /// Show the state of all sync targets
+    Status {
+        /// Sync profile, defaults to the default profile
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+impl Command {
+    // Paths are relative to the directory the command was run from, which is changed during startup
+    pub fn make_paths_absolute(&mut self) {
+        let path = match self {
+            Command::InstallContent { file: Some(path), .. } => path,
+            Command::Import { path: Some(path), .. } => path,
+            _ => return,
+        };
+        if let Ok(absolute) = std::path::absolute(&*path) {
+            *path = absolute;
+        }
+    }
+}
+
+fn parse_loader(value: &str) -> Result<Loader, String> {
+    match Loader::from_name(value) {
+        Loader::Unknown => Err(format!("unknown loader {value}")),
+        loader => Ok(loader),
+    }
+}
+
+fn parse_launcher(value: &str) -> Result<OtherLauncher, String> {
+    OtherLauncher::iter()
+        .find(|launcher| launcher.to_string().eq_ignore_ascii_case(value) || format!("{launcher:?}").eq_ignore_ascii_case(value))
+        .ok_or_else(|| format!("unknown launcher {value}"))
+}
+
+struct CliInstance {
+    id: InstanceID,
+    name: String,
+    root_path: Arc<Path>,
+    dot_minecraft_folder: Arc<Path>,
+    configuration: InstanceConfiguration,
+}
+
+struct Session {
+    backend_handle: BackendHandle,
+    receiver: mpsc::Receiver<MessageToFrontend>,
+    flush_sender: tokio::sync::mpsc::UnboundedSender<(tokio::sync::oneshot::Receiver<()>, mpsc::Sender<()>)>,
+    instances: Vec<CliInstance>,
+}
+
+impl Session {
+    fn start(launcher_dir: PathBuf) -> Self {
+        let (backend_recv, backend_handle, mut frontend_recv, frontend_handle) = bridge::handle::create_pair();
+
+        backend::start(launcher_dir, frontend_handle, backend_handle.clone(), backend_recv);
+
+        let (sender, receiver) = mpsc::channel();
+        let (flush_sender, mut flush_receiver) = tokio::sync::mpsc::unbounded_channel::<(tokio::sync::oneshot::Receiver<()>, mpsc::Sender<()>)>();
+        let mut session = Self {
+            backend_handle,
+            receiver,
+            flush_sender,
+            instances: Vec::new(),
+        };
+
+        // Instances are loaded synchronously by backend::start, so they're already available
+        while let Some(message) = frontend_recv.try_recv() {
+            session.handle_message(&message);
+        }
+
+        // The frontend channel needs to be drained continuously, otherwise the backend will block (or panic in debug)
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            runtime.block_on(async move {
+                loop {
+                    tokio::select! {
+                        message = frontend_recv.recv() => {
+                            if !forward_message(&sender, message) {
+                                return;
+                            }
+                        },
+                        Some((mut backend_flushed, flushed)) = flush_receiver.recv() => {
+                            // Keep draining while the backend handles everything that was sent before the flush
+                            loop {
+                                tokio::select! {
+                                    message = frontend_recv.recv() => {
+                                        if !forward_message(&sender, message) {
+                                            return;
+                                        }
+                                    },
+                                    _ = &mut backend_flushed => break,
+                                }
+                            }
+                            if !drain_messages(&sender, &mut frontend_recv) {
+                                return;
+                            }
+                            _ = flushed.send(());
+                        },
+                    }
+                }
+            });
+        });
+
+        session
+    }
+
+    // Waits until the backend has handled every message sent so far and its notifications have been printed
+    fn flush(&self) {
+        let (backend_flushed_send, backend_flushed_recv) = tokio::sync::oneshot::channel();
+        let (flushed_send, flushed_recv) = mpsc::channel();
+        self.backend_handle.send(MessageToBackend::Flush { channel: backend_flushed_send });
+        if self.flush_sender.send((backend_flushed_recv, flushed_send)).is_ok() {
+            _ = flushed_recv.recv();
+        }
+    }
+
+    fn handle_message(&mut self, message: &MessageToFrontend) {
+        match message {
+            MessageToFrontend::InstanceAdded { id, name, root_path, dot_minecraft_folder, configuration, .. } => {
+                self.instances.push(CliInstance {
+                    id: *id,
+                    name: name.to_string(),
+                    root_path: root_path.clone(),
+                    dot_minecraft_folder: dot_minecraft_folder.clone(),
+                    configuration: configuration.clone(),
+                });
+            },
+            MessageToFrontend::InstanceModified { id, name, root_path, dot_minecraft_folder, configuration, .. } => {
+                if let Some(instance) = self.instances.iter_mut().find(|instance| instance.id == *id) {
+                    instance.name = name.to_string();
+                    instance.root_path = root_path.clone();
+                    instance.dot_minecraft_folder = dot_minecraft_folder.clone();
+                    instance.configuration = configuration.clone();
+                }
+            },
+            MessageToFrontend::InstanceRemoved { id } => {
+                self.instances.retain(|instance| instance.id != *id);
+            },
+            _ => {},
+        }
+    }
+
+    fn wait_for<T>(&mut self, timeout: Duration, mut matches: impl FnMut(&MessageToFrontend) -> Option<T>) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let message = self.receiver.recv_timeout(remaining).ok()?;
+            self.handle_message(&message);
+            if let Some(value) = (matches)(&message) {
+                return Some(value);
+            }
+        }
+    }
+
+    fn find_instance(&self, name: &str) -> Result<&CliInstance, String> {
+        self.instances.iter().find(|instance| instance.name == name)
+            .ok_or_else(|| format!("Unable to find instance {name}"))
+    }
+
+    fn run_modal_action(&self, modal_action: ModalAction) -> Result<(), String> {
+        crate::run_modal_action(modal_action, false).map_err(|error| error.to_string())
+    }
+}
+
+fn forward_message(sender: &mpsc::Sender<MessageToFrontend>, message: Option<MessageToFrontend>) -> bool {
+    let Some(message) = message else {
+        return false;
+    };
+    if let MessageToFrontend::AddNotification { notification_type, message } = &message {
+        match notification_type {
+            BridgeNotificationType::Error => eprintln!("error: {message}"),
+            BridgeNotificationType::Warning => eprintln!("warning: {message}"),
+            BridgeNotificationType::Success | BridgeNotificationType::Info => eprintln!("{message}"),
+        }
+        return true;
+    }
+    sender.send(message).is_ok()
+}
+
+// Messages sent before the backend acknowledged a flush are already queued
+fn drain_messages(sender: &mpsc::Sender<MessageToFrontend>, frontend_recv: &mut FrontendReceiver) -> bool {
+    while let Some(message) = frontend_recv.try_recv() {
+        if !forward_message(sender, Some(message)) {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn run(launcher_dir: PathBuf, command: Command, json: bool) -> i32 {
+    let mut session = Session::start(launcher_dir);
+
+    let result = match command {
+        Command::List => list(&session, json),
+        Command::Create { name, version, loader, server } => {
+            let kind = if server { InstanceKind::Server } else { InstanceKind::Client };
+            create(&mut session, name, version, loader, kind, json)
+        },
+        Command::Launch { instance, accept_eula } => launch(&session, &instance, accept_eula, json),
+        Command::InstallContent { instance, modrinth, version, curseforge, file, no_dependencies } => {
+            let download = if let Some(project_id) = modrinth {
+                (ContentDownload::Modrinth {
+                    project_id: project_id.clone().into(),
+                    version_id: version.map(Into::into),
+                    install_dependencies: !no_dependencies,
+                }, ContentSource::ModrinthProject { project_id: project_id.into() })
+            } else if let Some(project_id) = curseforge {
+                (ContentDownload::Curseforge {
+                    project_id,
+                    install_dependencies: !no_dependencies,
+                }, ContentSource::CurseforgeProject { project_id })
+            } else if let Some(path) = file {
+                (ContentDownload::File { path }, ContentSource::Manual)
+            } else {
+                unreachable!("clap requires one of the content sources")
+            };
+            install_content(&session, &instance, download, json)
+        },
+        Command::Update { instance, check_only } => update(&mut session, &instance, check_only, json),
+        Command::Sync { command: SyncCommand::Status { profile } } => sync_status(&session, profile, json),
+        Command::Rcon { instance, command } => rcon(&session, &instance, &command.join(" "), json),
+        Command::Import { launcher, path, accounts } => import(&session, launcher, path, accounts, json),
+    };
+
+    // Let the backend finish writing any files and print its remaining notifications
+    session.flush();
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            log::error!("{error}");
+            if json {
+                println!("{}", json!({ "success": false, "error": error }));
+            } else {
+                eprintln!("error: {error}");
+            }
+            1
+        },
+    }
+}
+
+fn print_success(json: bool, message: String, mut value: serde_json::Value) {
+    if json {
+        if let Some(object) = value.as_object_mut() {
+            object.insert("success".into(), true.into());
+        }
+        println!("{value}");
+    } else {
+        println!("{message}");
+    }
+}
+
+fn instance_json(instance: &CliInstance) -> serde_json::Value {
+    json!({
+        "name": instance.name,
+        "minecraft_version": instance.configuration.minecraft_version.as_str(),
+        "loader": instance.configuration.loader.name(),
+        "server": instance.configuration.kind == InstanceKind::Server,
+        "loader_version": instance.configuration.preferred_loader_version.map(|version| version.as_str()),
+        "path": instance.root_path.to_string_lossy(),
+        "minecraft_path": instance.dot_minecraft_folder.to_string_lossy(),
+    })
+}
+
+fn list(session: &Session, json: bool) -> Result<(), String> {
+    let message = if session.instances.is_empty() {
+        "No instances".to_string()
+    } else {
+        session.instances.iter().map(|instance| {
+            format!("{} ({} {})", instance.name, instance.configuration.loader.name(), instance.configuration.minecraft_version)
+        }).collect::<Vec<_>>().join("\n")
+    };
+    let instances: Vec<_> = session.instances.iter().map(instance_json).collect();
+    print_success(json, message, json!({ "instances": instances }));
+    Ok(())
+}
+
+fn create(session: &mut Session, name: String, version: String, loader: Loader, kind: InstanceKind, json: bool) -> Result<(), String> {
+    if session.find_instance(&name).is_ok() {
+        return Err(format!("An instance named {name} already exists"));
+    }
+
+    let (send, recv) = tokio::sync::oneshot::channel();
+    session.backend_handle.send(MessageToBackend::CreateInstance {
+        name: name.as_str().into(),
+        version: version.as_str().into(),
+        loader,
+        kind,
+        icon: None,
+        channel: Some(send),
+    });
+    let instance_dir = recv.blocking_recv().map_err(|_| format!("Instance {name} was not created"))?.map_err(|error| error.to_string())?;
+
+    // The instance is picked up by the filesystem watcher
+    let added = session.wait_for(Duration::from_secs(10), |message| {
+        matches!(message, MessageToFrontend::InstanceAdded { root_path, .. } if root_path.file_name() == instance_dir.file_name()).then_some(())
+    });
+    if added.is_none() {
+        return Err(format!("Instance {name} was created at {} but wasn't loaded", instance_dir.display()));
+    }
+
+    let instance = session.find_instance(&name)?;
+    print_success(json, format!("Created instance {name}"), json!({ "instance": instance_json(instance) }));
+    Ok(())
+}
+
+fn launch(session: &Session, name: &str, accept_eula: bool, json: bool) -> Result<(), String> {
+    let instance = session.find_instance(name)?;
+
+    if instance.configuration.kind == InstanceKind::Server {
+        if accept_eula {
+            session.backend_handle.send(MessageToBackend::AcceptServerEula { id: instance.id });
+        } else {
+            let eula = std::fs::read_to_string(instance.dot_minecraft_folder.join("eula.txt")).unwrap_or_default();
+            if !eula.lines().any(|line| line.trim().eq_ignore_ascii_case("eula=true")) {
+                return Err(format!("{name} is a server, pass --accept-eula to agree to the Minecraft EULA"));
+            }
+        }
+    }
+
+    let modal_action = ModalAction::default();
+    session.backend_handle.send(MessageToBackend::StartInstance {
+        id: instance.id,
+        quick_play: None,
+        screen_size: None,
+        modal_action: modal_action.clone(),
+    });
+    session.run_modal_action(modal_action)?;
+
+    print_success(json, format!("Launched instance {name}"), json!({ "instance": name }));
+    Ok(())
+}
+
+fn install_content(session: &Session, name: &str, (download, content_source): (ContentDownload, ContentSource), json: bool) -> Result<(), String> {
+    let instance = session.find_instance(name)?;
+
+    let modal_action = ModalAction::default();
+    session.backend_handle.send(MessageToBackend::InstallContent {
+        content: ContentInstall {
+            target: InstallTarget::Instance(instance.id),
+            loader_hint: instance.configuration.loader,
+            version_hint: Some(instance.configuration.minecraft_version.as_str().into()),
+            files: [ContentInstallFile {
+                replace_old: None,
+                path: ContentInstallPath::Automatic,
+                download,
+                content_source,
+            }].into(),
+        },
+        modal_action: modal_action.clone(),
+    });
+    session.run_modal_action(modal_action)?;
+
+    print_success(json, format!("Installed content into {name}"), json!({ "instance": name }));
+    Ok(())
+}
+
+fn update(session: &mut Session, name: &str, check_only: bool, json: bool) -> Result<(), String> {
+    let instance = session.find_instance(name)?;
+    let id = instance.id;
+    let loader = instance.configuration.loader;
+    let minecraft_version = instance.configuration.minecraft_version.as_str();
+
+    let modal_action = ModalAction::default();
+    session.backend_handle.send(MessageToBackend::UpdateCheck {
+        instance: id,
+        modal_action: modal_action.clone(),
+    });
+    session.run_modal_action(modal_action)?;
+
+    // Reload content so the summaries contain the update status
+    session.backend_handle.send(MessageToBackend::RequestLoadMods { id });
+    session.backend_handle.send(MessageToBackend::RequestLoadResourcePacks { id });
+
+    let mut mods: Option<Arc<[InstanceContentSummary]>> = None;
+    let mut resource_packs: Option<Arc<[InstanceContentSummary]>> = None;
+    session.wait_for(Duration::from_secs(30), |message| {
+        match message {
+            MessageToFrontend::InstanceModsUpdated { id: updated_id, mods: updated } if *updated_id == id => {
+                mods = Some(updated.clone());
+            },
+            MessageToFrontend::InstanceResourcePacksUpdated { id: updated_id, resource_packs: updated } if *updated_id == id => {
+                resource_packs = Some(updated.clone());
+            },
+            _ => {},
+        }
+        (mods.is_some() && resource_packs.is_some()).then_some(())
+    }).ok_or_else(|| "Timed out while loading instance content".to_string())?;
+
+    let updatable: Vec<&InstanceContentSummary> = mods.iter().chain(resource_packs.iter())
+        .flat_map(|summaries| summaries.iter())
+        .filter(|summary| summary.update.can_update(loader, minecraft_version))
+        .collect();
+
+    let updates_json: Vec<_> = updatable.iter().map(|summary| json!({
+        "filename": summary.filename,
+        "name": summary.content_summary.name,
+        "version": summary.content_summary.version_str,
+    })).collect();
+
+    if !json {
+        if updatable.is_empty() {
+            println!("All content is up-to-date");
+        } else {
+            for summary in &updatable {
+                println!("Update available: {}", summary.filename);
+            }
+        }
+    }
+
+    if check_only || updatable.is_empty() {
+        if json {
+            println!("{}", json!({ "success": true, "updates": updates_json, "updated": false }));
+        }
+        return Ok(());
+    }
+
+    let modal_action = ModalAction::default();
+    session.backend_handle.send(MessageToBackend::UpdateContent {
+        instance: id,
+        content_ids: updatable.iter().map(|summary| summary.id).collect(),
+        modal_action: modal_action.clone(),
+    });
+    session.run_modal_action(modal_action)?;
+
+    print_success(json, format!("Updated {} file(s)", updatable.len()), json!({ "updates": updates_json, "updated": true }));
+    Ok(())
+}
+
+fn sync_status(session: &Session, profile: Option<String>, json: bool) -> Result<(), String> {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    session.backend_handle.send(MessageToBackend::GetSyncState {
+        profile: profile.map(Arc::from),
+        channel: send,
+    });
+    let state = recv.blocking_recv().map_err(|_| "Unable to get sync state".to_string())?;
+
+    let mut message = format!("Profile: {}\nSync folder: {}", state.profile.as_deref().unwrap_or("default"), state.sync_folder.display());
+    for (name, target) in &state.targets {
+        let status = if target.enabled { "enabled" } else { "disabled" };
+        message.push_str(&format!("\n{name}: {status}, synced in {}/{} instances", target.sync_count, state.total_count));
+        if target.cannot_sync_count > 0 {
+            message.push_str(&format!(" ({} unable to sync)", target.cannot_sync_count));
+        }
+    }
+
+    let targets: serde_json::Map<String, serde_json::Value> = state.targets.iter().map(|(name, target)| {
+        (name.to_string(), json!({
+            "enabled": target.enabled,
+            "is_file": target.is_file,
+            "sync_count": target.sync_count,
+            "cannot_sync_count": target.cannot_sync_count,
+            "strategy": target.is_file.then_some(target.strategy),
+        }))
+    }).collect();
+    print_success(json, message, json!({
+        "profile": state.profile.as_deref(),
+        "profiles": state.profiles.iter().map(|profile| profile.to_string()).collect::<Vec<_>>(),
+        "sync_folder": state.sync_folder.to_string_lossy(),
+        "total_count": state.total_count,
+        "targets": targets,
+    }));
+    Ok(())
+}
+
+fn rcon(session: &Session, name: &str, command: &str, json: bool) -> Result<(), String> {
+    let instance = session.find_instance(name)?;
+
+    let (send, recv) = tokio::sync::oneshot::channel();
+    session.backend_handle.send(MessageToBackend::RunRconCommand {
+        id: instance.id,
+        command: command.into(),
+        channel: send,
+    });
+    let response = recv.blocking_recv().map_err(|_| "Unable to run command".to_string())?.map_err(|err| err.to_string())?;
+
+    if json {
+        println!("{}", json!({ "success": true, "instance": name, "response": &*response }));
+    } else if !response.is_empty() {
+        println!("{response}");
+    }
+    Ok(())
+}
+
+fn import(session: &Session, launcher: OtherLauncher, path: Option<PathBuf>, accounts: bool, json: bool) -> Result<(), String> {
+    let path: Arc<Path> = if let Some(path) = path {
+        path.into()
+    } else {
+        let base_dirs = directories::BaseDirs::new().ok_or_else(|| "Unable to determine home directory".to_string())?;
+        launcher.default_path(&base_dirs)
+    };
+
+    let (send, recv) = tokio::sync::oneshot::channel();
+    session.backend_handle.send(MessageToBackend::GetImportFromOtherLauncherJob {
+        channel: send,
+        launcher,
+        path: path.clone(),
+    });
+    let Ok(Some(import_job)) = recv.blocking_recv() else {
+        return Err(format!("Unable to find anything to import from {launcher} in {}", path.display()));
+    };
+
+    // Instances that would conflict with existing ones are skipped by the importer
+    let (send, recv) = tokio::sync::oneshot::channel();
+    let modal_action = ModalAction::default();
+    session.backend_handle.send(MessageToBackend::ImportFromOtherLauncher {
+        launcher,
+        import_job: ImportFromOtherLauncherJob {
+            import_accounts: import_job.import_accounts && accounts,
+            root: import_job.root,
+            paths: import_job.paths,
+        },
+        modal_action: modal_action.clone(),
+        channel: Some(send),
+    });
+    session.run_modal_action(modal_action)?;
+    let result = recv.blocking_recv().map_err(|_| "Unable to import instances".to_string())?;
+
+    let mut message = format!("Imported {} instance(s) from {launcher}", result.imported.len());
+    if !result.skipped.is_empty() {
+        message.push_str(&format!(", skipped {} that already exist: {}", result.skipped.len(), result.skipped.join(", ")));
+    }
+    print_success(json, message, json!({
+        "launcher": launcher.to_string(),
+        "imported": result.imported.len(),
+        "skipped": result.skipped.len(),
+        "imported_instances": result.imported.iter().map(|name| &**name).collect::<Vec<_>>(),
+        "skipped_instances": result.skipped.iter().map(|name| &**name).collect::<Vec<_>>(),
+    }));
+    Ok(())
+}
@@ -21,12 +21,32 @@ struct Args {
     /// Instance to launch, instead of opening the launcher
     #[arg(long)]
     run_instance: Option<String>,
+
+    /// Print command output as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 }
 
+pub mod cli;
 pub mod panic;
 
 fn main() {
-    let args = Args::parse();
+    // Release builds use the windows subsystem, so there's no console to print to unless the one we were run from is attached
+    #[cfg(all(target_os = "windows", not(debug_assertions)))]
+    if std::env::args_os().len() > 1 {
+        unsafe {
+            _ = windows::Win32::System::Console::AttachConsole(windows::Win32::System::Console::ATTACH_PARENT_PROCESS);
+        }
+    }
+
+    let mut args = Args::parse();
+
+    if let Some(command) = &mut args.command {
+        command.make_paths_absolute();
+    }
 
     let data_dir = if let Some(portable_dir) = get_portable_dir() {
         portable_dir
@@ -44,7 +64,8 @@ fn main() {
         _ = std::fs::rename(log_path, old_log_path);
     }
 
-    if let Err(error) = setup_logging(log::LevelFilter::Debug) {
+    // Logging to stdout would interfere with command output, commands only log to launcher.log
+    if let Err(error) = setup_logging(log::LevelFilter::Debug, args.command.is_none()) {
         eprintln!("Unable to enable logging: {error:?}");
     }
 
@@ -53,7 +74,10 @@ fn main() {
 
     panic::install_logging_hook();
 
-    if let Some(run_instance) = args.run_instance {
+    if let Some(command) = args.command {
+        let exit_code = cli::run(launcher_dir, command, args.json);
+        std::process::exit(exit_code);
+    } else if let Some(run_instance) = args.run_instance {
         let (backend_recv, backend_handle, mut frontend_recv, frontend_handle) = bridge::handle::create_pair();
 
         backend::start(launcher_dir.clone(), frontend_handle, backend_handle.clone(), backend_recv);
@@ -66,9 +90,12 @@ fn main() {
                     backend_handle.send(bridge::message::MessageToBackend::StartInstance {
                         id,
                         quick_play: None,
+                        screen_size: None,
                         modal_action: modal_action.clone()
                     });
-                    run_modal_action(modal_action);
+                    if let Err(error) = run_modal_action(modal_action, true) {
+                        show_error(error.to_string());
+                    }
                     std::thread::sleep(std::time::Duration::from_millis(100));
                     return;
                 }
@@ -92,7 +119,7 @@ fn show_error(error: String) {
         .show();
 }
 
-fn run_modal_action(modal_action: ModalAction) {
+fn run_modal_action(modal_action: ModalAction, interactive: bool) -> Result<(), Arc<str>> {
     let m = MultiProgress::new();
     let sty = ProgressStyle::with_template(
         "[{elapsed_precise}] {bar:40.cyan/blue} {msg}",
@@ -107,8 +134,7 @@ fn run_modal_action(modal_action: ModalAction) {
         std::thread::sleep(std::time::Duration::from_millis(100));
 
         if let Some(error) = &*modal_action.error.read() {
-            show_error(error.to_string());
-            return;
+            return Err(error.clone());
         }
 
         if modal_action.refcnt() <= 1 {
@@ -116,12 +142,15 @@ fn run_modal_action(modal_action: ModalAction) {
         }
 
         if modal_action.get_finished_at().is_some() {
-            return;
+            return Ok(());
         }
 
         if let Some(visit_url) = &*modal_action.visit_url.write() {
             if opened.insert(visit_url.url.clone()) {
                 _ = m.println(format!("Open this URL in your browser to continue: {}", visit_url.url));
+                if !interactive {
+                    continue;
+                }
                 let open = DialogBuilder::message()
                     .set_title("Open URL")
                     .set_text(&visit_url.message)
@@ -131,7 +160,7 @@ fn run_modal_action(modal_action: ModalAction) {
                 if open {
                     _ = open::that_detached(&*visit_url.url);
                 } else {
-                    return;
+                    return Ok(());
                 }
             }
         }
@@ -205,7 +234,7 @@ fn run_gui(launcher_dir: PathBuf) {
     frontend::start(launcher_dir.clone(), panic_message, deadlock_message, backend_handle, frontend_recv);
 }
 
-fn setup_logging(level: log::LevelFilter) -> Result<(), fern::InitError> {
+fn setup_logging(level: log::LevelFilter, log_to_stdout: bool) -> Result<(), fern::InitError> {
     let base_config = fern::Dispatch::new()
         .level_for("pandora_launcher", level)
         .level_for("auth", level)
@@ -245,10 +274,11 @@ fn setup_logging(level: log::LevelFilter) -> Result<(), fern::InitError> {
         })
         .chain(std::io::stdout());
 
-    base_config
-        .chain(file_config)
-        .chain(stdout_config)
-        .apply()?;
+    let mut base_config = base_config.chain(file_config);
+    if log_to_stdout {
+        base_config = base_config.chain(stdout_config);
+    }
+    base_config.apply()?;
 
     Ok(())
 }
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdoptiumAsset {
+    pub binary: AdoptiumBinary,
+    pub release_name: Arc<str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdoptiumBinary {
+    pub architecture: Arc<str>,
+    pub os: Arc<str>,
+    pub image_type: Arc<str>,
+    pub package: AdoptiumPackage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdoptiumPackage {
+    pub name: Arc<str>,
+    pub link: Arc<str>,
+    // Hex encoded sha256
+    pub checksum: Arc<str>,
+    pub size: usize,
+}
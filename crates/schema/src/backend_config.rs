@@ -1,16 +1,118 @@
-use std::{collections::BTreeSet, sync::Arc};
+use std::{collections::{BTreeMap, BTreeSet}, sync::Arc};
 
 use enumset::{EnumSet, EnumSetType};
 use serde::{Deserialize, Serialize};
 
+use crate::instance::{InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration, InstanceSystemLibrariesConfiguration, InstanceWrapperCommandConfiguration, WindowConfiguration};
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct BackendConfig {
     #[serde(default, skip_serializing_if = "is_default_sync_targets", deserialize_with = "try_deserialize_sync_targets")]
     pub sync_targets: SyncTargets,
+    // Named profiles with their own synced folder, the unnamed default profile uses `sync_targets`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", deserialize_with = "crate::try_deserialize")]
+    pub sync_profiles: BTreeMap<Arc<str>, SyncTargets>,
     #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
     pub dont_open_game_output_when_launching: bool,
     #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub offline_mode: bool,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub dont_poll_server_history: bool,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
     pub proxy: ProxyConfig,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub java_runtimes: JavaRuntimeRegistry,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub instance_defaults: InstanceDefaults,
+}
+
+impl BackendConfig {
+    pub fn sync_targets_for(&self, profile: Option<&str>) -> Option<&SyncTargets> {
+        match profile {
+            Some(profile) => self.sync_profiles.get(profile),
+            None => Some(&self.sync_targets),
+        }
+    }
+
+    pub fn sync_targets_for_mut(&mut self, profile: Option<&str>) -> Option<&mut SyncTargets> {
+        match profile {
+            Some(profile) => self.sync_profiles.get_mut(profile),
+            None => Some(&mut self.sync_targets),
+        }
+    }
+}
+
+// Used by every instance whose settings are set to inherit, see `InstanceConfiguration::resolve_defaults`.
+// Settings that have a mode are only used when it is override
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct InstanceDefaults {
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub memory: Option<InstanceMemoryConfiguration>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub wrapper_command: Option<InstanceWrapperCommandConfiguration>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub jvm_flags: Option<InstanceJvmFlagsConfiguration>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub jvm_binary: Option<InstanceJvmBinaryConfiguration>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub linux_wrapper: Option<InstanceLinuxWrapperConfiguration>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub system_libraries: Option<InstanceSystemLibrariesConfiguration>,
+    // One KEY=VALUE pair per line, see `InstanceEnvironmentVariablesConfiguration`
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub environment_variables: Arc<str>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub game_arguments: Arc<str>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub window: WindowConfiguration,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct JavaRuntimeRegistry {
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub discovered: Vec<JavaRuntimeInfo>,
+    // Path of the java binary to use for each major version
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub defaults: BTreeMap<u32, Arc<str>>,
+    // Use any discovered runtime of the right major version when no default is set, instead of downloading one
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub prefer_discovered: bool,
+    // Used when Mojang has no runtime for the platform, can point to a local mirror of the Adoptium API
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub adoptium_api_url: Option<Arc<str>>,
+}
+
+pub const DEFAULT_ADOPTIUM_API_URL: &str = "https://api.adoptium.net";
+
+impl JavaRuntimeRegistry {
+    pub fn find_for_major_version(&self, major_version: u32) -> Option<&Arc<str>> {
+        if let Some(path) = self.defaults.get(&major_version) {
+            return Some(path);
+        }
+        if self.prefer_discovered {
+            return self.discovered.iter().find(|runtime| runtime.major_version == major_version).map(|runtime| &runtime.path);
+        }
+        None
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct JavaRuntimeInfo {
+    pub path: Arc<str>,
+    pub major_version: u32,
+    pub version: Arc<str>,
+    pub vendor: Arc<str>,
+    pub arch: Arc<str>,
+    pub source: JavaRuntimeSource,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JavaRuntimeSource {
+    JavaHome,
+    System,
+    Sdkman,
+    Managed,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -88,10 +190,43 @@ impl ProxyProtocol {
 pub struct SyncTargets {
     pub files: BTreeSet<Arc<str>>,
     pub folders: BTreeSet<Arc<str>>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub strategies: BTreeMap<Arc<str>, SyncMergeStrategy>,
+}
+
+impl SyncTargets {
+    pub fn strategy_for(&self, file: &str) -> SyncMergeStrategy {
+        if let Some(strategy) = self.strategies.get(file) {
+            return *strategy;
+        }
+        SyncMergeStrategy::default_for(file)
+    }
 }
 
 fn is_default_sync_targets(sync_targets: &SyncTargets) -> bool {
-    sync_targets.files.is_empty() && sync_targets.folders.is_empty()
+    sync_targets.files.is_empty() && sync_targets.folders.is_empty() && sync_targets.strategies.is_empty()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMergeStrategy {
+    // Copy the most recently modified file
+    Latest,
+    // Merge `key:value` or `key=value` lines, newer files override older ones
+    KeyValue,
+    // Union of the server lists in servers.dat, de-duplicated by ip. Servers removed in one instance are added back from the others
+    ServersUnion,
+    // Recursively merge json objects, newer files override older ones
+    JsonDeepMerge,
+}
+
+impl SyncMergeStrategy {
+    pub fn default_for(file: &str) -> Self {
+        match file {
+            "options.txt" => Self::KeyValue,
+            _ => Self::Latest,
+        }
+    }
 }
 
 fn try_deserialize_sync_targets<'de, D>(deserializer: D) -> Result<SyncTargets, D::Error>
@@ -42,6 +42,12 @@ pub struct CurseforgeGetFilesRequest {
     pub file_ids: Vec<u32>,
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeFingerprintsRequest {
+    pub fingerprints: Vec<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CurseforgeSearchResult {
     pub data: Arc<[CurseforgeHit]>,
@@ -53,6 +59,24 @@ pub struct CurseforgeGetModFilesResult {
     pub data: Arc<[CurseforgeFile]>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CurseforgeFingerprintsResult {
+    pub data: CurseforgeFingerprintMatches,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeFingerprintMatches {
+    pub exact_matches: Arc<[CurseforgeFingerprintMatch]>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeFingerprintMatch {
+    pub id: u32,
+    pub file: CurseforgeFile,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CurseforgePagination {
@@ -257,11 +281,12 @@ pub struct CurseforgeModpackManifestJson {
     pub overrides: Option<Arc<str>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CurseforgeModpackMinecraft {
     pub version: Option<Arc<str>>,
     pub mod_loaders: Arc<[CurseforgeModpackModLoader]>,
+    #[serde(skip_serializing_if = "crate::skip_if_none")]
     pub recommended_ram: Option<u32>,
 }
 
@@ -277,6 +302,8 @@ impl CurseforgeModpackMinecraft {
                     Some(Loader::NeoForge)
                 } else if loader.id.starts_with("fabric-") {
                     Some(Loader::Fabric)
+                } else if loader.id.starts_with("quilt-") {
+                    Some(Loader::Quilt)
                 } else {
                     None
                 }
@@ -284,13 +311,13 @@ impl CurseforgeModpackMinecraft {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct CurseforgeModpackModLoader {
     pub id: Arc<str>,
     pub primary: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct CurseforgeModpackFile {
     #[serde(rename = "projectID")]
     pub project_id: u32,
@@ -299,6 +326,19 @@ pub struct CurseforgeModpackFile {
     pub required: bool,
 }
 
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeModpackManifestJsonExport {
+    pub minecraft: CurseforgeModpackMinecraft,
+    pub manifest_type: &'static str,
+    pub manifest_version: u32,
+    pub name: Arc<str>,
+    pub version: Arc<str>,
+    pub author: Arc<str>,
+    pub files: Vec<CurseforgeModpackFile>,
+    pub overrides: &'static str,
+}
+
 #[derive(Clone, Debug)]
 pub struct CachedCurseforgeFileInfo {
     pub hash: [u8; 20],
@@ -11,17 +11,39 @@ pub struct FabricModJson {
     // pub description: Option<Arc<str>>,
     pub authors: Option<Vec<Person>>,
     pub icon: Option<Icon>,
-    // #[serde(alias = "requires")]
-    // pub depends: Option<HashMap<Arc<str>, Dependency>>,
-    // pub breaks: Option<HashMap<Arc<str>, Dependency>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub provides: Vec<Arc<str>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub jars: Vec<FabricNestedJar>,
+    #[serde(default, alias = "requires", deserialize_with = "crate::try_deserialize")]
+    pub depends: HashMap<Arc<str>, FabricVersionPredicates>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub breaks: HashMap<Arc<str>, FabricVersionPredicates>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub conflicts: HashMap<Arc<str>, FabricVersionPredicates>,
 }
 
-// #[derive(Deserialize, Debug)]
-// #[serde(untagged)]
-// enum Dependency {
-//     Single(Arc<str>),
-//     Multiple(Vec<Arc<str>>)
-// }
+#[derive(Deserialize, Debug)]
+pub struct FabricNestedJar {
+    pub file: Arc<str>,
+}
+
+// Any of the predicates must match
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum FabricVersionPredicates {
+    Single(Arc<str>),
+    Multiple(Vec<Arc<str>>)
+}
+
+impl FabricVersionPredicates {
+    pub fn into_vec(self) -> Vec<Arc<str>> {
+        match self {
+            FabricVersionPredicates::Single(predicate) => vec![predicate],
+            FabricVersionPredicates::Multiple(predicates) => predicates,
+        }
+    }
+}
 
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use serde::Deserialize;
 
@@ -6,7 +6,21 @@ use crate::fabric_mod::Person;
 
 #[derive(Deserialize, Debug)]
 pub struct ModsToml {
-    pub mods: Vec<ModsTomlMod>
+    pub mods: Vec<ModsTomlMod>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub dependencies: HashMap<Arc<str>, Vec<ModsTomlDependency>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModsTomlDependency {
+    pub mod_id: Arc<str>,
+    // Forge uses `mandatory`, NeoForge uses `type`
+    pub mandatory: Option<bool>,
+    #[serde(rename = "type")]
+    pub dependency_type: Option<Arc<str>>,
+    pub version_range: Option<Arc<str>>,
+    pub side: Option<Arc<str>>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -5,12 +5,14 @@ use serde::{Deserialize, Serialize};
 use ustr::Ustr;
 use uuid::Uuid;
 
-use crate::loader::Loader;
+use crate::{backend_config::InstanceDefaults, loader::Loader};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct InstanceConfiguration {
     pub minecraft_version: Ustr,
     pub loader: Loader,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "crate::skip_if_default")]
+    pub kind: InstanceKind,
     #[serde(default, skip_serializing_if = "crate::skip_if_none")]
     pub preferred_loader_version: Option<Ustr>,
     #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "crate::skip_if_none")]
@@ -19,8 +21,16 @@ pub struct InstanceConfiguration {
     pub memory: Option<InstanceMemoryConfiguration>,
     #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_wrapper_command_configuration")]
     pub wrapper_command: Option<InstanceWrapperCommandConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_hook_commands_configuration")]
+    pub hook_commands: Option<InstanceHookCommandsConfiguration>,
     #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_jvm_flags_configuration")]
     pub jvm_flags: Option<InstanceJvmFlagsConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_environment_variables_configuration")]
+    pub environment_variables: Option<InstanceEnvironmentVariablesConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_game_arguments_configuration")]
+    pub game_arguments: Option<InstanceGameArgumentsConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_window_configuration")]
+    pub window: Option<InstanceWindowConfiguration>,
     #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_jvm_binary_configuration")]
     pub jvm_binary: Option<InstanceJvmBinaryConfiguration>,
     #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_linux_wrapper_configuration")]
@@ -31,6 +41,17 @@ pub struct InstanceConfiguration {
     pub instance_fallback_icon: Option<Ustr>,
     #[serde(default, deserialize_with = "crate::try_deserialize")]
     pub disable_file_syncing: bool,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "crate::skip_if_none")]
+    pub sync_profile: Option<Arc<str>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_world_backup_configuration")]
+    pub world_backups: Option<InstanceWorldBackupConfiguration>,
+    // Instances with the same group are shown together on the instances page
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "crate::skip_if_none")]
+    pub group: Option<Arc<str>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Arc<str>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "crate::skip_if_default")]
+    pub favourite: bool,
 }
 
 impl InstanceConfiguration {
@@ -38,23 +59,186 @@ impl InstanceConfiguration {
         Self {
             minecraft_version,
             loader,
+            kind: InstanceKind::Client,
             preferred_loader_version: None,
             preferred_account: None,
             memory: None,
             wrapper_command: None,
+            hook_commands: None,
             jvm_flags: None,
+            environment_variables: None,
+            game_arguments: None,
+            window: None,
             jvm_binary: None,
             linux_wrapper: None,
             system_libraries: None,
             instance_fallback_icon: None,
             disable_file_syncing: false,
+            sync_profile: None,
+            world_backups: None,
+            group: None,
+            tags: Vec::new(),
+            favourite: false,
+        }
+    }
+
+    // The settings an instance is launched with, with inherited settings taken from the defaults.
+    // Every setting that is `Some` in the result is in override mode
+    pub fn resolve_defaults(&self, defaults: &InstanceDefaults) -> Self {
+        let environment_variables = match InstanceSettingMode::of(&self.environment_variables) {
+            InstanceSettingMode::Inherit => Some(defaults.environment_variables.clone()),
+            InstanceSettingMode::Override => self.environment_variables.as_ref().map(|config| join_lines(&defaults.environment_variables, &config.variables)),
+            InstanceSettingMode::Disable => None,
+        };
+        let game_arguments = match InstanceSettingMode::of(&self.game_arguments) {
+            InstanceSettingMode::Inherit => Some(defaults.game_arguments.clone()),
+            InstanceSettingMode::Override => self.game_arguments.as_ref().map(|config| join_lines(&defaults.game_arguments, &config.arguments)),
+            InstanceSettingMode::Disable => None,
+        };
+        let window = match InstanceSettingMode::of(&self.window) {
+            InstanceSettingMode::Inherit => defaults.window,
+            InstanceSettingMode::Override => self.window.map(|config| config.window).unwrap_or_default(),
+            InstanceSettingMode::Disable => WindowConfiguration::default(),
+        };
+
+        Self {
+            memory: resolve_setting(&self.memory, &defaults.memory),
+            wrapper_command: resolve_setting(&self.wrapper_command, &defaults.wrapper_command),
+            jvm_flags: resolve_setting(&self.jvm_flags, &defaults.jvm_flags),
+            environment_variables: environment_variables.map(|variables| InstanceEnvironmentVariablesConfiguration {
+                mode: InstanceSettingMode::Override,
+                variables,
+            }),
+            game_arguments: game_arguments.map(|arguments| InstanceGameArgumentsConfiguration {
+                mode: InstanceSettingMode::Override,
+                arguments,
+            }),
+            window: Some(InstanceWindowConfiguration {
+                mode: InstanceSettingMode::Override,
+                window,
+            }),
+            jvm_binary: resolve_setting(&self.jvm_binary, &defaults.jvm_binary),
+            linux_wrapper: resolve_setting(&self.linux_wrapper, &defaults.linux_wrapper),
+            system_libraries: resolve_setting(&self.system_libraries, &defaults.system_libraries),
+            ..self.clone()
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceKind {
+    #[default]
+    Client,
+    Server,
+}
+
+// Whether an instance setting uses the launcher's instance defaults, its own value or neither
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceSettingMode {
+    #[default]
+    Inherit,
+    Override,
+    Disable,
+}
+
+impl InstanceSettingMode {
+    // How an imported or legacy `enabled` flag maps onto the modes
+    pub fn from_enabled(enabled: bool) -> Self {
+        if enabled {
+            Self::Override
+        } else {
+            Self::Inherit
+        }
+    }
+
+    pub fn of<T: InheritableSetting>(setting: &Option<T>) -> Self {
+        setting.as_ref().map(T::mode).unwrap_or_default()
+    }
+}
+
+impl<'de> Deserialize<'de> for InstanceSettingMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum Mode {
+            Inherit,
+            Override,
+            Disable,
+        }
+
+        // Settings used to be a plain `enabled` flag, which didn't know about the defaults
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ModeOrEnabled {
+            Mode(Mode),
+            Enabled(bool),
+        }
+
+        Ok(match ModeOrEnabled::deserialize(deserializer)? {
+            ModeOrEnabled::Mode(Mode::Inherit) => Self::Inherit,
+            ModeOrEnabled::Mode(Mode::Override) => Self::Override,
+            ModeOrEnabled::Mode(Mode::Disable) => Self::Disable,
+            ModeOrEnabled::Enabled(enabled) => Self::from_enabled(enabled),
+        })
+    }
+}
+
+fn default_override_mode() -> InstanceSettingMode {
+    InstanceSettingMode::Override
+}
+
+pub trait InheritableSetting: Clone {
+    fn mode(&self) -> InstanceSettingMode;
+}
+
+macro_rules! impl_inheritable_setting {
+    ($($ty:ty),*) => {
+        $(impl InheritableSetting for $ty {
+            fn mode(&self) -> InstanceSettingMode {
+                self.mode
+            }
+        })*
+    };
+}
+
+impl_inheritable_setting!(
+    InstanceMemoryConfiguration,
+    InstanceWrapperCommandConfiguration,
+    InstanceJvmFlagsConfiguration,
+    InstanceEnvironmentVariablesConfiguration,
+    InstanceGameArgumentsConfiguration,
+    InstanceWindowConfiguration,
+    InstanceJvmBinaryConfiguration,
+    InstanceLinuxWrapperConfiguration,
+    InstanceSystemLibrariesConfiguration
+);
+
+// In the instance defaults a setting is only used when its mode is override
+fn resolve_setting<T: InheritableSetting>(setting: &Option<T>, default: &Option<T>) -> Option<T> {
+    match InstanceSettingMode::of(setting) {
+        InstanceSettingMode::Inherit => default.clone().filter(|default| default.mode() == InstanceSettingMode::Override),
+        InstanceSettingMode::Override => setting.clone(),
+        InstanceSettingMode::Disable => None,
+    }
+}
+
+fn join_lines(default: &str, setting: &str) -> Arc<str> {
+    match (default.trim_ascii().is_empty(), setting.trim_ascii().is_empty()) {
+        (true, _) => setting.into(),
+        (false, true) => default.into(),
+        (false, false) => format!("{default}\n{setting}").into(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
 pub struct InstanceMemoryConfiguration {
-    pub enabled: bool,
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
     pub min: u32,
     pub max: u32,
 }
@@ -67,7 +251,7 @@ impl InstanceMemoryConfiguration {
 impl Default for InstanceMemoryConfiguration {
     fn default() -> Self {
         Self {
-            enabled: false,
+            mode: InstanceSettingMode::Inherit,
             min: Self::DEFAULT_MIN,
             max: Self::DEFAULT_MAX
         }
@@ -76,7 +260,7 @@ impl Default for InstanceMemoryConfiguration {
 
 fn is_default_memory_configuration(config: &Option<InstanceMemoryConfiguration>) -> bool {
     if let Some(config) = config {
-        !config.enabled
+        config.mode == InstanceSettingMode::Inherit
             && config.min == InstanceMemoryConfiguration::DEFAULT_MIN
             && config.max == InstanceMemoryConfiguration::DEFAULT_MAX
     } else {
@@ -84,50 +268,210 @@ fn is_default_memory_configuration(config: &Option<InstanceMemoryConfiguration>)
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct InstanceWrapperCommandConfiguration {
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldBackupSchedule {
+    #[default]
+    EveryLaunch,
+    Daily,
+    Weekly,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct InstanceWorldBackupConfiguration {
     pub enabled: bool,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub schedule: WorldBackupSchedule,
+    pub keep_last: u32,
+}
+
+impl InstanceWorldBackupConfiguration {
+    pub const DEFAULT_KEEP_LAST: u32 = 5;
+}
+
+impl Default for InstanceWorldBackupConfiguration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: WorldBackupSchedule::EveryLaunch,
+            keep_last: Self::DEFAULT_KEEP_LAST,
+        }
+    }
+}
+
+fn is_default_world_backup_configuration(config: &Option<InstanceWorldBackupConfiguration>) -> bool {
+    if let Some(config) = config {
+        !config.enabled
+            && config.schedule == WorldBackupSchedule::EveryLaunch
+            && config.keep_last == InstanceWorldBackupConfiguration::DEFAULT_KEEP_LAST
+    } else {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceWrapperCommandConfiguration {
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
     pub flags: Arc<str>,
 }
 
 fn is_default_wrapper_command_configuration(config: &Option<InstanceWrapperCommandConfiguration>) -> bool {
     if let Some(config) = config {
-        !config.enabled && config.flags.trim_ascii().is_empty()
+        config.mode == InstanceSettingMode::Inherit && config.flags.trim_ascii().is_empty()
     } else {
         true
     }
 }
 
+// Commands run before the game is launched and after it exits. Supports the placeholders
+// $INST_DIR, $INST_NAME, $INST_MC_DIR and $INST_JAVA
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct InstanceJvmFlagsConfiguration {
+pub struct InstanceHookCommandsConfiguration {
     pub enabled: bool,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub pre_launch: Arc<str>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub post_exit: Arc<str>,
+}
+
+fn is_default_hook_commands_configuration(config: &Option<InstanceHookCommandsConfiguration>) -> bool {
+    if let Some(config) = config {
+        !config.enabled && config.pre_launch.trim_ascii().is_empty() && config.post_exit.trim_ascii().is_empty()
+    } else {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceJvmFlagsConfiguration {
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
     pub flags: Arc<str>,
 }
 
 fn is_default_jvm_flags_configuration(config: &Option<InstanceJvmFlagsConfiguration>) -> bool {
     if let Some(config) = config {
-        !config.enabled && config.flags.trim_ascii().is_empty()
+        config.mode == InstanceSettingMode::Inherit && config.flags.trim_ascii().is_empty()
+    } else {
+        true
+    }
+}
+
+// One KEY=VALUE pair per line
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InstanceEnvironmentVariablesConfiguration {
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
+    pub variables: Arc<str>,
+}
+
+impl InstanceEnvironmentVariablesConfiguration {
+    // Blank lines, lines starting with # and lines without a key are ignored
+    pub fn parse_variables(variables: &str) -> impl Iterator<Item = (&str, &str)> {
+        // Splitting on lone carriage returns too means values never contain a line break
+        variables.split(['\n', '\r'])
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                let key = key.trim();
+                (!key.is_empty()).then_some((key, value.trim()))
+            })
+    }
+}
+
+fn is_default_environment_variables_configuration(config: &Option<InstanceEnvironmentVariablesConfiguration>) -> bool {
+    if let Some(config) = config {
+        config.mode == InstanceSettingMode::Inherit && config.variables.trim_ascii().is_empty()
     } else {
         true
     }
 }
 
+// Extra arguments passed to the game (or server) after the arguments from the version
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InstanceGameArgumentsConfiguration {
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
+    pub arguments: Arc<str>,
+}
+
+fn is_default_game_arguments_configuration(config: &Option<InstanceGameArgumentsConfiguration>) -> bool {
+    if let Some(config) = config {
+        config.mode == InstanceSettingMode::Inherit && config.arguments.trim_ascii().is_empty()
+    } else {
+        true
+    }
+}
+
+// Size and mode of the game window when it's first opened
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowConfiguration {
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub custom_size: bool,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub maximised: bool,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub fullscreen: bool,
+}
+
+impl WindowConfiguration {
+    // The size Minecraft uses when no size is given
+    pub const DEFAULT_WIDTH: u32 = 854;
+    pub const DEFAULT_HEIGHT: u32 = 480;
+}
+
+impl Default for WindowConfiguration {
+    fn default() -> Self {
+        Self {
+            custom_size: false,
+            width: Self::DEFAULT_WIDTH,
+            height: Self::DEFAULT_HEIGHT,
+            maximised: false,
+            fullscreen: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct InstanceWindowConfiguration {
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
+    #[serde(flatten)]
+    pub window: WindowConfiguration,
+}
+
+fn is_default_window_configuration(config: &Option<InstanceWindowConfiguration>) -> bool {
+    if let Some(config) = config {
+        config.mode == InstanceSettingMode::Inherit && config.window == WindowConfiguration::default()
+    } else {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct InstanceJvmBinaryConfiguration {
-    pub enabled: bool,
+    #[serde(alias = "enabled", default, deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
     pub path: Option<Arc<Path>>,
 }
 
 fn is_default_jvm_binary_configuration(config: &Option<InstanceJvmBinaryConfiguration>) -> bool {
     if let Some(config) = config {
-        !config.enabled && config.path.is_none()
+        config.mode == InstanceSettingMode::Inherit && config.path.is_none()
     } else {
         true
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct InstanceLinuxWrapperConfiguration {
+    // Older configurations were always used when present
+    #[serde(default = "default_override_mode", deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
     #[serde(default, deserialize_with = "crate::try_deserialize")]
     pub use_mangohud: bool,
     #[serde(default, deserialize_with = "crate::try_deserialize")]
@@ -141,6 +485,7 @@ pub struct InstanceLinuxWrapperConfiguration {
 impl Default for InstanceLinuxWrapperConfiguration {
     fn default() -> Self {
         Self {
+            mode: InstanceSettingMode::Inherit,
             use_mangohud: false,
             use_gamemode: false,
             use_discrete_gpu: true,
@@ -151,22 +496,25 @@ impl Default for InstanceLinuxWrapperConfiguration {
 
 fn is_default_linux_wrapper_configuration(config: &Option<InstanceLinuxWrapperConfiguration>) -> bool {
     if let Some(config) = config {
-        !config.use_mangohud && !config.use_gamemode && config.use_discrete_gpu && !config.disable_gl_threaded_optimizations
+        config.mode == InstanceSettingMode::Inherit && !config.use_mangohud && !config.use_gamemode && config.use_discrete_gpu && !config.disable_gl_threaded_optimizations
     } else {
         true
     }
 }
 
 
-#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
 pub struct InstanceSystemLibrariesConfiguration {
+    // Older configurations were always used when present
+    #[serde(default = "default_override_mode", deserialize_with = "crate::try_deserialize")]
+    pub mode: InstanceSettingMode,
     pub override_glfw: bool,
     pub glfw: LwjglLibraryPath,
     pub override_openal: bool,
     pub openal: LwjglLibraryPath,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
 pub enum LwjglLibraryPath {
     #[default]
     Auto,
@@ -176,7 +524,7 @@ pub enum LwjglLibraryPath {
 
 fn is_default_system_libraries_configuration(config: &Option<InstanceSystemLibrariesConfiguration>) -> bool {
     if let Some(config) = config {
-        matches!(config.glfw, LwjglLibraryPath::Auto) && matches!(config.openal, LwjglLibraryPath::Auto)
+        config.mode == InstanceSettingMode::Inherit && matches!(config.glfw, LwjglLibraryPath::Auto) && matches!(config.openal, LwjglLibraryPath::Auto)
     } else {
         true
     }
@@ -231,3 +579,166 @@ fn get_shared_library_path_for_name(name: &str) -> Option<Arc<Path>> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{backend_config::InstanceDefaults, loader::Loader};
+
+    use super::{
+        InstanceConfiguration, InstanceEnvironmentVariablesConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration,
+        InstanceSettingMode, InstanceSystemLibrariesConfiguration, InstanceWindowConfiguration, LwjglLibraryPath, WindowConfiguration,
+    };
+
+    fn configuration_with(key: &str, value: serde_json::Value) -> InstanceConfiguration {
+        let mut json = serde_json::to_value(InstanceConfiguration::new("1.21.1".into(), Loader::Fabric)).unwrap();
+        json.as_object_mut().unwrap().insert(key.into(), value);
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn memory(mode: InstanceSettingMode, max: u32) -> InstanceMemoryConfiguration {
+        InstanceMemoryConfiguration {
+            mode,
+            min: InstanceMemoryConfiguration::DEFAULT_MIN,
+            max,
+        }
+    }
+
+    #[test]
+    fn test_legacy_enabled_flag() {
+        let enabled: InstanceMemoryConfiguration = serde_json::from_str(r#"{"enabled": true, "min": 1024, "max": 2048}"#).unwrap();
+        assert_eq!(enabled.mode, InstanceSettingMode::Override);
+        let disabled: InstanceMemoryConfiguration = serde_json::from_str(r#"{"enabled": false, "min": 1024, "max": 2048}"#).unwrap();
+        assert_eq!(disabled.mode, InstanceSettingMode::Inherit);
+
+        let configuration = configuration_with("jvm_flags", serde_json::json!({ "enabled": true, "flags": "-Xss4M" }));
+        assert_eq!(InstanceSettingMode::of(&configuration.jvm_flags), InstanceSettingMode::Override);
+    }
+
+    #[test]
+    fn test_legacy_enabled_flag_round_trip() {
+        for (enabled, mode) in [(true, InstanceSettingMode::Override), (false, InstanceSettingMode::Inherit)] {
+            let configuration = configuration_with("memory", serde_json::json!({ "enabled": enabled, "min": 1024, "max": 8192 }));
+            assert_eq!(configuration.memory.map(|memory| memory.mode), Some(mode));
+
+            let json = serde_json::to_value(&configuration).unwrap();
+            let memory = &json["memory"];
+            assert!(memory.get("enabled").is_none());
+            assert_eq!(memory["mode"], serde_json::to_value(mode).unwrap());
+
+            let reloaded: InstanceConfiguration = serde_json::from_value(json).unwrap();
+            assert_eq!(reloaded.memory, configuration.memory);
+        }
+    }
+
+    #[test]
+    fn test_modes_round_trip() {
+        for mode in [InstanceSettingMode::Inherit, InstanceSettingMode::Override, InstanceSettingMode::Disable] {
+            let json = serde_json::to_string(&mode).unwrap();
+            assert_eq!(serde_json::from_str::<InstanceSettingMode>(&json).unwrap(), mode);
+        }
+    }
+
+    #[test]
+    fn test_legacy_wrapper_and_libraries_default_to_override() {
+        // These were always used when present, before they had a mode
+        let linux_wrapper: InstanceLinuxWrapperConfiguration = serde_json::from_str(r#"{"use_mangohud": true}"#).unwrap();
+        assert_eq!(linux_wrapper.mode, InstanceSettingMode::Override);
+        assert!(linux_wrapper.use_discrete_gpu);
+
+        let system_libraries: InstanceSystemLibrariesConfiguration =
+            serde_json::from_str(r#"{"override_glfw": true, "glfw": "Auto", "override_openal": false, "openal": "Auto"}"#).unwrap();
+        assert_eq!(system_libraries.mode, InstanceSettingMode::Override);
+        assert_eq!(system_libraries.glfw, LwjglLibraryPath::Auto);
+
+        // The default value used by new settings still inherits
+        assert_eq!(InstanceLinuxWrapperConfiguration::default().mode, InstanceSettingMode::Inherit);
+        assert_eq!(InstanceSystemLibrariesConfiguration::default().mode, InstanceSettingMode::Inherit);
+    }
+
+    #[test]
+    fn test_resolve_inherited_settings() {
+        let defaults = InstanceDefaults {
+            memory: Some(memory(InstanceSettingMode::Override, 8192)),
+            linux_wrapper: Some(InstanceLinuxWrapperConfiguration {
+                mode: InstanceSettingMode::Inherit,
+                use_mangohud: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut configuration = InstanceConfiguration::new("1.21.1".into(), Loader::Fabric);
+        configuration.memory = Some(memory(InstanceSettingMode::Inherit, 2048));
+        let resolved = configuration.resolve_defaults(&defaults);
+        assert_eq!(resolved.memory, defaults.memory);
+        // Defaults are only used when they're set to override
+        assert_eq!(resolved.linux_wrapper, None);
+
+        configuration.memory = Some(memory(InstanceSettingMode::Override, 2048));
+        assert_eq!(configuration.resolve_defaults(&defaults).memory, Some(memory(InstanceSettingMode::Override, 2048)));
+
+        configuration.memory = Some(memory(InstanceSettingMode::Disable, 2048));
+        assert_eq!(configuration.resolve_defaults(&defaults).memory, None);
+    }
+
+    #[test]
+    fn test_resolve_joined_settings() {
+        let defaults = InstanceDefaults {
+            environment_variables: "A=1".into(),
+            game_arguments: "--demo".into(),
+            ..Default::default()
+        };
+
+        let mut configuration = InstanceConfiguration::new("1.21.1".into(), Loader::Fabric);
+        let resolved = configuration.resolve_defaults(&defaults);
+        let environment_variables = resolved.environment_variables.unwrap();
+        assert_eq!(environment_variables.mode, InstanceSettingMode::Override);
+        assert_eq!(&*environment_variables.variables, "A=1");
+        assert_eq!(resolved.game_arguments.map(|arguments| arguments.arguments), Some(Arc::from("--demo")));
+
+        configuration.environment_variables = Some(InstanceEnvironmentVariablesConfiguration {
+            mode: InstanceSettingMode::Override,
+            variables: "B=2".into(),
+        });
+        let resolved = configuration.resolve_defaults(&defaults);
+        assert_eq!(resolved.environment_variables.map(|variables| variables.variables), Some(Arc::from("A=1\nB=2")));
+
+        configuration.environment_variables = Some(InstanceEnvironmentVariablesConfiguration {
+            mode: InstanceSettingMode::Disable,
+            variables: "B=2".into(),
+        });
+        assert!(configuration.resolve_defaults(&defaults).environment_variables.is_none());
+    }
+
+    #[test]
+    fn test_resolve_window() {
+        let defaults = InstanceDefaults {
+            window: WindowConfiguration {
+                custom_size: true,
+                width: 1280,
+                height: 720,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut configuration = InstanceConfiguration::new("1.21.1".into(), Loader::Fabric);
+        let resolved = configuration.resolve_defaults(&defaults).window.unwrap();
+        assert_eq!(resolved.mode, InstanceSettingMode::Override);
+        assert_eq!(resolved.window, defaults.window);
+
+        configuration.window = Some(InstanceWindowConfiguration {
+            mode: InstanceSettingMode::Disable,
+            window: defaults.window,
+        });
+        assert_eq!(configuration.resolve_defaults(&defaults).window.unwrap().window, WindowConfiguration::default());
+    }
+
+    #[test]
+    fn test_parse_variables_line_breaks() {
+        let variables: Vec<_> = InstanceEnvironmentVariablesConfiguration::parse_variables("A=1\r\n# comment\nB = 2\rC=3\n\nD").collect();
+        assert_eq!(variables, vec![("A", "1"), ("B", "2"), ("C", "3")]);
+    }
+}
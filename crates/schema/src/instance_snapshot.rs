@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::instance::InstanceConfiguration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceSnapshotReason {
+    UpdateCheck,
+    UpdateContent,
+    LoaderChange,
+    VersionChange,
+    BeforeRestore,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceSnapshotManifest {
+    pub created: i64,
+    pub reason: InstanceSnapshotReason,
+    pub configuration: InstanceConfiguration,
+    pub content: Vec<InstanceSnapshotFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceSnapshotFile {
+    pub path: Arc<str>,
+    pub sha1: Arc<str>,
+}
@@ -1,5 +1,6 @@
 use serde::Deserialize;
 
+pub mod adoptium;
 pub mod assets_index;
 pub mod auxiliary;
 pub mod backend_config;
@@ -11,6 +12,7 @@ pub mod fabric_mod;
 pub mod forge;
 pub mod forge_mod;
 pub mod instance;
+pub mod instance_snapshot;
 pub mod java_runtime_component;
 pub mod java_runtimes;
 pub mod loader;
@@ -20,7 +22,11 @@ pub mod modification;
 pub mod modrinth;
 pub mod mrpack;
 pub mod pandora_update;
+pub mod quilt_launch;
+pub mod quilt_loader_manifest;
+pub mod quilt_mod;
 pub mod resourcepack;
+pub mod server_history;
 pub mod server_status;
 pub mod text_component;
 pub mod version;
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use indexmap::IndexMap;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{fabric_mod::Person, modification::ModrinthModpackFileDownload};
 
@@ -19,3 +19,29 @@ pub struct ModrinthIndexJson {
     #[serde(default, deserialize_with = "crate::try_deserialize")]
     pub author: Option<Person>,
 }
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModrinthIndexJsonExport {
+    pub format_version: u32,
+    pub game: &'static str,
+    pub version_id: Arc<str>,
+    pub name: Arc<str>,
+    pub files: Vec<ModrinthIndexJsonExportFile>,
+    pub dependencies: IndexMap<&'static str, Arc<str>>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModrinthIndexJsonExportFile {
+    pub path: Arc<str>,
+    pub hashes: ModrinthIndexJsonExportHashes,
+    pub downloads: Vec<Arc<str>>,
+    pub file_size: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ModrinthIndexJsonExportHashes {
+    pub sha1: Arc<str>,
+    pub sha512: Arc<str>,
+}
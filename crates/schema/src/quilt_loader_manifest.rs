@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use ustr::Ustr;
+
+pub const QUILT_LOADER_MANIFEST_URL: &str = "https://meta.quiltmc.org/v3/versions/loader";
+
+#[derive(Deserialize, Debug)]
+pub struct QuiltLoaderManifest(pub Vec<QuiltLoaderVersion>);
+
+#[derive(Deserialize, Debug)]
+pub struct QuiltLoaderVersion {
+    pub separator: Ustr,
+    pub build: usize,
+    pub maven: Ustr,
+    pub version: Ustr,
+}
+
+impl QuiltLoaderVersion {
+    pub fn is_stable(&self) -> bool {
+        // Quilt's meta doesn't have a stable flag, prereleases are marked with a suffix such as -beta.1
+        !self.version.contains('-')
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::fabric_mod::Icon;
+
+#[derive(Deserialize, Debug)]
+pub struct QuiltModJson {
+    pub quilt_loader: QuiltLoaderSection,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QuiltLoaderSection {
+    pub id: Arc<str>,
+    pub version: Arc<str>,
+    #[serde(default)]
+    pub metadata: QuiltModMetadata,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub provides: Vec<QuiltProvides>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub depends: Vec<QuiltDependency>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub breaks: Vec<QuiltDependency>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum QuiltProvides {
+    Id(Arc<str>),
+    Object {
+        id: Arc<str>,
+        version: Option<Arc<str>>,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum QuiltDependency {
+    Id(Arc<str>),
+    Object {
+        id: Arc<str>,
+        // Can also be an object with any/all, which isn't supported
+        #[serde(default, deserialize_with = "crate::single_or_seq")]
+        versions: Vec<Arc<str>>,
+        #[serde(default)]
+        optional: bool,
+    },
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct QuiltModMetadata {
+    pub name: Option<Arc<str>>,
+    // pub description: Option<Arc<str>>,
+    // Maps a contributor's name to their role
+    pub contributors: Option<IndexMap<Arc<str>, Arc<str>>>,
+    pub icon: Option<Icon>,
+}
@@ -0,0 +1,45 @@
+use std::{collections::{BTreeMap, VecDeque}, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ServerHistory {
+    // Keyed by the address from servers.dat, so the same server in multiple instances shares its history
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", deserialize_with = "crate::try_deserialize")]
+    pub servers: BTreeMap<Arc<str>, ServerHistoryEntry>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerHistoryEntry {
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub favourite: bool,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub motd: Option<Arc<str>>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none", deserialize_with = "crate::try_deserialize")]
+    pub version: Option<Arc<str>>,
+    // Samples used to be stored here, they're moved into the sample log when loading
+    #[serde(default, skip_serializing, deserialize_with = "crate::try_deserialize")]
+    pub samples: VecDeque<ServerStatusSample>,
+}
+
+// A line of the append-only sample log
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerStatusSampleRecord {
+    pub address: Arc<str>,
+    #[serde(flatten)]
+    pub sample: ServerStatusSample,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerStatusSample {
+    pub time_unix_ms: i64,
+    pub online: bool,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none")]
+    pub players: Option<i32>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none")]
+    pub max_players: Option<i32>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none")]
+    pub latency_ms: Option<u32>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_none")]
+    pub version: Option<Arc<str>>,
+}